@@ -1,9 +1,34 @@
 import { describe, it } from 'node:test';
 import assert from 'node:assert/strict';
-import { writeParquet, readParquet } from '../src/index.js';
+import { createServer } from 'node:http';
+import { readFileSync } from 'node:fs';
+import { readParquet, readParquetMetadata, readParquetSchema, inspectPages, dumpFooter, readParquetBatches, ParquetFile } from '../src/index.js';
+// The codec tests need every encoder, so the suite writes through the full build
+import { writeParquet, ParquetWriter, mergeParquet, appendToParquet, inferSchema } from '../src/writer-full.js';
+import * as defaultWriter from '../src/writer.js';
+import { sourcesHash } from '../scripts/wasm-sources.mjs';
+
+// ── WASM builds ──────────────────────────────────────────────────────────────
+
+describe('wasm builds', () => {
+    for (const side of ['reader', 'writer', 'writer-full']) {
+        it(`wasm/${side}.wasm is built from the current Rust sources`, () => {
+            let built = null;
+            try {
+                built = readFileSync(new URL(`../wasm/${side}.sources`, import.meta.url), 'utf8').trim();
+            } catch {}
+            assert.equal(built, sourcesHash(side), `wasm/${side}.wasm is stale: run scripts/build-wasm.sh ${side}`);
+        });
+    }
+});
 
 // ── Helpers ──────────────────────────────────────────────────────────────────
 
+/** Bytes of a file in _test/fixtures (see its README for where each comes from) */
+function fixture(name) {
+    return readFileSync(new URL(`fixtures/${name}`, import.meta.url));
+}
+
 function roundtrip(schema, data, opts = {}) {
     return async () => {
         const bytes = await writeParquet(schema, data, opts);
@@ -84,6 +109,373 @@ describe('types', () => {
         const result = await roundtrip(schema, data)();
         assert.deepEqual(result.data.val, data.val);
     });
+
+    it('timestamp_micros and timestamp_nanos roundtrip', async () => {
+        const schema = [
+            { name: 'us', type: 'timestamp_micros' },
+            { name: 'ns', type: 'timestamp_nanos' },
+        ];
+        const data = { us: [1708000000123456n, 0n], ns: [1708000000123456789n, -1n] };
+        const bytes = await writeParquet(schema, data);
+        const result = await readParquet(bytes, { int64: 'bigint', timestamps: 'raw' });
+        assert.deepEqual(result.schema.map(c => [c.type, c.unit]), [['timestamp', 'micros'], ['timestamp', 'nanos']]);
+        assert.deepEqual(result.data, data);
+    });
+
+    it('isAdjustedToUTC marks local timestamps and times', async () => {
+        const schema = [
+            { name: 'utc', type: 'timestamp' },
+            { name: 'local', type: 'timestamp_micros', isAdjustedToUTC: false },
+            { name: 't', type: 'time_millis', isAdjustedToUTC: false },
+            { name: 'n', type: 'int32' },
+        ];
+        const bytes = await writeParquet(schema, { utc: [0], local: [0], t: [0], n: [0] });
+        const result = await readParquet(bytes);
+        assert.deepEqual(result.schema.map(c => c.isAdjustedToUTC), [true, false, false, undefined]);
+        await assert.rejects(
+            writeParquet([{ name: 'n', type: 'int32', isAdjustedToUTC: false }], { n: [0] }),
+            /column 'n': isAdjustedToUTC only applies to timestamp and time columns/,
+        );
+    });
+
+    it('normalizes timestamp units on read', async () => {
+        const schema = [
+            { name: 'ms', type: 'timestamp' },
+            { name: 'us', type: 'timestamp_micros' },
+            { name: 'ns', type: 'timestamp_nanos' },
+        ];
+        const data = { ms: [1708000000123], us: [1708000000123456n], ns: [1708000000123456789n] };
+        const bytes = await writeParquet(schema, data);
+        const millis = await readParquet(bytes);
+        assert.deepEqual(millis.data, { ms: [1708000000123], us: [1708000000123], ns: [1708000000123] });
+        assert.deepEqual(millis.schema.map(c => c.unit), ['millis', 'millis', 'millis']);
+        const micros = await readParquet(bytes, { timestamps: 'micros', int64: 'bigint' });
+        assert.deepEqual(micros.data, { ms: [1708000000123000n], us: [1708000000123456n], ns: [1708000000123456n] });
+        await assert.rejects(readParquet(bytes, { timestamps: 'seconds' }), /timestamps must be/);
+    });
+
+    it('takes Dates in timestamp columns, in the column unit', async () => {
+        const at = new Date('2024-03-01T12:00:00.123Z');
+        const schema = [
+            { name: 'ms', type: 'timestamp' },
+            { name: 'us', type: 'timestamp_micros' },
+            { name: 'ns', type: 'timestamp_nanos' },
+        ];
+        const bytes = await writeParquet(schema, { ms: [at], us: [at], ns: [at] }, { strict: true });
+        const raw = await readParquet(bytes, { timestamps: 'raw', int64: 'bigint' });
+        assert.deepEqual(raw.data, { ms: [1709294400123n], us: [1709294400123000n], ns: [1709294400123000000n] });
+        await assert.rejects(writeParquet(schema.slice(0, 1), { ms: [new Date(NaN)] }), /invalid Date/);
+        await assert.rejects(
+            writeParquet(schema.slice(2), { ns: [new Date('3000-01-01')] }),
+            /column 'ns' row 0: 3000-01-01T00:00:00.000Z is out of range for timestamp_nanos/,
+        );
+    });
+
+    it('narrow integer types roundtrip', async () => {
+        const schema = [
+            { name: 'i8', type: 'int8' },
+            { name: 'i16', type: 'int16' },
+            { name: 'u8', type: 'uint8' },
+            { name: 'u16', type: 'uint16' },
+            { name: 'u32', type: 'uint32' },
+        ];
+        const data = {
+            i8: [-128, 0, 127], i16: [-32768, 1, 32767], u8: Uint8Array.from([0, 1, 255]),
+            u16: [0, 1, 65535], u32: [0, 2147483648, 4294967295],
+        };
+        const bytes = await writeParquet(schema, data, { dataPageRows: 1 });
+        const result = await readParquet(bytes);
+        assert.deepEqual(result.schema.map(c => c.type), ['int8', 'int16', 'uint8', 'uint16', 'uint32']);
+        assert.deepEqual(result.data, { ...data, u8: [0, 1, 255] });
+        assert.equal(result.schema[4].statistics.max, 4294967295);
+        assert.equal(result.schema[4].statistics.min, 0);
+    });
+
+    it('narrow integer types reject out-of-range values', async () => {
+        await assert.rejects(writeParquet([{ name: 'u', type: 'uint8' }], { u: [1, 256] }), /column 'u' row 1: 256 is out of range for uint8/);
+        await assert.rejects(writeParquet([{ name: 'i', type: 'int8' }], { i: Int16Array.from([0, -129]) }), /row 1: -129 is out of range for int8/);
+        await assert.rejects(writeParquet([{ name: 'u', type: 'uint32' }], { u: [-1] }), /out of range for uint32/);
+    });
+
+    it('uint64 roundtrips as unsigned BigInt', async () => {
+        const schema = [{ name: 'u', type: 'uint64' }];
+        const big = [0n, 9007199254740993n, 18446744073709551615n];
+        const result = await readParquet(await writeParquet(schema, { u: BigUint64Array.from(big) }, { dataPageRows: 1 }));
+        assert.equal(result.schema[0].type, 'uint64');
+        assert.deepEqual(result.data.u, big);
+        assert.equal(result.schema[0].statistics.min, 0n);
+        assert.equal(result.schema[0].statistics.max, 18446744073709551615n);
+        await assert.rejects(writeParquet(schema, { u: [1, -1] }), /column 'u' row 1: -1 is out of range for uint64/);
+    });
+
+    it('uint64: error returns Numbers and rejects unsafe values', async () => {
+        const schema = [{ name: 'u', type: 'uint64' }];
+        const small = await readParquet(await writeParquet(schema, { u: [0, 42] }), { uint64: 'error' });
+        assert.deepEqual(small.data.u, [0, 42]);
+        const bytes = await writeParquet(schema, { u: [1n, 18446744073709551615n] });
+        await assert.rejects(readParquet(bytes, { uint64: 'error' }), /column 'u' row 1: uint64 value 18446744073709551615 exceeds/);
+    });
+
+    it('float16 stores two bytes per value', async () => {
+        const values = Array.from({ length: 1000 }, (_, i) => i / 7);
+        const opts = { compression: 'none', dictionary: false };
+        const half = await writeParquet([{ name: 'v', type: 'float16' }], { v: values }, opts);
+        const single = await writeParquet([{ name: 'v', type: 'float32' }], { v: Float32Array.from(values) }, opts);
+        assert.ok(half.length < single.length - 1500);
+    });
+
+    it('float16 roundtrips to the nearest half', async () => {
+        const schema = [{ name: 'v', type: 'float16', optional: true }];
+        const data = { v: [1.5, -2, null, 0.1, 65504, 1e6, -0] };
+        const result = await roundtrip(schema, data, { dataPageRows: 2 })();
+        assert.equal(result.schema[0].type, 'float16');
+        assert.deepEqual(result.data.v, [1.5, -2, null, 0.0999755859375, 65504, Infinity, -0]);
+        assert.equal(result.schema[0].statistics.min, -2);
+        assert.equal(result.schema[0].statistics.max, Infinity);
+    });
+
+    it('float16 reads as Float32Array with typedArrays', async () => {
+        const bytes = await writeParquet([{ name: 'v', type: 'float16' }], { v: Float32Array.from([0.5, 3, -7.25]) });
+        const result = await readParquet(bytes, { typedArrays: true });
+        assert.ok(result.data.v instanceof Float32Array);
+        assert.deepEqual(Array.from(result.data.v), [0.5, 3, -7.25]);
+    });
+
+    it('json stringifies values and parses them back on request', async () => {
+        const schema = [{ name: 'doc', type: 'json', optional: true }];
+        const data = { doc: [{ a: 1, b: [true, null] }, '{"pre":"serialized"}', null, 42] };
+        const bytes = await writeParquet(schema, data);
+        const text = await readParquet(bytes);
+        assert.equal(text.schema[0].type, 'json');
+        assert.deepEqual(text.data.doc, ['{"a":1,"b":[true,null]}', '{"pre":"serialized"}', null, '42']);
+        const parsed = await readParquet(bytes, { json: 'parse' });
+        assert.deepEqual(parsed.data.doc, [{ a: 1, b: [true, null] }, { pre: 'serialized' }, null, 42]);
+        await assert.rejects(writeParquet(schema, { doc: [1n] }), /column 'doc' row 0: JSON.stringify failed/);
+        const bad = await writeParquet(schema, { doc: ['not json'] });
+        await assert.rejects(readParquet(bad, { json: 'parse' }), /column 'doc' row 0: invalid JSON/);
+    });
+
+    it('bson passes encoded documents through', async () => {
+        const schema = [{ name: 'doc', type: 'bson', optional: true }];
+        // {"a": 1} as BSON
+        const doc = Uint8Array.from([12, 0, 0, 0, 16, 97, 0, 1, 0, 0, 0, 0]);
+        const result = await roundtrip(schema, { doc: [doc, null] })();
+        assert.equal(result.schema[0].type, 'bson');
+        assert.deepEqual(result.data.doc, [doc, null]);
+        await assert.rejects(writeParquet(schema, { doc: [{ a: 1 }] }), /column 'doc' row 0: expected an encoded BSON document/);
+    });
+
+    it('list columns validate their rows', async () => {
+        const schema = [{ name: 'tags', type: 'list', items: 'string' }];
+        const bytes = await writeParquet(schema, { tags: [['a', 'b'], [], ['c']] });
+        assert.ok(bytes instanceof Uint8Array);
+        await assert.rejects(writeParquet(schema, { tags: [['a'], 'b'] }), /column 'tags' row 1: expected an array/);
+        await assert.rejects(writeParquet(schema, { tags: [['a', null]] }), /column 'tags' row 0: null value for required field 'element'/);
+        await assert.rejects(writeParquet([{ name: 'n', type: 'list', items: 'int8' }], { n: [[1], [2, 300]] }), /column 'n.list.element' row 1: 300 is out of range/);
+        await assert.rejects(writeParquet([{ name: 'x', type: 'list' }], { x: [] }), /column 'x': list columns need 'items'/);
+    });
+
+    it('list roundtrip', async () => {
+        const schema = [
+            { name: 'tags', type: 'list', items: 'string', optional: true },
+            { name: 'scores', type: 'list', items: { type: 'int32', optional: true } },
+            { name: 'grid', type: 'list', items: { type: 'list', items: 'float64' } },
+        ];
+        const data = {
+            tags: [['a', 'b'], [], null, ['c']],
+            scores: [[1, null, 3], [], [null], [4]],
+            grid: [[[1.5], []], [], [[2, 3]], [[], [4]]],
+        };
+        const result = await roundtrip(schema, data)();
+        assert.deepEqual(result.data, data);
+        assert.equal(result.schema[0].type, 'list');
+        assert.equal(result.schema[0].optional, true);
+        assert.equal(result.schema[0].items.type, 'string');
+        assert.equal(result.schema[1].items.optional, true);
+        assert.equal(result.schema[2].items.items.type, 'float64');
+
+        const bytes = await writeParquet(schema, data, { rowGroupSize: 2 });
+        const page = await readParquet(bytes, { offset: 1, maxRows: 2, columns: ['scores'], format: 'rows' });
+        assert.deepEqual(page.data, [{ scores: [] }, { scores: [null] }]);
+    });
+
+    it('struct columns validate their rows', async () => {
+        const schema = [{
+            name: 'address', type: 'struct', optional: true,
+            fields: [{ name: 'city', type: 'string' }, { name: 'zip', type: 'int32', optional: true }],
+        }];
+        const bytes = await writeParquet(schema, { address: [{ city: 'Oslo', zip: 150 }, null, { city: 'Rome' }] });
+        assert.ok(bytes instanceof Uint8Array);
+        await assert.rejects(writeParquet(schema, { address: [{ zip: 1 }] }), /column 'address' row 0: null value for required field 'city'/);
+        await assert.rejects(writeParquet(schema, { address: [{ city: 'a' }, 'b'] }), /column 'address' row 1: expected an object/);
+        await assert.rejects(writeParquet([{ name: 's', type: 'struct' }], { s: [] }), /column 's': struct columns need a 'fields' array/);
+        const nested = [{ name: 'people', type: 'list', items: { type: 'struct', fields: [{ name: 'age', type: 'uint8' }] } }];
+        await assert.rejects(writeParquet(nested, { people: [[{ age: 3 }, { age: 300 }]] }), /column 'people.list.element.age' row 0: 300 is out of range/);
+    });
+
+    it('struct roundtrip', async () => {
+        const schema = [
+            {
+                name: 'address', type: 'struct', optional: true,
+                fields: [{ name: 'city', type: 'string' }, { name: 'zip', type: 'int32', optional: true }],
+            },
+            {
+                name: 'people', type: 'list',
+                items: { type: 'struct', fields: [{ name: 'name', type: 'string' }, { name: 'tags', type: 'list', items: 'string' }] },
+            },
+        ];
+        const data = {
+            address: [{ city: 'Oslo', zip: 150 }, null, { city: 'Rome', zip: null }],
+            people: [[{ name: 'Ann', tags: ['x'] }, { name: 'Bo', tags: [] }], [], [{ name: 'Cy', tags: ['y', 'z'] }]],
+        };
+        const result = await roundtrip(schema, data)();
+        assert.deepEqual(result.data, data);
+        assert.equal(result.schema[0].type, 'struct');
+        assert.deepEqual(result.schema[0].fields.map(f => [f.name, f.type, f.optional]), [['city', 'string', undefined], ['zip', 'int32', true]]);
+        assert.equal(result.schema[1].items.fields[1].items.type, 'string');
+
+        const rows = await readParquet(await writeParquet(schema, data), { format: 'rows', columns: ['address'] });
+        assert.deepEqual(rows.data[0], { address: { city: 'Oslo', zip: 150 } });
+    });
+
+    it('map columns accept objects and Maps', async () => {
+        const schema = [{ name: 'prices', type: 'map', key: 'string', value: { type: 'float64', optional: true } }];
+        const bytes = await writeParquet(schema, { prices: [{ a: 1.5, b: null }, new Map([['c', 2]]), {}] });
+        assert.ok(bytes instanceof Uint8Array);
+        const ids = [{ name: 'ids', type: 'map', key: 'int32', value: 'string' }];
+        assert.ok(await writeParquet(ids, { ids: [new Map([[1, 'x'], [2, 'y']])] }));
+        await assert.rejects(writeParquet(schema, { prices: [[1, 2]] }), /column 'prices' row 0: expected an object or Map/);
+        await assert.rejects(writeParquet(ids, { ids: [new Map([[null, 'x']])] }), /null value for required field 'key'/);
+        await assert.rejects(writeParquet([{ name: 'm', type: 'map', key: 'string' }], { m: [] }), /column 'm': map columns need 'key' and 'value'/);
+        await assert.rejects(
+            writeParquet([{ name: 'm', type: 'map', key: { type: 'string', optional: true }, value: 'int32' }], { m: [] }),
+            /map keys must be a required primitive type/,
+        );
+    });
+
+    it('map roundtrip', async () => {
+        const schema = [
+            { name: 'prices', type: 'map', key: 'string', value: { type: 'float64', optional: true }, optional: true },
+            { name: 'ids', type: 'map', key: 'int32', value: { type: 'list', items: 'string' } },
+        ];
+        const bytes = await writeParquet(schema, {
+            prices: [{ a: 1.5, b: null }, new Map([['c', 2]]), {}, null],
+            ids: [new Map([[1, ['x']], [2, []]]), new Map(), new Map([[3, ['y', 'z']]]), new Map()],
+        });
+        const result = await readParquet(bytes);
+        assert.deepEqual(result.data.prices, [{ a: 1.5, b: null }, { c: 2 }, {}, null]);
+        assert.deepEqual(result.data.ids, [{ 1: ['x'], 2: [] }, {}, { 3: ['y', 'z'] }, {}]);
+        assert.equal(result.schema[0].type, 'map');
+        assert.equal(result.schema[0].key.type, 'string');
+        assert.equal(result.schema[0].value.optional, true);
+        assert.equal(result.schema[1].value.items.type, 'string');
+
+        const maps = await readParquet(bytes, { maps: 'map', columns: ['ids'] });
+        assert.deepEqual(maps.data.ids[0], new Map([[1, ['x']], [2, []]]));
+        await assert.rejects(readParquet(bytes, { maps: 'dict' }), /maps must be 'object' or 'map'/);
+    });
+
+    it('date accepts Date objects and day counts', async () => {
+        const schema = [{ name: 'val', type: 'date' }];
+        const data = { val: [new Date('2024-03-01T23:59:00Z'), 19783, new Date(-86400000)] };
+        const result = await roundtrip(schema, data)();
+        assert.deepEqual(result.data.val, ['2024-03-01', '2024-03-01', '1969-12-31']);
+        await assert.rejects(writeParquet(schema, { val: [new Date('nope')] }), /row 0: invalid Date/);
+    });
+
+    it('time_millis and time_micros roundtrip', async () => {
+        const schema = [
+            { name: 'ms', type: 'time_millis' },
+            { name: 'us', type: 'time_micros' },
+        ];
+        const data = { ms: [0, 45296789, 86399999], us: [0, 45296789012, 86399999999] };
+        const result = await roundtrip(schema, data)();
+        assert.deepEqual(result.schema.map(c => c.type), ['time_millis', 'time_micros']);
+        assert.deepEqual(result.data.ms, ['00:00:00.000', '12:34:56.789', '23:59:59.999']);
+        assert.deepEqual(result.data.us, ['00:00:00.000000', '12:34:56.789012', '23:59:59.999999']);
+        const raw = await readParquet(await writeParquet(schema, data), { times: 'number' });
+        assert.deepEqual(raw.data, data);
+    });
+
+    it('decimal accepts strings and numbers', async () => {
+        const schema = [
+            { name: 'small', type: 'decimal(9,2)' },
+            { name: 'mid', type: 'decimal(18, 4)' },
+        ];
+        const data = { small: ['12.34', -0.5, '1.005'], mid: ['12345678901234.5678', 0, '-1'] };
+        const bytes = await writeParquet(schema, data);
+        const result = await readParquet(bytes);
+        assert.deepEqual(result.data.small, [12.34, -0.5, 1.01]);
+        assert.deepEqual(result.data.mid, [12345678901234.568, 0, -1]);
+        assert.deepEqual(result.schema.map(c => [c.type, c.precision, c.scale]), [['decimal', 9, 2], ['decimal', 18, 4]]);
+        const exact = await readParquet(bytes, { decimals: 'string' });
+        assert.deepEqual(exact.data.mid, ['12345678901234.5678', '0.0000', '-1.0000']);
+    });
+
+    it('decimal with precision > 18 roundtrips through fixed-length bytes', async () => {
+        const schema = [{ name: 'big', type: 'decimal(38,10)', optional: true }];
+        const big = ['9999999999999999999999999999.9999999999', null, '-0.0000000001'];
+        const bytes = await writeParquet(schema, { big });
+        const result = await readParquet(bytes, { decimals: 'string' });
+        assert.deepEqual(result.data.big, big);
+        assert.equal(result.schema[0].statistics.min, '-0.0000000001');
+    });
+
+    it('uuid roundtrip', async () => {
+        const schema = [{ name: 'id', type: 'uuid', optional: true }];
+        const bytes = await writeParquet(schema, { id: ['123E4567-e89b-12d3-a456-426614174000', null] });
+        const result = await readParquet(bytes);
+        assert.equal(result.schema[0].type, 'uuid');
+        assert.deepEqual(result.data.id, ['123e4567-e89b-12d3-a456-426614174000', null]);
+        assert.equal(result.schema[0].statistics.min, '123e4567-e89b-12d3-a456-426614174000');
+        await assert.rejects(writeParquet(schema, { id: ['123e4567e89b12d3a456426614174000'] }), /row 0: '.*' is not a UUID/);
+        await assert.rejects(writeParquet(schema, { id: ['+23e4567-e89b-12d3-a456-426614174000'] }), /is not a UUID/);
+    });
+
+    it('fixed(n) stores Uint8Arrays of n bytes', async () => {
+        const schema = [{ name: 'h', type: 'fixed(4)', optional: true }];
+        const h = [Uint8Array.from([1, 2, 3, 4]), null, Uint8Array.from([255, 0, 0, 1])];
+        const result = await roundtrip(schema, { h })();
+        assert.deepEqual(result.data.h, h);
+        assert.equal(result.schema[0].type, 'fixed');
+        assert.equal(result.schema[0].length, 4);
+        const hex = await readParquet(await writeParquet(schema, { h }), { binary: 'hex' });
+        assert.deepEqual(hex.data.h, ['01020304', null, 'ff000001']);
+        await assert.rejects(writeParquet(schema, { h: [Uint8Array.from([1, 2])] }), /row 0: expected 4 bytes, got 2/);
+        await assert.rejects(writeParquet([{ name: 'h', type: 'fixed(0)' }], { h: [] }), /invalid type 'fixed\(0\)'/);
+    });
+
+    it('binary stores Uint8Array values as raw bytes', async () => {
+        const schema = [{ name: 'blob', type: 'binary', optional: true }];
+        const blob = [Uint8Array.from([0xff, 0x00, 0x80]), null, new TextEncoder().encode('ok')];
+        const bytes = await writeParquet(schema, { blob });
+        const result = await readParquet(bytes);
+        assert.equal(result.schema[0].type, 'binary');
+        assert.deepEqual(result.data.blob, blob);
+        assert.deepEqual(result.schema[0].statistics.max, Uint8Array.from([0xff, 0x00, 0x80]));
+        const hex = await readParquet(bytes, { binary: 'hex' });
+        assert.deepEqual(hex.data.blob, ['ff0080', null, '6f6b']);
+        await assert.rejects(writeParquet(schema, { blob: [42] }), /row 0: expected a Uint8Array/);
+    });
+
+    it('decimal statistics compare as signed across pages', async () => {
+        const schema = [{ name: 'd', type: 'decimal(20,2)' }];
+        const bytes = await writeParquet(schema, { d: ['5.00', '-7.25', '3.10'] }, { dataPageRows: 1 });
+        const { statistics } = (await readParquet(bytes, { decimals: 'string' })).schema[0];
+        assert.equal(statistics.min, '-7.25');
+        assert.equal(statistics.max, '5.00');
+    });
+
+    it('decimal rejects bad types and values', async () => {
+        await assert.rejects(writeParquet([{ name: 'd', type: 'decimal(39,2)' }], { d: [] }), /column 'd': invalid type/);
+        await assert.rejects(writeParquet([{ name: 'd', type: 'decimal(2,3)' }], { d: [] }), /invalid type/);
+        await assert.rejects(writeParquet([{ name: 'd', type: 'decimal(4,2)' }], { d: ['123.4'] }), /row 0: '123.4' does not fit decimal\(4, 2\)/);
+        await assert.rejects(writeParquet([{ name: 'd', type: 'decimal(4,2)' }], { d: ['1x'] }), /not a decimal number/);
+        await assert.rejects(
+            writeParquet([{ name: 'd', type: 'decimal(4,2)' }], { d: ['1.234'] }, { strict: true }),
+            /more than 2 fraction digits/,
+        );
+    });
 });
 
 // ── Multi-column schemas ─────────────────────────────────────────────────────
@@ -184,126 +576,2291 @@ describe('payload sizes', () => {
     });
 });
 
-// ── Compression ──────────────────────────────────────────────────────────────
+// ── Row input ────────────────────────────────────────────────────────────────
 
-describe('compression', () => {
-    const schema = [{ name: 'msg', type: 'string' }];
-    const data = { msg: Array.from({ length: 100 }, (_, i) => `repeated-value-${i % 10}`) };
+describe('row input', () => {
+    const schema = [
+        { name: 'id', type: 'int32' },
+        { name: 'name', type: 'string', optional: true },
+        { name: 'score', type: 'float64', optional: true },
+    ];
 
-    it('snappy (default)', async () => {
-        const result = await roundtrip(schema, data, { compression: 'snappy' })();
-        assert.equal(result.numRows, 100);
+    it('accepts an array of row objects', async () => {
+        const rows = [
+            { id: 1, name: 'a', score: 0.5 },
+            { id: 2, score: 1.5 },
+            { id: 3, name: 'c', extra: 'ignored' },
+        ];
+        const bytes = await writeParquet(schema, rows);
+        const result = await readParquet(bytes);
+        assert.deepEqual(result.data, {
+            id: [1, 2, 3],
+            name: ['a', null, 'c'],
+            score: [0.5, 1.5, null],
+        });
     });
 
-    it('none', async () => {
-        const result = await roundtrip(schema, data, { compression: 'none' })();
-        assert.equal(result.numRows, 100);
+    it('writes the same file as the columnar shape', async () => {
+        const rows = [{ id: 1, name: 'x', score: 2 }, { id: 2, name: null, score: null }];
+        const cols = { id: [1, 2], name: ['x', null], score: [2, null] };
+        assert.deepEqual(await writeParquet(schema, rows), await writeParquet(schema, cols));
     });
+});
 
-    it('snappy produces smaller output than none', async () => {
-        const snappy = await writeParquet(schema, data, { compression: 'snappy' });
-        const none = await writeParquet(schema, data, { compression: 'none' });
-        assert.ok(snappy.length < none.length, `snappy (${snappy.length}B) should be smaller than none (${none.length}B)`);
+// ── Schema inference ─────────────────────────────────────────────────────────
+
+describe('schema inference', () => {
+    it('infers column types from values', async () => {
+        const at = new Date('2024-03-01T12:00:00Z');
+        const data = {
+            id: [1, 2, 3],
+            score: [1, 2.5, null],
+            big: [1n, 2n, 3n],
+            ok: [true, false, true],
+            name: ['a', 'b', undefined],
+            at: [at, at, at],
+            raw: [new Uint8Array([1]), new Uint8Array(0), null],
+            tags: [['x'], [], null],
+            point: [{ x: 1, y: 2 }, { x: 3 }, null],
+            nothing: [null, null, null],
+            f32: new Float32Array([1, 2, 3]),
+        };
+        const schema = await inferSchema(data);
+        assert.deepEqual(schema.map((c) => [c.name, c.type, !!c.optional]), [
+            ['id', 'int32', false], ['score', 'float64', true], ['big', 'int64', false], ['ok', 'boolean', false],
+            ['name', 'string', true], ['at', 'timestamp', false], ['raw', 'binary', true], ['tags', 'list', true],
+            ['point', 'struct', true], ['nothing', 'string', true], ['f32', 'float32', false],
+        ]);
+        assert.equal(schema[7].items.type, 'string');
+        assert.deepEqual(schema[8].fields, [{ name: 'x', type: 'int32' }, { name: 'y', type: 'int32', optional: true }]);
+
+        const result = await readParquet(await writeParquet(null, data));
+        assert.deepEqual(result.data.score, [1, 2.5, null]);
+        assert.deepEqual(result.data.tags, [['x'], [], null]);
+        assert.deepEqual(result.data.point, [{ x: 1, y: 2 }, { x: 3, y: null }, null]);
+        assert.deepEqual(result.data.at, [at.getTime(), at.getTime(), at.getTime()]);
+    });
+
+    it('infers from row objects', async () => {
+        const rows = [{ id: 1, big: 2 ** 40 }, { id: 2, note: 'late key' }];
+        assert.deepEqual(await inferSchema(rows), [
+            { name: 'id', type: 'int32' },
+            { name: 'big', type: 'float64', optional: true },
+            { name: 'note', type: 'string', optional: true },
+        ]);
+        const result = await readParquet(await writeParquet(null, rows));
+        assert.deepEqual(result.data, { id: [1, 2], big: [2 ** 40, null], note: [null, 'late key'] });
+    });
+
+    it('rejects mixed columns', async () => {
+        await assert.rejects(writeParquet(null, { x: [1, 'a'] }), /column 'x': mixes int32 and string values/);
+        await assert.rejects(inferSchema({ p: [{ q: [1, true] }] }), /column 'p.q.list.element': mixes/);
+        await assert.rejects(inferSchema(5), /data must be/);
     });
 });
 
-// ── Edge cases ───────────────────────────────────────────────────────────────
+// ── Int64 input ──────────────────────────────────────────────────────────────
 
-describe('edge cases', () => {
-    it('empty strings', async () => {
-        const schema = [{ name: 'val', type: 'string' }];
-        const data = { val: ['', '', ''] };
-        const result = await roundtrip(schema, data)();
-        assert.deepEqual(result.data.val, data.val);
+describe('int64 input', () => {
+    const schema = [{ name: 'big', type: 'int64', optional: true }];
+
+    it('accepts BigInt cells and BigInt64Array columns', async () => {
+        const max = 2n ** 63n - 1n;
+        const a = await writeParquet(schema, { big: [1n, null, max, -5n] });
+        const b = await writeParquet([{ name: 'big', type: 'int64' }], { big: BigInt64Array.from([1n, max]) });
+        assert.ok(a.length > 0 && b.length > 0);
+        // Same bytes as plain Numbers where those are exact
+        assert.deepEqual(
+            await writeParquet(schema, { big: [1n, 2n, 3n] }),
+            await writeParquet(schema, { big: [1, 2, 3] }),
+        );
     });
 
-    it('long strings', async () => {
-        const schema = [{ name: 'val', type: 'string' }];
-        const longStr = 'x'.repeat(10_000);
-        const data = { val: [longStr, longStr] };
-        const result = await roundtrip(schema, data)();
-        assert.equal(result.data.val[0].length, 10_000);
+    it('rejects BigInts outside int64', async () => {
+        await assert.rejects(writeParquet(schema, { big: [2n ** 64n] }), /row 0/);
     });
 
-    it('unicode and emoji', async () => {
-        const schema = [{ name: 'val', type: 'string' }];
-        const data = { val: ['日本語', '한국어', '🎉🔥💯', 'Ñoño'] };
-        const result = await roundtrip(schema, data)();
-        assert.deepEqual(result.data.val, data.val);
+    it('rejects unsafe Numbers in strict mode', async () => {
+        await writeParquet(schema, { big: [2 ** 60] });
+        await assert.rejects(writeParquet(schema, { big: [1, 2 ** 60] }, { strict: true }), /column 'big' row 1/);
     });
+});
 
-    it('zero values across types', async () => {
+// ── Strict mode ──────────────────────────────────────────────────────────────
+
+describe('strict mode', () => {
+    const strict = { strict: true };
+
+    it('rejects cells of the wrong JS type', async () => {
+        const cases = [
+            ['int32', ['1'], "expected a number, got string"],
+            ['float64', [1n], 'expected a number, got bigint'],
+            ['int64', [true], 'expected a number or BigInt, got boolean'],
+            ['boolean', [1], 'expected a boolean, got number'],
+            ['string', [{}], 'expected a string, got Object'],
+            ['string', [new Date(0)], 'expected a string, got Date'],
+            ['decimal(5,2)', [false], 'expected a number, BigInt or string, got boolean'],
+            ['date', ['2024-01-01'], 'expected a Date or day count, got string'],
+        ];
+        for (const [type, cells, message] of cases) {
+            const schema = [{ name: 'c', type }];
+            await writeParquet(schema, { c: cells });
+            await assert.rejects(writeParquet(schema, { c: cells }, strict), (err) => err === `column 'c' row 0: ${message}`);
+        }
+    });
+
+    it('names the row, also in row input and nested columns', async () => {
+        const schema = [{ name: 'id', type: 'int32' }, { name: 'tags', type: 'list', items: 'string', optional: true }];
+        await assert.rejects(
+            writeParquet(schema, [{ id: 1, tags: ['a'] }, { id: 2.5 }], strict),
+            /column 'id' row 1: 2.5 is not an integer/,
+        );
+        await assert.rejects(
+            writeParquet(schema, [{ id: 1 }, { id: 2, tags: null }, { id: 3, tags: ['b', 7] }], strict),
+            /column 'tags.list.element' row 2: expected a string, got number/,
+        );
+        await assert.rejects(writeParquet(schema, [{ tags: [] }], strict), /column 'id' row 0: got undefined in a required column/);
+        await assert.rejects(writeParquet([{ name: 'n', type: 'int32' }], { n: [2 ** 31] }, strict), /out of range for int32/);
+    });
+
+    it('applies the coercion policy', async () => {
         const schema = [
-            { name: 'i', type: 'int32' },
-            { name: 'f', type: 'float64' },
-            { name: 'b', type: 'boolean' },
+            { name: 'qty', type: 'int32', default: -1 },
+            { name: 'note', type: 'string', optional: true },
+            { name: 'score', type: 'float64', optional: true },
         ];
-        const data = { i: [0, 0, 0], f: [0.0, 0.0, 0.0], b: [false, false, false] };
-        const result = await roundtrip(schema, data)();
-        assert.deepEqual(result.data.i, [0, 0, 0]);
-        assert.deepEqual(result.data.f, [0, 0, 0]);
-        assert.deepEqual(result.data.b, [false, false, false]);
+        const data = { qty: [3, NaN, '7', undefined], note: ['ok', 42, null, undefined], score: [NaN, 'x', 1, null] };
+        const read = async (config) => (await readParquet(await writeParquet(schema, data, config))).data;
+
+        assert.deepEqual(await read({ coercion: 'default' }), {
+            qty: [3, -1, -1, -1], note: ['ok', null, null, null], score: [NaN, null, 1, null],
+        });
+        assert.deepEqual((await read({})).qty, [3, 0, 0, 0]);
+        await assert.rejects(read({ coercion: 'null' }), /column 'qty' row 1: got NaN/);
+        await assert.rejects(read({ coercion: 'error' }), /column 'qty' row 1: got NaN/);
+        const nulls = await readParquet(await writeParquet(schema.slice(1), data, { coercion: 'null' }));
+        assert.deepEqual(nulls.data.note, ['ok', null, null, null]);
+        await assert.rejects(writeParquet(schema.slice(1), data, { coercion: 'error' }), /column 'note' row 1: expected a string, got number/);
+        await assert.rejects(
+            writeParquet([{ name: 'n', type: 'int32', optional: true }], { n: [1.5, 'x'] }, { strict: true, coercion: 'null' }),
+            /1.5 is not an integer/,
+        );
+        await assert.rejects(writeParquet([{ name: 'n', type: 'int32', default: 'zero' }], { n: [1] }), /default must be a number/);
+        await assert.rejects(writeParquet(schema, data, { coercion: 'maybe' }), /coercion must be/);
     });
 
-    it('negative numbers', async () => {
+    it('keeps valid data unchanged', async () => {
+        const schema = [
+            { name: 'n', type: 'int32', optional: true },
+            { name: 'd', type: 'date' },
+            { name: 'j', type: 'json' },
+        ];
+        const data = { n: [1, null, undefined], d: [new Date(0), 1, 2], j: [{ a: 1 }, 'null', [1]] };
+        const result = await readParquet(await writeParquet(schema, data, strict));
+        assert.deepEqual(result.data.n, [1, null, null]);
+        assert.deepEqual(result.data.d, ['1970-01-01', '1970-01-02', '1970-01-03']);
+    });
+});
+
+// ── Typed arrays ─────────────────────────────────────────────────────────────
+
+describe('typed array input', () => {
+    it('accepts TypedArray columns', async () => {
         const schema = [
-            { name: 'i32', type: 'int32' },
-            { name: 'i64', type: 'int64' },
             { name: 'f64', type: 'float64' },
+            { name: 'f32', type: 'float32' },
+            { name: 'i32', type: 'int32' },
+            { name: 'i64', type: 'int64', optional: true },
+            { name: 'flag', type: 'boolean' },
         ];
-        const data = { i32: [-1, -100, -2147483648], i64: [-1, -999999], f64: [-0.001, -1e50] };
+        const data = {
+            f64: new Float64Array([1.5, -2.25, 3]),
+            f32: new Float32Array([0.5, 1, 2]),
+            i32: new Int32Array([-1, 0, 2_000_000_000]),
+            i64: new Uint16Array([1, 65_535, 7]),
+            flag: new Uint8Array([1, 0, 1]),
+        };
         const result = await roundtrip(schema, data)();
-        assert.deepEqual(result.data.i32, data.i32);
-        assert.deepEqual(result.data.i64, data.i64);
-        assert.deepEqual(result.data.f64, data.f64);
+        assert.deepEqual(result.data, {
+            f64: [1.5, -2.25, 3],
+            f32: [0.5, 1, 2],
+            i32: [-1, 0, 2_000_000_000],
+            i64: [1, 65_535, 7],
+            flag: [true, false, true],
+        });
     });
 
-    it('single column, many rows', async () => {
-        const schema = [{ name: 'id', type: 'int32' }];
-        const n = 5_000;
-        const data = { id: Array.from({ length: n }, (_, i) => i) };
-        const result = await roundtrip(schema, data)();
-        assert.equal(result.numRows, n);
-        assert.equal(result.data.id[0], 0);
-        assert.equal(result.data.id[n - 1], n - 1);
+    it('matches the plain-array output', async () => {
+        const schema = [{ name: 'x', type: 'float64' }];
+        const values = Array.from({ length: 1_000 }, (_, i) => i / 7);
+        assert.deepEqual(
+            await writeParquet(schema, { x: Float64Array.from(values) }),
+            await writeParquet(schema, { x: values }),
+        );
     });
+});
 
-    it('many columns, few rows', async () => {
-        const cols = 20;
-        const schema = Array.from({ length: cols }, (_, i) => ({ name: `col_${i}`, type: 'int32' }));
-        const data = {};
-        for (let i = 0; i < cols; i++) data[`col_${i}`] = [i, i * 2, i * 3];
-        const result = await roundtrip(schema, data)();
-        assert.equal(result.numRows, 3);
-        assert.equal(result.schema.length, cols);
+// ── Row groups ───────────────────────────────────────────────────────────────
+
+describe('row groups', () => {
+    const schema = [
+        { name: 'id', type: 'int32' },
+        { name: 'tag', type: 'string', optional: true },
+    ];
+    const n = 2_500;
+    const data = {
+        id: Array.from({ length: n }, (_, i) => i),
+        tag: Array.from({ length: n }, (_, i) => (i % 5 === 0 ? null : `t${i % 3}`)),
+    };
+
+    it('splits rows across row groups and reads them back in order', async () => {
+        const result = await roundtrip(schema, data, { rowGroupSize: 1_000 })();
+        assert.deepEqual(result.data.id, data.id);
+        assert.deepEqual(result.data.tag, data.tag);
+    });
+
+    it('decodes only the selected row groups', async () => {
+        const bytes = await writeParquet(schema, data, { rowGroupSize: 1_000 });
+        const result = await readParquet(bytes, { rowGroups: [2, 0], maxRows: n });
+        assert.deepEqual(result.data.id, [...data.id.slice(2_000), ...data.id.slice(0, 1_000)]);
+        assert.deepEqual(result.data.tag, [...data.tag.slice(2_000), ...data.tag.slice(0, 1_000)]);
+        await assert.rejects(readParquet(bytes, { rowGroups: [3] }), /out of range/);
+    });
+
+    it('pages through rows with offset + maxRows', async () => {
+        const bytes = await writeParquet(schema, data, { rowGroupSize: 1_000, dataPageRows: 128 });
+        for (const offset of [0, 999, 1_000, 1_337, 2_400]) {
+            const result = await readParquet(bytes, { offset, maxRows: 200 });
+            assert.deepEqual(result.data.id, data.id.slice(offset, offset + 200));
+            assert.deepEqual(result.data.tag, data.tag.slice(offset, offset + 200));
+        }
+        const past = await readParquet(bytes, { offset: n });
+        assert.deepEqual(past.data.id, []);
+    });
+
+    it('maxRows stops across row group boundaries', async () => {
+        const bytes = await writeParquet(schema, data, { rowGroupSize: 1_000 });
+        const result = await readParquet(bytes, 1_500);
+        assert.equal(result.data.id.length, 1_500);
+        assert.equal(result.data.id[1_499], 1_499);
+    });
+
+    it('reports the layout of every row group', async () => {
+        const bytes = await writeParquet(schema, data, { rowGroupSize: 1_000, compression: 'snappy' });
+        const { rowGroups } = await readParquet(bytes, { rowGroups: [1], maxRows: 0 });
+        assert.deepEqual(rowGroups.map((rg) => rg.numRows), [1_000, 1_000, 500]);
+        for (const rg of rowGroups) {
+            assert.deepEqual(rg.columns.map((c) => c.path), ['id', 'tag']);
+            assert.ok(rg.totalByteSize > 0);
+            assert.equal(rg.compressedSize, rg.columns.reduce((sum, c) => sum + c.compressedSize, 0));
+            for (const c of rg.columns) {
+                assert.equal(c.codec, 'SNAPPY');
+                assert.equal(c.numValues, rg.numRows);
+                assert.ok(c.encodings.length > 0);
+            }
+        }
+        assert.deepEqual(rowGroups, (await readParquetMetadata(bytes)).rowGroups);
+    });
+
+    it('rejects columns of different lengths', async () => {
+        await assert.rejects(writeParquet(schema, { id: [1, 2], tag: ['a'] }));
     });
 });
 
-// ── Output format ────────────────────────────────────────────────────────────
+describe('bloom filters', () => {
+    const schema = [
+        { name: 'id', type: 'int64' },
+        { name: 'email', type: 'string', optional: true },
+        { name: 'active', type: 'boolean' },
+    ];
+    const n = 1_000;
+    const data = {
+        id: Array.from({ length: n }, (_, i) => i),
+        email: Array.from({ length: n }, (_, i) => (i % 7 ? `user${i}@example.com` : null)),
+        active: Array.from({ length: n }, (_, i) => i % 2 === 0),
+    };
+    const offsets = (footer) => footer.rowGroups.map((rg) => rg.columns.map((c) => c.metaData.bloomFilterOffset));
 
-describe('output format', () => {
-    it('produces valid Parquet magic bytes', async () => {
-        const bytes = await writeParquet(
-            [{ name: 'x', type: 'int32' }],
-            { x: [1] },
-        );
-        // Parquet files start and end with "PAR1"
-        const magic = String.fromCharCode(...bytes.slice(0, 4));
-        const footer = String.fromCharCode(...bytes.slice(-4));
-        assert.equal(magic, 'PAR1', 'should start with PAR1');
-        assert.equal(footer, 'PAR1', 'should end with PAR1');
+    it('writes one filter per listed column and row group', async () => {
+        const bytes = await writeParquet(schema, data, { rowGroupSize: 400, bloomFilters: ['email'] });
+        const found = offsets(await dumpFooter(bytes));
+        assert.equal(found.length, 3);
+        for (const [id, email, active] of found) {
+            assert.equal(id, undefined);
+            assert.ok(email > 0);
+            assert.equal(active, undefined);
+        }
+        const { data: back } = await readParquet(bytes, n);
+        assert.deepEqual(back.email, data.email);
     });
 
-    it('readParquet returns correct schema types', async () => {
-        const schema = [
-            { name: 'a', type: 'string' },
-            { name: 'b', type: 'int32' },
-            { name: 'c', type: 'boolean' },
-        ];
-        const data = { a: ['x'], b: [1], c: [true] };
-        const result = await roundtrip(schema, data)();
-        assert.equal(result.schema[0].type, 'string');
-        assert.equal(result.schema[1].type, 'int32');
-        assert.equal(result.schema[2].type, 'boolean');
+    it('sizes filters by fpp and ndv', async () => {
+        const size = async (bloomFilters) => (await writeParquet(schema, data, { bloomFilters, statistics: false })).length;
+        const base = await size(undefined);
+        const loose = await size({ id: true });
+        const tight = await size({ id: { fpp: 0.0001 } });
+        const big = await size({ id: { ndv: 100_000 } });
+        assert.ok(base < loose && loose < tight && tight < big);
+    });
+
+    it('keeps filters through sink, merge and append', async () => {
+        const chunks = [];
+        await writeParquet(schema, data, { rowGroupSize: 400, bloomFilters: ['id'], sink: (c) => chunks.push(c.slice()) });
+        const streamed = new Uint8Array(chunks.reduce((len, c) => len + c.length, 0));
+        chunks.reduce((at, c) => (streamed.set(c, at), at + c.length), 0);
+        const bytes = await writeParquet(schema, data, { rowGroupSize: 400, bloomFilters: ['id'] });
+        assert.deepEqual(streamed, bytes);
+
+        const merged = offsets(await dumpFooter(await mergeParquet([bytes, bytes])));
+        assert.equal(merged.length, 6);
+        assert.ok(merged.every(([id]) => id > 0));
+        assert.equal(new Set(merged.map(([id]) => id)).size, 6);
+    });
+
+    it('rejects bad settings', async () => {
+        await assert.rejects(writeParquet(schema, data, { bloomFilters: ['nope'] }), /bloomFilters: unknown column 'nope'/);
+        await assert.rejects(writeParquet(schema, data, { bloomFilters: ['active'] }), /boolean/);
+        await assert.rejects(writeParquet(schema, data, { bloomFilters: { id: { fpp: 1.5 } } }), /fpp for 'id' must be between 0 and 1/);
+        await assert.rejects(writeParquet(schema, data, { bloomFilters: 'id' }), /bloomFilters must be/);
+    });
+
+    it('answers mightContain and lists filter offsets', async () => {
+        const bytes = await writeParquet(schema, data, { rowGroupSize: 400, bloomFilters: ['id', 'email'] });
+        const file = await ParquetFile.open(bytes);
+        for (const i of [0, 1, 399, 400, 999]) assert.equal(file.mightContain('id', i), true);
+        assert.equal(file.mightContain('id', 5n), true);
+        assert.equal(file.mightContain('email', 'user1@example.com'), true);
+        assert.equal(file.mightContain('email', 'nobody@example.com'), false);
+        // Each of the three row groups' filters lets about 1% of absent values
+        // through, so roughly 6 of these 200 come back as maybe
+        const absent = Array.from({ length: 200 }, (_, i) => n + i).filter((v) => file.mightContain('id', v));
+        assert.ok(absent.length < 20, `${absent.length} false positives`);
+        // No filter, or a value of another type: maybe
+        assert.equal(file.mightContain('active', true), true);
+        assert.equal(file.mightContain('id', '12345'), true);
+        assert.throws(() => file.mightContain('nope', 1), /unknown column 'nope'/);
+
+        const { rowGroups } = await readParquetMetadata(bytes);
+        for (const rg of rowGroups) {
+            const [id, email, active] = rg.columns.map((c) => c.bloomFilterOffset);
+            assert.ok(id > 0 && email > 0);
+            assert.equal(active, null);
+        }
+        file.free();
+    });
+
+    it('skips row groups an equality filter rules out', async () => {
+        // Interleaved ids: every row group's min/max covers every id
+        const ids = Array.from({ length: n }, (_, i) => (i % 250) * 4 + Math.floor(i / 250));
+        const write = (config) => writeParquet(schema, { ...data, id: ids }, { rowGroupSize: 250, ...config });
+        // Zero the id chunks of all row groups but the second, so decoding them fails
+        const corrupt = async (bytes) => {
+            const { rowGroups } = await readParquetMetadata(bytes);
+            rowGroups.forEach((rg, i) => i !== 1 && bytes.fill(0, rg.columns[0].offset, rg.columns[0].offset + rg.columns[0].compressedSize));
+            return bytes;
+        };
+        const filter = [{ column: 'id', op: '=', value: 5 }];
+        await assert.rejects(readParquet(await corrupt(await write({})), { filter }));
+
+        const bytes = await corrupt(await write({ bloomFilters: ['id'] }));
+        const { data: found } = await readParquet(bytes, { filter, columns: ['id'] });
+        assert.deepEqual(found.id, [5]);
+        const some = await readParquet(bytes, { filter: [{ column: 'id', op: 'in', value: [1, 9, 13] }], columns: ['id'] });
+        assert.deepEqual(some.data.id, [1, 9, 13]);
+    });
+});
+
+describe('page indexes', () => {
+    const schema = [
+        { name: 'id', type: 'int32' },
+        { name: 'score', type: 'float64', optional: true },
+    ];
+    const n = 1_000;
+    const data = {
+        id: Array.from({ length: n }, (_, i) => i),
+        score: Array.from({ length: n }, (_, i) => (i < 100 ? null : (i * 7919) % 1000)),
+    };
+    const indexes = (footer) => footer.rowGroups.flatMap((rg) => rg.columns.map((c) => [c.columnIndexOffset, c.offsetIndexOffset]));
+
+    it('writes a column and offset index for every chunk', async () => {
+        const bytes = await writeParquet(schema, data, { rowGroupSize: 400, dataPageRows: 50 });
+        const found = indexes(await dumpFooter(bytes));
+        assert.equal(found.length, 6);
+        for (const [column, offset] of found) assert.ok(column > 0 && offset > column);
+
+        const filter = [{ column: 'id', op: '>=', value: 420 }, { column: 'id', op: '<', value: 425 }];
+        assert.deepEqual((await readParquet(bytes, { filter })).data.id, [420, 421, 422, 423, 424]);
+        const nulls = await readParquet(bytes, { filter: [{ column: 'score', op: 'is null' }], maxRows: Infinity });
+        assert.deepEqual(nulls.data.id, data.id.slice(0, 100));
+    });
+
+    it('keeps indexes in step with sink output and checksums', async () => {
+        const chunks = [];
+        await writeParquet(schema, data, { rowGroupSize: 400, dataPageRows: 50, sink: (c) => chunks.push(c.slice()) });
+        const streamed = new Uint8Array(chunks.reduce((len, c) => len + c.length, 0));
+        chunks.reduce((at, c) => (streamed.set(c, at), at + c.length), 0);
+        assert.deepEqual(streamed, await writeParquet(schema, data, { rowGroupSize: 400, dataPageRows: 50 }));
+
+        const bytes = await writeParquet(schema, data, { rowGroupSize: 400, dataPageRows: 50, pageChecksums: true });
+        const filter = [{ column: 'id', op: '=', value: 777 }];
+        assert.deepEqual((await readParquet(bytes, { filter, verifyChecksums: true })).data.id, [777]);
+    });
+
+    it('writes only offset indexes without statistics', async () => {
+        const bytes = await writeParquet(schema, data, { dataPageRows: 50, statistics: false });
+        for (const [column, offset] of indexes(await dumpFooter(bytes))) {
+            assert.equal(column, undefined);
+            assert.ok(offset > 0);
+        }
+    });
+});
+
+describe('page index reads', () => {
+    const schema = [
+        { name: 'id', type: 'int32' },
+        { name: 'tag', type: 'string', optional: true },
+    ];
+    const n = 2_000;
+    const data = {
+        id: Array.from({ length: n }, (_, i) => i),
+        tag: Array.from({ length: n }, (_, i) => (i % 4 ? `t${i}` : null)),
+    };
+
+    it('decodes row ranges from the pages that hold them', async () => {
+        const bytes = await writeParquet(schema, data, { rowGroupSize: 1_000, dataPageRows: 100 });
+        for (const [offset, maxRows] of [[0, 5], [95, 10], [950, 100], [1_999, 5], [450, 1_000]]) {
+            const { data: got } = await readParquet(bytes, { offset, maxRows });
+            assert.deepEqual(got.id, data.id.slice(offset, offset + maxRows));
+            assert.deepEqual(got.tag, data.tag.slice(offset, offset + maxRows));
+        }
+        const batches = [];
+        for await (const batch of readParquetBatches(bytes, { batchSize: 300, columns: ['tag'] })) batches.push(...batch.tag);
+        assert.deepEqual(batches, data.tag);
+    });
+
+    it('never touches pages outside the rows read', async () => {
+        const bytes = await writeParquet(schema, data, { rowGroupSize: 1_000, dataPageRows: 100, dictionary: false, compression: 'none' });
+        // Zero every page of the id column but the one holding rows 300..400
+        const pages = await inspectPages(bytes, 'id');
+        for (const page of pages.filter((p) => p.rowGroup !== 0 || p.offset !== pages[3].offset)) {
+            bytes.fill(0, page.offset, page.offset + page.headerSize + page.compressedSize);
+        }
+        const { data: got } = await readParquet(bytes, { columns: ['id'], offset: 310, maxRows: 20 });
+        assert.deepEqual(got.id, data.id.slice(310, 330));
+        const filter = [{ column: 'id', op: '=', value: 377 }];
+        assert.deepEqual((await readParquet(bytes, { columns: ['tag'], filter })).data.tag, ['t377']);
+    });
+});
+
+describe('encryption', () => {
+    const schema = [{ name: 'id', type: 'int32' }, { name: 'ssn', type: 'string' }];
+    const data = { id: [1, 2, 3], ssn: ['secret-111-22-3333', 'secret-444-55-6666', 'secret-777-88-9999'] };
+    const footerKey = new Uint8Array(16).fill(1);
+    const ssnKey = new Uint8Array(32).fill(2);
+    const text = (bytes) => Buffer.from(bytes).toString('latin1');
+
+    it('writes PARE files with nothing readable but the crypto metadata', async () => {
+        const bytes = await writeParquet(schema, data, {
+            compression: 'none',
+            metadata: { owner: 'plain-metadata-value' },
+            bloomFilters: ['ssn'],
+            encryption: { footerKey, footerKeyMetadata: 'kms:footer', columnKeys: { ssn: { key: ssnKey, keyMetadata: 'kms:ssn' } } },
+        });
+        assert.equal(text(bytes.subarray(0, 4)), 'PARE');
+        assert.equal(text(bytes.subarray(-4)), 'PARE');
+        assert.ok(!text(bytes).includes('secret-'));
+        assert.ok(!text(bytes).includes('plain-metadata-value'));
+        assert.ok(!text(bytes).includes('kms:ssn')); // inside the encrypted footer
+        const footerLen = new DataView(bytes.buffer, bytes.byteOffset).getUint32(bytes.length - 8, true);
+        assert.ok(text(bytes.subarray(-8 - footerLen, -8)).includes('kms:footer'));
+    });
+
+    it('seals the footer as standard AES-GCM that WebCrypto can open', async () => {
+        for (const key of [footerKey, new Uint8Array(24).fill(3), ssnKey]) {
+            const bytes = await writeParquet(schema, data, { encryption: { footerKey: key } });
+            const footerLen = new DataView(bytes.buffer, bytes.byteOffset).getUint32(bytes.length - 8, true);
+            const footer = bytes.subarray(-8 - footerLen, -8);
+            // FileCryptoMetaData { AES_GCM_V1 { aad_file_unique: 8 bytes } }, then the footer module
+            assert.deepEqual([...footer.subarray(0, 4)], [0x1c, 0x1c, 0x28, 0x08]);
+            const unique = footer.subarray(4, 12);
+            const module = footer.subarray(15);
+            assert.equal(new DataView(module.buffer, module.byteOffset).getUint32(0, true), module.length - 4);
+            const cryptoKey = await crypto.subtle.importKey('raw', key, 'AES-GCM', false, ['decrypt']);
+            const plain = await crypto.subtle.decrypt(
+                { name: 'AES-GCM', iv: module.subarray(4, 16), additionalData: new Uint8Array([...unique, 0]) },
+                cryptoKey,
+                module.subarray(16),
+            );
+            assert.ok(text(new Uint8Array(plain)).includes('ssn'));
+        }
+    });
+
+    it('uses fresh nonces for every file', async () => {
+        const a = await writeParquet(schema, data, { encryption: { footerKey } });
+        const b = await writeParquet(schema, data, { encryption: { footerKey } });
+        assert.equal(a.length, b.length);
+        assert.notDeepEqual(a, b);
+    });
+
+    it('encrypts checksummed pages of every kind', async () => {
+        const bytes = await writeParquet(schema, data, { version: 'v2', pageChecksums: true, dataPageRows: 1, encryption: { footerKey, aadPrefix: 'exports/users.parquet' } });
+        assert.equal(text(bytes.subarray(0, 4)), 'PARE');
+        assert.ok(!text(bytes).includes('secret-'));
+    });
+
+    it('rejects bad keys and unsupported combinations', async () => {
+        await assert.rejects(writeParquet(schema, data, { encryption: {} }), /footerKey is required/);
+        await assert.rejects(writeParquet(schema, data, { encryption: { footerKey: new Uint8Array(10) } }), /16, 24 or 32 bytes, got 10/);
+        await assert.rejects(writeParquet(schema, data, { encryption: { footerKey: 'key' } }), /footerKey must be a Uint8Array/);
+        await assert.rejects(writeParquet(schema, data, { encryption: { footerKey, columnKeys: { nope: ssnKey } } }), /unknown column 'nope'/);
+        await assert.rejects(writeParquet(schema, data, { encryption: { footerKey }, sink: () => {} }), /encryption can't be combined with sink/);
+        const plain = await writeParquet(schema, data);
+        await assert.rejects(appendToParquet(plain, schema, data, { encryption: { footerKey } }), /doesn't support encryption/);
+        const encrypted = await writeParquet(schema, data, { encryption: { footerKey } });
+        await assert.rejects(mergeParquet([plain, encrypted]), /file 1: encrypted files aren't supported/);
+    });
+
+    it('reads files back with the footer and column keys', async () => {
+        const bytes = await writeParquet(schema, data, {
+            bloomFilters: ['ssn'],
+            encryption: { footerKey, columnKeys: { ssn: ssnKey }, aadPrefix: 'users' },
+        });
+        const decryption = { footerKey, columnKeys: { ssn: ssnKey } };
+        const result = await readParquet(bytes, { decryption });
+        assert.deepEqual(result.data, data);
+        const filtered = await readParquet(bytes, { decryption, filter: [{ column: 'ssn', op: '=', value: 'secret-444-55-6666' }] });
+        assert.deepEqual(filtered.data.id, [2]);
+        const file = await ParquetFile.open(bytes, { decryption });
+        assert.equal(file.mightContain('ssn', 'nope'), false);
+        file.free();
+        const info = await readParquetMetadata(bytes, { decryption: { footerKey } });
+        assert.equal(info.numRows, 3);
+    });
+
+    it('reads other columns without a column key', async () => {
+        const bytes = await writeParquet(schema, data, { encryption: { footerKey, columnKeys: { ssn: { key: ssnKey, keyMetadata: 'kms:ssn' } } } });
+        const { data: ids } = await readParquet(bytes, { columns: ['id'], decryption: { footerKey } });
+        assert.deepEqual(ids, { id: [1, 2, 3] });
+        await assert.rejects(readParquet(bytes, { decryption: { footerKey } }), /column 'ssn' is encrypted with its own key.*kms:ssn/);
+    });
+
+    it('tells missing keys apart from wrong keys and corruption', async () => {
+        const bytes = await writeParquet(schema, data, { compression: 'none', encryption: { footerKey, footerKeyMetadata: 'kms:footer' } });
+        await assert.rejects(readParquet(bytes), /file is encrypted: pass decryption.footerKey to read it.*kms:footer/);
+        await assert.rejects(readParquet(bytes, { decryption: { footerKey: new Uint8Array(16) } }), /wrong footerKey, or the file is corrupted/);
+        const tampered = bytes.slice();
+        tampered[20] ^= 1;
+        await assert.rejects(readParquet(tampered, { decryption: { footerKey } }), /can't decrypt .*wrong key, or the file is corrupted/);
+    });
+});
+
+describe('encrypted files from parquet-cpp', () => {
+    const encrypted = (name) => fixture(`${name}.parquet.encrypted`);
+    const key = (s) => new TextEncoder().encode(s);
+    const footerKey = key('0123456789012345');
+    const columnKeys = { double_field: key('1234567890123450'), float_field: key('1234567890123451') };
+
+    const check = async (name, decryption) => {
+        const { numRows, data } = await readParquet(encrypted(name), { decryption });
+        assert.equal(numRows, 50);
+        assert.deepEqual(data.boolean_field.slice(0, 4), [true, false, true, false]);
+        assert.deepEqual(data.double_field.slice(0, 4), [0, 1.1111111, 2.2222222, 3.3333333]);
+        assert.deepEqual(data.float_field.slice(0, 4), [0, 1, 2, 3].map((i) => Math.fround(i * Math.fround(1.1))));
+        assert.equal(Buffer.from(data.ba_field[2]).toString(), 'parquet002');
+    };
+
+    it('reads AES_GCM_V1 files with the footer and column keys', async () => {
+        await check('uniform_encryption', { footerKey });
+        await check('encrypt_columns_and_footer', { footerKey, columnKeys });
+    });
+
+    it('reads AES_GCM_CTR_V1 files', async () => {
+        await check('encrypt_columns_and_footer_ctr', { footerKey, columnKeys });
+    });
+
+    it('uses the AAD prefix the file stores, or the one passed when it does not', async () => {
+        await check('encrypt_columns_and_footer_aad', { footerKey, columnKeys });
+        await check('encrypt_columns_and_footer_disable_aad_storage', { footerKey, columnKeys, aadPrefix: 'tester' });
+        const bytes = encrypted('encrypt_columns_and_footer_disable_aad_storage');
+        await assert.rejects(readParquet(bytes, { decryption: { footerKey, columnKeys } }), /pass decryption.aadPrefix/);
+        await assert.rejects(readParquet(bytes, { decryption: { footerKey, columnKeys, aadPrefix: 'nope' } }), /wrong footerKey/);
+    });
+
+    it('checks the signature of plaintext footers', async () => {
+        const bytes = encrypted('encrypt_columns_plaintext_footer');
+        await check('encrypt_columns_plaintext_footer', { footerKey, columnKeys });
+        const { data } = await readParquet(bytes, { columns: ['boolean_field'] });
+        assert.equal(data.boolean_field.length, 50);
+        await assert.rejects(readParquet(bytes, { decryption: { footerKey: key('0123456789012346') } }), /footer signature doesn't match/);
+    });
+
+    it('rejects a wrong column key', async () => {
+        const wrong = { ...columnKeys, float_field: columnKeys.double_field };
+        await assert.rejects(readParquet(encrypted('encrypt_columns_and_footer'), { decryption: { footerKey, columnKeys: wrong } }), /column 'float_field': can't decrypt its metadata/);
+    });
+});
+
+describe('INT96 timestamps from Impala', () => {
+    it('reads them as epoch milliseconds', async () => {
+        const { schema, data } = await readParquet(fixture('alltypes_plain.parquet'), { columns: ['id', 'timestamp_col'] });
+        assert.equal(schema.find((c) => c.name === 'timestamp_col').type, 'timestamp');
+        assert.deepEqual(Array.from(data.id), [4, 5, 6, 7, 2, 3, 0, 1]);
+        // Midnight and one minute past on the 1st of each month, January to April 2009
+        assert.deepEqual(Array.from(data.timestamp_col), [
+            Date.UTC(2009, 2, 1), Date.UTC(2009, 2, 1, 0, 1), Date.UTC(2009, 3, 1), Date.UTC(2009, 3, 1, 0, 1),
+            Date.UTC(2009, 1, 1), Date.UTC(2009, 1, 1, 0, 1), Date.UTC(2009, 0, 1), Date.UTC(2009, 0, 1, 0, 1),
+        ]);
+    });
+});
+
+describe('incremental writer', () => {
+    const schema = [
+        { name: 'id', type: 'int32' },
+        { name: 'tag', type: 'string', optional: true },
+    ];
+
+    it('writes one row group per append', async () => {
+        const writer = new ParquetWriter(schema, { rowGroupSize: 3 });
+        await writer.appendRows([{ id: 1, tag: 'a' }, { id: 2 }]);
+        await writer.appendColumns({ id: [3, 4, 5, 6], tag: ['b', null, 'c', 'd'] });
+        assert.equal(writer.numRows, 6);
+        assert.equal(writer.numRowGroups, 3);
+        const bytes = await writer.finish();
+
+        const result = await readParquet(bytes);
+        assert.equal(result.numRows, 6);
+        assert.deepEqual(result.data.id, [1, 2, 3, 4, 5, 6]);
+        assert.deepEqual(result.data.tag, ['a', null, 'b', null, 'c', 'd']);
+        assert.deepEqual((await readParquet(bytes, { rowGroups: [1] })).data.id, [3, 4, 5]);
+    });
+
+    it('keeps going after a rejected append', async () => {
+        const writer = new ParquetWriter(schema);
+        await writer.appendRows([{ id: 1 }]);
+        await assert.rejects(writer.appendColumns({ id: [2, 3], tag: ['x'] }), /column 'tag' has 1 values, expected 2/);
+        await assert.rejects(writer.appendRows({ id: [2] }), /appendRows expects an array/);
+        await writer.appendRows([{ id: 2 }]);
+        const result = await readParquet(await writer.finish());
+        assert.deepEqual(result.data.id, [1, 2]);
+        await assert.rejects(writer.appendRows([{ id: 3 }]), /already finished/);
+    });
+
+    it('hands row groups to a sink as they are written', async () => {
+        const chunks = [];
+        const writer = new ParquetWriter(schema, { sink: (chunk) => chunks.push(chunk.slice()) });
+        await writer.appendRows([{ id: 1, tag: 'a' }]);
+        assert.equal(chunks.length, 1);
+        await writer.appendRows([{ id: 2 }]);
+        assert.equal(chunks.length, 2);
+        assert.equal(await writer.finish(), undefined);
+        assert.equal(chunks.length, 3);
+
+        const bytes = new Uint8Array(chunks.reduce((n, c) => n + c.length, 0));
+        chunks.reduce((offset, c) => (bytes.set(c, offset), offset + c.length), 0);
+        assert.deepEqual((await readParquet(bytes)).data, { id: [1, 2], tag: ['a', null] });
+    });
+
+    it('writes to a WritableStream and closes it', async () => {
+        const data = { id: [1, 2, 3], tag: ['x', null, 'z'] };
+        const expected = await writeParquet(schema, data);
+        const chunks = [];
+        let closed = false;
+        const stream = new WritableStream({ write: (c) => { chunks.push(c); }, close: () => { closed = true; } });
+        assert.equal(await writeParquet(schema, data, { sink: stream }), undefined);
+        assert.ok(closed);
+        assert.deepEqual(Buffer.concat(chunks), Buffer.from(expected));
+        await assert.rejects(writeParquet(schema, data, { sink: () => {}, pageChecksums: true }), /pageChecksums/);
+    });
+
+    it('reports schema errors on first use', async () => {
+        const writer = new ParquetWriter([{ name: 'x', type: 'list' }]);
+        await assert.rejects(writer.finish(), /column 'x': list columns need 'items'/);
+    });
+});
+
+describe('mergeParquet', () => {
+    const schema = [
+        { name: 'id', type: 'int32' },
+        { name: 'tag', type: 'string', optional: true },
+        { name: 'half', type: 'float16' },
+    ];
+    const part = (from, n, config) => writeParquet(schema, {
+        id: Array.from({ length: n }, (_, i) => from + i),
+        tag: Array.from({ length: n }, (_, i) => (i % 2 ? null : `t${from + i}`)),
+        half: Array.from({ length: n }, (_, i) => i / 2),
+    }, config);
+
+    it('concatenates row groups in order', async () => {
+        const a = await part(0, 5, { rowGroupSize: 2, metadata: { source: 'a' } });
+        const b = await part(5, 3, { compression: 'zstd', metadata: { source: 'b' } });
+        const merged = await mergeParquet([a, b]);
+        const result = await readParquet(merged, { maxRows: Infinity });
+        assert.equal(result.numRows, 8);
+        assert.deepEqual(result.data.id, [0, 1, 2, 3, 4, 5, 6, 7]);
+        assert.deepEqual(result.data.tag, ['t0', null, 't2', null, 't4', 't5', null, 't7']);
+        assert.deepEqual(result.data.half, [0, 0.5, 1, 1.5, 2, 0, 0.5, 1]);
+        assert.equal(result.schema[2].type, 'float16');
+        assert.equal(result.metadata.source, 'a');
+        const file = await ParquetFile.open(merged);
+        assert.equal(file.numRowGroups, 4);
+        assert.deepEqual(file.readRowGroup(3).id, [5, 6, 7]);
+        file.free();
+    });
+
+    it('keeps page indexes and checksums usable', async () => {
+        const a = await part(0, 50, { dataPageRows: 10, pageChecksums: true });
+        const b = await part(50, 50, { dataPageRows: 10, pageChecksums: true });
+        const merged = await mergeParquet([a, b, a]);
+        const result = await readParquet(merged, {
+            columns: ['id'],
+            filter: [{ column: 'id', op: '>=', value: 73 }, { column: 'id', op: '<', value: 76 }],
+            verifyChecksums: true,
+        });
+        assert.deepEqual(result.data.id, [73, 74, 75]);
+        assert.equal(result.numRows, 150);
+    });
+
+    it('rejects mismatched or broken input', async () => {
+        const a = await part(0, 2);
+        const other = await writeParquet([{ name: 'id', type: 'int64' }], { id: [1] });
+        await assert.rejects(mergeParquet([a, other]), /file 1: schema differs from file 0/);
+        await assert.rejects(mergeParquet([a, new Uint8Array(20)]), /file 1: not a parquet file/);
+        await assert.rejects(mergeParquet([]), /at least one file/);
+        await assert.rejects(mergeParquet(a), /array of Uint8Arrays/);
+    });
+});
+
+describe('appendToParquet', () => {
+    const schema = [
+        { name: 'id', type: 'int32' },
+        { name: 'tag', type: 'string', optional: true },
+    ];
+
+    it('adds row groups after the existing ones', async () => {
+        const original = await writeParquet(schema, { id: [1, 2], tag: ['a', null] }, { metadata: { source: 'v1' } });
+        const updated = await appendToParquet(original, schema, [{ id: 3, tag: 'c' }, { id: 4 }], { compression: 'gzip' });
+        const result = await readParquet(updated);
+        assert.equal(result.numRows, 4);
+        assert.deepEqual(result.data, { id: [1, 2, 3, 4], tag: ['a', null, 'c', null] });
+        assert.equal(result.metadata.source, 'v1');
+
+        // The original bytes before its footer are untouched
+        const footerLen = new DataView(original.buffer, original.byteOffset).getUint32(original.length - 8, true);
+        const kept = original.length - 8 - footerLen;
+        assert.deepEqual(Buffer.from(updated.subarray(0, kept)), Buffer.from(original.subarray(0, kept)));
+
+        const again = await appendToParquet(updated, schema, { id: [5], tag: ['e'] });
+        const file = await ParquetFile.open(again);
+        assert.equal(file.numRowGroups, 3);
+        assert.deepEqual(file.readColumn('id'), [1, 2, 3, 4, 5]);
+        file.free();
+    });
+
+    it('rejects a different schema', async () => {
+        const original = await writeParquet(schema, { id: [1], tag: ['a'] });
+        await assert.rejects(appendToParquet(original, [{ name: 'id', type: 'int64' }], { id: [2] }), /schema doesn't match/);
+        await assert.rejects(appendToParquet(new Uint8Array(16), schema, { id: [2], tag: [null] }), /not a parquet file/);
+        await assert.rejects(appendToParquet(original, schema, { id: [2], tag: [null] }, { sink: () => {} }), /sink/);
+    });
+});
+
+describe('ParquetFile', () => {
+    const schema = [
+        { name: 'id', type: 'int32' },
+        { name: 'tag', type: 'string', optional: true },
+    ];
+    const data = { id: [1, 2, 3, 4, 5], tag: ['a', null, 'b', 'c', null] };
+    const write = () => writeParquet(schema, data, { rowGroupSize: 2, metadata: { source: 'test' } });
+
+    it('reads row groups and columns from one parsed footer', async () => {
+        const file = await ParquetFile.open(await write());
+        assert.equal(file.numRows, 5);
+        assert.equal(file.numRowGroups, 3);
+        assert.deepEqual(file.metadata(), { source: 'test' });
+        assert.deepEqual(file.schema({ columns: ['tag'] }).map((s) => s.name), ['tag']);
+        assert.deepEqual(file.readRowGroup(1), { id: [3, 4], tag: ['b', 'c'] });
+        assert.deepEqual(file.readRowGroup(2, { format: 'rows' }), [{ id: 5, tag: null }]);
+        assert.deepEqual(file.readColumn('tag'), data.tag);
+        assert.deepEqual(file.readColumn('id', { rowGroups: [2, 0] }), [5, 1, 2]);
+        file.free();
+    });
+
+    it('matches readParquet', async () => {
+        const bytes = await write();
+        const file = await ParquetFile.open(bytes);
+        const options = { columns: ['tag', 'id'], offset: 1, maxRows: 3 };
+        assert.deepEqual(file.read(options), await readParquet(bytes, options));
+        file.free();
+    });
+
+    it('rejects unknown columns and row groups', async () => {
+        const file = await ParquetFile.open(await write());
+        assert.throws(() => file.readColumn('nope'), /unknown column 'nope'/);
+        assert.throws(() => file.readRowGroup(3), /row group 3 out of range/);
+        file.free();
+        assert.throws(() => file.numRows, /freed/);
+    });
+});
+
+describe('readParquetMetadata', () => {
+    const schema = [
+        { name: 'id', type: 'int32' },
+        { name: 'tag', type: 'string', optional: true },
+    ];
+    const data = { id: [1, 2, 3, 4, 5], tag: ['a', null, 'b', 'c', null] };
+    const write = (config = {}) => writeParquet(schema, data, { rowGroupSize: 2, metadata: { source: 'test' }, ...config });
+
+    it('describes the footer without reading pages', async () => {
+        const info = await readParquetMetadata(await write({ compression: 'none' }));
+        assert.equal(info.numRows, 5);
+        assert.equal(typeof info.version, 'number');
+        assert.deepEqual(info.schema.map((s) => [s.name, s.type]), [['id', 'int32'], ['tag', 'string']]);
+        assert.deepEqual(info.metadata, { source: 'test' });
+        assert.deepEqual(info.rowGroups.map((rg) => rg.numRows), [2, 2, 1]);
+        const [id, tag] = info.rowGroups[0].columns;
+        assert.equal(id.path, 'id');
+        assert.equal(tag.path, 'tag');
+        assert.equal(id.codec, 'UNCOMPRESSED');
+        assert.equal(id.numValues, 2);
+        assert.ok(id.encodings.length > 0);
+        assert.equal(id.compressedSize, id.uncompressedSize);
+        assert.equal(id.offset, 4);
+        assert.equal(info.rowGroups[0].compressedSize, id.compressedSize + tag.compressedSize);
+    });
+
+    it('only needs the footer', async () => {
+        const bytes = await write();
+        const info = await readParquetMetadata(bytes);
+        // Garbage in place of the pages doesn't matter
+        const damaged = bytes.slice();
+        damaged.fill(0xff, 4, info.rowGroups.at(-1).columns.at(-1).offset);
+        assert.deepEqual(await readParquetMetadata(damaged), info);
+        assert.deepEqual(await readParquetMetadata(new Blob([bytes])), info);
+    });
+
+    it('matches ParquetFile.info and takes schema options', async () => {
+        const bytes = await write({ compression: 'snappy' });
+        const file = await ParquetFile.open(bytes);
+        assert.deepEqual(await readParquetMetadata(file), await readParquetMetadata(bytes));
+        assert.equal(file.info().rowGroups[0].columns[0].codec, 'SNAPPY');
+        assert.deepEqual(file.info({ columns: ['tag'] }).schema.map((s) => s.name), ['tag']);
+        file.free();
+    });
+
+    it('rejects bytes that aren\'t parquet', async () => {
+        await assert.rejects(readParquetMetadata(new Uint8Array([1, 2, 3])), /metadata/);
+    });
+});
+
+describe('readParquetSchema', () => {
+    const schema = [
+        { name: 'id', type: 'int32' },
+        { name: 'tags', type: 'list', items: { type: 'string' }, optional: true },
+        { name: 'point', type: 'struct', fields: [{ name: 'x', type: 'float64' }, { name: 'y', type: 'float64' }] },
+    ];
+    const data = {
+        id: [1, 2],
+        tags: [['a'], null],
+        point: [{ x: 1, y: 2 }, { x: 3, y: 4 }],
+    };
+
+    it('returns names and types without statistics', async () => {
+        const bytes = await writeParquet(schema, data);
+        const entries = await readParquetSchema(bytes);
+        assert.deepEqual(entries, [
+            { name: 'id', type: 'int32' },
+            { name: 'tags', type: 'list', optional: true, items: { type: 'string' } },
+            { name: 'point', type: 'struct', fields: [{ name: 'x', type: 'float64' }, { name: 'y', type: 'float64' }] },
+        ]);
+        const { schema: full } = await readParquet(bytes);
+        assert.ok(full[0].statistics);
+    });
+
+    it('reads from the tail of a file', async () => {
+        const bytes = await writeParquet(schema, data);
+        const expected = await readParquetSchema(bytes);
+        const footer = new DataView(bytes.buffer, bytes.byteOffset).getUint32(bytes.length - 8, true) + 8;
+        assert.deepEqual(await readParquetSchema(bytes.slice(-footer)), expected);
+        assert.deepEqual(await readParquetSchema(new Blob([bytes])), expected);
+        await assert.rejects(readParquetSchema(bytes.slice(1 - footer)), new RegExp(`footer is ${footer} bytes`));
+    });
+});
+
+describe('dumpFooter', () => {
+    it('returns the thrift metadata field for field', async () => {
+        const schema = [
+            { name: 'id', type: 'int64' },
+            { name: 'ts', type: 'timestamp', optional: true },
+            { name: 'tags', type: 'list', items: { type: 'string' } },
+        ];
+        const data = { id: [1, 2, 3], ts: [0, null, 1000], tags: [['a'], [], ['b', 'c']] };
+        const bytes = await writeParquet(schema, data, { compression: 'snappy', metadata: { source: 'test' } });
+        const footer = await dumpFooter(bytes);
+        assert.equal(footer.numRows, 3);
+        assert.deepEqual(footer.schema.map((el) => el.name), ['schema', 'id', 'ts', 'tags', 'list', 'element']);
+        assert.equal(footer.schema[0].numChildren, 3);
+        assert.deepEqual([footer.schema[1].type, footer.schema[1].repetitionType], ['INT64', 'REQUIRED']);
+        assert.equal(footer.schema[2].repetitionType, 'OPTIONAL');
+        assert.ok(footer.schema[2].logicalType.TIMESTAMP);
+        assert.equal(footer.schema[5].convertedType, 'UTF8');
+        assert.deepEqual(footer.schema[3].logicalType, { LIST: {} });
+        assert.deepEqual(footer.keyValueMetadata, [{ key: 'source', value: 'test' }]);
+
+        const [id, , element] = footer.rowGroups[0].columns;
+        assert.equal(id.metaData.codec, 'SNAPPY');
+        assert.deepEqual(id.metaData.pathInSchema, ['id']);
+        assert.deepEqual(element.metaData.pathInSchema, ['tags', 'list', 'element']);
+        assert.equal(id.metaData.numValues, 3);
+        assert.equal(id.metaData.statistics.nullCount, 0);
+        assert.equal(id.metaData.statistics.minValue, '0100000000000000');
+        assert.deepEqual(JSON.parse(JSON.stringify(footer)), footer);
+    });
+
+    it('needs only the footer', async () => {
+        const bytes = await writeParquet([{ name: 'a', type: 'int32' }], { a: [1, 2] });
+        const footer = new DataView(bytes.buffer, bytes.byteOffset).getUint32(bytes.length - 8, true) + 8;
+        assert.deepEqual(await dumpFooter(bytes.slice(-footer)), await dumpFooter(bytes));
+        await assert.rejects(dumpFooter(new Uint8Array(16)), /metadata/);
+    });
+});
+
+describe('inspectPages', () => {
+    const schema = [
+        { name: 'id', type: 'int32' },
+        { name: 'tag', type: 'string', optional: true },
+        { name: 'tags', type: 'list', items: { type: 'string' } },
+    ];
+    const data = {
+        id: Array.from({ length: 10 }, (_, i) => i),
+        tag: Array.from({ length: 10 }, (_, i) => (i % 3 ? 'x' : null)),
+        tags: Array.from({ length: 10 }, () => ['a', 'b']),
+    };
+
+    it('lists dictionary and data pages from their headers', async () => {
+        const bytes = await writeParquet(schema, data, { rowGroupSize: 5, dataPageRows: 2, version: 'v2', pageChecksums: true });
+        const pages = await inspectPages(bytes, 'tag');
+        assert.deepEqual(pages.map((p) => [p.rowGroup, p.type]), [
+            [0, 'dictionary'], [0, 'data'], [0, 'data'], [0, 'data'],
+            [1, 'dictionary'], [1, 'data'], [1, 'data'], [1, 'data'],
+        ]);
+        const [dict, first] = pages;
+        assert.equal(dict.numValues, 1);
+        assert.equal(dict.version, undefined);
+        assert.equal(first.version, 2);
+        assert.equal(first.encoding, 'RLE_DICTIONARY');
+        assert.equal(first.numRows, 2);
+        assert.equal(first.numNulls, 1);
+        assert.equal(first.offset, dict.offset + dict.headerSize + dict.compressedSize);
+        assert.ok(pages.every((p) => p.crc));
+
+        const { rowGroups } = await readParquetMetadata(bytes);
+        assert.equal(dict.offset, rowGroups[0].columns[1].offset);
+    });
+
+    it('takes leaf paths and open files', async () => {
+        const bytes = await writeParquet(schema, data, { dictionary: false });
+        const file = await ParquetFile.open(bytes);
+        const pages = file.inspectPages('tags.list.element');
+        assert.deepEqual(pages.map((p) => [p.type, p.version, p.encoding, p.numValues]), [['data', 1, 'PLAIN', 20]]);
+        assert.deepEqual(await inspectPages(file, 'tags.list.element'), pages);
+        // A top-level column with a single leaf can go by its name
+        assert.deepEqual(file.inspectPages('tags'), pages);
+        assert.throws(() => file.inspectPages('tags.list'), /unknown column 'tags.list'/);
+        file.free();
+    });
+});
+
+describe('readParquetBatches', () => {
+    const schema = [{ name: 'id', type: 'int32' }];
+    const data = { id: Array.from({ length: 25 }, (_, i) => i) };
+
+    it('yields the file in batches', async () => {
+        const bytes = await writeParquet(schema, data, { rowGroupSize: 10 });
+        const batches = [];
+        for await (const batch of readParquetBatches(bytes, { batchSize: 7 })) batches.push(batch.id);
+        assert.deepEqual(batches.map((b) => b.length), [7, 7, 7, 4]);
+        assert.deepEqual(batches.flat(), data.id);
+    });
+
+    it('honours offset, maxRows and format on an open file', async () => {
+        const file = await ParquetFile.open(await writeParquet(schema, data));
+        const batches = [];
+        for await (const rows of readParquetBatches(file, { batchSize: 4, offset: 3, maxRows: 6, format: 'rows' })) batches.push(rows);
+        assert.deepEqual(batches, [[{ id: 3 }, { id: 4 }, { id: 5 }, { id: 6 }], [{ id: 7 }, { id: 8 }]]);
+        assert.equal(file.numRows, 25); // still open
+        file.free();
+    });
+});
+
+describe('stream input', () => {
+    const schema = [{ name: 'id', type: 'int32' }, { name: 's', type: 'string' }];
+    const data = { id: [1, 2, 3], s: ['a', 'b', 'c'] };
+    const chunked = (bytes, size) => Array.from({ length: Math.ceil(bytes.length / size) }, (_, i) => bytes.subarray(i * size, (i + 1) * size));
+
+    it('reads a ReadableStream', async () => {
+        const chunks = chunked(await writeParquet(schema, data), 7);
+        const stream = new ReadableStream({
+            pull(controller) {
+                if (chunks.length) controller.enqueue(chunks.shift());
+                else controller.close();
+            },
+        });
+        assert.deepEqual((await readParquet(stream)).data, data);
+    });
+
+    it('reads async iterables into a ParquetFile', async () => {
+        const bytes = await writeParquet(schema, data);
+        async function* gen() { yield* chunked(bytes, 100); }
+        const file = await ParquetFile.open(gen());
+        assert.deepEqual(file.readColumn('s'), data.s);
+        file.free();
+        await assert.rejects(readParquet('nope'), /expected a Uint8Array/);
+    });
+});
+
+describe('ParquetFile.fromUrl', () => {
+    // Serves `bytes` with Range support, recording the ranges asked for
+    async function serve(bytes) {
+        const requests = [];
+        const server = createServer((req, res) => {
+            const [, from, to] = /bytes=(\d*)-(\d*)/.exec(req.headers.range);
+            const start = from === '' ? bytes.length - Math.min(Number(to), bytes.length) : Number(from);
+            const end = from === '' || to === '' ? bytes.length : Number(to) + 1;
+            requests.push([start, end]);
+            res.writeHead(206, { 'Content-Range': `bytes ${start}-${end - 1}/${bytes.length}` });
+            res.end(bytes.subarray(start, end));
+        });
+        await new Promise((resolve) => server.listen(0, resolve));
+        return { url: `http://localhost:${server.address().port}/file.parquet`, requests, close: () => server.close() };
+    }
+
+    it('fetches only the chunks a read needs', async () => {
+        const n = 20_000;
+        const schema = [
+            { name: 'id', type: 'int32' },
+            { name: 'payload', type: 'string' },
+        ];
+        const data = { id: Array.from({ length: n }, (_, i) => i), payload: Array.from({ length: n }, (_, i) => `row ${i} `.repeat(20)) };
+        const bytes = await writeParquet(schema, data, { rowGroupSize: 5_000, compression: 'none' });
+        const server = await serve(bytes);
+        try {
+            const file = await ParquetFile.fromUrl(server.url);
+            assert.equal(file.numRows, n);
+            assert.equal(server.requests.length, 1);
+
+            const result = await file.read({ columns: ['id'], offset: 6_000, maxRows: 10 });
+            assert.deepEqual(result.data.id, data.id.slice(6_000, 6_010));
+            assert.equal(server.requests.length, 2);
+            const [start, end] = server.requests[1];
+            assert.ok(end - start < 50_000, `fetched ${end - start} bytes`);
+
+            assert.deepEqual(await file.readColumn('id', { rowGroups: [1] }), data.id.slice(5_000, 10_000));
+            assert.equal(server.requests.length, 2); // already loaded
+            assert.deepEqual((await file.readRowGroup(3)).payload, data.payload.slice(15_000));
+            file.free();
+        } finally {
+            server.close();
+        }
+    });
+
+    it('fetches only the pages a read needs', async () => {
+        const n = 20_000;
+        const schema = [
+            { name: 'id', type: 'int32' },
+            { name: 'payload', type: 'string' },
+        ];
+        const data = { id: Array.from({ length: n }, (_, i) => i), payload: Array.from({ length: n }, (_, i) => `row ${i} `.repeat(20)) };
+        const bytes = await writeParquet(schema, data, { compression: 'none', dictionary: false, dataPageRows: 500 });
+        const server = await serve(bytes);
+        const fetched = () => server.requests.slice(1).reduce((sum, [start, end]) => sum + end - start, 0);
+        try {
+            const file = await ParquetFile.fromUrl(server.url);
+            const result = await file.read({ columns: ['payload'], offset: 10_000, maxRows: 10 });
+            assert.deepEqual(result.data.payload, data.payload.slice(10_000, 10_010));
+            assert.ok(fetched() < bytes.length / 20, `fetched ${fetched()} of ${bytes.length} bytes`);
+
+            const before = fetched();
+            const filter = [{ column: 'id', op: '>=', value: 15_250 }, { column: 'id', op: '<', value: 15_253 }];
+            const found = await file.read({ columns: ['payload'], filter });
+            assert.deepEqual(found.data.payload, data.payload.slice(15_250, 15_253));
+            assert.ok(fetched() - before < bytes.length / 20, `fetched ${fetched() - before} more bytes`);
+            file.free();
+        } finally {
+            server.close();
+        }
+    });
+
+    it('has nothing missing for files opened from bytes', async () => {
+        const bytes = await writeParquet([{ name: 'x', type: 'int32' }], { x: [1, 2, 3] });
+        const file = await ParquetFile.open(bytes);
+        assert.deepEqual(file.missingRanges(), []);
+        file.free();
+    });
+});
+
+describe('reader options', () => {
+    const schema = [
+        { name: 'a', type: 'int32' },
+        { name: 'b', type: 'string' },
+        { name: 'c', type: 'float64', optional: true },
+    ];
+    const data = { a: [1, 2, 3], b: ['x', 'y', 'z'], c: [0.5, null, 2.5] };
+
+    it('projects columns in the requested order', async () => {
+        const bytes = await writeParquet(schema, data);
+        const result = await readParquet(bytes, { columns: ['c', 'a'] });
+        assert.deepEqual(result.schema.map((s) => s.name), ['c', 'a']);
+        assert.deepEqual(result.data, { c: data.c, a: data.a });
+    });
+
+    it('returns row objects with format: rows', async () => {
+        const bytes = await writeParquet(schema, data);
+        const result = await readParquet(bytes, { format: 'rows', columns: ['a', 'c'] });
+        assert.deepEqual(result.data, [
+            { a: 1, c: 0.5 },
+            { a: 2, c: null },
+            { a: 3, c: 2.5 },
+        ]);
+        await assert.rejects(readParquet(bytes, { format: 'table' }));
+    });
+
+    it('returns TypedArrays for required numeric columns', async () => {
+        const schema = [
+            { name: 'i', type: 'int32' },
+            { name: 'l', type: 'int64' },
+            { name: 'f', type: 'float32' },
+            { name: 'd', type: 'float64' },
+            { name: 'b', type: 'boolean' },
+            { name: 's', type: 'string' },
+            { name: 'n', type: 'float64', optional: true },
+        ];
+        const data = {
+            i: [1, 2, 2, 1], l: [10, 20, 30, 40], f: [0.5, 1.5, 0.5, 1.5], d: [0.1, 0.2, 0.3, 0.4],
+            b: [true, false, true, true], s: ['a', 'b', 'a', 'b'], n: [1, null, 3, null],
+        };
+        const bytes = await writeParquet(schema, data);
+        const result = await readParquet(bytes, { typedArrays: true, offset: 1 });
+        assert.deepEqual(result.data.i, Int32Array.from([2, 2, 1]));
+        assert.deepEqual(result.data.l, Float64Array.from([20, 30, 40]));
+        assert.deepEqual(result.data.f, Float32Array.from([1.5, 0.5, 1.5]));
+        assert.deepEqual(result.data.d, Float64Array.from([0.2, 0.3, 0.4]));
+        assert.deepEqual(result.data.b, Uint8Array.from([0, 1, 1]));
+        assert.deepEqual(result.data.s, ['b', 'a', 'b']);
+        assert.deepEqual(result.data.n, [null, 3, null]);
+    });
+
+    it('returns int64 as BigInt with int64: bigint', async () => {
+        const schema = [
+            { name: 'l', type: 'int64' },
+            { name: 'o', type: 'int64', optional: true },
+        ];
+        const big = 2n ** 60n + 1n;
+        const bytes = await writeParquet(schema, { l: [big, -5n], o: [big, null] });
+        const result = await readParquet(bytes, { int64: 'bigint' });
+        assert.deepEqual(result.data.l, [big, -5n]);
+        assert.deepEqual(result.data.o, [big, null]);
+        const typed = await readParquet(bytes, { int64: 'bigint', typedArrays: true });
+        assert.deepEqual(typed.data.l, BigInt64Array.from([big, -5n]));
+        assert.deepEqual(typed.data.o, [big, null]);
+        await assert.rejects(readParquet(bytes, { int64: 'string' }), /int64 must be/);
+    });
+
+    it('returns dates as ISO strings, Dates or day counts', async () => {
+        const schema = [{ name: 'd', type: 'date', optional: true }];
+        const bytes = await writeParquet(schema, { d: [19783, null, 0] });
+        const result = await readParquet(bytes);
+        assert.equal(result.schema[0].type, 'date');
+        assert.deepEqual(result.data.d, ['2024-03-01', null, '1970-01-01']);
+        assert.equal(result.schema[0].statistics.min, '1970-01-01');
+        const asDates = await readParquet(bytes, { dates: 'date' });
+        assert.deepEqual(asDates.data.d, [new Date('2024-03-01'), null, new Date(0)]);
+        const asDays = await readParquet(bytes, { dates: 'number' });
+        assert.deepEqual(asDays.data.d, [19783, null, 0]);
+        await assert.rejects(readParquet(bytes, { dates: 'iso' }), /dates must be/);
+    });
+
+    it('rejects unknown projected columns', async () => {
+        const bytes = await writeParquet(schema, data);
+        await assert.rejects(readParquet(bytes, { columns: ['nope'] }), /unknown column/);
+    });
+});
+
+describe('data pages', () => {
+    const schema = [
+        { name: 'id', type: 'int64' },
+        { name: 'tag', type: 'string', optional: true },
+        { name: 'flag', type: 'boolean', optional: true },
+    ];
+    const n = 3_000;
+    const data = {
+        id: Array.from({ length: n }, (_, i) => i),
+        tag: Array.from({ length: n }, (_, i) => (i % 7 === 0 ? null : `t${i % 4}`)),
+        flag: Array.from({ length: n }, (_, i) => (i % 11 === 0 ? null : i % 2 === 0)),
+    };
+
+    it('splits chunks by dataPageSize', async () => {
+        const result = await roundtrip(schema, data, { dataPageSize: 1_000 })();
+        assert.deepEqual(result.data, data);
+    });
+
+    it('splits chunks by dataPageRows', async () => {
+        const result = await roundtrip(schema, data, { dataPageRows: 100, dictionary: false })();
+        assert.deepEqual(result.data, data);
+    });
+
+    it('writes v2 data pages', async () => {
+        const required = { id: data.id, tag: data.tag.map((t) => t ?? 'x') };
+        const bytes = await writeParquet(
+            [{ name: 'id', type: 'int64' }, { name: 'tag', type: 'string' }],
+            required,
+            { version: 'v2', compression: 'zstd' },
+        );
+        const result = await readParquet(bytes, n);
+        assert.deepEqual(result.data, required);
+    });
+
+    for (const compression of ['none', 'snappy', 'gzip']) {
+        it(`reads v2 pages with nulls (${compression})`, async () => {
+            const result = await roundtrip(schema, data, { version: 'v2', compression, dataPageRows: 500 })();
+            assert.deepEqual(result.data, data);
+        });
+    }
+
+    it('rejects unknown page versions', async () => {
+        await assert.rejects(writeParquet(schema, data, { version: 'v3' }));
+    });
+
+    it('rejects non-positive page sizes', async () => {
+        await assert.rejects(writeParquet(schema, data, { dataPageSize: 0 }));
+        await assert.rejects(writeParquet(schema, data, { dataPageRows: -1 }));
+    });
+});
+
+// ── Compression ──────────────────────────────────────────────────────────────
+
+describe('compression', () => {
+    const schema = [{ name: 'msg', type: 'string' }];
+    const data = { msg: Array.from({ length: 100 }, (_, i) => `repeated-value-${i % 10}`) };
+
+    it('snappy (default)', async () => {
+        const result = await roundtrip(schema, data, { compression: 'snappy' })();
+        assert.equal(result.numRows, 100);
+    });
+
+    it('none', async () => {
+        const result = await roundtrip(schema, data, { compression: 'none' })();
+        assert.equal(result.numRows, 100);
+    });
+
+    for (const codec of ['gzip', 'zstd', 'lz4', 'brotli']) {
+        it(`${codec} roundtrip`, async () => {
+            const result = await roundtrip(schema, data, { compression: codec })();
+            assert.deepEqual(result.data.msg, data.msg);
+        });
+    }
+
+    it('gzip produces smaller output than none', async () => {
+        const gzip = await writeParquet(schema, data, { compression: 'gzip' });
+        const none = await writeParquet(schema, data, { compression: 'none' });
+        assert.ok(gzip.length < none.length, `gzip (${gzip.length}B) should be smaller than none (${none.length}B)`);
+    });
+
+    it('zstd level trades speed for size', async () => {
+        const words = ['alpha', 'bravo', 'charlie', 'delta', 'echo', 'foxtrot', 'golf', 'hotel'];
+        let seed = 7;
+        const next = () => (seed = (seed * 1103515245 + 12345) % 2147483648);
+        const text = { msg: Array.from({ length: 20000 }, () => `${words[next() % 8]}/${words[next() % 8]}-${next() % 1000}`) };
+        const config = { compression: 'zstd', dictionary: false };
+        const fast = await writeParquet(schema, text, { ...config, compressionLevel: 1 });
+        const best = await writeParquet(schema, text, { ...config, compressionLevel: 19 });
+        assert.ok(best.length < fast.length, `zstd 19 (${best.length}B) should be smaller than zstd 1 (${fast.length}B)`);
+        assert.deepEqual((await readParquet(best, { maxRows: 20000 })).data, text);
+    });
+
+    it('rejects out-of-range levels', async () => {
+        await assert.rejects(writeParquet(schema, data, { compression: 'zstd', compressionLevel: 99 }));
+        await assert.rejects(writeParquet(schema, data, { compression: 'gzip', compressionLevel: 11 }));
+        await assert.rejects(writeParquet(schema, data, { compression: 'brotli', compressionLevel: -1 }));
+    });
+
+    it('gzip and brotli accept levels', async () => {
+        const gzip = await roundtrip(schema, data, { compression: 'gzip', compressionLevel: 9 })();
+        const brotli = await roundtrip(schema, data, { compression: 'brotli', compressionLevel: 11 })();
+        assert.deepEqual(gzip.data.msg, data.msg);
+        assert.deepEqual(brotli.data.msg, data.msg);
+    });
+
+    it('lz4 produces smaller output than none', async () => {
+        const lz4 = await writeParquet(schema, data, { compression: 'lz4' });
+        const none = await writeParquet(schema, data, { compression: 'none' });
+        assert.ok(lz4.length < none.length, `lz4 (${lz4.length}B) should be smaller than none (${none.length}B)`);
+    });
+
+    it('reads LZ4 pages in Hadoop framing and as raw blocks', async () => {
+        // The same 4 rows, written by parquet-mr 1.10 and parquet-cpp 1.5 under the same LZ4 codec id
+        for (const name of ['hadoop_lz4_compressed.parquet', 'non_hadoop_lz4_compressed.parquet']) {
+            const bytes = fixture(name);
+            const { rowGroups } = await readParquetMetadata(bytes);
+            assert.deepEqual(rowGroups[0].columns.map((c) => c.codec), ['LZ4', 'LZ4', 'LZ4']);
+            const { data } = await readParquet(bytes);
+            assert.deepEqual(data.c0, [1593604800, 1593604800, 1593604801, 1593604801]);
+            assert.deepEqual(data.c1.map((b) => Buffer.from(b).toString()), ['abc', 'def', 'abc', 'def']);
+            assert.deepEqual(data.v11, [42, 7.7, 42.125, 7.7]);
+        }
+    });
+
+    it('per-column codecs override the default', async () => {
+        const schema = [
+            { name: 'msg', type: 'string' },
+            { name: 'blob', type: 'binary' },
+            { name: 'n', type: 'int32' },
+        ];
+        const blobs = Array.from({ length: 100 }, (_, i) => Uint8Array.from({ length: 64 }, (_, j) => (i * 31 + j * 17) & 255));
+        const data3 = { msg: data.msg, blob: blobs, n: data.msg.map((_, i) => i) };
+        for (const version of ['v1', 'v2']) {
+            const bytes = await writeParquet(schema, data3, { version, compression: { default: 'zstd', perColumn: { blob: 'none', n: 'gzip' } } });
+            const { rowGroups } = await readParquetMetadata(bytes);
+            assert.deepEqual(rowGroups[0].columns.map((c) => c.codec), ['ZSTD', 'UNCOMPRESSED', 'GZIP']);
+            const { data: back } = await readParquet(bytes);
+            assert.deepEqual(back, data3);
+        }
+        const onlyOne = await writeParquet(schema, data3, { version: 'v2', compression: { perColumn: { msg: 'brotli' } } });
+        const { rowGroups } = await readParquetMetadata(onlyOne);
+        assert.deepEqual(rowGroups[0].columns.map((c) => c.codec), ['BROTLI', 'SNAPPY', 'SNAPPY']);
+        assert.deepEqual((await readParquet(onlyOne)).data, data3);
+        const noneButOne = await writeParquet(schema, data3, { version: 'v2', compression: { default: 'none', perColumn: { msg: 'zstd' } } });
+        assert.deepEqual((await readParquet(noneButOne)).data, data3);
+    });
+
+    it('rejects unknown per-column codecs and columns', async () => {
+        await assert.rejects(writeParquet(schema, data, { compression: { perColumn: { msg: 'lzo' } } }), /compression.perColumn: 'msg' must map to/);
+        await assert.rejects(writeParquet(schema, data, { compression: { perColumn: { nope: 'zstd' } } }), /compression.perColumn: unknown column 'nope'/);
+        await assert.rejects(writeParquet(schema, data, { compression: { perColumn: ['msg'] } }), /compression.perColumn must be an object/);
+        await assert.rejects(writeParquet(schema, data, { compression: ['zstd'] }), /compression must be a codec name/);
+    });
+
+    it('brotli produces smaller output than none', async () => {
+        const brotli = await writeParquet(schema, data, { compression: 'brotli' });
+        const none = await writeParquet(schema, data, { compression: 'none' });
+        assert.ok(brotli.length < none.length, `brotli (${brotli.length}B) should be smaller than none (${none.length}B)`);
+    });
+
+    it('snappy produces smaller output than none', async () => {
+        const snappy = await writeParquet(schema, data, { compression: 'snappy' });
+        const none = await writeParquet(schema, data, { compression: 'none' });
+        assert.ok(snappy.length < none.length, `snappy (${snappy.length}B) should be smaller than none (${none.length}B)`);
+    });
+
+    it('the default writer has snappy and none, and points to writer-full for the rest', async () => {
+        for (const compression of ['snappy', 'none']) {
+            const bytes = await defaultWriter.writeParquet(schema, data, { compression });
+            assert.deepEqual((await readParquet(bytes)).data.msg, data.msg);
+        }
+        for (const codec of ['gzip', 'zstd', 'lz4', 'brotli']) {
+            await assert.rejects(
+                defaultWriter.writeParquet(schema, data, { compression: { default: 'snappy', perColumn: { msg: codec } } }),
+                new RegExp(`compression '${codec}' needs the full writer: import from 'tiny-parquet/writer-full'`),
+            );
+        }
+    });
+});
+
+// ── Dictionary ───────────────────────────────────────────────────────────────
+
+describe('dictionary', () => {
+    it('low-cardinality numeric columns roundtrip', async () => {
+        const schema = [
+            { name: 'status', type: 'int32' },
+            { name: 'bucket', type: 'int64' },
+            { name: 'ratio', type: 'float64' },
+        ];
+        const n = 1_000;
+        const data = {
+            status: Array.from({ length: n }, (_, i) => [200, 404, 500][i % 3]),
+            bucket: Array.from({ length: n }, (_, i) => (i % 7) * 1000),
+            ratio: Array.from({ length: n }, (_, i) => [0.5, -0, 1.25][i % 3]),
+        };
+        const result = await roundtrip(schema, data)();
+        assert.deepEqual(result.data.status, data.status);
+        assert.deepEqual(result.data.bucket, data.bucket);
+        assert.deepEqual(result.data.ratio, data.ratio);
+    });
+
+    it('dictionary shrinks low-cardinality numeric columns', async () => {
+        const schema = [{ name: 'status', type: 'int64' }];
+        const data = { status: Array.from({ length: 5_000 }, (_, i) => [200, 301, 404][i % 3]) };
+        const dict = await writeParquet(schema, data, { compression: 'none' });
+        const plain = await writeParquet(schema, data, { compression: 'none', dictionary: false });
+        assert.ok(dict.length < plain.length, `dict (${dict.length}B) should be smaller than plain (${plain.length}B)`);
+    });
+});
+
+describe('delta encoding', () => {
+    const schema = [
+        { name: 'id', type: 'int64' },
+        { name: 'ts', type: 'timestamp', optional: true },
+        { name: 'n', type: 'int32' },
+    ];
+    const n = 5_000;
+    const data = {
+        id: Array.from({ length: n }, (_, i) => 1_000_000 + i),
+        ts: Array.from({ length: n }, (_, i) => (i % 10 === 0 ? null : 1_700_000_000_000 + i * 1000)),
+        n: Array.from({ length: n }, (_, i) => (i * 7919) % 100_003),
+    };
+
+    it('writes DELTA_BINARY_PACKED pages for the listed columns only', async () => {
+        const bytes = await writeParquet(schema, data, { deltaEncoding: ['id', 'ts'] });
+        const { rowGroups } = await readParquetMetadata(bytes);
+        const encodings = Object.fromEntries(rowGroups[0].columns.map((c) => [c.path, c.encodings]));
+        assert.ok(encodings.id.includes('DELTA_BINARY_PACKED'));
+        assert.ok(encodings.ts.includes('DELTA_BINARY_PACKED'));
+        assert.ok(!encodings.n.includes('DELTA_BINARY_PACKED'));
+        assert.ok(!encodings.id.includes('RLE_DICTIONARY'));
+    });
+
+    it('shrinks increasing integers well below PLAIN', async () => {
+        const plain = await writeParquet(schema, data, { compression: 'none', dictionary: false });
+        const delta = await writeParquet(schema, data, { compression: 'none', deltaEncoding: ['id', 'ts'] });
+        assert.ok(delta.length * 3 < plain.length, `delta (${delta.length}B) should be far smaller than plain (${plain.length}B)`);
+    });
+
+    it('reads delta-encoded columns back', async () => {
+        const bytes = await writeParquet(schema, data, { deltaEncoding: ['id', 'ts', 'n'], dataPageRows: 300 });
+        const result = await readParquet(bytes, { maxRows: Infinity, offset: 0 });
+        assert.deepEqual(result.data.id, data.id);
+        assert.deepEqual(result.data.ts, data.ts);
+        assert.deepEqual(result.data.n, data.n);
+        const page = await readParquet(bytes, { offset: 1234, maxRows: 10, columns: ['ts'] });
+        assert.deepEqual(page.data.ts, data.ts.slice(1234, 1244));
+        const { data: filtered } = await readParquet(bytes, { filter: [{ column: 'id', op: '=', value: 1_004_321 }] });
+        assert.deepEqual(filtered.id, [1_004_321]);
+    });
+
+    it('keeps values whose deltas overflow', async () => {
+        const schema = [
+            { name: 'a', type: 'int32' },
+            { name: 'b', type: 'int64' },
+            { name: 'u', type: 'uint32' },
+        ];
+        const data = {
+            a: [2147483647, -2147483648, 0, 2147483647, -1],
+            b: [9223372036854775807n, -9223372036854775808n, 1n, -1n, 0n],
+            u: [4294967295, 0, 4294967295, 1, 2147483648],
+        };
+        const bytes = await writeParquet(schema, data, { deltaEncoding: ['a', 'b', 'u'] });
+        const { data: back } = await readParquet(bytes, { int64: 'bigint' });
+        assert.deepEqual(back, data);
+        const typed = await readParquet(bytes, { typedArrays: true, int64: 'bigint' });
+        assert.deepEqual(Array.from(typed.data.a), data.a);
+        assert.deepEqual(Array.from(typed.data.b), data.b);
+    });
+
+    it('reads delta-encoded list elements', async () => {
+        const schema = [{ name: 'xs', type: 'list', items: 'int64', optional: true }];
+        const data = { xs: [[1, 2, 3], null, [], [10, -10, 1000000]] };
+        const bytes = await writeParquet(schema, data, { deltaEncoding: ['xs.list.element'] });
+        assert.deepEqual((await readParquet(bytes)).data, data);
+    });
+
+    it('prefix-encodes strings and binary', async () => {
+        const schema = [
+            { name: 'url', type: 'string', optional: true },
+            { name: 'body', type: 'string' },
+            { name: 'raw', type: 'binary' },
+        ];
+        const n = 2_000;
+        const data = {
+            url: Array.from({ length: n }, (_, i) => (i % 13 === 0 ? null : `https://example.com/users/${String(i).padStart(6, '0')}/profile`)),
+            body: Array.from({ length: n }, (_, i) => (i % 5 === 0 ? '' : `row ${i} ✓`)),
+            raw: Array.from({ length: n }, (_, i) => new Uint8Array([1, 2, 3, i & 255])),
+        };
+        const bytes = await writeParquet(schema, data, { compression: 'none', dataPageRows: 700, deltaEncoding: { url: true, body: 'length', raw: true } });
+        const { rowGroups } = await readParquetMetadata(bytes);
+        assert.deepEqual(rowGroups[0].columns.map((c) => c.encodings.find((e) => e.startsWith('DELTA'))), ['DELTA_BYTE_ARRAY', 'DELTA_LENGTH_BYTE_ARRAY', 'DELTA_BYTE_ARRAY']);
+        const { data: back } = await readParquet(bytes, { maxRows: Infinity });
+        assert.deepEqual(back, data);
+        const { data: tail } = await readParquet(bytes, { offset: 1500, maxRows: 3, columns: ['url'] });
+        assert.deepEqual(tail.url, data.url.slice(1500, 1503));
+
+        const plain = await writeParquet(schema, data, { compression: 'none', dictionary: false });
+        const urls = (b) => readParquetMetadata(b).then((m) => m.rowGroups[0].columns[0].compressedSize);
+        assert.ok((await urls(bytes)) * 2 < (await urls(plain)));
+    });
+
+    it('rejects block headers whose sizes overflow', async () => {
+        const bytes = await writeParquet([{ name: 'n', type: 'int32' }], { n: [1, 2, 3] }, { compression: 'none', dictionary: false, deltaEncoding: ['n'] });
+        // Block size 128, 4 miniblocks, 3 values, first value 1; min delta 1, all bit widths 0
+        const at = Buffer.from(bytes).indexOf(Buffer.from([0x80, 0x01, 0x04, 0x03, 0x02, 0x02, 0, 0, 0, 0]));
+        assert.ok(at > 0);
+        // Block size 2^31 in 1 miniblock of 8-bit offsets: 2^34 bits, past a 32-bit usize
+        bytes.set([0x80, 0x80, 0x80, 0x80, 0x08, 0x01, 0x03, 0x00, 0x00, 0x08], at);
+        await assert.rejects(readParquet(bytes), /DELTA_BINARY_PACKED values are truncated or corrupt/);
+    });
+
+    it('rejects unknown and unsupported columns', async () => {
+        await assert.rejects(writeParquet(schema, data, { deltaEncoding: ['nope'] }), /deltaEncoding: unknown column 'nope'/);
+        await assert.rejects(writeParquet([{ name: 'f', type: 'float64' }], { f: [1] }, { deltaEncoding: ['f'] }), /column 'f' isn't stored as int32, int64 or a byte array/);
+        await assert.rejects(writeParquet(schema, data, { deltaEncoding: { id: 'length' } }), /column 'id' isn't a byte array, which 'length' is for/);
+        await assert.rejects(writeParquet([{ name: 's', type: 'string' }], { s: ['a'] }, { deltaEncoding: { s: 'prefix' } }), /'s' must map to true or 'length'/);
+        await assert.rejects(writeParquet(schema, data, { deltaEncoding: 'id' }), /deltaEncoding must be an array/);
+    });
+});
+
+describe('boolean encoding', () => {
+    it('writes RLE pages for long runs and PLAIN for noisy ones', async () => {
+        const n = 10_000;
+        const schema = [
+            { name: 'deleted', type: 'boolean', optional: true },
+            { name: 'coin', type: 'boolean' },
+        ];
+        const data = {
+            deleted: Array.from({ length: n }, (_, i) => (i === 5000 ? null : i % 4000 === 7)),
+            coin: Array.from({ length: n }, (_, i) => ((i * 2654435761) >>> 13) % 2 === 0),
+        };
+        const bytes = await writeParquet(schema, data, { compression: 'none' });
+        const deleted = await inspectPages(bytes, 'deleted');
+        assert.deepEqual(deleted.map((p) => p.encoding), ['RLE']);
+        assert.ok(deleted[0].compressedSize < 100, `${deleted[0].compressedSize}B`);
+        assert.deepEqual((await inspectPages(bytes, 'coin')).map((p) => p.encoding), ['PLAIN']);
+        const { data: back } = await readParquet(bytes, { maxRows: Infinity });
+        assert.deepEqual(back, data);
+        const typed = await readParquet(bytes, { columns: ['coin'], typedArrays: true, offset: 123, maxRows: 50 });
+        assert.deepEqual(Array.from(typed.data.coin, Boolean), data.coin.slice(123, 173));
+    });
+});
+
+describe('RLE/bit-packed levels and indices', () => {
+    it('reads mixed runs of nulls, levels and dictionary indices', async () => {
+        const n = 3_000;
+        const schema = [
+            { name: 'tag', type: 'string', optional: true },
+            { name: 'xs', type: 'list', items: 'int32', optional: true },
+        ];
+        const data = {
+            tag: Array.from({ length: n }, (_, i) => (i % 500 < 300 ? 'a' : i % 7 === 0 ? null : `t${i % 11}`)),
+            xs: Array.from({ length: n }, (_, i) => (i % 100 < 50 ? [1, 1, 1] : i % 3 === 0 ? null : [i])),
+        };
+        const bytes = await writeParquet(schema, data, { dataPageRows: 1_000 });
+        const { data: back } = await readParquet(bytes, { maxRows: Infinity });
+        assert.deepEqual(back, data);
+        const { data: part } = await readParquet(bytes, { offset: 1_295, maxRows: 10, columns: ['tag'] });
+        assert.deepEqual(part.tag, data.tag.slice(1_295, 1_305));
+    });
+
+    it('rejects truncated definition levels', async () => {
+        const schema = [{ name: 'v', type: 'int32', optional: true }];
+        const bytes = await writeParquet(schema, { v: [1, null, 3, 4, null, 6, 7, 8, 9, 10] }, { compression: 'none', dictionary: false });
+        const [page] = await inspectPages(bytes, 'v');
+        const { rowGroups } = await readParquetMetadata(bytes);
+        const chunk = rowGroups[0].columns[0];
+        const body = chunk.offset + chunk.compressedSize - page.compressedSize;
+        bytes.set([1, 0, 0, 0], body); // the levels now claim a single byte
+        await assert.rejects(readParquet(bytes), /page: definition levels: RLE\/bit-packed data ends after \d+ of 10 values/);
+    });
+});
+
+describe('byte stream split', () => {
+    const schema = [
+        { name: 'temp', type: 'float64' },
+        { name: 'lat', type: 'float32', optional: true },
+    ];
+    const n = 5_000;
+    const data = {
+        temp: Array.from({ length: n }, (_, i) => 20 + Math.sin(i / 100) * 5 + i / 1e4),
+        lat: Array.from({ length: n }, (_, i) => (i % 17 === 0 ? null : Math.fround(52.52 + i * 1e-5))),
+    };
+
+    it('writes BYTE_STREAM_SPLIT pages that compress better', async () => {
+        const split = await writeParquet(schema, data, { compression: 'zstd', byteStreamSplit: ['temp', 'lat'] });
+        const { rowGroups } = await readParquetMetadata(split);
+        for (const c of rowGroups[0].columns) assert.ok(c.encodings.includes('BYTE_STREAM_SPLIT'), c.path);
+        const plain = await writeParquet(schema, data, { compression: 'zstd', dictionary: false });
+        assert.ok(split.length < plain.length, `split (${split.length}B) should be smaller than plain (${plain.length}B)`);
+    });
+
+    it('reads BYTE_STREAM_SPLIT pages back', async () => {
+        const bytes = await writeParquet(schema, data, { byteStreamSplit: ['temp', 'lat'], dataPageRows: 999, version: 'v2' });
+        const { data: back } = await readParquet(bytes, { maxRows: Infinity });
+        assert.deepEqual(back, data);
+        const typed = await readParquet(bytes, { columns: ['temp'], typedArrays: true, offset: 4000, maxRows: 5 });
+        assert.deepEqual(Array.from(typed.data.temp), data.temp.slice(4000, 4005));
+        const { data: cold } = await readParquet(bytes, { columns: ['temp'], filter: [{ column: 'temp', op: '<', value: 15.2 }] });
+        assert.ok(cold.temp.length > 0 && cold.temp.every((t) => t < 15.2));
+    });
+
+    it('rejects unknown and non-float columns', async () => {
+        await assert.rejects(writeParquet(schema, data, { byteStreamSplit: ['nope'] }), /byteStreamSplit: unknown column 'nope'/);
+        await assert.rejects(writeParquet([{ name: 'i', type: 'int32' }], { i: [1] }, { byteStreamSplit: ['i'] }), /column 'i' isn't float32 or float64/);
+        await assert.rejects(writeParquet(schema, data, { byteStreamSplit: 'temp' }), /byteStreamSplit must be an array/);
+    });
+});
+
+describe('dictionary fallback', () => {
+    const dataEncodings = (pages) => pages.filter((p) => p.type === 'data').map((p) => p.encoding);
+
+    it('falls back to PLAIN for later pages once the dictionary holds 256 values', async () => {
+        const schema = [{ name: 'v', type: 'string' }];
+        const data = { v: Array.from({ length: 1000 }, (_, i) => `v${Math.floor(i / 2)}`) };
+        const bytes = await writeParquet(schema, data, { dataPageRows: 100 });
+        const pages = await inspectPages(bytes, 'v');
+        assert.equal(pages[0].type, 'dictionary');
+        assert.equal(pages[0].numValues, 250);
+        assert.deepEqual(dataEncodings(pages), [...Array(5).fill('RLE_DICTIONARY'), ...Array(5).fill('PLAIN')]);
+        assert.deepEqual((await readParquet(bytes, { maxRows: Infinity })).data, data);
+    });
+
+    it('falls back once the dictionary passes dictionaryPageSize', async () => {
+        const schema = [{ name: 'v', type: 'string', optional: true }];
+        const words = Array.from({ length: 20 }, (_, i) => `${i}`.padStart(100, '-'));
+        const data = { v: Array.from({ length: 200 }, (_, i) => (i % 7 === 3 ? null : words[i % 20])) };
+        const bytes = await writeParquet(schema, data, { dataPageRows: 10, dictionaryPageSize: 1500 });
+        const pages = await inspectPages(bytes, 'v');
+        assert.deepEqual(dataEncodings(pages), ['RLE_DICTIONARY', ...Array(19).fill('PLAIN')]);
+        assert.deepEqual((await readParquet(bytes)).data, data);
+        const roomy = await inspectPages(await writeParquet(schema, data, { dataPageRows: 10 }), 'v');
+        assert.deepEqual(dataEncodings(roomy), Array(20).fill('RLE_DICTIONARY'));
+    });
+
+    it('skips the dictionary when the first page is too distinct', async () => {
+        const schema = [{ name: 'n', type: 'int32' }];
+        const data = { n: Array.from({ length: 100 }, (_, i) => Math.floor(i / 2)) };
+        const sparse = await inspectPages(await writeParquet(schema, data, { dictionaryRatio: 0.4 }), 'n');
+        assert.deepEqual(sparse.map((p) => [p.type, p.encoding]), [['data', 'PLAIN']]);
+        const dense = await inspectPages(await writeParquet(schema, data, { dictionaryRatio: 0.5 }), 'n');
+        assert.deepEqual(dense.map((p) => p.type), ['dictionary', 'data']);
+    });
+
+    it('rejects bad limits', async () => {
+        const schema = [{ name: 'n', type: 'int32' }];
+        await assert.rejects(writeParquet(schema, { n: [1] }, { dictionaryRatio: 0 }), /dictionaryRatio must be in \(0, 1\], got 0/);
+        await assert.rejects(writeParquet(schema, { n: [1] }, { dictionaryRatio: 1.5 }), /dictionaryRatio must be in/);
+        await assert.rejects(writeParquet(schema, { n: [1] }, { dictionaryRatio: 'low' }), /dictionaryRatio must be a number/);
+        await assert.rejects(writeParquet(schema, { n: [1] }, { dictionaryPageSize: 0 }), /dictionaryPageSize must be >= 1/);
+    });
+});
+
+describe('per-column encodings', () => {
+    const schema = [
+        { name: 'country', type: 'string' },
+        { name: 'id', type: 'int64' },
+        { name: 'temp', type: 'float64', optional: true },
+        { name: 'code', type: 'int32' },
+        { name: 'flag', type: 'boolean' },
+    ];
+    const n = 2_000;
+    const data = {
+        country: Array.from({ length: n }, (_, i) => ['DE', 'FR', 'NL'][i % 3]),
+        id: Array.from({ length: n }, (_, i) => 10_000_000_000 + i),
+        temp: Array.from({ length: n }, (_, i) => (i % 11 === 0 ? null : 18 + Math.cos(i / 50))),
+        code: Array.from({ length: n }, (_, i) => i % 5),
+        flag: Array.from({ length: n }, () => false),
+    };
+    const encodingsOf = async (bytes) => {
+        const { rowGroups } = await readParquetMetadata(bytes);
+        return Object.fromEntries(rowGroups[0].columns.map((c) => [c.path, c.encodings]));
+    };
+
+    it('encodes each column as configured', async () => {
+        const bytes = await writeParquet(schema, data, {
+            dictionary: false,
+            encodings: { country: 'dictionary', id: 'delta', temp: 'byte_stream_split', code: 'plain', flag: 'plain' },
+        });
+        const encodings = await encodingsOf(bytes);
+        assert.ok(encodings.country.includes('RLE_DICTIONARY'));
+        assert.ok(encodings.id.includes('DELTA_BINARY_PACKED'));
+        assert.ok(encodings.temp.includes('BYTE_STREAM_SPLIT'));
+        assert.ok(!encodings.code.includes('RLE_DICTIONARY'));
+        assert.deepEqual((await inspectPages(bytes, 'flag')).map((p) => p.encoding), ['PLAIN']);
+        const { data: back } = await readParquet(bytes, { maxRows: Infinity });
+        assert.deepEqual(back, data);
+    });
+
+    it('leaves unlisted columns to the global settings', async () => {
+        const encodings = await encodingsOf(await writeParquet(schema, data, { encodings: { code: 'plain' } }));
+        assert.ok(encodings.country.includes('RLE_DICTIONARY'));
+        assert.ok(!encodings.code.includes('RLE_DICTIONARY'));
+        assert.deepEqual((await inspectPages(await writeParquet(schema, data), 'flag')).map((p) => p.encoding), ['RLE']);
+    });
+
+    it('agrees with deltaEncoding and byteStreamSplit', async () => {
+        const bytes = await writeParquet(schema, data, { deltaEncoding: ['id'], byteStreamSplit: ['temp'], encodings: { id: 'delta', temp: 'byte_stream_split' } });
+        const encodings = await encodingsOf(bytes);
+        assert.ok(encodings.id.includes('DELTA_BINARY_PACKED'));
+        assert.ok(encodings.temp.includes('BYTE_STREAM_SPLIT'));
+    });
+
+    it('rejects unknown columns, encodings and conflicts', async () => {
+        await assert.rejects(writeParquet(schema, data, { encodings: { nope: 'plain' } }), /encodings: unknown column 'nope'/);
+        await assert.rejects(writeParquet(schema, data, { encodings: { id: 'rle' } }), /encodings: 'id' must map to 'dictionary', 'plain', 'delta' or 'byte_stream_split'/);
+        await assert.rejects(writeParquet(schema, data, { encodings: ['id'] }), /encodings must be an object/);
+        await assert.rejects(writeParquet(schema, data, { encodings: { flag: 'dictionary' } }), /column 'flag' is boolean, which has no dictionary encoding/);
+        await assert.rejects(writeParquet(schema, data, { encodings: { temp: 'delta' } }), /column 'temp' isn't stored as int32, int64 or a byte array/);
+        await assert.rejects(writeParquet(schema, data, { encodings: { id: 'byte_stream_split' } }), /column 'id' isn't float32 or float64/);
+        await assert.rejects(
+            writeParquet(schema, data, { deltaEncoding: ['id'], encodings: { id: 'plain' } }),
+            /encodings: column 'id' already has an encoding from another option/,
+        );
+    });
+});
+
+// ── Nulls ────────────────────────────────────────────────────────────────────
+
+describe('nulls', () => {
+    it('optional columns roundtrip nulls', async () => {
+        const schema = [
+            { name: 's', type: 'string', optional: true },
+            { name: 'i', type: 'int32', optional: true },
+            { name: 'f', type: 'float64', optional: true },
+            { name: 'b', type: 'boolean', optional: true },
+        ];
+        const data = {
+            s: ['a', null, 'c', undefined],
+            i: [1, null, null, 4],
+            f: [null, 2.5, 3.5, null],
+            b: [true, null, false, null],
+        };
+        const result = await roundtrip(schema, data)();
+        assert.deepEqual(result.data.s, ['a', null, 'c', null]);
+        assert.deepEqual(result.data.i, [1, null, null, 4]);
+        assert.deepEqual(result.data.f, [null, 2.5, 3.5, null]);
+        assert.deepEqual(result.data.b, [true, null, false, null]);
+        assert.equal(result.schema[0].optional, true);
+    });
+
+    it('all-null optional column', async () => {
+        const schema = [{ name: 'x', type: 'int64', optional: true }];
+        const data = { x: [null, null, null] };
+        const result = await roundtrip(schema, data)();
+        assert.deepEqual(result.data.x, [null, null, null]);
+    });
+});
+
+// ── GeoParquet ───────────────────────────────────────────────────────────────
+
+describe('GeoParquet', () => {
+    const point = { type: 'Point', coordinates: [13.4, 52.5] };
+    const line = { type: 'LineString', coordinates: [[0, 0, 1], [2, -3, 4]] };
+    const polygon = { type: 'Polygon', coordinates: [[[0, 0], [4, 0], [4, 4], [0, 0]]] };
+    const collection = { type: 'GeometryCollection', geometries: [point, { type: 'MultiPoint', coordinates: [[-1, 1], [1, -1]] }] };
+    const schema = [{ name: 'id', type: 'int32' }, { name: 'geom', type: 'geometry', optional: true }];
+    const data = { id: [1, 2, 3, 4, 5], geom: [point, line, null, polygon, collection] };
+
+    // WKB of POINT(1 2), little-endian
+    const wkbPoint = () => {
+        const b = new Uint8Array(21);
+        const v = new DataView(b.buffer);
+        b[0] = 1;
+        v.setUint32(1, 1, true);
+        v.setFloat64(5, 1, true);
+        v.setFloat64(13, 2, true);
+        return b;
+    };
+
+    it('round-trips GeoJSON geometries', async () => {
+        const result = await readParquet(await writeParquet(schema, data));
+        assert.deepEqual(result.data.geom, data.geom);
+        assert.equal(result.schema[1].type, 'geometry');
+    });
+
+    it('describes the columns in the geo metadata', async () => {
+        const crs = { id: { authority: 'EPSG', code: 4326 } };
+        const bytes = await writeParquet([...schema, { name: 'area', type: 'geometry', crs, edges: 'spherical' }], { ...data, area: data.geom.map(() => polygon) });
+        const geo = JSON.parse((await readParquetMetadata(bytes)).metadata.geo);
+        assert.equal(geo.version, '1.1.0');
+        assert.equal(geo.primary_column, 'geom');
+        assert.deepEqual(geo.columns.geom, {
+            encoding: 'WKB',
+            geometry_types: ['GeometryCollection', 'LineString Z', 'Point', 'Polygon'],
+            bbox: [-1, -3, 13.4, 52.5],
+        });
+        assert.deepEqual(geo.columns.area, { encoding: 'WKB', geometry_types: ['Polygon'], bbox: [0, 0, 4, 4], crs, edges: 'spherical' });
+    });
+
+    it('takes WKB as is and returns it on request', async () => {
+        const bytes = await writeParquet([{ name: 'g', type: 'geometry' }], { g: [wkbPoint()] });
+        assert.deepEqual((await readParquet(bytes)).data.g, [{ type: 'Point', coordinates: [1, 2] }]);
+        assert.deepEqual((await readParquet(bytes, { geometry: 'wkb' })).data.g, [wkbPoint()]);
+        await assert.rejects(readParquet(bytes, { geometry: 'wkt' }), /geometry must be 'geojson' or 'wkb'/);
+    });
+
+    it('leaves plain binary columns alone', async () => {
+        const bytes = await writeParquet([{ name: 'g', type: 'binary' }], { g: [wkbPoint()] });
+        const result = await readParquet(bytes);
+        assert.deepEqual(result.data.g, [wkbPoint()]);
+        assert.equal(result.schema[0].type, 'binary');
+    });
+
+    it('rejects invalid geometries', async () => {
+        const g = [{ name: 'g', type: 'geometry' }];
+        await assert.rejects(writeParquet(g, { g: [wkbPoint().subarray(0, 20)] }), /row 0: invalid WKB: ends early/);
+        await assert.rejects(writeParquet(g, { g: [{ type: 'Circle', coordinates: [0, 0] }] }), /unknown GeoJSON geometry type/);
+        await assert.rejects(writeParquet(g, { g: [{ type: 'LineString', coordinates: [[0, 0], [1, 1, 1]] }] }), /positions must all have 2 numbers/);
+        await assert.rejects(writeParquet(g, { g: ['POINT (0 0)'] }));
+        await assert.rejects(writeParquet([{ name: 's', type: 'struct', fields: g }], { s: [{ g: point }] }), /geometry columns must be top-level/);
+        await assert.rejects(writeParquet(g, { g: [point] }, { metadata: { geo: '{}' } }), /'geo' metadata entry/);
+        await assert.rejects(writeParquet([{ name: 'g', type: 'geometry', edges: 'curved' }], { g: [point] }), /edges must be 'planar' or 'spherical'/);
+    });
+});
+
+// ── Pandas metadata ──────────────────────────────────────────────────────────
+
+describe('pandas metadata', () => {
+    const schema = [
+        { name: 'id', type: 'int64' },
+        { name: 'count', type: 'int32', optional: true },
+        { name: 'ok', type: 'boolean', optional: true },
+        { name: 'ts', type: 'timestamp' },
+        { name: 'price', type: 'decimal(9,2)' },
+        { name: 'tags', type: 'list', items: 'string' },
+    ];
+    const data = { id: [1n, 2n], count: [1, null], ok: [true, null], ts: [0, 1], price: ['1.50', '2.25'], tags: [['a'], []] };
+    const pandas = async (config) => JSON.parse((await readParquetMetadata(await writeParquet(schema, data, config))).metadata.pandas);
+
+    it('records dtypes and a default index', async () => {
+        const meta = await pandas({ pandas: true });
+        assert.deepEqual(meta.index_columns, []);
+        assert.deepEqual(meta.columns.map(c => [c.name, c.pandas_type, c.numpy_type]), [
+            ['id', 'int64', 'int64'],
+            ['count', 'int32', 'Int32'],
+            ['ok', 'bool', 'boolean'],
+            ['ts', 'datetimetz', 'datetime64[ms]'],
+            ['price', 'decimal', 'object'],
+            ['tags', 'list[unicode]', 'object'],
+        ]);
+        assert.deepEqual(meta.columns[3].metadata, { timezone: 'UTC' });
+        assert.deepEqual(meta.columns[4].metadata, { precision: 9, scale: 2 });
+        assert.equal(meta.column_indexes[0].pandas_type, 'unicode');
+    });
+
+    it('records index columns', async () => {
+        assert.deepEqual((await pandas({ pandas: { index: 'id' } })).index_columns, ['id']);
+        assert.deepEqual((await pandas({ pandas: { index: ['id', 'ts'] } })).index_columns, ['id', 'ts']);
+    });
+
+    it('is off by default', async () => {
+        const result = await readParquetMetadata(await writeParquet(schema, data));
+        assert.equal(result.metadata?.pandas, undefined);
+    });
+
+    it('rejects bad options', async () => {
+        await assert.rejects(writeParquet(schema, data, { pandas: { index: 'nope' } }), /pandas.index: unknown column 'nope'/);
+        await assert.rejects(writeParquet(schema, data, { pandas: { index: 1 } }), /pandas must be true or/);
+        await assert.rejects(writeParquet(schema, data, { pandas: true, metadata: { pandas: '{}' } }), /already has a 'pandas' entry/);
+    });
+});
+
+// ── Statistics ───────────────────────────────────────────────────────────────
+
+describe('statistics', () => {
+    it('exposes min/max/nullCount per column', async () => {
+        const schema = [
+            { name: 'n', type: 'int32', optional: true },
+            { name: 's', type: 'string' },
+            { name: 'f', type: 'float64' },
+        ];
+        const data = { n: [5, null, -3, 12], s: ['pear', 'apple', 'zebra', 'mango'], f: [0.5, -1.5, NaN, 2] };
+        const result = await roundtrip(schema, data)();
+        assert.deepEqual(result.schema[0].statistics, { min: -3, max: 12, nullCount: 1 });
+        assert.deepEqual(result.schema[1].statistics, { min: 'apple', max: 'zebra', nullCount: 0 });
+        assert.equal(result.schema[2].statistics.min, -1.5);
+        assert.equal(result.schema[2].statistics.max, 2);
+    });
+
+    it('statistics: false omits them', async () => {
+        const bytes = await writeParquet([{ name: 'n', type: 'int32' }], { n: [1, 2, 3] }, { statistics: false });
+        const result = await readParquet(bytes);
+        assert.equal(result.schema[0].statistics, undefined);
+    });
+});
+
+// ── Edge cases ───────────────────────────────────────────────────────────────
+
+describe('edge cases', () => {
+    it('empty strings', async () => {
+        const schema = [{ name: 'val', type: 'string' }];
+        const data = { val: ['', '', ''] };
+        const result = await roundtrip(schema, data)();
+        assert.deepEqual(result.data.val, data.val);
+    });
+
+    it('long strings', async () => {
+        const schema = [{ name: 'val', type: 'string' }];
+        const longStr = 'x'.repeat(10_000);
+        const data = { val: [longStr, longStr] };
+        const result = await roundtrip(schema, data)();
+        assert.equal(result.data.val[0].length, 10_000);
+    });
+
+    it('unicode and emoji', async () => {
+        const schema = [{ name: 'val', type: 'string' }];
+        const data = { val: ['日本語', '한국어', '🎉🔥💯', 'Ñoño'] };
+        const result = await roundtrip(schema, data)();
+        assert.deepEqual(result.data.val, data.val);
+    });
+
+    it('zero values across types', async () => {
+        const schema = [
+            { name: 'i', type: 'int32' },
+            { name: 'f', type: 'float64' },
+            { name: 'b', type: 'boolean' },
+        ];
+        const data = { i: [0, 0, 0], f: [0.0, 0.0, 0.0], b: [false, false, false] };
+        const result = await roundtrip(schema, data)();
+        assert.deepEqual(result.data.i, [0, 0, 0]);
+        assert.deepEqual(result.data.f, [0, 0, 0]);
+        assert.deepEqual(result.data.b, [false, false, false]);
+    });
+
+    it('negative numbers', async () => {
+        const schema = [
+            { name: 'i32', type: 'int32' },
+            { name: 'i64', type: 'int64' },
+            { name: 'f64', type: 'float64' },
+        ];
+        const data = { i32: [-1, -100, -2147483648], i64: [-1, -999999], f64: [-0.001, -1e50] };
+        const result = await roundtrip(schema, data)();
+        assert.deepEqual(result.data.i32, data.i32);
+        assert.deepEqual(result.data.i64, data.i64);
+        assert.deepEqual(result.data.f64, data.f64);
+    });
+
+    it('columns of unequal length', async () => {
+        const schema = [
+            { name: 'a', type: 'int32' },
+            { name: 'b', type: 'int32' },
+        ];
+        await assert.rejects(writeParquet(schema, { a: [1, 2, 3], b: [1, 2] }), /column 'b' has 2 values, expected 3/);
+    });
+
+    it('single column, many rows', async () => {
+        const schema = [{ name: 'id', type: 'int32' }];
+        const n = 5_000;
+        const data = { id: Array.from({ length: n }, (_, i) => i) };
+        const result = await roundtrip(schema, data)();
+        assert.equal(result.numRows, n);
+        assert.equal(result.data.id[0], 0);
+        assert.equal(result.data.id[n - 1], n - 1);
+    });
+
+    it('many columns, few rows', async () => {
+        const cols = 20;
+        const schema = Array.from({ length: cols }, (_, i) => ({ name: `col_${i}`, type: 'int32' }));
+        const data = {};
+        for (let i = 0; i < cols; i++) data[`col_${i}`] = [i, i * 2, i * 3];
+        const result = await roundtrip(schema, data)();
+        assert.equal(result.numRows, 3);
+        assert.equal(result.schema.length, cols);
+    });
+});
+
+// ── Output format ────────────────────────────────────────────────────────────
+
+describe('output format', () => {
+    it('produces valid Parquet magic bytes', async () => {
+        const bytes = await writeParquet(
+            [{ name: 'x', type: 'int32' }],
+            { x: [1] },
+        );
+        // Parquet files start and end with "PAR1"
+        const magic = String.fromCharCode(...bytes.slice(0, 4));
+        const footer = String.fromCharCode(...bytes.slice(-4));
+        assert.equal(magic, 'PAR1', 'should start with PAR1');
+        assert.equal(footer, 'PAR1', 'should end with PAR1');
+    });
+
+    it('readParquet returns correct schema types', async () => {
+        const schema = [
+            { name: 'a', type: 'string' },
+            { name: 'b', type: 'int32' },
+            { name: 'c', type: 'boolean' },
+        ];
+        const data = { a: ['x'], b: [1], c: [true] };
+        const result = await roundtrip(schema, data)();
+        assert.equal(result.schema[0].type, 'string');
+        assert.equal(result.schema[1].type, 'int32');
+        assert.equal(result.schema[2].type, 'boolean');
+    });
+    it('writes key-value metadata into the footer', async () => {
+        const bytes = await writeParquet(
+            [{ name: 'x', type: 'int32' }],
+            { x: [1] },
+            { metadata: { source: 'export-2024.csv', app: 'tiny-parquet-test' } },
+        );
+        const text = new TextDecoder().decode(bytes);
+        assert.ok(text.includes('export-2024.csv'));
+        assert.ok(text.includes('tiny-parquet-test'));
+    });
+
+    it('reads key-value metadata back', async () => {
+        const meta = { source: 'export-2024.csv', exportedAt: '2024-06-01T00:00:00Z' };
+        const bytes = await writeParquet([{ name: 'x', type: 'int32' }], { x: [1] }, { metadata: meta });
+        const result = await readParquet(bytes);
+        assert.deepEqual(result.metadata, meta);
+    });
+
+    it('embeds an Arrow IPC schema with arrowSchema', async () => {
+        const schema = [
+            { name: 'created_at', type: 'timestamp_micros' },
+            { name: 'id', type: 'uuid' },
+            { name: 'tags', type: 'list', items: 'string' },
+        ];
+        const data = { created_at: [0], id: ['00000000-0000-0000-0000-000000000001'], tags: [['a']] };
+        const bytes = await writeParquet(schema, data, { arrowSchema: true, metadata: { app: 'test' } });
+        const { metadata } = await readParquet(bytes);
+        assert.equal(metadata.app, 'test');
+        const ipc = Buffer.from(metadata['ARROW:schema'], 'base64');
+        // Encapsulated message: continuation marker, then the flatbuffer length
+        assert.equal(ipc.readUInt32LE(0), 0xffffffff);
+        assert.equal(ipc.readUInt32LE(4), ipc.length - 8);
+        const text = ipc.toString('latin1');
+        for (const s of ['created_at', 'UTC', 'arrow.uuid', 'tags', 'element']) assert.ok(text.includes(s), s);
+
+        const plain = await readParquet(await writeParquet(schema, data));
+        assert.equal(plain.metadata['ARROW:schema'], undefined);
+        await assert.rejects(
+            writeParquet(schema, data, { arrowSchema: true, metadata: { 'ARROW:schema': 'x' } }),
+            /already has an 'ARROW:schema' entry/,
+        );
+    });
+
+    it('returns empty metadata when the footer has none', async () => {
+        const result = await roundtrip([{ name: 'x', type: 'int32' }], { x: [1] })();
+        assert.deepEqual(result.metadata, {});
+    });
+
+    it('writes createdBy and schemaName into the footer', async () => {
+        const bytes = await writeParquet(
+            [{ name: 'x', type: 'int32' }],
+            { x: [1] },
+            { createdBy: 'my-app version 1.2.0', schemaName: 'my_root' },
+        );
+        const text = new TextDecoder().decode(bytes);
+        assert.ok(text.includes('my-app version 1.2.0'));
+        assert.ok(text.includes('my_root'));
+        const result = await readParquet(bytes);
+        assert.deepEqual(result.data.x, [1]);
+    });
+
+    it('stores field IDs', async () => {
+        const schema = [
+            { name: 'a', type: 'int32', id: 1 },
+            { name: 'b', type: 'string', id: 7 },
+            { name: 'c', type: 'boolean' },
+        ];
+        const result = await roundtrip(schema, { a: [1], b: ['x'], c: [true] })();
+        assert.equal(result.schema[0].id, 1);
+        assert.equal(result.schema[1].id, 7);
+        assert.equal(result.schema[2].id, undefined);
+    });
+
+    it('rejects non-integer field IDs', async () => {
+        await assert.rejects(writeParquet([{ name: 'a', type: 'int32', id: 1.5 }], { a: [1] }));
+    });
+
+    it('records sortedBy without changing the data', async () => {
+        const schema = [{ name: 'k', type: 'int32' }, { name: 'v', type: 'string' }];
+        const data = { k: [1, 2, 3], v: ['c', 'b', 'a'] };
+        const result = await roundtrip(schema, data, {
+            sortedBy: [{ column: 'k' }, { column: 'v', descending: true, nullsFirst: true }],
+            rowGroupSize: 2,
+        })();
+        assert.deepEqual(result.data, data);
+    });
+
+    it('sorts rows by sortBy keys before writing', async () => {
+        const schema = [
+            { name: 'ts', type: 'timestamp', optional: true },
+            { name: 'id', type: 'int64' },
+            { name: 'name', type: 'string', optional: true },
+            { name: 'tags', type: 'list', items: 'string', optional: true },
+            { name: 'pos', type: 'struct', fields: [{ name: 'x', type: 'float64' }] },
+        ];
+        const n = 500;
+        const rows = Array.from({ length: n }, (_, i) => ({
+            ts: i % 13 === 0 ? null : 1_700_000_000_000 + ((i * 7919) % 50) * 1000,
+            id: (i * 31) % 97,
+            name: i % 5 === 0 ? null : `n${i}`,
+            tags: i % 4 === 0 ? null : Array.from({ length: i % 3 }, (_, j) => `t${i}-${j}`),
+            pos: { x: i / 2 },
+        }));
+        const bytes = await writeParquet(schema, rows, { sortBy: ['ts', '-id'], rowGroupSize: 200, dataPageRows: 50 });
+        const sorted = rows
+            .map((r, i) => [r, i])
+            .sort(([a, i], [b, j]) => (a.ts === null) - (b.ts === null) || a.ts - b.ts || b.id - a.id || i - j)
+            .map(([r]) => r);
+        const { data } = await readParquet(bytes, { maxRows: Infinity });
+        for (const key of ['ts', 'id', 'name', 'tags']) assert.deepEqual(data[key], sorted.map((r) => r[key]), key);
+        assert.deepEqual(data.pos.map((p) => p.x), sorted.map((r) => r.pos.x));
+
+        const footer = await dumpFooter(bytes);
+        assert.deepEqual(footer.rowGroups[0].sortingColumns, [
+            { columnIdx: 0, descending: false, nullsFirst: false },
+            { columnIdx: 1, descending: true, nullsFirst: false },
+        ]);
+    });
+
+    it('sorts strings by bytes and descending keys with nulls last', async () => {
+        const schema = [{ name: 's', type: 'string', optional: true }, { name: 'k', type: 'int32' }];
+        const data = { s: ['b', null, 'a', 'é', 'B', null], k: [1, 2, 3, 4, 5, 6] };
+        const asc = await readParquet(await writeParquet(schema, data, { sortBy: ['s'] }));
+        assert.deepEqual(asc.data, { s: ['B', 'a', 'b', 'é', null, null], k: [5, 3, 1, 4, 2, 6] });
+        const desc = await readParquet(await writeParquet(schema, data, { sortBy: ['-s'] }));
+        assert.deepEqual(desc.data, { s: ['é', 'b', 'a', 'B', null, null], k: [4, 1, 3, 5, 2, 6] });
+    });
+
+    it('rejects bad sortBy keys', async () => {
+        const schema = [{ name: 'k', type: 'int32' }, { name: 'xs', type: 'list', items: 'int32' }];
+        const data = { k: [1], xs: [[1]] };
+        await assert.rejects(writeParquet(schema, data, { sortBy: ['nope'] }), /sortBy: unknown column 'nope'/);
+        await assert.rejects(writeParquet(schema, data, { sortBy: ['xs.list.element'] }), /sortBy: column 'xs.list.element' is nested/);
+        await assert.rejects(writeParquet(schema, data, { sortBy: 'k' }), /sortBy must be an array/);
+        await assert.rejects(writeParquet(schema, data, { sortBy: ['k'], sortedBy: [{ column: 'k' }] }), /drop sortedBy/);
+    });
+
+    it('writes page checksums', async () => {
+        const schema = [{ name: 'id', type: 'int32' }, { name: 'tag', type: 'string', optional: true }];
+        const data = {
+            id: Array.from({ length: 1_000 }, (_, i) => i),
+            tag: Array.from({ length: 1_000 }, (_, i) => (i % 9 === 0 ? null : `t${i % 3}`)),
+        };
+        const opts = { rowGroupSize: 400, dataPageRows: 100, sortedBy: [{ column: 'id' }] };
+        const plain = await writeParquet(schema, data, opts);
+        const bytes = await writeParquet(schema, data, { ...opts, pageChecksums: true });
+        assert.ok(bytes.length > plain.length, 'CRC fields should add bytes');
+        const result = await readParquet(bytes, 1_000);
+        assert.deepEqual(result.data, data);
+    });
+
+    describe('filter', () => {
+        const schema = [
+            { name: 'id', type: 'int32' },
+            { name: 'tag', type: 'string', optional: true },
+            { name: 'big', type: 'int64' },
+        ];
+        const n = 100;
+        const data = {
+            id: Array.from({ length: n }, (_, i) => i),
+            tag: Array.from({ length: n }, (_, i) => (i % 3 === 0 ? null : `t${i % 5}`)),
+            big: Array.from({ length: n }, (_, i) => i * 1000),
+        };
+        const write = () => writeParquet(schema, data, { rowGroupSize: 10, dataPageRows: 4 });
+        const expected = (pred) => data.id.filter((_, i) => pred(i));
+
+        it('keeps matching rows across row groups', async () => {
+            const bytes = await write();
+            const range = [{ column: 'id', op: '>=', value: 42 }, { column: 'id', op: '<', value: 57 }];
+            let result = await readParquet(bytes, { filter: range, columns: ['id'] });
+            assert.deepEqual(result.data.id, expected((i) => i >= 42 && i < 57));
+            result = await readParquet(bytes, { filter: range, columns: ['id'], offset: 1, maxRows: 3 });
+            assert.deepEqual(result.data.id, [43, 44, 45]);
+            assert.equal(result.numRows, n);
+        });
+
+        it('supports every op, on columns outside the projection', async () => {
+            const bytes = await write();
+            const ids = async (filter) => (await readParquet(bytes, { filter, columns: ['id'], maxRows: n })).data.id;
+            assert.deepEqual(await ids([{ column: 'tag', op: '=', value: 't2' }]), expected((i) => data.tag[i] === 't2'));
+            assert.deepEqual(await ids([{ column: 'tag', op: '!=', value: 't2' }]), expected((i) => data.tag[i] !== null && data.tag[i] !== 't2'));
+            assert.deepEqual(await ids([{ column: 'tag', op: 'is null' }]), expected((i) => data.tag[i] === null));
+            assert.deepEqual(await ids([{ column: 'tag', op: 'is not null' }, { column: 'id', op: '<=', value: 5 }]), [1, 2, 4, 5]);
+            assert.deepEqual(await ids([{ column: 'id', op: 'in', value: [3, 99, 500] }]), [3, 99]);
+            assert.deepEqual(await ids([{ column: 'id', op: 'not in', value: [0, 1] }, { column: 'id', op: '<', value: 4 }]), [2, 3]);
+            assert.deepEqual(await ids([{ column: 'big', op: '>', value: 97_000 }]), [98, 99]);
+            assert.deepEqual(await ids([{ column: 'big', op: '=', value: 5_000n }]), [5]);
+            assert.deepEqual(await ids([{ column: 'id', op: '>', value: 1_000 }]), []);
+        });
+
+        it('filters rows output, row groups and batches', async () => {
+            const bytes = await write();
+            const filter = [{ column: 'id', op: '>=', value: 95 }];
+            const rows = (await readParquet(bytes, { filter, format: 'rows', columns: ['id', 'tag'] })).data;
+            assert.deepEqual(rows.map((r) => r.id), [95, 96, 97, 98, 99]);
+            const file = await ParquetFile.open(bytes);
+            assert.deepEqual(file.readRowGroup(9, { filter, columns: ['big'] }).big, [95_000, 96_000, 97_000, 98_000, 99_000]);
+            assert.deepEqual(file.readRowGroup(0, { filter }).id, []);
+            file.free();
+            const batches = [];
+            for await (const batch of readParquetBatches(bytes, { filter: [{ column: 'id', op: '<', value: 7 }], batchSize: 3 })) batches.push(batch.id);
+            assert.deepEqual(batches, [[0, 1, 2], [3, 4, 5], [6]]);
+        });
+
+        it('keeps rows a where callback accepts', async () => {
+            const bytes = await write();
+            const seen = [];
+            const result = await readParquet(bytes, {
+                columns: ['id', 'tag'],
+                filter: [{ column: 'id', op: '>=', value: 50 }],
+                where: (row, index) => {
+                    seen.push(index);
+                    assert.equal(row.id, index);
+                    return row.tag === 't1';
+                },
+                maxRows: n,
+            });
+            assert.deepEqual(result.data.id, expected((i) => i >= 50 && data.tag[i] === 't1'));
+            assert.equal(seen[0], 50);
+            const limited = await readParquet(bytes, { columns: ['big'], where: (row) => row.big % 2000 === 0, offset: 2, maxRows: 3 });
+            assert.deepEqual(limited.data.big, [4000, 6000, 8000]);
+            await assert.rejects(readParquet(bytes, { where: () => { throw new Error('boom'); } }), /boom/);
+            await assert.rejects(readParquet(bytes, { where: true }), /where must be a function/);
+        });
+
+        it('renames and projects with select', async () => {
+            const bytes = await write();
+            const result = await readParquet(bytes, {
+                select: { label: 'tag', key: 'id', copy: 'id' },
+                filter: [{ column: 'id', op: '<', value: 3 }],
+            });
+            assert.deepEqual(result.schema.map((s) => s.name), ['label', 'key', 'copy']);
+            assert.deepEqual(Object.keys(result.data), ['label', 'key', 'copy']);
+            assert.deepEqual(result.data.key, [0, 1, 2]);
+            assert.deepEqual(result.data.copy, [0, 1, 2]);
+            assert.deepEqual(result.data.label, [null, 't1', 't2']);
+            const rows = await readParquet(bytes, { select: { n: 'id' }, format: 'rows', maxRows: 2, where: (row) => 'n' in row });
+            assert.deepEqual(rows.data, [{ n: 0 }, { n: 1 }]);
+            const file = await ParquetFile.open(bytes);
+            assert.deepEqual(Object.keys(file.readRowGroup(1, { select: { x: 'big' } })), ['x']);
+            file.free();
+            await assert.rejects(readParquet(bytes, { select: { a: 'nope' } }), /unknown column 'nope'/);
+            await assert.rejects(readParquet(bytes, { select: { a: 'id' }, columns: ['id'] }), /can't both be set/);
+            await assert.rejects(readParquet(bytes, { select: ['id'] }), /select must map/);
+        });
+
+        it('rejects bad conditions', async () => {
+            const bytes = await write();
+            await assert.rejects(readParquet(bytes, { filter: [{ column: 'nope', op: '=', value: 1 }] }), /unknown filter column 'nope'/);
+            await assert.rejects(readParquet(bytes, { filter: [{ column: 'id', op: '~', value: 1 }] }), /op must be/);
+            await assert.rejects(readParquet(bytes, { filter: [{ column: 'id', op: '=' }] }), /missing value/);
+            await assert.rejects(readParquet(bytes, { filter: [{ column: 'id', op: 'in', value: 1 }] }), /need an array/);
+        });
+    });
+
+    describe('verifyChecksums', () => {
+        const schema = [{ name: 'id', type: 'int32' }];
+        const data = { id: Array.from({ length: 1_000 }, (_, i) => i) };
+        const write = () => writeParquet(schema, data, { pageChecksums: true, compression: 'none', dictionary: false });
+
+        it('accepts intact pages', async () => {
+            const result = await readParquet(await write(), { maxRows: 1_000, verifyChecksums: true });
+            assert.deepEqual(result.data, data);
+            assert.equal(result.warnings, undefined);
+        });
+
+        it('fails or warns on corrupted pages', async () => {
+            const bytes = await write();
+            bytes[500] ^= 0xff; // inside the first page's values
+            await assert.rejects(readParquet(bytes, { verifyChecksums: true }), /checksum mismatch/);
+            const result = await readParquet(bytes, { verifyChecksums: 'warn' });
+            assert.equal(result.warnings.length, 1);
+            await readParquet(bytes); // unchecked by default
+        });
+    });
+
+    it('rejects sortedBy on unknown columns', async () => {
+        await assert.rejects(writeParquet([{ name: 'k', type: 'int32' }], { k: [1] }, { sortedBy: [{ column: 'nope' }] }));
+    });
+
+    it('rejects non-string metadata values', async () => {
+        await assert.rejects(writeParquet([{ name: 'x', type: 'int32' }], { x: [1] }, { metadata: { n: 1 } }));
     });
 });
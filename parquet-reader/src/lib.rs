@@ -1,135 +1,743 @@
-use std::io::Cursor;
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+use std::ops::Range;
+use std::rc::Rc;
 
-use js_sys::{Array, Object, Reflect, Uint8Array};
+use aes_gcm::{
+    aead::{consts::U12, AeadInPlace, KeyInit},
+    aes::{Aes128Enc, Aes192Enc, Aes256Enc},
+    AesGcm as Gcm, Nonce, Tag,
+};
+use ctr::{
+    cipher::{InnerIvInit, StreamCipher},
+    Ctr32BE, CtrCore,
+};
+use js_sys::{Array, BigInt64Array, Date, Float32Array, Float64Array, Function, Int32Array, Object, Reflect, Uint8Array};
 use parquet2::{
-    read::{decompress, get_page_iterator, read_metadata},
-    page::Page,
-    schema::types::{PhysicalType, PrimitiveLogicalType},
+    bloom_filter::{self, hash_byte, is_in_set},
+    compression::Compression,
+    metadata::{ColumnChunkMetaData, ColumnDescriptor, Descriptor, FileMetaData, SchemaDescriptor},
+    read::{read_metadata, PageMetaData},
+    page::{DataPage, DataPageHeader, DictPage, Page},
+    schema::{types::{FieldInfo, GroupConvertedType, GroupLogicalType, PhysicalType, IntegerType, ParquetType, PrimitiveConvertedType, PrimitiveLogicalType, PrimitiveType, TimeUnit}, Repetition},
     encoding::Encoding,
 };
+use parquet_format_safe::{
+    thrift::protocol::{ReadThrift, TCompactInputProtocol, TCompactOutputProtocol, TInputProtocol, TType},
+    BloomFilterHeader, ColumnCryptoMetaData, ColumnIndex, ColumnMetaData, CompressionCodec,
+    EncryptionAlgorithm, FileCryptoMetaData, FileMetaData as TFileMetaData, OffsetIndex, PageHeader, PageType, Type,
+};
 use wasm_bindgen::prelude::*;
 
 /// Map physical + logical type to a JS-friendly label
-fn type_label(phys: PhysicalType, logical: &Option<PrimitiveLogicalType>) -> &'static str {
-    match (phys, logical) {
-        (PhysicalType::Int64, Some(PrimitiveLogicalType::Timestamp { .. })) => "timestamp",
+fn type_label(prim: &PrimitiveType) -> &'static str {
+    if is_date(prim) {
+        return "date";
+    }
+    if decimal_spec(prim).is_some() {
+        return "decimal";
+    }
+    if is_uuid(prim) {
+        return "uuid";
+    }
+    if timestamp_unit(prim).is_some() {
+        return "timestamp";
+    }
+    if let Some(int) = integer_type(prim) {
+        return match int {
+            IntegerType::Int8 => "int8",
+            IntegerType::Int16 => "int16",
+            IntegerType::Int32 => "int32",
+            IntegerType::Int64 => "int64",
+            IntegerType::UInt8 => "uint8",
+            IntegerType::UInt16 => "uint16",
+            IntegerType::UInt32 => "uint32",
+            IntegerType::UInt64 => "uint64",
+        };
+    }
+    match time_unit(prim) {
+        Some(TimeUnit::Milliseconds) => return "time_millis",
+        Some(TimeUnit::Microseconds) => return "time_micros",
+        Some(TimeUnit::Nanoseconds) => return "time_nanos",
+        None => {}
+    }
+    match (prim.physical_type, &prim.logical_type) {
+        // Legacy Impala/Spark timestamps, decoded to epoch milliseconds
+        (PhysicalType::Int96, _) => "timestamp",
         (PhysicalType::Int32, _) => "int32",
         (PhysicalType::Int64, _) => "int64",
         (PhysicalType::Float, _) => "float32",
         (PhysicalType::Double, _) => "float64",
         (PhysicalType::Boolean, _) => "boolean",
-        (PhysicalType::ByteArray, _) => "string",
-        _ => "binary",
+        (PhysicalType::ByteArray, _) if is_json(prim) => "json",
+        (PhysicalType::ByteArray, Some(PrimitiveLogicalType::Bson)) => "bson",
+        (PhysicalType::ByteArray, _) if prim.converted_type == Some(PrimitiveConvertedType::Bson) => "bson",
+        (PhysicalType::ByteArray, _) if is_text(prim) => "string",
+        (PhysicalType::ByteArray, _) => "binary",
+        (PhysicalType::FixedLenByteArray(_), _) if is_float16(prim) => "float16",
+        (PhysicalType::FixedLenByteArray(_), _) => "fixed",
     }
 }
 
-// ── Dictionary helpers ──────────────────────────────────────────────────────
+/// Parquet codec name, as other tools print it
+fn codec_label(c: Compression) -> &'static str {
+    match c {
+        Compression::Uncompressed => "UNCOMPRESSED",
+        Compression::Snappy => "SNAPPY",
+        Compression::Gzip => "GZIP",
+        Compression::Lzo => "LZO",
+        Compression::Brotli => "BROTLI",
+        // Deprecated codec: Hadoop-framed LZ4 (raw LZ4 blocks are tried as a fallback)
+        Compression::Lz4 => "LZ4",
+        Compression::Zstd => "ZSTD",
+        Compression::Lz4Raw => "LZ4_RAW",
+    }
+}
 
-/// Decode a PLAIN-encoded dictionary of byte arrays (strings).
-/// Returns a Vec of string values.
-fn decode_dict_binary(buf: &[u8]) -> Vec<String> {
-    let mut values = Vec::new();
-    let mut off = 0;
-    while off + 4 <= buf.len() {
-        let len = u32::from_le_bytes(buf[off..off + 4].try_into().unwrap()) as usize;
-        off += 4;
-        if off + len > buf.len() { break; }
-        let s = std::str::from_utf8(&buf[off..off + len]).unwrap_or("<binary>");
-        values.push(s.to_string());
-        off += len;
+/// Parquet encoding name, as other tools print it
+fn encoding_label(e: parquet_format_safe::Encoding) -> &'static str {
+    match Encoding::try_from(e) {
+        Ok(Encoding::Plain) => "PLAIN",
+        Ok(Encoding::PlainDictionary) => "PLAIN_DICTIONARY",
+        Ok(Encoding::Rle) => "RLE",
+        Ok(Encoding::BitPacked) => "BIT_PACKED",
+        Ok(Encoding::DeltaBinaryPacked) => "DELTA_BINARY_PACKED",
+        Ok(Encoding::DeltaLengthByteArray) => "DELTA_LENGTH_BYTE_ARRAY",
+        Ok(Encoding::DeltaByteArray) => "DELTA_BYTE_ARRAY",
+        Ok(Encoding::RleDictionary) => "RLE_DICTIONARY",
+        Ok(Encoding::ByteStreamSplit) => "BYTE_STREAM_SPLIT",
+        Err(_) => "UNKNOWN",
     }
-    values
 }
 
-/// Decode RLE/bit-packed hybrid encoded dictionary indices.
-/// The buffer starts with a 1-byte bit_width, then RLE/bit-packed data.
-fn decode_rle_dict_indices(buf: &[u8], num_values: usize) -> Vec<u32> {
-    if buf.is_empty() {
-        return vec![0; num_values];
+// ── Logical types ───────────────────────────────────────────────────────────
+
+/// Int32 column annotated as DATE (logical type, or the legacy converted type)
+fn is_date(prim: &PrimitiveType) -> bool {
+    prim.physical_type == PhysicalType::Int32
+        && (matches!(prim.logical_type, Some(PrimitiveLogicalType::Date))
+            || matches!(prim.converted_type, Some(PrimitiveConvertedType::Date)))
+}
+
+/// Unit of a TIME column (logical type, or the legacy converted types)
+fn time_unit(prim: &PrimitiveType) -> Option<TimeUnit> {
+    match (&prim.logical_type, &prim.converted_type) {
+        (Some(PrimitiveLogicalType::Time { unit, .. }), _) => Some(*unit),
+        (_, Some(PrimitiveConvertedType::TimeMillis)) => Some(TimeUnit::Milliseconds),
+        (_, Some(PrimitiveConvertedType::TimeMicros)) => Some(TimeUnit::Microseconds),
+        _ => None,
+    }
+}
+
+/// (precision, scale) of a DECIMAL column
+fn decimal_spec(prim: &PrimitiveType) -> Option<(usize, usize)> {
+    match (&prim.logical_type, &prim.converted_type) {
+        (Some(PrimitiveLogicalType::Decimal(p, s)), _) => Some((*p, *s)),
+        (_, Some(PrimitiveConvertedType::Decimal(p, s))) => Some((*p, *s)),
+        _ => None,
     }
+}
 
-    let bit_width = buf[0] as usize;
-    let mut indices = Vec::with_capacity(num_values);
-    let mut pos = 1; // skip bit_width byte
+/// BYTE_ARRAY column holding UTF-8 text (STRING/ENUM/JSON, or the legacy UTF8
+/// converted type); other byte arrays are returned as raw bytes
+fn is_text(prim: &PrimitiveType) -> bool {
+    matches!(
+        prim.logical_type,
+        Some(PrimitiveLogicalType::String | PrimitiveLogicalType::Enum | PrimitiveLogicalType::Json)
+    ) || matches!(
+        prim.converted_type,
+        Some(PrimitiveConvertedType::Utf8 | PrimitiveConvertedType::Enum | PrimitiveConvertedType::Json)
+    )
+}
 
-    if bit_width == 0 {
-        // All values are the same dictionary entry (index 0)
-        return vec![0; num_values];
+/// BYTE_ARRAY column annotated as JSON
+fn is_json(prim: &PrimitiveType) -> bool {
+    prim.physical_type == PhysicalType::ByteArray
+        && (matches!(prim.logical_type, Some(PrimitiveLogicalType::Json))
+            || matches!(prim.converted_type, Some(PrimitiveConvertedType::Json)))
+}
+
+/// Integer annotation of an int32/int64 column (logical type, or the legacy converted types)
+fn integer_type(prim: &PrimitiveType) -> Option<IntegerType> {
+    match (&prim.logical_type, &prim.converted_type) {
+        (Some(PrimitiveLogicalType::Integer(t)), _) => Some(*t),
+        (_, Some(PrimitiveConvertedType::Int8)) => Some(IntegerType::Int8),
+        (_, Some(PrimitiveConvertedType::Int16)) => Some(IntegerType::Int16),
+        (_, Some(PrimitiveConvertedType::Int32)) => Some(IntegerType::Int32),
+        (_, Some(PrimitiveConvertedType::Int64)) => Some(IntegerType::Int64),
+        (_, Some(PrimitiveConvertedType::Uint8)) => Some(IntegerType::UInt8),
+        (_, Some(PrimitiveConvertedType::Uint16)) => Some(IntegerType::UInt16),
+        (_, Some(PrimitiveConvertedType::Uint32)) => Some(IntegerType::UInt32),
+        (_, Some(PrimitiveConvertedType::Uint64)) => Some(IntegerType::UInt64),
+        _ => None,
     }
+}
 
-    while pos < buf.len() && indices.len() < num_values {
-        if pos >= buf.len() { break; }
+/// FIXED_LEN_BYTE_ARRAY(16) column annotated as UUID
+fn is_uuid(prim: &PrimitiveType) -> bool {
+    prim.physical_type == PhysicalType::FixedLenByteArray(16)
+        && matches!(prim.logical_type, Some(PrimitiveLogicalType::Uuid))
+}
 
-        // Read ULEB128 header
-        let mut header: u64 = 0;
-        let mut shift = 0;
-        loop {
-            if pos >= buf.len() { break; }
-            let byte = buf[pos] as u64;
-            pos += 1;
-            header |= (byte & 0x7F) << shift;
-            shift += 7;
-            if byte & 0x80 == 0 { break; }
-        }
+/// FIXED_LEN_BYTE_ARRAY(2) column annotated as FLOAT16. parquet2 has no such
+/// logical type; `metadata_from_thrift` marks these columns `Unknown` instead.
+fn is_float16(prim: &PrimitiveType) -> bool {
+    prim.physical_type == PhysicalType::FixedLenByteArray(2)
+        && matches!(prim.logical_type, Some(PrimitiveLogicalType::Unknown))
+}
 
-        if header & 1 == 1 {
-            // Bit-packed: groups of 8 values
-            let num_groups = (header >> 1) as usize;
-            let num_vals = num_groups * 8;
-            let total_bits = num_vals * bit_width;
-            let total_bytes = (total_bits + 7) / 8;
+/// Unit of a TIMESTAMP column's decoded values (INT96 decodes to milliseconds)
+fn timestamp_unit(prim: &PrimitiveType) -> Option<TimeUnit> {
+    match (prim.physical_type, &prim.logical_type, &prim.converted_type) {
+        (PhysicalType::Int96, _, _) => Some(TimeUnit::Milliseconds),
+        (PhysicalType::Int64, Some(PrimitiveLogicalType::Timestamp { unit, .. }), _) => Some(*unit),
+        (PhysicalType::Int64, _, Some(PrimitiveConvertedType::TimestampMillis)) => Some(TimeUnit::Milliseconds),
+        (PhysicalType::Int64, _, Some(PrimitiveConvertedType::TimestampMicros)) => Some(TimeUnit::Microseconds),
+        _ => None,
+    }
+}
 
-            let data_start = pos;
-            let data_end = (pos + total_bytes).min(buf.len());
+/// isAdjustedToUTC of a timestamp/time column (the legacy converted types imply true)
+fn adjusted_to_utc(prim: &PrimitiveType) -> Option<bool> {
+    match (&prim.logical_type, &prim.converted_type) {
+        (Some(PrimitiveLogicalType::Timestamp { is_adjusted_to_utc, .. }), _)
+        | (Some(PrimitiveLogicalType::Time { is_adjusted_to_utc, .. }), _) => Some(*is_adjusted_to_utc),
+        (
+            _,
+            Some(
+                PrimitiveConvertedType::TimestampMillis
+                | PrimitiveConvertedType::TimestampMicros
+                | PrimitiveConvertedType::TimeMillis
+                | PrimitiveConvertedType::TimeMicros,
+            ),
+        ) => Some(true),
+        _ => None,
+    }
+}
 
-            for i in 0..num_vals {
-                if indices.len() >= num_values { break; }
-                let bit_offset = i * bit_width;
-                let byte_offset = data_start + bit_offset / 8;
-                let bit_shift = bit_offset % 8;
+/// Short name of a time unit, as used in options and schema output
+fn unit_label(unit: TimeUnit) -> &'static str {
+    match unit {
+        TimeUnit::Milliseconds => "millis",
+        TimeUnit::Microseconds => "micros",
+        TimeUnit::Nanoseconds => "nanos",
+    }
+}
 
-                if byte_offset >= data_end { break; }
+/// Ticks per second of a time unit
+fn ticks_per_second(unit: TimeUnit) -> i128 {
+    match unit {
+        TimeUnit::Milliseconds => 1_000,
+        TimeUnit::Microseconds => 1_000_000,
+        TimeUnit::Nanoseconds => 1_000_000_000,
+    }
+}
 
-                let mut val: u32 = 0;
-                let bytes_needed = ((bit_shift + bit_width) + 7) / 8;
-                for b in 0..bytes_needed {
-                    if byte_offset + b < data_end {
-                        val |= (buf[byte_offset + b] as u32) << (b * 8);
-                    }
+/// Convert a timestamp between units, flooring when the target is coarser
+fn rescale(t: i64, from: TimeUnit, to: TimeUnit) -> i128 {
+    let (f, g) = (ticks_per_second(from), ticks_per_second(to));
+    if g >= f {
+        t as i128 * (g / f)
+    } else {
+        (t as i128).div_euclid(f / g)
+    }
+}
+
+/// How DATE columns are returned (`dates` option)
+#[derive(Clone, Copy, PartialEq)]
+enum DateMode {
+    /// "YYYY-MM-DD"
+    Iso,
+    /// JS Date at UTC midnight
+    Object,
+    /// Raw days since the epoch
+    Days,
+}
+
+/// Conversion applied to a column's decoded values for its logical type
+#[derive(Clone, Copy, PartialEq)]
+enum Convert {
+    None,
+    Date(DateMode),
+    /// "HH:MM:SS.fff" with as many fraction digits as the unit has
+    Time(TimeUnit),
+    /// Apply the scale; as a string when the flag is set, else a Number
+    Decimal(usize, bool),
+    /// 16 bytes → canonical hyphenated lowercase string
+    Uuid,
+    /// Bytes → lowercase hex string (`binary: 'hex'`)
+    Hex,
+    /// Timestamp from the column's unit to the `timestamps` unit
+    Timestamp(TimeUnit, TimeUnit),
+    /// uint32 stored in int32: reinterpret the bits as unsigned
+    UInt32,
+    /// uint64 stored in int64: as BigInt when set, else as Numbers — failing
+    /// on values a Number can't hold exactly
+    UInt64(bool),
+    /// FLOAT16 bytes → Number
+    Float16,
+    /// JSON text → parsed value
+    Json,
+    /// WKB bytes of a GeoParquet column → GeoJSON geometry
+    Geometry,
+}
+
+impl Convert {
+    fn for_column(prim: &PrimitiveType, opts: &ReadOptions) -> Self {
+        if is_date(prim) && opts.dates != DateMode::Days {
+            return Convert::Date(opts.dates);
+        }
+        if let Some((_, scale)) = decimal_spec(prim) {
+            return Convert::Decimal(scale, opts.decimals_as_strings);
+        }
+        match (timestamp_unit(prim), opts.timestamp_unit) {
+            (Some(from), Some(to)) if from != to => return Convert::Timestamp(from, to),
+            (Some(_), _) => return Convert::None,
+            _ => {}
+        }
+        if is_uuid(prim) {
+            return Convert::Uuid;
+        }
+        if is_float16(prim) {
+            return Convert::Float16;
+        }
+        if is_json(prim) && opts.parse_json {
+            return Convert::Json;
+        }
+        match integer_type(prim) {
+            Some(IntegerType::UInt32) => return Convert::UInt32,
+            Some(IntegerType::UInt64) => return Convert::UInt64(opts.uint64_bigint),
+            _ => {}
+        }
+        let bytes = match prim.physical_type {
+            PhysicalType::FixedLenByteArray(_) => true,
+            PhysicalType::ByteArray => !is_text(prim),
+            _ => false,
+        };
+        if bytes && opts.binary_hex {
+            return Convert::Hex;
+        }
+        match time_unit(prim) {
+            Some(unit) if opts.times_as_strings => Convert::Time(unit),
+            _ => Convert::None,
+        }
+    }
+
+    /// Convert one non-null decoded column value, rejecting values this
+    /// conversion can't return exactly and JSON that doesn't parse
+    fn try_apply(self, v: JsValue) -> Result<JsValue, String> {
+        match (self, js_i64(&v)) {
+            (Convert::UInt64(false), Some(n)) if n as u64 > MAX_SAFE_INTEGER => Err(format!(
+                "uint64 value {} exceeds Number.MAX_SAFE_INTEGER; read with uint64: 'bigint'",
+                n as u64
+            )),
+            (Convert::Json, _) => match v.as_string() {
+                Some(text) => js_sys::JSON::parse(&text).map_err(|_| format!("invalid JSON {:?}", text)),
+                None => Ok(v),
+            },
+            (Convert::Geometry, _) => match v.dyn_ref::<Uint8Array>() {
+                Some(bytes) => wkb_geojson(&bytes.to_vec()),
+                None => Ok(v),
+            },
+            _ => Ok(self.apply(v)),
+        }
+    }
+
+    /// Convert one non-null decoded value
+    fn apply(self, v: JsValue) -> JsValue {
+        match (self, v.as_f64()) {
+            (Convert::Date(DateMode::Iso), Some(days)) => JsValue::from_str(&iso_date(days as i64)),
+            (Convert::Date(DateMode::Object), Some(days)) => {
+                Date::new(&JsValue::from_f64(days * 86_400_000.0)).into()
+            }
+            (Convert::Time(unit), _) => match js_i64(&v) {
+                Some(t) => JsValue::from_str(&time_of_day(t, unit)),
+                None => v,
+            },
+            (Convert::Decimal(scale, as_string), _) => match decimal_unscaled(&v) {
+                Some(u) if as_string => JsValue::from_str(&decimal_string(u, scale)),
+                Some(u) => JsValue::from_f64(decimal_string(u, scale).parse().unwrap_or(f64::NAN)),
+                None => v,
+            },
+            (Convert::Uuid, _) => match v.dyn_ref::<Uint8Array>() {
+                Some(bytes) => JsValue::from_str(&uuid_string(&bytes.to_vec())),
+                None => v,
+            },
+            (Convert::Timestamp(from, to), _) => match js_i64(&v) {
+                Some(t) if v.is_bigint() => JsValue::from(rescale(t, from, to)),
+                Some(t) => JsValue::from_f64(rescale(t, from, to) as f64),
+                None => v,
+            },
+            (Convert::UInt32, Some(n)) => JsValue::from_f64(n as i32 as u32 as f64),
+            (Convert::UInt64(bigint), _) => match js_i64(&v) {
+                Some(n) if bigint => JsValue::from(n as u64),
+                Some(n) => JsValue::from_f64(n as u64 as f64),
+                None => v,
+            },
+            (Convert::Float16, _) => match v.dyn_ref::<Uint8Array>() {
+                Some(bytes) if bytes.length() == 2 => {
+                    JsValue::from_f64(f16_value(u16::from_le_bytes([bytes.get_index(0), bytes.get_index(1)])))
                 }
-                val >>= bit_shift as u32;
-                val &= (1u32 << bit_width) - 1;
-                indices.push(val);
+                _ => v,
+            },
+            (Convert::Hex, _) => match v.dyn_ref::<Uint8Array>() {
+                Some(bytes) => JsValue::from_str(&hex_string(&bytes.to_vec())),
+                None => v,
+            },
+            _ => v,
+        }
+    }
+}
+
+/// Largest integer a JS Number holds exactly (Number.MAX_SAFE_INTEGER)
+const MAX_SAFE_INTEGER: u64 = 9_007_199_254_740_991;
+
+/// Integer value of a decoded Number or BigInt
+fn js_i64(v: &JsValue) -> Option<i64> {
+    if v.is_bigint() {
+        i64::try_from(v.clone()).ok()
+    } else {
+        v.as_f64().map(|n| n as i64)
+    }
+}
+
+/// Unscaled value of a decoded decimal cell: a Number, a BigInt, or the
+/// big-endian two's complement bytes of a FIXED_LEN_BYTE_ARRAY
+fn decimal_unscaled(v: &JsValue) -> Option<i128> {
+    match v.dyn_ref::<Uint8Array>() {
+        Some(bytes) => be_signed(&bytes.to_vec()),
+        None => js_i64(v).map(i128::from),
+    }
+}
+
+/// IEEE half-precision bits → value
+fn f16_value(h: u16) -> f64 {
+    let magnitude = match (h >> 10) & 0x1f {
+        0 => (h & 0x3ff) as f64 * 2f64.powi(-24),
+        0x1f if h & 0x3ff == 0 => f64::INFINITY,
+        0x1f => f64::NAN,
+        e => (0x400 + (h & 0x3ff)) as f64 * 2f64.powi(e as i32 - 25),
+    };
+    if h & 0x8000 != 0 { -magnitude } else { magnitude }
+}
+
+/// Big-endian two's complement bytes (at most 16) → integer
+fn be_signed(b: &[u8]) -> Option<i128> {
+    if b.is_empty() || b.len() > 16 {
+        return None;
+    }
+    let mut full = [if b[0] & 0x80 != 0 { 0xff } else { 0 }; 16];
+    full[16 - b.len()..].copy_from_slice(b);
+    Some(i128::from_be_bytes(full))
+}
+
+/// Unscaled integer → decimal string: (-1234, 2) → "-12.34"
+fn decimal_string(unscaled: i128, scale: usize) -> String {
+    let sign = if unscaled < 0 { "-" } else { "" };
+    let digits = format!("{:0>width$}", unscaled.unsigned_abs(), width = scale + 1);
+    let (int, frac) = digits.split_at(digits.len() - scale);
+    if scale == 0 {
+        format!("{}{}", sign, int)
+    } else {
+        format!("{}{}.{}", sign, int, frac)
+    }
+}
+
+/// 16 bytes → "123e4567-e89b-12d3-a456-426614174000"
+fn uuid_string(b: &[u8]) -> String {
+    let hex = hex_string(b);
+    if hex.len() != 32 {
+        return hex;
+    }
+    format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+}
+
+/// Bytes → lowercase hex
+fn hex_string(b: &[u8]) -> String {
+    b.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Time since midnight → "HH:MM:SS.fff" (3, 6 or 9 fraction digits for millis, micros, nanos)
+fn time_of_day(t: i64, unit: TimeUnit) -> String {
+    let (per_sec, digits) = match unit {
+        TimeUnit::Milliseconds => (1_000, 3),
+        TimeUnit::Microseconds => (1_000_000, 6),
+        TimeUnit::Nanoseconds => (1_000_000_000, 9),
+    };
+    let secs = t.div_euclid(per_sec);
+    format!(
+        "{:02}:{:02}:{:02}.{:0width$}",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60,
+        t.rem_euclid(per_sec),
+        width = digits
+    )
+}
+
+/// Days since 1970-01-01 → "YYYY-MM-DD" (proleptic Gregorian, Hinnant's civil_from_days)
+fn iso_date(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// GeoJSON names of WKB geometry types 1-7
+const GEOMETRY_TYPES: [&str; 7] = ["Point", "LineString", "Polygon", "MultiPoint", "MultiLineString", "MultiPolygon", "GeometryCollection"];
+
+/// WKB (ISO or EWKB, either byte order) → GeoJSON geometry object. Positions
+/// keep Z and drop M; an all-NaN point is an empty one.
+fn wkb_geojson(wkb: &[u8]) -> Result<JsValue, String> {
+    let mut pos = 0;
+    let g = wkb_geometry(wkb, &mut pos, 0).map_err(|e| format!("invalid WKB: {}", e))?;
+    if pos != wkb.len() {
+        return Err(format!("invalid WKB: {} bytes after the geometry", wkb.len() - pos));
+    }
+    Ok(g)
+}
+
+/// Read the WKB geometry at `pos` as a GeoJSON object
+fn wkb_geometry(wkb: &[u8], pos: &mut usize, depth: usize) -> Result<JsValue, String> {
+    let take = |pos: &mut usize, n: usize| -> Result<&[u8], String> {
+        let bytes = wkb.get(*pos..*pos + n).ok_or("ends early")?;
+        *pos += n;
+        Ok(bytes)
+    };
+    let little = match take(pos, 1)?[0] {
+        0 => false,
+        1 => true,
+        b => return Err(format!("byte order {}", b)),
+    };
+    let u32_at = |pos: &mut usize| -> Result<u32, String> {
+        let b: [u8; 4] = take(pos, 4)?.try_into().unwrap();
+        Ok(if little { u32::from_le_bytes(b) } else { u32::from_be_bytes(b) })
+    };
+    let raw = u32_at(pos)?;
+    // EWKB flags, else ISO thousands
+    let (code, mut z, mut m) = ((raw & 0x0fff_ffff) % 1000, raw & 0x8000_0000 != 0, raw & 0x4000_0000 != 0);
+    match (raw & 0x0fff_ffff) / 1000 {
+        0 => {}
+        1 => z = true,
+        2 => m = true,
+        3 => (z, m) = (true, true),
+        _ => return Err(format!("geometry type {}", raw)),
+    }
+    if raw & 0x2000_0000 != 0 {
+        take(pos, 4)?; // EWKB SRID
+    }
+    let dims = 2 + z as usize + m as usize;
+    let point = |pos: &mut usize| -> Result<JsValue, String> {
+        let coords: Vec<f64> = take(pos, dims * 8)?
+            .chunks_exact(8)
+            .take(2 + z as usize)
+            .map(|b| {
+                let b: [u8; 8] = b.try_into().unwrap();
+                if little { f64::from_le_bytes(b) } else { f64::from_be_bytes(b) }
+            })
+            .collect();
+        if coords.iter().all(|c| c.is_nan()) {
+            return Ok(Array::new().into());
+        }
+        Ok(coords.into_iter().map(JsValue::from_f64).collect::<Array>().into())
+    };
+    // Counts are checked against the bytes left before looping over them
+    let count = |pos: &mut usize, min_size: usize| -> Result<u32, String> {
+        let n = u32_at(pos)?;
+        if (n as usize).saturating_mul(min_size) > wkb.len() - *pos {
+            return Err(format!("count {} beyond the end", n));
+        }
+        Ok(n)
+    };
+    let points = |pos: &mut usize| -> Result<JsValue, String> {
+        (0..count(pos, dims * 8)?).map(|_| point(pos)).collect::<Result<Array, String>>().map(JsValue::from)
+    };
+    let obj = Object::new();
+    let set = |key: &str, v: &JsValue| Reflect::set(&obj, &key.into(), v).map(|_| ()).map_err(|_| "can't build the geometry".to_string());
+    let name = *GEOMETRY_TYPES.get((code as usize).wrapping_sub(1)).ok_or_else(|| format!("geometry type {}", raw))?;
+    set("type", &name.into())?;
+    let coordinates = match code {
+        1 => point(pos)?,
+        2 => points(pos)?,
+        3 => (0..count(pos, 4)?).map(|_| points(pos)).collect::<Result<Array, String>>()?.into(),
+        _ => {
+            if depth >= 32 {
+                return Err("nested too deeply".to_string());
             }
-            pos = data_end;
-        } else {
-            // RLE: repeat a value
-            let count = (header >> 1) as usize;
-            let byte_width = (bit_width + 7) / 8;
-            let mut val: u32 = 0;
-            for b in 0..byte_width {
-                if pos + b < buf.len() {
-                    val |= (buf[pos + b] as u32) << (b * 8);
+            let parts = (0..count(pos, 9)?).map(|_| wkb_geometry(wkb, pos, depth + 1)).collect::<Result<Array, String>>()?;
+            if code == 7 {
+                set("geometries", &parts)?;
+                return Ok(obj.into());
+            }
+            // Multi*: the parts' own coordinates
+            let single = GEOMETRY_TYPES[code as usize - 4];
+            let coords = Array::new();
+            for part in parts.iter() {
+                if Reflect::get(&part, &"type".into()).ok().and_then(|t| t.as_string()).as_deref() != Some(single) {
+                    return Err(format!("{} holding something other than a {}", name, single));
                 }
+                coords.push(&Reflect::get(&part, &"coordinates".into()).unwrap_or(JsValue::UNDEFINED));
+            }
+            coords.into()
+        }
+    };
+    set("coordinates", &coordinates)?;
+    Ok(obj.into())
+}
+
+// ── RLE/bit-packed hybrid ───────────────────────────────────────────────────
+
+/// Decode `num_values` values of the RLE/bit-packed hybrid encoding, which
+/// stores repetition and definition levels, dictionary indices and RLE
+/// booleans. It's a sequence of runs, each a ULEB128 header and then either
+/// one value repeated (header `count << 1`, the value in `ceil(bit_width / 8)`
+/// little-endian bytes) or groups of 8 values bit-packed LSB first (header
+/// `groups << 1 | 1`). Runs may hold more values than needed; the padding
+/// after the last value of a bit-packed run may be cut off.
+fn decode_rle_hybrid(buf: &[u8], bit_width: usize, num_values: usize) -> Result<Vec<u32>, String> {
+    if bit_width > 32 {
+        return Err(format!("RLE/bit-packed data has a bit width of {}, over 32", bit_width));
+    }
+    if bit_width == 0 {
+        // Zero-width values are all 0
+        return Ok(vec![0; num_values]);
+    }
+    let truncated = |n: usize| format!("RLE/bit-packed data ends after {} of {} values", n, num_values);
+    let mut values = Vec::with_capacity(num_values);
+    let mut pos = 0;
+    while values.len() < num_values {
+        let header = read_uleb128(buf, &mut pos).ok_or_else(|| truncated(values.len()))?;
+        let wanted = num_values - values.len();
+        if header & 1 == 1 {
+            let count = ((header >> 1) as usize).saturating_mul(8).min(wanted);
+            let data = &buf[pos..];
+            for i in 0..count {
+                let bit_offset = i * bit_width;
+                let bytes = data.get(bit_offset / 8..(bit_offset + bit_width).div_ceil(8)).ok_or_else(|| truncated(values.len()))?;
+                let raw = bytes.iter().rev().fold(0u64, |acc, &b| acc << 8 | b as u64);
+                values.push(((raw >> (bit_offset % 8)) & ((1 << bit_width) - 1)) as u32);
             }
-            pos += byte_width;
-            for _ in 0..count {
-                if indices.len() >= num_values { break; }
-                indices.push(val);
+            // Skip the whole run, minus whatever padding is missing
+            pos += ((header >> 1) as usize).saturating_mul(bit_width).min(data.len());
+        } else {
+            let width = bit_width.div_ceil(8);
+            let bytes = buf.get(pos..pos + width).ok_or_else(|| truncated(values.len()))?;
+            let value = bytes.iter().rev().fold(0u32, |acc, &b| acc << 8 | b as u32);
+            pos += width;
+            let count = ((header >> 1) as usize).min(wanted);
+            if count == 0 {
+                return Err("RLE/bit-packed data has an empty run".to_string());
             }
+            values.extend(std::iter::repeat_n(value, count));
         }
     }
+    Ok(values)
+}
+
+/// Decode RLE/bit-packed hybrid encoded dictionary indices.
+/// The buffer starts with a 1-byte bit_width, then RLE/bit-packed data.
+fn decode_rle_dict_indices(buf: &[u8], num_values: usize) -> Result<Vec<u32>, String> {
+    match buf.split_first() {
+        Some((&bit_width, rest)) => decode_rle_hybrid(rest, bit_width as usize, num_values),
+        None if num_values == 0 => Ok(Vec::new()),
+        None => Err("dictionary indices are missing".to_string()),
+    }
+}
+
+// ── Level helpers ───────────────────────────────────────────────────────────
+
+/// Number of bits needed to store levels 0..=max_level
+fn level_bit_width(max_level: i16) -> usize {
+    (16 - (max_level as u16).leading_zeros()) as usize
+}
+
+/// Read one length-prefixed RLE level section of a V1 data page.
+/// Returns the decoded levels and the number of bytes consumed.
+fn read_v1_levels(buf: &[u8], max_level: i16, num_values: usize) -> Result<(Vec<u32>, usize), String> {
+    let len = buf.get(..4).ok_or("the length prefix is missing")?;
+    let end = (4 + u32::from_le_bytes(len.try_into().unwrap()) as usize).min(buf.len());
+    let levels = decode_rle_hybrid(&buf[4..end], level_bit_width(max_level), num_values)?;
+    Ok((levels, end))
+}
+
+/// Repetition and definition levels of a data page. Either is `None` when
+/// its max level is 0 (every entry starts a row / holds a value).
+struct PageLevels {
+    rep: Option<Vec<u32>>,
+    def: Option<Vec<u32>>,
+}
+
+/// Split a V1 data page into its levels and the encoded values.
+fn split_v1_levels(
+    buf: &[u8],
+    max_rep: i16,
+    max_def: i16,
+    num_values: usize,
+) -> Result<(PageLevels, &[u8]), String> {
+    let mut off = 0;
+    let mut levels = PageLevels { rep: None, def: None };
+    if max_rep > 0 {
+        let (rep, used) = read_v1_levels(buf, max_rep, num_values).map_err(|e| format!("repetition levels: {}", e))?;
+        levels.rep = Some(rep);
+        off += used;
+    }
+    if max_def > 0 {
+        let (def, used) = read_v1_levels(&buf[off..], max_def, num_values).map_err(|e| format!("definition levels: {}", e))?;
+        levels.def = Some(def);
+        off += used;
+    }
+    Ok((levels, &buf[off..]))
+}
+
+/// Split a V2 data page into its levels and the encoded values.
+/// V2 level sections carry no length prefix; their sizes come from the header
+/// and they are never compressed.
+fn split_v2_levels(
+    buf: &[u8],
+    rep_len: usize,
+    def_len: usize,
+    max_rep: i16,
+    max_def: i16,
+    num_values: usize,
+) -> Result<(PageLevels, &[u8]), String> {
+    let rep_end = rep_len.min(buf.len());
+    let def_end = (rep_len + def_len).min(buf.len());
+    let levels = PageLevels {
+        rep: (max_rep > 0)
+            .then(|| decode_rle_hybrid(&buf[..rep_end], level_bit_width(max_rep), num_values).map_err(|e| format!("repetition levels: {}", e)))
+            .transpose()?,
+        def: (max_def > 0)
+            .then(|| decode_rle_hybrid(&buf[rep_end..def_end], level_bit_width(max_def), num_values).map_err(|e| format!("definition levels: {}", e)))
+            .transpose()?,
+    };
+    Ok((levels, &buf[def_end..]))
+}
+
+/// Julian day number of 1970-01-01
+const JULIAN_UNIX_EPOCH: i64 = 2_440_588;
 
-    indices
+/// INT96 timestamp (8 bytes nanoseconds of day, then 4 bytes Julian day,
+/// little-endian) → epoch milliseconds
+fn int96_millis(b: [u8; 12]) -> f64 {
+    let nanos = i64::from_le_bytes(b[..8].try_into().unwrap());
+    let day = u32::from_le_bytes(b[8..].try_into().unwrap()) as i64;
+    ((day - JULIAN_UNIX_EPOCH) * 86_400_000) as f64 + nanos as f64 / 1e6
 }
 
-/// Decode PLAIN-encoded page buffer into a JS Array.
+/// Decode PLAIN-encoded page buffer into `out`.
 /// Returns number of values pushed.
 fn decode_plain(
     buf: &[u8],
     phys: PhysicalType,
     num_vals: usize,
-    arr: &Array,
+    out: &mut ColumnBuf,
     limit: usize,
 ) -> usize {
     let n = num_vals.min(limit);
@@ -139,7 +747,7 @@ fn decode_plain(
                 let off = i * 4;
                 if off + 4 > buf.len() { return i; }
                 let v = i32::from_le_bytes(buf[off..off + 4].try_into().unwrap());
-                arr.push(&JsValue::from_f64(v as f64));
+                out.push_num(v as f64);
             }
             n
         }
@@ -148,7 +756,7 @@ fn decode_plain(
                 let off = i * 8;
                 if off + 8 > buf.len() { return i; }
                 let v = i64::from_le_bytes(buf[off..off + 8].try_into().unwrap());
-                arr.push(&JsValue::from_f64(v as f64));
+                out.push_i64(v);
             }
             n
         }
@@ -157,7 +765,7 @@ fn decode_plain(
                 let off = i * 4;
                 if off + 4 > buf.len() { return i; }
                 let v = f32::from_le_bytes(buf[off..off + 4].try_into().unwrap());
-                arr.push(&JsValue::from_f64(v as f64));
+                out.push_num(v as f64);
             }
             n
         }
@@ -166,7 +774,15 @@ fn decode_plain(
                 let off = i * 8;
                 if off + 8 > buf.len() { return i; }
                 let v = f64::from_le_bytes(buf[off..off + 8].try_into().unwrap());
-                arr.push(&JsValue::from_f64(v));
+                out.push_num(v);
+            }
+            n
+        }
+        PhysicalType::Int96 => {
+            for i in 0..n {
+                let off = i * 12;
+                if off + 12 > buf.len() { return i; }
+                out.push_num(int96_millis(buf[off..off + 12].try_into().unwrap()));
             }
             n
         }
@@ -175,7 +791,7 @@ fn decode_plain(
                 let byte_idx = i / 8;
                 let bit_idx = i % 8;
                 if byte_idx >= buf.len() { return i; }
-                arr.push(&JsValue::from_bool((buf[byte_idx] >> bit_idx) & 1 == 1));
+                out.push_bool((buf[byte_idx] >> bit_idx) & 1 == 1);
             }
             n
         }
@@ -186,127 +802,3686 @@ fn decode_plain(
                 let len = u32::from_le_bytes(buf[off..off + 4].try_into().unwrap()) as usize;
                 off += 4;
                 if off + len > buf.len() { break; }
-                let s = std::str::from_utf8(&buf[off..off + len]).unwrap_or("<binary>");
-                arr.push(&JsValue::from_str(s));
+                out.push_bytes(&buf[off..off + len]);
                 off += len;
                 count += 1;
             }
             count
         }
-        _ => 0,
+        PhysicalType::FixedLenByteArray(size) => {
+            let count = n.min(buf.len().checked_div(size).unwrap_or(0));
+            for i in 0..count {
+                let value = &buf[i * size..(i + 1) * size];
+                match out {
+                    // Only float16 columns get a native buffer
+                    ColumnBuf::F32(v) => v.push(f16_value(u16::from_le_bytes([value[0], value[1]])) as f32),
+                    _ => out.push_js(Uint8Array::from(value).into()),
+                }
+            }
+            count
+        }
     }
 }
 
-/// Read a Parquet file from bytes and return { schema, data, numRows }.
-///
-/// - `data`: raw Uint8Array of the entire Parquet file
-/// - `max_rows`: optional row limit (default 500, for preview)
+// ── Delta decoding ──────────────────────────────────────────────────────────
+
+/// Read a ULEB128 integer at `*pos`, moving past it
+fn read_uleb128(buf: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut v = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *buf.get(*pos)?;
+        *pos += 1;
+        v |= ((byte & 0x7F) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(v);
+        }
+    }
+    None
+}
+
+/// Read a zigzag ULEB128 signed integer at `*pos`, moving past it
+fn read_zigzag(buf: &[u8], pos: &mut usize) -> Option<i64> {
+    let v = read_uleb128(buf, pos)?;
+    Some((v >> 1) as i64 ^ -((v & 1) as i64))
+}
+
+/// Decode DELTA_BINARY_PACKED integers: a header (block size, miniblocks per
+/// block, value count, first value), then blocks of deltas, each stored as
+/// the block's smallest delta plus bit-packed offsets with one bit width per
+/// miniblock. Returns the values and the bytes they took; None when `buf` is
+/// truncated or malformed.
 ///
-/// Returns a JS object:
-/// ```js
-/// {
-///   schema: [{ name: "col1", type: "string" }, ...],
-///   data:   { col1: ["a", "b"], col2: [1, 2], ... },
-///   numRows: 12345   // total rows in file (preview may be fewer)
-/// }
-/// ```
-#[wasm_bindgen(js_name = "readParquet")]
-pub fn read_parquet(data: &Uint8Array, max_rows: Option<u32>) -> Result<JsValue, JsValue> {
-    let bytes = data.to_vec();
-    let limit = max_rows.unwrap_or(500) as usize;
+/// Sums wrap, as writers let the deltas of int32 columns wrap at 32 bits: the
+/// low 32 bits of each value are then right.
+fn decode_delta(buf: &[u8]) -> Option<(Vec<i64>, usize)> {
+    let mut pos = 0;
+    let block_size = usize::try_from(read_uleb128(buf, &mut pos)?).ok()?;
+    let miniblocks = usize::try_from(read_uleb128(buf, &mut pos)?).ok()?;
+    let total = usize::try_from(read_uleb128(buf, &mut pos)?).ok()?;
+    let mut last = read_zigzag(buf, &mut pos)?;
+    let per_miniblock = block_size.checked_div(miniblocks).filter(|&n| n > 0 && n % 8 == 0)?;
 
-    // Read metadata (footer)
-    let mut cursor = Cursor::new(&bytes[..]);
-    let metadata = read_metadata(&mut cursor)
-        .map_err(|e| JsValue::from_str(&format!("metadata: {}", e)))?;
+    // A corrupt count only fails once the blocks run out: don't trust it up front
+    let mut vals = Vec::with_capacity(total.min(buf.len().saturating_mul(8)));
+    if total == 0 {
+        return Some((vals, pos));
+    }
+    vals.push(last);
+    while vals.len() < total {
+        let min = read_zigzag(buf, &mut pos)?;
+        let widths = buf.get(pos..pos.checked_add(miniblocks)?)?;
+        pos += miniblocks;
+        for &width in widths {
+            if vals.len() >= total {
+                break;
+            }
+            let width = width as usize;
+            if width > 64 {
+                return None;
+            }
+            // Sizes come from the header: on wasm32 a corrupt one overflows usize
+            let data = buf.get(pos..pos.checked_add(per_miniblock.checked_mul(width)? / 8)?)?;
+            pos += data.len();
+            let mask = if width == 64 { u64::MAX } else { (1 << width) - 1 };
+            for i in 0..per_miniblock.min(total - vals.len()) {
+                let bit_offset = i * width;
+                let mut raw = 0u128;
+                for (b, &byte) in data[bit_offset / 8..].iter().take((bit_offset % 8 + width).div_ceil(8)).enumerate() {
+                    raw |= (byte as u128) << (b * 8);
+                }
+                let offset = (raw >> (bit_offset % 8)) as u64 & mask;
+                last = last.wrapping_add(min.wrapping_add(offset as i64));
+                vals.push(last);
+            }
+        }
+    }
+    Some((vals, pos))
+}
 
-    let col_descriptors = metadata.schema_descr.columns();
+/// Push up to `n` DELTA_BINARY_PACKED values of an int32 or int64 column
+fn push_delta(buf: &[u8], phys: PhysicalType, n: usize, out: &mut ColumnBuf) -> Result<(), String> {
+    let (vals, _) = decode_delta(buf).ok_or("DELTA_BINARY_PACKED values are truncated or corrupt")?;
+    for &v in vals.iter().take(n) {
+        match phys {
+            PhysicalType::Int32 => out.push_num(v as i32 as f64),
+            PhysicalType::Int64 => out.push_i64(v),
+            _ => return Err(format!("DELTA_BINARY_PACKED isn't valid for {:?} columns", phys)),
+        }
+    }
+    Ok(())
+}
 
-    // ── Build JS schema array ────────────────────────────────────────────────
-    let schema_arr = Array::new();
-    for desc in col_descriptors {
-        let obj = Object::new();
-        let name = &desc.descriptor.primitive_type.field_info.name;
-        let phys = desc.descriptor.primitive_type.physical_type;
-        let logical = &desc.descriptor.primitive_type.logical_type;
-        Reflect::set(&obj, &"name".into(), &JsValue::from_str(name))?;
-        Reflect::set(&obj, &"type".into(), &JsValue::from_str(type_label(phys, logical)))?;
-        schema_arr.push(&obj);
+/// Push up to `n` DELTA_LENGTH_BYTE_ARRAY values or, when `prefixed`,
+/// DELTA_BYTE_ARRAY values (each a prefix of the previous one plus a suffix
+/// stored as DELTA_LENGTH_BYTE_ARRAY)
+fn push_delta_bytes(buf: &[u8], phys: PhysicalType, n: usize, out: &mut ColumnBuf, prefixed: bool) -> Result<(), String> {
+    let name = if prefixed { "DELTA_BYTE_ARRAY" } else { "DELTA_LENGTH_BYTE_ARRAY" };
+    let corrupt = || format!("{} values are truncated or corrupt", name);
+    let (prefixes, mut pos) = if prefixed { decode_delta(buf).ok_or_else(corrupt)? } else { (Vec::new(), 0) };
+    let (lengths, used) = decode_delta(&buf[pos..]).ok_or_else(corrupt)?;
+    pos += used;
+    let mut value = Vec::new();
+    for (i, &len) in lengths.iter().take(n).enumerate() {
+        let prefix = prefixes.get(i).map_or(Ok(0), |&p| usize::try_from(p)).map_err(|_| corrupt())?;
+        let suffix = usize::try_from(len).ok().and_then(|len| buf.get(pos..pos.checked_add(len)?)).ok_or_else(corrupt)?;
+        pos += suffix.len();
+        if prefix > value.len() {
+            return Err(corrupt());
+        }
+        value.truncate(prefix);
+        value.extend_from_slice(suffix);
+        match phys {
+            PhysicalType::ByteArray => out.push_bytes(&value),
+            PhysicalType::FixedLenByteArray(_) => {
+                decode_plain(&value, phys, 1, out, 1);
+            }
+            _ => return Err(format!("{} isn't valid for {:?} columns", name, phys)),
+        }
     }
+    Ok(())
+}
 
-    // ── Read column data ─────────────────────────────────────────────────────
-    let data_obj = Object::new();
+/// Push `n` RLE-encoded booleans: a 4-byte length, then the RLE/bit-packed
+/// hybrid with a bit width of 1
+fn push_rle_bools(buf: &[u8], n: usize, out: &mut ColumnBuf) -> Result<(), String> {
+    if n == 0 {
+        return Ok(());
+    }
+    let len = buf.get(..4).ok_or("RLE booleans are missing")?;
+    let end = (4 + u32::from_le_bytes(len.try_into().unwrap()) as usize).min(buf.len());
+    for bit in decode_rle_hybrid(&buf[4..end], 1, n)? {
+        out.push_bool(bit == 1);
+    }
+    Ok(())
+}
 
-    for rg in &metadata.row_groups {
-        for (ci, col_chunk) in rg.columns().iter().enumerate() {
-            let desc = &col_descriptors[ci];
-            let name = &desc.descriptor.primitive_type.field_info.name;
-            let phys = desc.descriptor.primitive_type.physical_type;
+/// Push up to `n` BYTE_STREAM_SPLIT values: the first byte of every value,
+/// then every second byte, and so on. Float and double columns use it most,
+/// but any fixed-width type may.
+fn push_byte_stream_split(buf: &[u8], phys: PhysicalType, n: usize, out: &mut ColumnBuf) -> Result<(), String> {
+    let width = match phys {
+        PhysicalType::Int32 | PhysicalType::Float => 4,
+        PhysicalType::Int64 | PhysicalType::Double => 8,
+        PhysicalType::FixedLenByteArray(size) if size > 0 => size,
+        _ => return Err(format!("BYTE_STREAM_SPLIT isn't valid for {:?} columns", phys)),
+    };
+    // Each stream holds one byte of every value on the page
+    let count = buf.len() / width;
+    if count < n {
+        return Err("BYTE_STREAM_SPLIT values are truncated".to_string());
+    }
+    let mut plain = vec![0; n * width];
+    for (i, value) in plain.chunks_exact_mut(width).enumerate() {
+        for (b, byte) in value.iter_mut().enumerate() {
+            *byte = buf[b * count + i];
+        }
+    }
+    decode_plain(&plain, phys, n, out, n);
+    Ok(())
+}
 
-            // Fresh cursor per column (get_page_iterator takes reader by value)
-            let col_cursor = Cursor::new(&bytes[..]);
-            let pages = get_page_iterator(col_chunk, col_cursor, None, vec![], usize::MAX)
-                .map_err(|e| JsValue::from_str(&format!("pages[{}]: {}", ci, e)))?;
+// ── Column buffers ──────────────────────────────────────────────────────────
 
-            let arr = Array::new();
-            let mut total = 0usize;
-            let mut dict: Option<Vec<String>> = None;
+/// Decoded values of one column: a JS array, or a native buffer that is
+/// handed to JS as a TypedArray in one copy (required numeric/boolean columns
+/// with `typedArrays: true`).
+enum ColumnBuf {
+    Js(Array),
+    /// JS array with int64 values as BigInt (`int64: 'bigint'`)
+    BigJs(Array),
+    /// JS array with byte-array values as Uint8Array (columns that aren't UTF-8 text)
+    Bytes(Array),
+    I32(Vec<i32>),
+    /// int64 → BigInt64Array
+    I64(Vec<i64>),
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+    /// Booleans as 0/1 bytes → Uint8Array
+    Bool(Vec<u8>),
+}
 
-            for maybe in pages {
-                if total >= limit { break; }
-                let cp = maybe.map_err(|e| JsValue::from_str(&format!("page: {}", e)))?;
-                let page = decompress(cp, &mut vec![])
-                    .map_err(|e| JsValue::from_str(&format!("decomp: {}", e)))?;
-
-                match page {
-                    Page::Dict(dp) => {
-                        // Store dictionary for subsequent data pages
-                        dict = Some(decode_dict_binary(dp.buffer.as_slice()));
-                    }
-                    Page::Data(dp) => {
-                        let nv = dp.num_values();
-                        let encoding = dp.encoding();
-
-                        match encoding {
-                            Encoding::RleDictionary | Encoding::PlainDictionary => {
-                                // Dictionary-encoded page
-                                if let Some(ref dict_values) = dict {
-                                    let indices = decode_rle_dict_indices(dp.buffer(), nv);
-                                    let remaining = limit - total;
-                                    let n = nv.min(remaining);
-                                    for i in 0..n {
-                                        let idx = indices[i] as usize;
-                                        if idx < dict_values.len() {
-                                            arr.push(&JsValue::from_str(&dict_values[idx]));
-                                        } else {
-                                            arr.push(&JsValue::from_str("<invalid>"));
-                                        }
-                                    }
-                                    total += n;
-                                }
-                            }
-                            _ => {
-                                // PLAIN encoding (existing path)
-                                total += decode_plain(dp.buffer(), phys, nv, &arr, limit - total);
-                            }
-                        }
+impl ColumnBuf {
+    /// Buffer for a column: native (TypedArray) when `typed` and the type has
+    /// one, else a JS array. int64 values become BigInts when `bigint`, byte
+    /// arrays become Uint8Arrays unless `text`.
+    fn for_column(phys: PhysicalType, typed: bool, bigint: bool, text: bool) -> Self {
+        if phys == PhysicalType::ByteArray && !text {
+            return ColumnBuf::Bytes(Array::new());
+        }
+        let is_int64 = phys == PhysicalType::Int64;
+        match (typed, is_int64 && bigint) {
+            (true, true) => ColumnBuf::I64(Vec::new()),
+            (true, false) => Self::typed_for(phys).unwrap_or_else(|| ColumnBuf::Js(Array::new())),
+            (false, true) => ColumnBuf::BigJs(Array::new()),
+            (false, false) => ColumnBuf::Js(Array::new()),
+        }
+    }
+
+    /// Native buffer for a column of this type, if it has one
+    fn typed_for(phys: PhysicalType) -> Option<Self> {
+        match phys {
+            PhysicalType::Int32 => Some(ColumnBuf::I32(Vec::new())),
+            PhysicalType::Int64 | PhysicalType::Double => Some(ColumnBuf::F64(Vec::new())),
+            PhysicalType::Float => Some(ColumnBuf::F32(Vec::new())),
+            PhysicalType::Boolean => Some(ColumnBuf::Bool(Vec::new())),
+            _ => None,
+        }
+    }
+
+    /// Empty buffer of the same kind
+    fn empty_like(&self) -> Self {
+        match self {
+            ColumnBuf::Js(_) => ColumnBuf::Js(Array::new()),
+            ColumnBuf::BigJs(_) => ColumnBuf::BigJs(Array::new()),
+            ColumnBuf::Bytes(_) => ColumnBuf::Bytes(Array::new()),
+            ColumnBuf::I32(_) => ColumnBuf::I32(Vec::new()),
+            ColumnBuf::I64(_) => ColumnBuf::I64(Vec::new()),
+            ColumnBuf::F32(_) => ColumnBuf::F32(Vec::new()),
+            ColumnBuf::F64(_) => ColumnBuf::F64(Vec::new()),
+            ColumnBuf::Bool(_) => ColumnBuf::Bool(Vec::new()),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            ColumnBuf::Js(a) | ColumnBuf::BigJs(a) | ColumnBuf::Bytes(a) => a.length() as usize,
+            ColumnBuf::I32(v) => v.len(),
+            ColumnBuf::I64(v) => v.len(),
+            ColumnBuf::F32(v) => v.len(),
+            ColumnBuf::F64(v) => v.len(),
+            ColumnBuf::Bool(v) => v.len(),
+        }
+    }
+
+    fn push_num(&mut self, v: f64) {
+        match self {
+            ColumnBuf::Js(a) | ColumnBuf::BigJs(a) | ColumnBuf::Bytes(a) => { a.push(&JsValue::from_f64(v)); }
+            ColumnBuf::I32(b) => b.push(v as i32),
+            ColumnBuf::I64(b) => b.push(v as i64),
+            ColumnBuf::F32(b) => b.push(v as f32),
+            ColumnBuf::F64(b) => b.push(v),
+            ColumnBuf::Bool(b) => b.push((v != 0.0) as u8),
+        }
+    }
+
+    fn push_i64(&mut self, v: i64) {
+        match self {
+            ColumnBuf::BigJs(a) => { a.push(&JsValue::from(v)); }
+            ColumnBuf::I64(b) => b.push(v),
+            other => other.push_num(v as f64),
+        }
+    }
+
+    /// Push a BYTE_ARRAY value: UTF-8 text, or a Uint8Array for binary columns
+    fn push_bytes(&mut self, b: &[u8]) {
+        match self {
+            ColumnBuf::Bytes(a) => { a.push(&Uint8Array::from(b)); }
+            other => other.push_js(JsValue::from_str(std::str::from_utf8(b).unwrap_or("<binary>"))),
+        }
+    }
+
+    fn push_bool(&mut self, v: bool) {
+        match self {
+            ColumnBuf::Js(a) => { a.push(&JsValue::from_bool(v)); }
+            other => other.push_num(v as u8 as f64),
+        }
+    }
+
+    /// Push a JS value (only JS buffers hold non-numeric values or nulls)
+    fn push_js(&mut self, v: JsValue) {
+        if let ColumnBuf::Js(a) | ColumnBuf::BigJs(a) | ColumnBuf::Bytes(a) = self {
+            a.push(&v);
+        }
+    }
+
+    /// Append element `i` of `other` (a buffer of the same kind)
+    fn push_from(&mut self, other: &ColumnBuf, i: usize) {
+        match (self, other) {
+            (ColumnBuf::Js(a), ColumnBuf::Js(b))
+            | (ColumnBuf::BigJs(a), ColumnBuf::BigJs(b))
+            | (ColumnBuf::Bytes(a), ColumnBuf::Bytes(b)) => {
+                a.push(&b.get(i as u32));
+            }
+            (ColumnBuf::I32(a), ColumnBuf::I32(b)) => a.push(b[i]),
+            (ColumnBuf::I64(a), ColumnBuf::I64(b)) => a.push(b[i]),
+            (ColumnBuf::F32(a), ColumnBuf::F32(b)) => a.push(b[i]),
+            (ColumnBuf::F64(a), ColumnBuf::F64(b)) => a.push(b[i]),
+            (ColumnBuf::Bool(a), ColumnBuf::Bool(b)) => a.push(b[i]),
+            _ => {}
+        }
+    }
+
+    /// Element `i` as a JS value
+    fn get(&self, i: usize) -> JsValue {
+        match self {
+            ColumnBuf::Js(a) | ColumnBuf::BigJs(a) | ColumnBuf::Bytes(a) => a.get(i as u32),
+            ColumnBuf::I32(v) => JsValue::from_f64(v[i] as f64),
+            ColumnBuf::I64(v) => JsValue::from(v[i]),
+            ColumnBuf::F32(v) => JsValue::from_f64(v[i] as f64),
+            ColumnBuf::F64(v) => JsValue::from_f64(v[i]),
+            ColumnBuf::Bool(v) => JsValue::from_bool(v[i] != 0),
+        }
+    }
+
+    /// Run a logical-type conversion over the non-null values of a JS array
+    /// column. Errors name the offending row.
+    fn convert(self, conv: Convert) -> Result<Self, String> {
+        match self {
+            ColumnBuf::Js(a) | ColumnBuf::BigJs(a) | ColumnBuf::Bytes(a) if conv != Convert::None => {
+                for i in 0..a.length() {
+                    let v = a.get(i);
+                    if !v.is_null() {
+                        a.set(i, conv.try_apply(v).map_err(|e| format!("row {}: {}", i, e))?);
                     }
                 }
+                Ok(ColumnBuf::Js(a))
             }
+            other => Ok(other),
+        }
+    }
+
+    fn into_js(self) -> JsValue {
+        match self {
+            ColumnBuf::Js(a) | ColumnBuf::BigJs(a) | ColumnBuf::Bytes(a) => a.into(),
+            ColumnBuf::I32(v) => Int32Array::from(&v[..]).into(),
+            ColumnBuf::I64(v) => BigInt64Array::from(&v[..]).into(),
+            ColumnBuf::F32(v) => Float32Array::from(&v[..]).into(),
+            ColumnBuf::F64(v) => Float64Array::from(&v[..]).into(),
+            ColumnBuf::Bool(v) => Uint8Array::from(&v[..]).into(),
+        }
+    }
+}
+
+// ── Statistics ──────────────────────────────────────────────────────────────
+
+/// Compare two PLAIN-encoded statistic values of the same physical type
+/// (fixed-length decimals compare as signed numbers, uint32 as unsigned)
+fn cmp_stat_bytes(phys: PhysicalType, conv: Convert, a: &[u8], b: &[u8]) -> Ordering {
+    match phys {
+        PhysicalType::Int32 if conv == Convert::UInt32 && a.len() == 4 && b.len() == 4 => {
+            u32::from_le_bytes(a.try_into().unwrap()).cmp(&u32::from_le_bytes(b.try_into().unwrap()))
+        }
+        PhysicalType::Int64 if matches!(conv, Convert::UInt64(_)) && a.len() == 8 && b.len() == 8 => {
+            u64::from_le_bytes(a.try_into().unwrap()).cmp(&u64::from_le_bytes(b.try_into().unwrap()))
+        }
+        PhysicalType::FixedLenByteArray(_) if matches!(conv, Convert::Decimal(..)) => be_signed(a).cmp(&be_signed(b)),
+        PhysicalType::FixedLenByteArray(2) if conv == Convert::Float16 && a.len() == 2 && b.len() == 2 => {
+            f16_value(u16::from_le_bytes([a[0], a[1]])).total_cmp(&f16_value(u16::from_le_bytes([b[0], b[1]])))
+        }
+        PhysicalType::Int32 if a.len() == 4 && b.len() == 4 => {
+            i32::from_le_bytes(a.try_into().unwrap()).cmp(&i32::from_le_bytes(b.try_into().unwrap()))
+        }
+        PhysicalType::Int64 if a.len() == 8 && b.len() == 8 => {
+            i64::from_le_bytes(a.try_into().unwrap()).cmp(&i64::from_le_bytes(b.try_into().unwrap()))
+        }
+        PhysicalType::Float if a.len() == 4 && b.len() == 4 => f32::from_le_bytes(a.try_into().unwrap())
+            .total_cmp(&f32::from_le_bytes(b.try_into().unwrap())),
+        PhysicalType::Double if a.len() == 8 && b.len() == 8 => f64::from_le_bytes(a.try_into().unwrap())
+            .total_cmp(&f64::from_le_bytes(b.try_into().unwrap())),
+        _ => a.cmp(b),
+    }
+}
 
-            Reflect::set(&data_obj, &JsValue::from_str(name), &arr)?;
+/// Decode a single PLAIN-encoded statistic value (no length prefix for byte arrays).
+/// Byte arrays are text when `text`, else Uint8Arrays.
+fn stat_value(phys: PhysicalType, text: bool, bytes: &[u8]) -> JsValue {
+    match phys {
+        PhysicalType::Boolean if !bytes.is_empty() => JsValue::from_bool(bytes[0] != 0),
+        PhysicalType::Int32 if bytes.len() == 4 => {
+            JsValue::from_f64(i32::from_le_bytes(bytes.try_into().unwrap()) as f64)
+        }
+        PhysicalType::Int64 if bytes.len() == 8 => {
+            JsValue::from_f64(i64::from_le_bytes(bytes.try_into().unwrap()) as f64)
+        }
+        PhysicalType::Float if bytes.len() == 4 => {
+            JsValue::from_f64(f32::from_le_bytes(bytes.try_into().unwrap()) as f64)
+        }
+        PhysicalType::Double if bytes.len() == 8 => {
+            JsValue::from_f64(f64::from_le_bytes(bytes.try_into().unwrap()))
         }
+        PhysicalType::ByteArray if text => {
+            JsValue::from_str(std::str::from_utf8(bytes).unwrap_or("<binary>"))
+        }
+        PhysicalType::ByteArray | PhysicalType::FixedLenByteArray(_) => Uint8Array::from(bytes).into(),
+        _ => JsValue::UNDEFINED,
     }
+}
 
-    // ── Build result object ──────────────────────────────────────────────────
-    let result = Object::new();
-    Reflect::set(&result, &"schema".into(), &schema_arr)?;
-    Reflect::set(&result, &"data".into(), &data_obj)?;
-    Reflect::set(
-        &result,
-        &"numRows".into(),
-        &JsValue::from_f64(metadata.num_rows as f64),
-    )?;
+/// Merge the column-chunk statistics of column `ci` across all row groups into
+/// `{ min, max, nullCount, distinctCount }`. Fields are omitted when any chunk
+/// lacks them; distinctCount is only reported for single-chunk columns.
+fn column_stats(metadata: &FileMetaData, ci: usize, prim: &PrimitiveType, conv: Convert) -> Result<JsValue, JsValue> {
+    let phys = prim.physical_type;
+    let mut min: Option<Vec<u8>> = None;
+    let mut max: Option<Vec<u8>> = None;
+    let mut null_count = Some(0i64);
+    let mut distinct_count = None;
+    let mut has_bounds = true;
+
+    if metadata.row_groups.is_empty() {
+        return Ok(JsValue::UNDEFINED);
+    }
+    // Deprecated min/max used signed byte order for binary; only trust them for numbers
+    let legacy_ok = !matches!(phys, PhysicalType::ByteArray | PhysicalType::FixedLenByteArray(_));
 
-    Ok(result.into())
+    for rg in &metadata.row_groups {
+        let chunk = &rg.columns()[ci];
+        let stats = match &chunk.metadata().statistics {
+            Some(s) => s,
+            None => return Ok(JsValue::UNDEFINED),
+        };
+        let lo = stats.min_value.as_ref().or(if legacy_ok { stats.min.as_ref() } else { None });
+        let hi = stats.max_value.as_ref().or(if legacy_ok { stats.max.as_ref() } else { None });
+        match (lo, hi) {
+            (Some(lo), Some(hi)) => {
+                if min.as_ref().is_none_or(|m| cmp_stat_bytes(phys, conv, lo, m) == Ordering::Less) {
+                    min = Some(lo.clone());
+                }
+                if max.as_ref().is_none_or(|m| cmp_stat_bytes(phys, conv, hi, m) == Ordering::Greater) {
+                    max = Some(hi.clone());
+                }
+            }
+            _ => {
+                // all-null chunks carry no bounds but don't invalidate the others
+                let all_null = stats.null_count == Some(chunk.num_values());
+                if !all_null { has_bounds = false; }
+            }
+        }
+        null_count = null_count.zip(stats.null_count).map(|(a, b)| a + b);
+        distinct_count = stats.distinct_count;
+    }
+
+    let obj = Object::new();
+    if has_bounds {
+        if let (Some(lo), Some(hi)) = (min, max) {
+            // uint64 bounds go through BigInt so values past 2^53 survive the conversion
+            let bound = |b: &[u8]| match conv {
+                Convert::UInt64(_) if b.len() == 8 => conv.apply(JsValue::from(i64::from_le_bytes(b.try_into().unwrap()))),
+                _ => conv.apply(stat_value(phys, is_text(prim), b)),
+            };
+            Reflect::set(&obj, &"min".into(), &bound(&lo))?;
+            Reflect::set(&obj, &"max".into(), &bound(&hi))?;
+        }
+    }
+    if let Some(n) = null_count {
+        Reflect::set(&obj, &"nullCount".into(), &JsValue::from_f64(n as f64))?;
+    }
+    if metadata.row_groups.len() == 1 {
+        if let Some(n) = distinct_count {
+            Reflect::set(&obj, &"distinctCount".into(), &JsValue::from_f64(n as f64))?;
+        }
+    }
+    Ok(obj.into())
+}
+
+// ── Footer ──────────────────────────────────────────────────────────────────
+
+/// Slice reader that counts the bytes consumed, so a thrift walk can tell
+/// where each field starts
+struct Tracked<'a> {
+    rest: &'a [u8],
+    pos: Rc<Cell<usize>>,
+}
+
+impl Read for Tracked<'_> {
+    fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.rest.read(out)?;
+        self.pos.set(self.pos.get() + n);
+        Ok(n)
+    }
+}
+
+/// LogicalType union member for FLOAT16 (newer than parquet-format-safe)
+const LOGICAL_FLOAT16: i16 = 15;
+
+/// Leaf columns whose schema element carries the FLOAT16 logical type, with
+/// the byte range of that logical_type field in the thrift-encoded `footer`.
+/// Only fields that end their element are reported, so cutting them out
+/// leaves the remaining field ids intact.
+fn float16_fields(footer: &[u8]) -> parquet_format_safe::thrift::Result<Vec<(usize, Range<usize>)>> {
+    let pos = Rc::new(Cell::new(0));
+    let mut p = TCompactInputProtocol::new(Tracked { rest: footer, pos: pos.clone() }, footer.len() * 2 + 1024);
+    let mut found = Vec::new();
+    p.read_struct_begin()?;
+    loop {
+        let field = p.read_field_begin()?;
+        if field.field_type == TType::Stop {
+            break;
+        }
+        // FileMetaData.schema (field 2): the flattened SchemaElement list
+        if field.id != Some(2) {
+            p.skip(field.field_type)?;
+            p.read_field_end()?;
+            continue;
+        }
+        let list = p.read_list_begin()?;
+        let mut leaf = 0;
+        for _ in 0..list.size {
+            let mut group = false;
+            let mut float16 = None;
+            p.read_struct_begin()?;
+            loop {
+                let start = pos.get();
+                let f = p.read_field_begin()?;
+                match (f.field_type, f.id) {
+                    (TType::Stop, _) => break,
+                    // num_children: a group, not a leaf column
+                    (_, Some(5)) => {
+                        group = true;
+                        p.skip(f.field_type)?;
+                    }
+                    // logical_type: a union, so a single member
+                    (TType::Struct, Some(10)) => {
+                        let mut member = None;
+                        p.read_struct_begin()?;
+                        loop {
+                            let m = p.read_field_begin()?;
+                            if m.field_type == TType::Stop {
+                                break;
+                            }
+                            member = member.or(m.id);
+                            p.skip(m.field_type)?;
+                            p.read_field_end()?;
+                        }
+                        p.read_struct_end()?;
+                        if member == Some(LOGICAL_FLOAT16) && footer.get(pos.get()) == Some(&0) {
+                            float16 = Some(start..pos.get());
+                        }
+                    }
+                    _ => p.skip(f.field_type)?,
+                }
+                p.read_field_end()?;
+            }
+            p.read_struct_end()?;
+            if !group {
+                if let Some(range) = float16 {
+                    found.push((leaf, range));
+                }
+                leaf += 1;
+            }
+        }
+        p.read_list_end()?;
+        break;
+    }
+    Ok(found)
+}
+
+/// Thrift-encoded footer of a parquet file, if the trailer is well formed
+fn footer_bytes(bytes: &[u8]) -> Option<&[u8]> {
+    let len = bytes.len();
+    if len < 12 || &bytes[len - 4..] != b"PAR1" {
+        return None;
+    }
+    let meta_len = u32::from_le_bytes(bytes[len - 8..len - 4].try_into().unwrap()) as usize;
+    bytes.get(len.checked_sub(8 + meta_len)?..len - 8)
+}
+
+/// Set the `Unknown` logical type on the leaves numbered in `leaves`
+fn mark_float16(t: ParquetType, leaf: &mut usize, leaves: &[usize]) -> ParquetType {
+    match t {
+        ParquetType::PrimitiveType(mut prim) => {
+            if leaves.contains(leaf) {
+                prim.logical_type = Some(PrimitiveLogicalType::Unknown);
+            }
+            *leaf += 1;
+            ParquetType::PrimitiveType(prim)
+        }
+        ParquetType::GroupType { field_info, logical_type, converted_type, fields } => ParquetType::GroupType {
+            field_info,
+            logical_type,
+            converted_type,
+            fields: fields.into_iter().map(|f| mark_float16(f, leaf, leaves)).collect(),
+        },
+    }
+}
+
+/// `footer` with the byte ranges of `fields` (from `float16_fields`) cut out
+fn without_fields(footer: &[u8], fields: &[(usize, Range<usize>)]) -> Vec<u8> {
+    let mut patched = Vec::with_capacity(footer.len());
+    let mut from = 0;
+    for (_, range) in fields {
+        patched.extend_from_slice(&footer[from..range.start]);
+        from = range.end;
+    }
+    patched.extend_from_slice(&footer[from..]);
+    patched
+}
+
+/// parquet2's metadata from a footer parsed by `thrift_footer`. parquet2
+/// rejects FLOAT16 logical types, so those fields were cut out before
+/// parsing; the `float16` leaves come back marked for `is_float16`.
+fn metadata_from_thrift(metadata: TFileMetaData, float16: &[usize]) -> Result<FileMetaData, String> {
+    let mut metadata = FileMetaData::try_from_thrift(metadata).map_err(|e| e.to_string())?;
+    if float16.is_empty() {
+        return Ok(metadata);
+    }
+    let mut leaf = 0;
+    let schema = metadata.schema_descr.fields().iter().cloned().map(|t| mark_float16(t, &mut leaf, float16)).collect();
+    metadata.schema_descr = SchemaDescriptor::new(metadata.schema_descr.name().to_string(), schema);
+    Ok(metadata)
+}
+
+// ── File bytes ──────────────────────────────────────────────────────────────
+
+/// The parts of a file held in memory: all of it for local files, the footer
+/// plus the column chunks fetched so far for remote ones
+struct FileBytes {
+    len: u64,
+    /// Non-overlapping, non-adjacent `(offset, bytes)` ranges, sorted by offset
+    ranges: Vec<(u64, Vec<u8>)>,
+}
+
+impl FileBytes {
+    fn whole(bytes: Vec<u8>) -> Self {
+        FileBytes { len: bytes.len() as u64, ranges: vec![(0, bytes)] }
+    }
+
+    /// Bytes `start..end` (clamped to the file), if one loaded range covers them
+    fn get(&self, start: u64, end: u64) -> Option<&[u8]> {
+        let end = end.min(self.len);
+        let start = start.min(end);
+        let (offset, bytes) = self.ranges.iter().rev().find(|(offset, _)| *offset <= start)?;
+        bytes.get((start - offset) as usize..(end - offset) as usize)
+    }
+
+    /// Bytes from `start` to the end of the loaded range holding it
+    fn loaded_from(&self, start: u64) -> Option<&[u8]> {
+        let (offset, bytes) = self.ranges.iter().rev().find(|(offset, _)| *offset <= start)?;
+        bytes.get((start - offset) as usize..)
+    }
+
+    /// The bytes of one column chunk
+    fn chunk(&self, chunk: &ColumnChunkMetaData) -> Result<&[u8], JsValue> {
+        let (start, len) = chunk.byte_range();
+        self.get(start, start + len)
+            .ok_or_else(|| JsValue::from_str(&format!("bytes {}..{} of the file are not loaded", start, start + len)))
+    }
+
+    /// The parts of `start..end` (clamped to the file) not loaded yet
+    fn missing(&self, start: u64, end: u64) -> Vec<Range<u64>> {
+        let end = end.min(self.len);
+        let mut gaps = Vec::new();
+        let mut pos = start;
+        for (offset, bytes) in &self.ranges {
+            if pos >= end {
+                break;
+            }
+            if *offset > pos {
+                gaps.push(pos..(*offset).min(end));
+            }
+            pos = pos.max(offset + bytes.len() as u64);
+        }
+        if pos < end {
+            gaps.push(pos..end);
+        }
+        gaps
+    }
+
+    /// Add `bytes` read at `offset`, merging them with ranges they overlap or touch
+    fn insert(&mut self, offset: u64, bytes: Vec<u8>) {
+        let (mut start, mut merged) = (offset, bytes);
+        let mut kept = Vec::with_capacity(self.ranges.len() + 1);
+        for (o, b) in std::mem::take(&mut self.ranges) {
+            let (end, merged_end) = (o + b.len() as u64, start + merged.len() as u64);
+            if end < start || o > merged_end {
+                kept.push((o, b));
+                continue;
+            }
+            let new_start = start.min(o);
+            let mut buf = vec![0; (merged_end.max(end) - new_start) as usize];
+            buf[(o - new_start) as usize..][..b.len()].copy_from_slice(&b);
+            buf[(start - new_start) as usize..][..merged.len()].copy_from_slice(&merged);
+            (start, merged) = (new_start, buf);
+        }
+        kept.push((start, merged));
+        kept.sort_by_key(|(o, _)| *o);
+        self.ranges = kept;
+    }
+}
+
+// ── Decryption ──────────────────────────────────────────────────────────────
+//
+// Parquet modular encryption stores every page header, page, page index,
+// bloom filter and — in "PARE" files — the footer as its own AES module:
+// length, nonce, ciphertext, tag. Given the keys, a whole file is decrypted
+// once into the plain layout the rest of the reader expects. Columns whose
+// key wasn't given stay in the schema but can't be read.
+
+/// AES-GCM with one key: AES-128, -192 or -256 by the key's length. Only
+/// the encrypting half of AES is kept; GCM and the AES-CTR pages of
+/// AES_GCM_CTR_V1 files both run it forwards.
+enum AesGcm {
+    Aes128(Aes128Enc),
+    Aes192(Aes192Enc),
+    Aes256(Aes256Enc),
+}
+
+impl AesGcm {
+    fn new(key: &[u8]) -> Option<Self> {
+        match key.len() {
+            16 => Aes128Enc::new_from_slice(key).ok().map(AesGcm::Aes128),
+            24 => Aes192Enc::new_from_slice(key).ok().map(AesGcm::Aes192),
+            32 => Aes256Enc::new_from_slice(key).ok().map(AesGcm::Aes256),
+            _ => None,
+        }
+    }
+
+    /// XOR `data` with the AES-CTR keystream whose first counter block is
+    /// the nonce followed by a 32-bit big-endian 1
+    fn ctr(&self, nonce: &[u8; 12], data: &mut [u8]) {
+        let mut iv = [0u8; 16];
+        iv[..12].copy_from_slice(nonce);
+        iv[15] = 1;
+        match self {
+            AesGcm::Aes128(aes) => Ctr32BE::from_core(CtrCore::inner_iv_init(aes.clone(), &iv.into())).apply_keystream(data),
+            AesGcm::Aes192(aes) => Ctr32BE::from_core(CtrCore::inner_iv_init(aes.clone(), &iv.into())).apply_keystream(data),
+            AesGcm::Aes256(aes) => Ctr32BE::from_core(CtrCore::inner_iv_init(aes.clone(), &iv.into())).apply_keystream(data),
+        }
+    }
+
+    /// Encrypt `data` in place and return the 16-byte tag; None if it's too
+    /// large for AES-GCM
+    fn seal(&self, nonce: &[u8; 12], aad: &[u8], data: &mut [u8]) -> Option<[u8; 16]> {
+        let nonce = Nonce::from_slice(nonce);
+        let tag = match self {
+            AesGcm::Aes128(aes) => Gcm::<_, U12>::from(aes.clone()).encrypt_in_place_detached(nonce, aad, data),
+            AesGcm::Aes192(aes) => Gcm::<_, U12>::from(aes.clone()).encrypt_in_place_detached(nonce, aad, data),
+            AesGcm::Aes256(aes) => Gcm::<_, U12>::from(aes.clone()).encrypt_in_place_detached(nonce, aad, data),
+        };
+        tag.ok().map(Into::into)
+    }
+
+    /// Check the tag and decrypt `data` in place
+    fn open(&self, nonce: &[u8; 12], aad: &[u8], data: &mut [u8], tag: &[u8]) -> bool {
+        if tag.len() != 16 {
+            return false;
+        }
+        let (nonce, tag) = (Nonce::from_slice(nonce), Tag::from_slice(tag));
+        match self {
+            AesGcm::Aes128(aes) => Gcm::<_, U12>::from(aes.clone()).decrypt_in_place_detached(nonce, aad, data, tag),
+            AesGcm::Aes192(aes) => Gcm::<_, U12>::from(aes.clone()).decrypt_in_place_detached(nonce, aad, data, tag),
+            AesGcm::Aes256(aes) => Gcm::<_, U12>::from(aes.clone()).decrypt_in_place_detached(nonce, aad, data, tag),
+        }
+        .is_ok()
+    }
+}
+
+/// Kinds of module, the first byte after the file's AAD
+const MODULE_FOOTER: u8 = 0;
+const MODULE_COLUMN_META: u8 = 1;
+const MODULE_DATA_PAGE: u8 = 2;
+const MODULE_DICT_PAGE: u8 = 3;
+const MODULE_DATA_PAGE_HEADER: u8 = 4;
+const MODULE_DICT_PAGE_HEADER: u8 = 5;
+const MODULE_COLUMN_INDEX: u8 = 6;
+const MODULE_OFFSET_INDEX: u8 = 7;
+const MODULE_BLOOM_HEADER: u8 = 8;
+const MODULE_BLOOM_BITSET: u8 = 9;
+
+/// `decryption` read option: keys by role, and the AAD prefix for files
+/// written without storing theirs
+struct DecryptionKeys {
+    footer: Option<AesGcm>,
+    /// Dotted leaf column path → key
+    columns: Vec<(String, AesGcm)>,
+    aad_prefix: Option<Vec<u8>>,
+}
+
+/// Read `decryption`: `{ footerKey?, columnKeys?: { path: key }, aadPrefix? }`
+fn parse_decryption(options: &JsValue) -> Result<Option<DecryptionKeys>, JsValue> {
+    let v = match options.is_object() {
+        true => Reflect::get(options, &"decryption".into())?,
+        false => JsValue::UNDEFINED,
+    };
+    if v.is_undefined() || v.is_null() {
+        return Ok(None);
+    }
+    let key = |v: JsValue, what: &str| -> Result<AesGcm, JsValue> {
+        let bytes = v
+            .dyn_into::<Uint8Array>()
+            .map_err(|_| JsValue::from_str(&format!("decryption: {} must be a Uint8Array", what)))?
+            .to_vec();
+        AesGcm::new(&bytes).ok_or_else(|| JsValue::from_str(&format!("decryption: {} must be 16, 24 or 32 bytes, got {}", what, bytes.len())))
+    };
+    let footer = Reflect::get(&v, &"footerKey".into())?;
+    let footer = if footer.is_undefined() || footer.is_null() { None } else { Some(key(footer, "footerKey")?) };
+    let mut columns = Vec::new();
+    let keys = Reflect::get(&v, &"columnKeys".into())?;
+    if keys.is_object() {
+        for e in Object::entries(keys.unchecked_ref::<Object>()).iter() {
+            let e: Array = e.into();
+            let path = e.get(0).as_string().unwrap_or_default();
+            let cipher = key(e.get(1), &format!("key for '{}'", path))?;
+            columns.push((path, cipher));
+        }
+    } else if !keys.is_undefined() && !keys.is_null() {
+        return Err(JsValue::from_str("decryption: columnKeys must be an object like { column: key }"));
+    }
+    let prefix = Reflect::get(&v, &"aadPrefix".into())?;
+    let aad_prefix = match (prefix.dyn_ref::<Uint8Array>(), prefix.as_string()) {
+        (Some(a), _) => Some(a.to_vec()),
+        (None, Some(s)) => Some(s.into_bytes()),
+        _ if prefix.is_undefined() || prefix.is_null() => None,
+        _ => return Err(JsValue::from_str("decryption: aadPrefix must be a string or Uint8Array")),
+    };
+    Ok(Some(DecryptionKeys { footer, columns, aad_prefix }))
+}
+
+/// Key metadata as the error messages show it: text if it is, else hex
+fn key_metadata_note(key_metadata: Option<&[u8]>) -> String {
+    match key_metadata {
+        Some(m) => match std::str::from_utf8(m) {
+            Ok(s) => format!(" (key metadata '{}')", s),
+            Err(_) => format!(" (key metadata 0x{})", hex_string(m)),
+        },
+        None => String::new(),
+    }
+}
+
+/// How the modules of one encrypted file are decrypted
+struct Decryptor {
+    /// AAD prefix and the file's unique part, ahead of every module's suffix
+    file_aad: Vec<u8>,
+    /// AES_GCM_CTR_V1: pages are AES-CTR, without a tag
+    ctr_pages: bool,
+}
+
+impl Decryptor {
+    fn new(algorithm: &EncryptionAlgorithm, keys: Option<&DecryptionKeys>) -> Result<Self, String> {
+        let (v1, ctr_pages) = match algorithm {
+            EncryptionAlgorithm::AESGCMV1(a) => ((&a.aad_prefix, &a.aad_file_unique, a.supply_aad_prefix), false),
+            EncryptionAlgorithm::AESGCMCTRV1(a) => ((&a.aad_prefix, &a.aad_file_unique, a.supply_aad_prefix), true),
+        };
+        let (stored_prefix, unique, supply) = v1;
+        let prefix = match (stored_prefix, keys.and_then(|k| k.aad_prefix.as_ref())) {
+            (_, Some(given)) => given.clone(),
+            (Some(stored), None) => stored.clone(),
+            (None, None) if supply == Some(true) => return Err("file was encrypted with an AAD prefix it doesn't store: pass decryption.aadPrefix".to_string()),
+            (None, None) => Vec::new(),
+        };
+        Ok(Decryptor { file_aad: [prefix.as_slice(), unique.as_deref().unwrap_or_default()].concat(), ctr_pages })
+    }
+
+    /// AAD of a module: the file's AAD, the module kind, then the row group,
+    /// column and (data pages only) page ordinals as 16-bit little-endian
+    fn aad(&self, kind: u8, ordinals: &[usize]) -> Vec<u8> {
+        let mut aad = self.file_aad.clone();
+        aad.push(kind);
+        for &n in ordinals {
+            aad.extend_from_slice(&(n as i16).to_le_bytes());
+        }
+        aad
+    }
+
+    /// Decrypt the module at the start of `buf`, returning the plaintext and
+    /// the module's size; None if it's truncated or fails authentication
+    fn open(&self, cipher: &AesGcm, buf: &[u8], kind: u8, ordinals: &[usize]) -> Option<(Vec<u8>, usize)> {
+        let len = u32::from_le_bytes(buf.get(..4)?.try_into().ok()?) as usize;
+        let module = buf.get(4..4usize.checked_add(len)?)?;
+        let nonce: [u8; 12] = module.get(..12)?.try_into().ok()?;
+        let aad = self.aad(kind, ordinals);
+        if self.ctr_pages && matches!(kind, MODULE_DATA_PAGE | MODULE_DICT_PAGE) {
+            let mut plain = module[12..].to_vec();
+            cipher.ctr(&nonce, &mut plain);
+            return Some((plain, 4 + len));
+        }
+        let (data, tag) = module[12..].split_at(module.len().checked_sub(28)?);
+        let mut plain = data.to_vec();
+        cipher.open(&nonce, &aad, &mut plain, tag).then_some((plain, 4 + len))
+    }
+}
+
+/// The thrift footer of an encrypted file, decrypted where it has to be,
+/// with the leaves carrying FLOAT16 (as `thrift_footer`) and the file's
+/// decryptor; the decryptor is None for plain files. `bytes` is the whole
+/// file or a tail holding the footer.
+fn open_footer(bytes: &[u8], keys: Option<&DecryptionKeys>) -> Result<(TFileMetaData, Vec<usize>, Option<Decryptor>), String> {
+    let len = bytes.len();
+    if len < 12 || &bytes[len - 4..] != b"PARE" {
+        let (metadata, float16) = thrift_footer(bytes)?;
+        let Some(algorithm) = &metadata.encryption_algorithm else { return Ok((metadata, float16, None)) };
+        // Plaintext footer: followed by a nonce and tag signing it with the footer key
+        let decryptor = Decryptor::new(algorithm, keys)?;
+        if let Some(footer_key) = keys.and_then(|k| k.footer.as_ref()) {
+            let footer = footer_bytes(bytes).ok_or("footer is truncated")?;
+            let (plain, signature) = footer.split_at(footer.len().checked_sub(28).ok_or("footer is truncated")?);
+            let mut sealed = plain.to_vec();
+            let tag = footer_key.seal(signature[..12].try_into().unwrap(), &decryptor.aad(MODULE_FOOTER, &[]), &mut sealed);
+            if tag.is_none_or(|tag| tag[..] != signature[12..]) {
+                return Err("footer signature doesn't match: wrong footerKey, or the footer was modified".to_string());
+            }
+        }
+        return Ok((metadata, float16, Some(decryptor)));
+    }
+    let footer_len = u32::from_le_bytes(bytes[len - 8..len - 4].try_into().unwrap()) as usize;
+    let footer = bytes.get(len.checked_sub(8 + footer_len).ok_or("footer is truncated")?..len - 8).ok_or("footer is truncated")?;
+    let mut rest = footer;
+    let crypto = FileCryptoMetaData::read_from_in_protocol(&mut TCompactInputProtocol::new(&mut rest, footer.len() * 2 + 1024))
+        .map_err(|e| format!("encryption metadata: {}", e))?;
+    let footer_key = keys.and_then(|k| k.footer.as_ref()).ok_or_else(|| {
+        format!("file is encrypted: pass decryption.footerKey to read it{}", key_metadata_note(crypto.key_metadata.as_deref()))
+    })?;
+    let decryptor = Decryptor::new(&crypto.encryption_algorithm, keys)?;
+    let (plain, _) = decryptor
+        .open(footer_key, rest, MODULE_FOOTER, &[])
+        .ok_or("can't decrypt the footer: wrong footerKey, or the file is corrupted")?;
+    let fields = float16_fields(&plain).unwrap_or_default();
+    let patched = without_fields(&plain, &fields);
+    let mut prot = TCompactInputProtocol::new(patched.as_slice(), patched.len() * 2 + 1024);
+    let metadata = TFileMetaData::read_from_in_protocol(&mut prot).map_err(|e| e.to_string())?;
+    Ok((metadata, fields.into_iter().map(|(leaf, _)| leaf).collect(), Some(decryptor)))
+}
+
+/// Serialize a thrift struct onto the end of `buf`, returning its size
+fn write_thrift(buf: &mut Vec<u8>, write: impl FnOnce(&mut TCompactOutputProtocol<&mut Vec<u8>>) -> parquet_format_safe::thrift::Result<usize>) -> Result<usize, String> {
+    write(&mut TCompactOutputProtocol::new(buf)).map_err(|e| e.to_string())
+}
+
+/// Read a thrift struct from the start of `buf`, returning it and its size
+fn read_thrift<T: ReadThrift>(buf: &[u8]) -> Result<(T, usize), String> {
+    let mut rest = buf;
+    let value = T::read_from_in_protocol(&mut TCompactInputProtocol::new(&mut rest, buf.len().saturating_mul(8))).map_err(|e| e.to_string())?;
+    Ok((value, buf.len() - rest.len()))
+}
+
+/// Decrypt the column chunks and their page indexes and bloom filters of an
+/// encrypted file into a plain one, moving the offsets in `metadata` to
+/// match. `ciphers` holds each leaf's key, None for plain (or locked,
+/// hence empty) chunks. `bytes` must be the whole file.
+fn decrypt_file(bytes: &[u8], metadata: &mut TFileMetaData, ciphers: &[Option<&AesGcm>], decryptor: &Decryptor) -> Result<Vec<u8>, String> {
+    let mut out = Vec::with_capacity(bytes.len());
+    out.extend_from_slice(b"PAR1");
+    let range = |start: i64, len: i64| usize::try_from(start).ok().zip(usize::try_from(len).ok()).and_then(|(s, l)| bytes.get(s..s.checked_add(l)?));
+    // `ciphers` follows the first row group; a later one may list more chunks
+    let cipher_of = |r: usize, c: usize| ciphers.get(c).copied().ok_or_else(|| format!("row group {}: column chunk {} has no column in the schema", r, c));
+    // old page offset → (new offset, new size incl. header), per column chunk
+    let mut moved: Vec<Vec<(i64, i64, i32)>> = Vec::new();
+
+    for (r, rg) in metadata.row_groups.iter_mut().enumerate() {
+        let mut size = 0;
+        for (c, col) in rg.columns.iter_mut().enumerate() {
+            let mut pages = Vec::new();
+            let Some(cm) = col.meta_data.as_mut() else {
+                moved.push(pages);
+                continue;
+            };
+            let cipher = cipher_of(r, c)?;
+            let path = cm.path_in_schema.join(".");
+            let fail = |what: &str| format!("column '{}' row group {}: can't decrypt {}: wrong key, or the file is corrupted", path, r, what);
+            let start = cm.dictionary_page_offset.unwrap_or(cm.data_page_offset).min(cm.data_page_offset);
+            let chunk = range(start, cm.total_compressed_size).ok_or_else(|| format!("column '{}' row group {}: column chunk out of range", path, r))?;
+            let new_start = out.len();
+            let (mut pos, mut ordinal) = (0, 0);
+            while pos < chunk.len() {
+                let dict = pos == 0 && cm.dictionary_page_offset.is_some();
+                let (mut header, body, size) = match cipher {
+                    Some(cipher) => {
+                        let (header_kind, body_kind, ordinals) = match dict {
+                            true => (MODULE_DICT_PAGE_HEADER, MODULE_DICT_PAGE, vec![r, c]),
+                            false => (MODULE_DATA_PAGE_HEADER, MODULE_DATA_PAGE, vec![r, c, ordinal]),
+                        };
+                        let (plain, header_len) = decryptor.open(cipher, &chunk[pos..], header_kind, &ordinals).ok_or_else(|| fail("a page header"))?;
+                        let (header, _) = read_thrift::<PageHeader>(&plain)?;
+                        let (body, body_len) = decryptor.open(cipher, &chunk[pos + header_len..], body_kind, &ordinals).ok_or_else(|| fail("a page"))?;
+                        (header, body, header_len + body_len)
+                    }
+                    None => {
+                        let (header, header_len) = read_thrift::<PageHeader>(&chunk[pos..])?;
+                        let end = (pos + header_len).checked_add(header.compressed_page_size as usize).filter(|&e| e <= chunk.len()).ok_or_else(|| fail("a page"))?;
+                        (header, chunk[pos + header_len..end].to_vec(), end - pos)
+                    }
+                };
+                if !dict {
+                    ordinal += 1;
+                }
+                // The tag already vouches for the page; a CRC now covers the plain bytes
+                header.compressed_page_size = body.len() as i32;
+                if header.crc.is_some() {
+                    header.crc = Some(crc32fast::hash(&body) as i32);
+                }
+                let page_start = out.len();
+                write_thrift(&mut out, |p| header.write_to_out_protocol(p))?;
+                out.extend_from_slice(&body);
+                pages.push((start + pos as i64, page_start as i64, (out.len() - page_start) as i32));
+                pos += size;
+            }
+            for offset in [Some(&mut cm.data_page_offset), cm.dictionary_page_offset.as_mut()].into_iter().flatten() {
+                *offset = pages.iter().find(|p| p.0 == *offset).map_or(*offset, |p| p.1);
+            }
+            cm.total_uncompressed_size -= cm.total_compressed_size - (out.len() - new_start) as i64;
+            cm.total_compressed_size = (out.len() - new_start) as i64;
+            col.file_offset = new_start as i64;
+            size += cm.total_compressed_size;
+            moved.push(pages);
+        }
+        rg.file_offset = rg.columns.first().map(|c| c.file_offset);
+        rg.total_compressed_size = Some(size);
+    }
+
+    let chunks = metadata.row_groups.iter_mut().enumerate().flat_map(|(r, rg)| rg.columns.iter_mut().enumerate().map(move |(c, col)| (r, c, col)));
+    for ((r, c, col), pages) in chunks.zip(&moved) {
+        let Some(cm) = col.meta_data.as_mut() else { continue };
+        let cipher = cipher_of(r, c)?;
+        let fail = |what: &str| format!("column '{}' row group {}: can't decrypt {}: wrong key, or the file is corrupted", cm.path_in_schema.join("."), r, what);
+        let open = |start: i64, len: i64, kind: u8, what: &str| match cipher {
+            Some(cipher) => range(start, len).and_then(|m| decryptor.open(cipher, m, kind, &[r, c])).map(|(plain, _)| plain).ok_or_else(|| fail(what)),
+            None => range(start, len).map(<[u8]>::to_vec).ok_or_else(|| fail(what)),
+        };
+        if let (Some(offset), Some(len)) = (col.column_index_offset, col.column_index_length) {
+            let index = open(offset, len as i64, MODULE_COLUMN_INDEX, "the column index")?;
+            col.column_index_offset = Some(out.len() as i64);
+            out.extend_from_slice(&index);
+            col.column_index_length = Some(index.len() as i32);
+        }
+        if let (Some(offset), Some(len)) = (col.offset_index_offset, col.offset_index_length) {
+            let (mut index, _) = read_thrift::<OffsetIndex>(&open(offset, len as i64, MODULE_OFFSET_INDEX, "the offset index")?)?;
+            for loc in index.page_locations.iter_mut() {
+                if let Some(&(_, offset, size)) = pages.iter().find(|p| p.0 == loc.offset) {
+                    loc.offset = offset;
+                    loc.compressed_page_size = size;
+                }
+            }
+            col.offset_index_offset = Some(out.len() as i64);
+            col.offset_index_length = Some(write_thrift(&mut out, |p| index.write_to_out_protocol(p))? as i32);
+        }
+        if let Some(offset) = cm.bloom_filter_offset {
+            let rest = usize::try_from(offset).ok().and_then(|o| bytes.get(o..)).ok_or_else(|| fail("the bloom filter"))?;
+            let (header, bitset) = match cipher {
+                Some(cipher) => {
+                    let (header, header_len) = decryptor.open(cipher, rest, MODULE_BLOOM_HEADER, &[r, c]).ok_or_else(|| fail("the bloom filter"))?;
+                    let (bitset, _) = decryptor.open(cipher, &rest[header_len..], MODULE_BLOOM_BITSET, &[r, c]).ok_or_else(|| fail("the bloom filter"))?;
+                    (header, bitset)
+                }
+                None => {
+                    let (header, header_len) = read_thrift::<BloomFilterHeader>(rest)?;
+                    let bitset = rest.get(header_len..header_len + header.num_bytes.max(0) as usize).ok_or_else(|| fail("the bloom filter"))?;
+                    (rest[..header_len].to_vec(), bitset.to_vec())
+                }
+            };
+            cm.bloom_filter_offset = Some(out.len() as i64);
+            out.extend_from_slice(&header);
+            out.extend_from_slice(&bitset);
+        }
+    }
+    Ok(out)
+}
+
+/// Leaf columns of an encrypted file that can't be decrypted, with the error
+/// reading them gives
+type Locked = Vec<(usize, String)>;
+
+/// Parse (and with `keys`, decrypt) the footer of `bytes` — the whole file
+/// when `whole`, else a tail holding the footer. Encrypted whole files come
+/// back decrypted; the leaf columns that can't be read are returned with
+/// the error reading them gives.
+fn open_file(bytes: Vec<u8>, whole: bool, keys: Option<&DecryptionKeys>) -> Result<(Vec<u8>, FileMetaData, Locked), String> {
+    if footer_bytes(&bytes).is_none() && !bytes.ends_with(b"PARE") {
+        // Not a parquet trailer: let parquet2 say what's wrong
+        return Err(read_metadata(&mut Cursor::new(&bytes)).err().map_or_else(|| "footer is truncated".to_string(), |e| e.to_string()));
+    }
+    let (mut metadata, float16, decryptor) = open_footer(&bytes, keys)?;
+    let Some(decryptor) = decryptor else {
+        return Ok((bytes, metadata_from_thrift(metadata, &float16)?, Vec::new()));
+    };
+
+    // Physical type of every leaf, for the placeholder metadata of locked columns
+    let leaf_types: Vec<Option<Type>> = metadata.schema.iter().filter(|el| el.num_children.is_none()).map(|el| el.type_).collect();
+    let mut locked = Locked::new();
+    let mut ciphers: Vec<Option<&AesGcm>> = Vec::new();
+    for (r, rg) in metadata.row_groups.iter_mut().enumerate() {
+        for (c, col) in rg.columns.iter_mut().enumerate() {
+            let path_in_schema = match (&col.meta_data, &col.crypto_metadata) {
+                (Some(m), _) => m.path_in_schema.clone(),
+                (None, Some(ColumnCryptoMetaData::ENCRYPTIONWITHCOLUMNKEY(k))) => k.path_in_schema.clone(),
+                _ => Vec::new(),
+            };
+            let path = path_in_schema.join(".");
+            let (cipher, missing) = match &col.crypto_metadata {
+                None => (None, None),
+                Some(ColumnCryptoMetaData::ENCRYPTIONWITHFOOTERKEY(_)) => match keys.and_then(|k| k.footer.as_ref()) {
+                    Some(key) => (Some(key), None),
+                    None => (None, Some(format!("column '{}' is encrypted with the footer key: pass decryption.footerKey", path))),
+                },
+                Some(ColumnCryptoMetaData::ENCRYPTIONWITHCOLUMNKEY(k)) => match keys.and_then(|keys| keys.columns.iter().find(|(p, _)| *p == path)) {
+                    Some((_, key)) => {
+                        if let Some(sealed) = &col.encrypted_column_metadata {
+                            let (plain, _) = decryptor
+                                .open(key, sealed, MODULE_COLUMN_META, &[r, c])
+                                .ok_or_else(|| format!("column '{}': can't decrypt its metadata: wrong key, or the file is corrupted", path))?;
+                            col.meta_data = Some(read_thrift::<ColumnMetaData>(&plain)?.0);
+                        }
+                        (Some(key), None)
+                    }
+                    None => {
+                        let note = key_metadata_note(k.key_metadata.as_deref());
+                        (None, Some(format!("column '{}' is encrypted with its own key: pass it in decryption.columnKeys{}", path, note)))
+                    }
+                },
+            };
+            // Chunks are decrypted along with the whole file; a tail only has the footer
+            let missing = missing.or_else(|| {
+                (col.crypto_metadata.is_some() && !whole).then(|| format!("column '{}' is encrypted: open the whole file to read it", path))
+            });
+            if r == 0 {
+                ciphers.push(cipher);
+            }
+            let Some(error) = missing else { continue };
+            if !locked.iter().any(|(leaf, _)| *leaf == c) {
+                locked.push((c, error));
+            }
+            // An empty chunk with no indexes or bloom filter, never read
+            let ty = leaf_types.get(c).copied().flatten().unwrap_or(Type::BYTE_ARRAY);
+            col.meta_data = Some(ColumnMetaData::new(ty, vec![], path_in_schema, CompressionCodec::UNCOMPRESSED, 0, 0, 0, None, 0, None, None, None, None, None));
+            (col.column_index_offset, col.column_index_length, col.offset_index_offset, col.offset_index_length) = (None, None, None, None);
+        }
+    }
+    for (leaf, _) in &locked {
+        if let Some(cipher) = ciphers.get_mut(*leaf) {
+            *cipher = None;
+        }
+    }
+    let bytes = match whole {
+        true => decrypt_file(&bytes, &mut metadata, &ciphers, &decryptor)?,
+        false => bytes,
+    };
+    metadata.encryption_algorithm = None;
+    metadata.footer_signing_key_metadata = None;
+    for col in metadata.row_groups.iter_mut().flat_map(|rg| rg.columns.iter_mut()) {
+        col.crypto_metadata = None;
+        col.encrypted_column_metadata = None;
+    }
+    Ok((bytes, metadata_from_thrift(metadata, &float16)?, locked))
+}
+
+// ── Page checksums ──────────────────────────────────────────────────────────
+
+/// Check the CRC of every page in a column chunk (`bytes`, read at file
+/// offset `start`) that carries one. Returns a description of the first
+/// mismatch, if any.
+fn verify_chunk_crcs(bytes: &[u8], start: u64) -> Result<Option<String>, JsValue> {
+    let end = bytes.len();
+    let mut pos = 0;
+    while pos < end {
+        let at = start + pos as u64;
+        let mut rest = &bytes[pos..end];
+        // The protocol's limit budgets decoded memory (8 bytes per list element), not input bytes
+        let limit = rest.len().saturating_mul(8);
+        let header = PageHeader::read_from_in_protocol(&mut TCompactInputProtocol::new(&mut rest, limit))
+            .map_err(|e| JsValue::from_str(&format!("page header at {}: {}", at, e)))?;
+        let body_start = end - rest.len();
+        let body_end = body_start + header.compressed_page_size.max(0) as usize;
+        if body_end > end {
+            return Ok(Some(format!("page at offset {} runs past the column chunk", at)));
+        }
+        if let Some(crc) = header.crc {
+            let actual = crc32fast::hash(&bytes[body_start..body_end]) as i32;
+            if actual != crc {
+                return Ok(Some(format!(
+                    "page at offset {}: stored {:08x}, computed {:08x}",
+                    at, crc as u32, actual as u32
+                )));
+            }
+        }
+        pos = body_end;
+    }
+    Ok(None)
+}
+
+/// Verify a chunk's page checksums per `opts`: mismatches fail the read or
+/// are added to `warnings`
+fn check_chunk(
+    file: &FileBytes,
+    chunk: &ColumnChunkMetaData,
+    name: &str,
+    ri: usize,
+    opts: &ReadOptions,
+    warnings: &Array,
+) -> Result<(), JsValue> {
+    if opts.verify_checksums == ChecksumMode::Ignore {
+        return Ok(());
+    }
+    if let Some(problem) = verify_chunk_crcs(file.chunk(chunk)?, chunk.byte_range().0)? {
+        let msg = format!("checksum mismatch in column '{}', row group {}: {}", name, ri, problem);
+        if opts.verify_checksums == ChecksumMode::Fail {
+            return Err(JsValue::from_str(&msg));
+        }
+        warnings.push(&JsValue::from_str(&msg));
+    }
+    Ok(())
+}
+
+// ── Decompression ───────────────────────────────────────────────────────────
+//
+// parquet2's codec features would link in every compressor along with the
+// decoders, so pages are read and inflated here instead.
+
+/// Read the page header at the start of `rest`, returning it and the page's
+/// (still compressed) body, and advance `rest` past both
+fn next_page<'a>(rest: &mut &'a [u8]) -> Result<(PageHeader, &'a [u8]), String> {
+    // The protocol's limit budgets decoded memory (8 bytes per list element), not input bytes
+    let limit = rest.len().saturating_mul(8);
+    let header = PageHeader::read_from_in_protocol(&mut TCompactInputProtocol::new(&mut *rest, limit))
+        .map_err(|e| e.to_string())?;
+    let size = usize::try_from(header.compressed_page_size).map_err(|_| "negative page size".to_string())?;
+    if size > rest.len() {
+        return Err("the page header reported the wrong page size".to_string());
+    }
+    let (body, after) = rest.split_at(size);
+    *rest = after;
+    Ok((header, body))
+}
+
+/// Values in a data page, 0 for other pages
+fn data_page_values(header: &PageHeader) -> usize {
+    let n = match (&header.data_page_header, &header.data_page_header_v2) {
+        (Some(h), _) => h.num_values,
+        (_, Some(h)) => h.num_values,
+        _ => 0,
+    };
+    n.max(0) as usize
+}
+
+/// Decompress a page body written with `codec`. V2 data pages keep their
+/// levels uncompressed in front of the values. None for index pages and
+/// other page types that hold no values.
+fn decompress_page(header: PageHeader, body: &[u8], codec: Compression, descriptor: &Descriptor) -> Result<Option<Page>, String> {
+    let size = usize::try_from(header.uncompressed_page_size).map_err(|_| "negative page size".to_string())?;
+    let (levels, compressed) = match &header.data_page_header_v2 {
+        Some(h) if header.type_ == PageType::DATA_PAGE_V2 => {
+            let levels = (h.definition_levels_byte_length.max(0) + h.repetition_levels_byte_length.max(0)) as usize;
+            (levels, h.is_compressed.unwrap_or(true))
+        }
+        _ => (0, true),
+    };
+    if levels > body.len() || levels > size {
+        return Err("V2 page header reported incorrect offset to compressed data".to_string());
+    }
+    let buffer = if codec == Compression::Uncompressed || !compressed {
+        body.to_vec()
+    } else {
+        let mut buffer = vec![0; size];
+        buffer[..levels].copy_from_slice(&body[..levels]);
+        decompress_into(codec, &body[levels..], &mut buffer[levels..])?;
+        buffer
+    };
+    let missing = |what: &str| format!("the page header type is a {} but its header is empty", what);
+    Ok(Some(match header.type_ {
+        PageType::DICTIONARY_PAGE => {
+            let h = header.dictionary_page_header.ok_or_else(|| missing("dictionary page"))?;
+            Page::Dict(DictPage::new(buffer, h.num_values.max(0) as usize, h.is_sorted.unwrap_or(false)))
+        }
+        PageType::DATA_PAGE => {
+            let h = header.data_page_header.ok_or_else(|| missing("v1 data page"))?;
+            Page::Data(DataPage::new(DataPageHeader::V1(h), buffer, descriptor.clone(), None))
+        }
+        PageType::DATA_PAGE_V2 => {
+            let h = header.data_page_header_v2.ok_or_else(|| missing("v2 data page"))?;
+            Page::Data(DataPage::new(DataPageHeader::V2(h), buffer, descriptor.clone(), None))
+        }
+        _ => return Ok(None),
+    }))
+}
+
+/// Decompress `input` into `out`, which has the exact uncompressed size
+fn decompress_into(codec: Compression, input: &[u8], out: &mut [u8]) -> Result<(), String> {
+    let written = match codec {
+        Compression::Snappy => snap::raw::Decoder::new().decompress(input, out).map_err(|e| e.to_string())?,
+        Compression::Gzip => gunzip(input, out)?,
+        Compression::Brotli => {
+            brotli_decompressor::Decompressor::new(input, 4096).read_exact(out).map_err(|e| e.to_string())?;
+            out.len()
+        }
+        Compression::Lz4 => lz4_hadoop(input, out).or_else(|_| lz4_flex::block::decompress_into(input, out).map_err(|e| e.to_string()))?,
+        Compression::Lz4Raw => lz4_flex::block::decompress_into(input, out).map_err(|e| e.to_string())?,
+        Compression::Zstd => ruzstd::decoding::FrameDecoder::new().decode_all(input, out).map_err(|e| e.to_string())?,
+        Compression::Uncompressed | Compression::Lzo => return Err("codec not supported".to_string()),
+    };
+    if written != out.len() {
+        return Err(format!("decompressed to {} bytes, expected {}", written, out.len()));
+    }
+    Ok(())
+}
+
+/// Inflate the gzip members (RFC 1952) in `input` into `out`, checking each
+/// one's CRC. Returns the bytes written.
+fn gunzip(mut input: &[u8], out: &mut [u8]) -> Result<usize, String> {
+    use miniz_oxide::inflate::{
+        core::{decompress, inflate_flags::TINFL_FLAG_USING_NON_WRAPPING_OUTPUT_BUF, DecompressorOxide},
+        TINFLStatus,
+    };
+    let mut pos = 0;
+    while !input.is_empty() {
+        let start = gzip_header_len(input).ok_or("not a gzip member")?;
+        let (status, read, written) = decompress(&mut DecompressorOxide::new(), &input[start..], out, pos, TINFL_FLAG_USING_NON_WRAPPING_OUTPUT_BUF);
+        if status != TINFLStatus::Done {
+            return Err(format!("deflate: {:?}", status));
+        }
+        let end = start + read;
+        let trailer = input.get(end..end + 8).ok_or("gzip member truncated")?;
+        if crc32fast::hash(&out[pos..pos + written]).to_le_bytes() != trailer[..4] {
+            return Err("gzip CRC mismatch".to_string());
+        }
+        pos += written;
+        input = &input[end + 8..];
+    }
+    Ok(pos)
+}
+
+/// Length of the gzip member header at the start of `b`
+fn gzip_header_len(b: &[u8]) -> Option<usize> {
+    const FHCRC: u8 = 2;
+    const FEXTRA: u8 = 4;
+    const FNAME: u8 = 8;
+    const FCOMMENT: u8 = 16;
+    if b.get(..3)? != [0x1f, 0x8b, 8] {
+        return None;
+    }
+    let flags = *b.get(3)?;
+    let mut pos = 10;
+    if flags & FEXTRA != 0 {
+        pos += 2 + u16::from_le_bytes([*b.get(pos)?, *b.get(pos + 1)?]) as usize;
+    }
+    for field in [FNAME, FCOMMENT] {
+        if flags & field != 0 {
+            pos += b.get(pos..)?.iter().position(|&c| c == 0)? + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        pos += 2;
+    }
+    (pos <= b.len()).then_some(pos)
+}
+
+/// Inflate LZ4 blocks in Hadoop's framing, as parquet-mr wrote the LZ4 codec:
+/// each block behind its big-endian decompressed and compressed sizes.
+/// Returns the bytes written.
+fn lz4_hadoop(mut input: &[u8], out: &mut [u8]) -> Result<usize, String> {
+    let mut pos = 0;
+    while input.len() >= 8 {
+        let be = |b: &[u8]| u32::from_be_bytes(b.try_into().unwrap()) as usize;
+        let (size, len) = (be(&input[..4]), be(&input[4..8]));
+        let block = input.get(8..8 + len).ok_or("not enough bytes for a Hadoop frame")?;
+        let target = out.get_mut(pos..pos + size).ok_or("Hadoop frame larger than the page")?;
+        if lz4_flex::block::decompress_into(block, target).map_err(|e| e.to_string())? != size {
+            return Err("Hadoop frame decompressed to the wrong size".to_string());
+        }
+        pos += size;
+        input = &input[8 + len..];
+    }
+    if !input.is_empty() {
+        return Err("trailing bytes after Hadoop frames".to_string());
+    }
+    Ok(pos)
+}
+
+/// Decode up to `limit` rows of one column chunk (`bytes`, holding the pages
+/// `meta` describes), after skipping its first `skip` rows, appending them to
+/// `out`. Data pages that lie entirely inside the skipped range are not
+/// decompressed. Returns the number of rows appended.
+///
+/// With `nested`, every entry's levels are collected there and only present
+/// values go to `out`; `skip` and `limit` then count entries, not rows.
+fn read_column_chunk(
+    bytes: &[u8],
+    meta: PageMetaData,
+    desc: &ColumnDescriptor,
+    out: &mut ColumnBuf,
+    mut nested: Option<&mut Levels>,
+    mut skip: usize,
+    limit: usize,
+) -> Result<usize, JsValue> {
+    let phys = desc.descriptor.primitive_type.physical_type;
+    let codec = meta.compression;
+    let max_def = desc.descriptor.max_def_level;
+    let max_rep = desc.descriptor.max_rep_level;
+
+    let mut rest = bytes;
+    let mut seen = 0i64;
+    let mut total = 0usize;
+    let mut dict: Option<ColumnBuf> = None;
+
+    while seen < meta.num_values {
+        if total >= limit { break; }
+        let (header, body) = next_page(&mut rest).map_err(|e| JsValue::from_str(&format!("page: {}", e)))?;
+        let num_values = data_page_values(&header);
+        seen += num_values as i64;
+        if num_values > 0 && num_values <= skip {
+            skip -= num_values;
+            continue;
+        }
+        let page = decompress_page(header, body, codec, &meta.descriptor)
+            .map_err(|e| JsValue::from_str(&format!("decomp ({}): {}", codec_label(codec), e)))?;
+        let Some(page) = page else { continue };
+
+        match page {
+            Page::Dict(dp) => {
+                // Store decoded dictionary values for subsequent data pages
+                let mut values = out.empty_like();
+                decode_plain(&dp.buffer, phys, dp.num_values, &mut values, dp.num_values);
+                dict = Some(values);
+            }
+            Page::Data(dp) => {
+                let nv = dp.num_values();
+                // Rows [skip, n) of this page are kept
+                let n = nv.min(skip + limit - total);
+                let split = match dp.header() {
+                    DataPageHeader::V1(_) => split_v1_levels(dp.buffer(), max_rep, max_def, nv),
+                    DataPageHeader::V2(h) => split_v2_levels(
+                        dp.buffer(),
+                        h.repetition_levels_byte_length.max(0) as usize,
+                        h.definition_levels_byte_length.max(0) as usize,
+                        max_rep,
+                        max_def,
+                        nv,
+                    ),
+                };
+                let (levels, values) = split.map_err(|e| JsValue::from_str(&format!("page: {}", e)))?;
+                let def_levels = levels.def;
+
+                // Non-null slots among the rows we keep
+                let present = match &def_levels {
+                    Some(levels) => levels.iter().take(n).filter(|&&l| l == max_def as u32).count(),
+                    None => n,
+                };
+
+                // Optional columns decode into a scratch buffer, then nulls are interleaved;
+                // skipped rows are decoded into it too and dropped
+                // Nested columns keep their levels and only the values that are present
+                let direct = nested.is_some() || (def_levels.is_none() && skip == 0);
+                let mut scratch = out.empty_like();
+                let target = if direct { &mut *out } else { &mut scratch };
+
+                let decoded = match dp.encoding() {
+                    Encoding::RleDictionary | Encoding::PlainDictionary => match &dict {
+                        // Dictionary-encoded page
+                        Some(dict_values) => decode_rle_dict_indices(values, present).map(|indices| {
+                            for &idx in &indices {
+                                if (idx as usize) < dict_values.len() {
+                                    target.push_from(dict_values, idx as usize);
+                                } else {
+                                    target.push_js(JsValue::from_str("<invalid>"));
+                                }
+                            }
+                        }),
+                        None => Ok(()),
+                    },
+                    Encoding::DeltaBinaryPacked => push_delta(values, phys, present, target),
+                    Encoding::Rle if phys == PhysicalType::Boolean => push_rle_bools(values, present, target),
+                    Encoding::ByteStreamSplit => push_byte_stream_split(values, phys, present, target),
+                    Encoding::DeltaLengthByteArray | Encoding::DeltaByteArray => {
+                        push_delta_bytes(values, phys, present, target, dp.encoding() == Encoding::DeltaByteArray)
+                    }
+                    _ => {
+                        // PLAIN encoding (existing path)
+                        decode_plain(values, phys, present, target, present);
+                        Ok(())
+                    }
+                };
+                decoded.map_err(|e| JsValue::from_str(&format!("page: {}", e)))?;
+
+                if let Some(lv) = nested.as_deref_mut() {
+                    let mut def = def_levels.unwrap_or_else(|| vec![max_def as u32; n]);
+                    let mut rep = levels.rep.unwrap_or_else(|| vec![0; n]);
+                    def.resize(n, 0);
+                    rep.resize(n, 0);
+                    lv.def.extend(def);
+                    lv.rep.extend(rep);
+                } else if let Some(levels) = def_levels {
+                    let mut k = 0;
+                    for (i, &l) in levels.iter().take(n).enumerate() {
+                        let present = l == max_def as u32;
+                        if i >= skip {
+                            if present { out.push_from(&scratch, k) } else { out.push_js(JsValue::NULL) }
+                        }
+                        k += present as usize;
+                    }
+                } else if skip > 0 {
+                    for i in skip..n.min(scratch.len()) {
+                        out.push_from(&scratch, i);
+                    }
+                }
+                total += n - skip;
+                skip = 0;
+            }
+        }
+    }
+
+    Ok(total)
+}
+
+/// The pages of a `num_rows`-row flat column chunk that hold rows `rows`, by
+/// its offset index: the file byte ranges of its dictionary page (if any) and
+/// of those data pages, and the rows the data pages span. None when the
+/// chunk has no offset index or it isn't loaded.
+fn page_selection(file: &FileBytes, chunk: &ColumnChunkMetaData, num_rows: usize, rows: Range<usize>) -> Option<(Vec<Range<u64>>, Range<usize>)> {
+    let cc = chunk.column_chunk();
+    let start = u64::try_from(cc.offset_index_offset?).ok()?;
+    let mut bytes = file.get(start, start + u64::try_from(cc.offset_index_length?).ok()?)?;
+    let limit = bytes.len().saturating_mul(8);
+    let index = OffsetIndex::read_from_in_protocol(&mut TCompactInputProtocol::new(&mut bytes, limit)).ok()?;
+    let pages = &index.page_locations;
+    let (chunk_start, _) = chunk.byte_range();
+    let first_page = u64::try_from(pages.first()?.offset).ok()?;
+    let mut ranges = Vec::new();
+    if first_page > chunk_start {
+        ranges.push(chunk_start..first_page);
+    }
+    let mut span: Option<Range<usize>> = None;
+    for (i, page) in pages.iter().enumerate() {
+        let first = page.first_row_index.max(0) as usize;
+        let end = pages.get(i + 1).map_or(num_rows, |next| next.first_row_index.max(0) as usize);
+        if end <= rows.start || first >= rows.end {
+            continue;
+        }
+        let offset = u64::try_from(page.offset).ok()?;
+        ranges.push(offset..offset + u64::try_from(page.compressed_page_size).ok()?);
+        span = Some(span.map_or(first..end, |s| s.start..end));
+    }
+    Some((ranges, span.unwrap_or(rows.start..rows.start)))
+}
+
+/// `read_column_chunk` for a flat column chunk, decoding up to `limit` rows
+/// after its first `skip`. With a loaded offset index only the pages holding
+/// those rows are read, and only they need to be loaded.
+fn read_rows(file: &FileBytes, chunk: &ColumnChunkMetaData, desc: &ColumnDescriptor, out: &mut ColumnBuf, skip: usize, limit: usize) -> Result<usize, JsValue> {
+    let num_rows = chunk.num_values() as usize;
+    let Some((ranges, span)) = page_selection(file, chunk, num_rows, skip..skip.saturating_add(limit).min(num_rows)) else {
+        return read_column_chunk(file.chunk(chunk)?, chunk.into(), desc, out, None, skip, limit);
+    };
+    let mut bytes = Vec::new();
+    for r in ranges {
+        let page = file
+            .get(r.start, r.end)
+            .ok_or_else(|| JsValue::from_str(&format!("bytes {}..{} of the file are not loaded", r.start, r.end)))?;
+        bytes.extend_from_slice(page);
+    }
+    // Flat columns have one value per row, so the pages hold `span.len()` values
+    let meta = PageMetaData { num_values: span.len() as i64, ..chunk.into() };
+    read_column_chunk(&bytes, meta, desc, out, None, skip.saturating_sub(span.start), limit)
+}
+
+// ── Page inspection ─────────────────────────────────────────────────────────
+
+/// One entry per page of a column chunk (`bytes`, read at file offset
+/// `start`), straight from the page headers: nothing is decompressed
+fn inspect_chunk(bytes: &[u8], start: u64, ri: usize, out: &Array) -> Result<(), JsValue> {
+    let end = bytes.len();
+    let mut pos = 0;
+    while pos < end {
+        let at = start + pos as u64;
+        let mut rest = &bytes[pos..end];
+        let limit = rest.len().saturating_mul(8);
+        let header = PageHeader::read_from_in_protocol(&mut TCompactInputProtocol::new(&mut rest, limit))
+            .map_err(|e| JsValue::from_str(&format!("page header at {}: {}", at, e)))?;
+        let header_size = end - rest.len() - pos;
+
+        let page = Object::new();
+        Reflect::set(&page, &"rowGroup".into(), &JsValue::from_f64(ri as f64))?;
+        Reflect::set(&page, &"offset".into(), &JsValue::from_f64(at as f64))?;
+        let (kind, version, encoding, num_values) = match (&header.data_page_header, &header.data_page_header_v2, &header.dictionary_page_header) {
+            (Some(h), ..) => ("data", Some(1), Some(h.encoding), Some(h.num_values)),
+            (_, Some(h), _) => ("data", Some(2), Some(h.encoding), Some(h.num_values)),
+            (.., Some(h)) => ("dictionary", None, Some(h.encoding), Some(h.num_values)),
+            _ if header.index_page_header.is_some() => ("index", None, None, None),
+            _ => ("unknown", None, None, None),
+        };
+        Reflect::set(&page, &"type".into(), &JsValue::from_str(kind))?;
+        if let Some(v) = version {
+            Reflect::set(&page, &"version".into(), &JsValue::from_f64(v as f64))?;
+        }
+        if let Some(e) = encoding {
+            Reflect::set(&page, &"encoding".into(), &JsValue::from_str(encoding_label(e)))?;
+        }
+        if let Some(n) = num_values {
+            Reflect::set(&page, &"numValues".into(), &JsValue::from_f64(n as f64))?;
+        }
+        if let Some(h) = &header.data_page_header_v2 {
+            Reflect::set(&page, &"numNulls".into(), &JsValue::from_f64(h.num_nulls as f64))?;
+            Reflect::set(&page, &"numRows".into(), &JsValue::from_f64(h.num_rows as f64))?;
+        }
+        Reflect::set(&page, &"headerSize".into(), &JsValue::from_f64(header_size as f64))?;
+        Reflect::set(&page, &"compressedSize".into(), &JsValue::from_f64(header.compressed_page_size as f64))?;
+        Reflect::set(&page, &"uncompressedSize".into(), &JsValue::from_f64(header.uncompressed_page_size as f64))?;
+        Reflect::set(&page, &"crc".into(), &JsValue::from_bool(header.crc.is_some()))?;
+        out.push(&page);
+
+        pos += header_size + header.compressed_page_size.max(0) as usize;
+    }
+    Ok(())
+}
+
+// ── Footer dump ─────────────────────────────────────────────────────────────
+
+const TYPE_NAMES: &[&str] = &["BOOLEAN", "INT32", "INT64", "INT96", "FLOAT", "DOUBLE", "BYTE_ARRAY", "FIXED_LEN_BYTE_ARRAY"];
+const REPETITION_NAMES: &[&str] = &["REQUIRED", "OPTIONAL", "REPEATED"];
+const CONVERTED_TYPE_NAMES: &[&str] = &[
+    "UTF8", "MAP", "MAP_KEY_VALUE", "LIST", "ENUM", "DECIMAL", "DATE", "TIME_MILLIS", "TIME_MICROS",
+    "TIMESTAMP_MILLIS", "TIMESTAMP_MICROS", "UINT_8", "UINT_16", "UINT_32", "UINT_64", "INT_8", "INT_16",
+    "INT_32", "INT_64", "JSON", "BSON", "INTERVAL",
+];
+const ENCODING_NAMES: &[&str] = &[
+    "PLAIN", "GROUP_VAR_INT", "PLAIN_DICTIONARY", "RLE", "BIT_PACKED", "DELTA_BINARY_PACKED",
+    "DELTA_LENGTH_BYTE_ARRAY", "DELTA_BYTE_ARRAY", "RLE_DICTIONARY", "BYTE_STREAM_SPLIT",
+];
+const CODEC_NAMES: &[&str] = &["UNCOMPRESSED", "SNAPPY", "GZIP", "LZO", "BROTLI", "LZ4", "ZSTD", "LZ4_RAW"];
+const PAGE_TYPE_NAMES: &[&str] = &["DATA_PAGE", "INDEX_PAGE", "DICTIONARY_PAGE", "DATA_PAGE_V2"];
+
+/// The footer of `bytes` as parquet-format's thrift structs. FLOAT16 logical
+/// types are cut out as in `metadata_from_thrift`; the leaves they were on are
+/// returned alongside.
+fn thrift_footer(bytes: &[u8]) -> Result<(TFileMetaData, Vec<usize>), String> {
+    if bytes.ends_with(b"PARE") {
+        return Err("the footer is encrypted".to_string());
+    }
+    let footer = footer_bytes(bytes).ok_or_else(|| "no PAR1 trailer, or the footer is truncated".to_string())?;
+    let fields = float16_fields(footer).unwrap_or_default();
+    let patched = without_fields(footer, &fields);
+    let mut prot = TCompactInputProtocol::new(patched.as_slice(), patched.len() * 2 + 1024);
+    let metadata = parquet_format_safe::FileMetaData::read_from_in_protocol(&mut prot).map_err(|e| e.to_string())?;
+    Ok((metadata, fields.into_iter().map(|(leaf, _)| leaf).collect()))
+}
+
+/// Name of thrift enum value `v`, or the number itself when it's newer than `names`
+fn enum_js(names: &[&str], v: i32) -> JsValue {
+    usize::try_from(v).ok().and_then(|i| names.get(i)).map_or_else(|| JsValue::from_f64(v as f64), |n| JsValue::from_str(n))
+}
+
+fn num_js(n: impl Into<f64>) -> JsValue {
+    JsValue::from_f64(n.into())
+}
+
+/// Set `key` on `obj` when the optional thrift field is present
+fn set_present<T>(obj: &Object, key: &str, value: Option<T>, to_js: impl FnOnce(T) -> JsValue) -> Result<(), JsValue> {
+    if let Some(v) = value {
+        Reflect::set(obj, &key.into(), &to_js(v))?;
+    }
+    Ok(())
+}
+
+/// A LogicalType union as `{ MEMBER: { ...fields } }`
+fn logical_type_js(lt: &parquet_format_safe::LogicalType) -> Result<JsValue, JsValue> {
+    use parquet_format_safe::{LogicalType as L, TimeUnit as U};
+    let unit = |u: &U| JsValue::from_str(match u {
+        U::MILLIS(_) => "MILLIS",
+        U::MICROS(_) => "MICROS",
+        U::NANOS(_) => "NANOS",
+    });
+    let fields = Object::new();
+    let member = match lt {
+        L::STRING(_) => "STRING",
+        L::MAP(_) => "MAP",
+        L::LIST(_) => "LIST",
+        L::ENUM(_) => "ENUM",
+        L::DECIMAL(d) => {
+            Reflect::set(&fields, &"scale".into(), &num_js(d.scale))?;
+            Reflect::set(&fields, &"precision".into(), &num_js(d.precision))?;
+            "DECIMAL"
+        }
+        L::DATE(_) => "DATE",
+        L::TIME(t) => {
+            Reflect::set(&fields, &"isAdjustedToUTC".into(), &JsValue::from_bool(t.is_adjusted_to_u_t_c))?;
+            Reflect::set(&fields, &"unit".into(), &unit(&t.unit))?;
+            "TIME"
+        }
+        L::TIMESTAMP(t) => {
+            Reflect::set(&fields, &"isAdjustedToUTC".into(), &JsValue::from_bool(t.is_adjusted_to_u_t_c))?;
+            Reflect::set(&fields, &"unit".into(), &unit(&t.unit))?;
+            "TIMESTAMP"
+        }
+        L::INTEGER(i) => {
+            Reflect::set(&fields, &"bitWidth".into(), &num_js(i.bit_width))?;
+            Reflect::set(&fields, &"isSigned".into(), &JsValue::from_bool(i.is_signed))?;
+            "INTEGER"
+        }
+        L::UNKNOWN(_) => "UNKNOWN",
+        L::JSON(_) => "JSON",
+        L::BSON(_) => "BSON",
+        L::UUID(_) => "UUID",
+    };
+    let obj = Object::new();
+    Reflect::set(&obj, &member.into(), &fields)?;
+    Ok(obj.into())
+}
+
+fn key_values_js(kvs: &Option<Vec<parquet_format_safe::KeyValue>>) -> Result<JsValue, JsValue> {
+    let out = Array::new();
+    for kv in kvs.iter().flatten() {
+        let obj = Object::new();
+        Reflect::set(&obj, &"key".into(), &JsValue::from_str(&kv.key))?;
+        let value = kv.value.as_deref().map_or(JsValue::NULL, JsValue::from_str);
+        Reflect::set(&obj, &"value".into(), &value)?;
+        out.push(&obj);
+    }
+    Ok(out.into())
+}
+
+/// Statistics with their raw plain-encoded bytes as hex
+fn thrift_statistics_js(s: &parquet_format_safe::Statistics) -> Result<JsValue, JsValue> {
+    let obj = Object::new();
+    let hex = |b: &Vec<u8>| JsValue::from_str(&hex_string(b));
+    set_present(&obj, "max", s.max.as_ref(), hex)?;
+    set_present(&obj, "min", s.min.as_ref(), hex)?;
+    set_present(&obj, "nullCount", s.null_count, |n| num_js(n as f64))?;
+    set_present(&obj, "distinctCount", s.distinct_count, |n| num_js(n as f64))?;
+    set_present(&obj, "maxValue", s.max_value.as_ref(), hex)?;
+    set_present(&obj, "minValue", s.min_value.as_ref(), hex)?;
+    Ok(obj.into())
+}
+
+fn column_chunk_js(c: &parquet_format_safe::ColumnChunk) -> Result<JsValue, JsValue> {
+    let obj = Object::new();
+    set_present(&obj, "filePath", c.file_path.as_deref(), JsValue::from_str)?;
+    Reflect::set(&obj, &"fileOffset".into(), &num_js(c.file_offset as f64))?;
+    if let Some(m) = &c.meta_data {
+        let meta = Object::new();
+        Reflect::set(&meta, &"type".into(), &enum_js(TYPE_NAMES, m.type_.0))?;
+        let encodings: Array = m.encodings.iter().map(|e| enum_js(ENCODING_NAMES, e.0)).collect();
+        Reflect::set(&meta, &"encodings".into(), &encodings)?;
+        let path: Array = m.path_in_schema.iter().map(|s| JsValue::from_str(s)).collect();
+        Reflect::set(&meta, &"pathInSchema".into(), &path)?;
+        Reflect::set(&meta, &"codec".into(), &enum_js(CODEC_NAMES, m.codec.0))?;
+        Reflect::set(&meta, &"numValues".into(), &num_js(m.num_values as f64))?;
+        Reflect::set(&meta, &"totalUncompressedSize".into(), &num_js(m.total_uncompressed_size as f64))?;
+        Reflect::set(&meta, &"totalCompressedSize".into(), &num_js(m.total_compressed_size as f64))?;
+        if m.key_value_metadata.is_some() {
+            Reflect::set(&meta, &"keyValueMetadata".into(), &key_values_js(&m.key_value_metadata)?)?;
+        }
+        Reflect::set(&meta, &"dataPageOffset".into(), &num_js(m.data_page_offset as f64))?;
+        set_present(&meta, "indexPageOffset", m.index_page_offset, |n| num_js(n as f64))?;
+        set_present(&meta, "dictionaryPageOffset", m.dictionary_page_offset, |n| num_js(n as f64))?;
+        if let Some(s) = &m.statistics {
+            Reflect::set(&meta, &"statistics".into(), &thrift_statistics_js(s)?)?;
+        }
+        if let Some(stats) = &m.encoding_stats {
+            let arr = Array::new();
+            for s in stats {
+                let entry = Object::new();
+                Reflect::set(&entry, &"pageType".into(), &enum_js(PAGE_TYPE_NAMES, s.page_type.0))?;
+                Reflect::set(&entry, &"encoding".into(), &enum_js(ENCODING_NAMES, s.encoding.0))?;
+                Reflect::set(&entry, &"count".into(), &num_js(s.count))?;
+                arr.push(&entry);
+            }
+            Reflect::set(&meta, &"encodingStats".into(), &arr)?;
+        }
+        set_present(&meta, "bloomFilterOffset", m.bloom_filter_offset, |n| num_js(n as f64))?;
+        Reflect::set(&obj, &"metaData".into(), &meta)?;
+    }
+    set_present(&obj, "offsetIndexOffset", c.offset_index_offset, |n| num_js(n as f64))?;
+    set_present(&obj, "offsetIndexLength", c.offset_index_length, num_js)?;
+    set_present(&obj, "columnIndexOffset", c.column_index_offset, |n| num_js(n as f64))?;
+    set_present(&obj, "columnIndexLength", c.column_index_length, num_js)?;
+    if c.crypto_metadata.is_some() || c.encrypted_column_metadata.is_some() {
+        Reflect::set(&obj, &"encrypted".into(), &JsValue::TRUE)?;
+    }
+    Ok(obj.into())
+}
+
+fn row_group_js(rg: &parquet_format_safe::RowGroup) -> Result<JsValue, JsValue> {
+    let obj = Object::new();
+    let columns = Array::new();
+    for c in &rg.columns {
+        columns.push(&column_chunk_js(c)?);
+    }
+    Reflect::set(&obj, &"columns".into(), &columns)?;
+    Reflect::set(&obj, &"totalByteSize".into(), &num_js(rg.total_byte_size as f64))?;
+    Reflect::set(&obj, &"numRows".into(), &num_js(rg.num_rows as f64))?;
+    if let Some(sorting) = &rg.sorting_columns {
+        let arr = Array::new();
+        for s in sorting {
+            let entry = Object::new();
+            Reflect::set(&entry, &"columnIdx".into(), &num_js(s.column_idx))?;
+            Reflect::set(&entry, &"descending".into(), &JsValue::from_bool(s.descending))?;
+            Reflect::set(&entry, &"nullsFirst".into(), &JsValue::from_bool(s.nulls_first))?;
+            arr.push(&entry);
+        }
+        Reflect::set(&obj, &"sortingColumns".into(), &arr)?;
+    }
+    set_present(&obj, "fileOffset", rg.file_offset, |n| num_js(n as f64))?;
+    set_present(&obj, "totalCompressedSize", rg.total_compressed_size, |n| num_js(n as f64))?;
+    set_present(&obj, "ordinal", rg.ordinal, num_js)?;
+    Ok(obj.into())
+}
+
+/// The whole thrift FileMetaData as plain JS values, field for field (names
+/// in camelCase, enums by name, binary as hex)
+fn footer_dump_js(metadata: &parquet_format_safe::FileMetaData, float16: &[usize]) -> Result<Object, JsValue> {
+    let schema = Array::new();
+    let mut leaf = 0;
+    for el in &metadata.schema {
+        let obj = Object::new();
+        set_present(&obj, "type", el.type_, |t| enum_js(TYPE_NAMES, t.0))?;
+        set_present(&obj, "typeLength", el.type_length, num_js)?;
+        set_present(&obj, "repetitionType", el.repetition_type, |r| enum_js(REPETITION_NAMES, r.0))?;
+        Reflect::set(&obj, &"name".into(), &JsValue::from_str(&el.name))?;
+        set_present(&obj, "numChildren", el.num_children, num_js)?;
+        set_present(&obj, "convertedType", el.converted_type, |c| enum_js(CONVERTED_TYPE_NAMES, c.0))?;
+        set_present(&obj, "scale", el.scale, num_js)?;
+        set_present(&obj, "precision", el.precision, num_js)?;
+        set_present(&obj, "fieldId", el.field_id, num_js)?;
+        if let Some(lt) = &el.logical_type {
+            Reflect::set(&obj, &"logicalType".into(), &logical_type_js(lt)?)?;
+        }
+        if el.num_children.is_none() {
+            if float16.contains(&leaf) {
+                let lt = Object::new();
+                Reflect::set(&lt, &"FLOAT16".into(), &Object::new())?;
+                Reflect::set(&obj, &"logicalType".into(), &lt)?;
+            }
+            leaf += 1;
+        }
+        schema.push(&obj);
+    }
+
+    let obj = Object::new();
+    Reflect::set(&obj, &"version".into(), &num_js(metadata.version))?;
+    Reflect::set(&obj, &"schema".into(), &schema)?;
+    Reflect::set(&obj, &"numRows".into(), &num_js(metadata.num_rows as f64))?;
+    let row_groups = Array::new();
+    for rg in &metadata.row_groups {
+        row_groups.push(&row_group_js(rg)?);
+    }
+    Reflect::set(&obj, &"rowGroups".into(), &row_groups)?;
+    if metadata.key_value_metadata.is_some() {
+        Reflect::set(&obj, &"keyValueMetadata".into(), &key_values_js(&metadata.key_value_metadata)?)?;
+    }
+    set_present(&obj, "createdBy", metadata.created_by.as_deref(), JsValue::from_str)?;
+    if let Some(orders) = &metadata.column_orders {
+        let arr: Array = orders.iter().map(|_| JsValue::from_str("TYPE_ORDER")).collect();
+        Reflect::set(&obj, &"columnOrders".into(), &arr)?;
+    }
+    if let Some(alg) = &metadata.encryption_algorithm {
+        let name = match alg {
+            parquet_format_safe::EncryptionAlgorithm::AESGCMV1(_) => "AES_GCM_V1",
+            parquet_format_safe::EncryptionAlgorithm::AESGCMCTRV1(_) => "AES_GCM_CTR_V1",
+        };
+        Reflect::set(&obj, &"encryptionAlgorithm".into(), &JsValue::from_str(name))?;
+    }
+    Ok(obj)
+}
+
+// ── Nested columns ──────────────────────────────────────────────────────────
+
+/// Levels of one nested leaf column, one entry per slot
+#[derive(Default)]
+struct Levels {
+    def: Vec<u32>,
+    rep: Vec<u32>,
+}
+
+/// How a top-level field is rebuilt from its leaf columns. Definition levels
+/// are the thresholds at which each part is present.
+enum Node {
+    /// Leaf column `column` (index among the field's leaves); null below `max_def`
+    Leaf { column: usize, max_def: u32 },
+    /// Null below `def`, empty below `filled`; elements repeat at level `rep`
+    List { def: u32, filled: u32, rep: u32, element: Box<Node> },
+    /// Object of named children; null below `def`
+    Struct { def: u32, fields: Vec<(String, Node)> },
+    /// Like `List`, with key/value pairs as the elements
+    Map { def: u32, filled: u32, rep: u32, key: Box<Node>, value: Box<Node> },
+}
+
+impl Node {
+    /// Tree for a field whose parent is present at level `def` and repeats at `rep`
+    fn build(t: &ParquetType, def: u32, rep: u32, leaf: &mut usize) -> Result<Self, String> {
+        match t.get_field_info().repetition {
+            Repetition::Required => Self::inner(t, def, rep, leaf),
+            Repetition::Optional => Self::inner(t, def + 1, rep, leaf),
+            // A bare repeated field is a list of required elements
+            Repetition::Repeated => Ok(Node::List {
+                def,
+                filled: def + 1,
+                rep: rep + 1,
+                element: Box::new(Self::inner(t, def + 1, rep + 1, leaf)?),
+            }),
+        }
+    }
+
+    /// Tree for a field that is present at level `def`, ignoring its own repetition
+    fn inner(t: &ParquetType, def: u32, rep: u32, leaf: &mut usize) -> Result<Self, String> {
+        let ParquetType::GroupType { field_info, logical_type, converted_type, fields } = t else {
+            *leaf += 1;
+            return Ok(Node::Leaf { column: *leaf - 1, max_def: def });
+        };
+        let is_list = logical_type == &Some(GroupLogicalType::List) || converted_type == &Some(GroupConvertedType::List);
+        let is_map = logical_type == &Some(GroupLogicalType::Map)
+            || matches!(converted_type, Some(GroupConvertedType::Map | GroupConvertedType::MapKeyValue));
+        match fields.as_slice() {
+            [ParquetType::GroupType { field_info: entry, fields, .. }]
+                if is_map && entry.repetition == Repetition::Repeated && fields.len() == 2 =>
+            {
+                Ok(Node::Map {
+                    def,
+                    filled: def + 1,
+                    rep: rep + 1,
+                    key: Box::new(Self::build(&fields[0], def + 1, rep + 1, leaf)?),
+                    value: Box::new(Self::build(&fields[1], def + 1, rep + 1, leaf)?),
+                })
+            }
+            _ if is_map => Err(format!("malformed MAP group '{}'", field_info.name)),
+            [repeated] if is_list && repeated.get_field_info().repetition == Repetition::Repeated => {
+                let element = match repeated {
+                    // Three-level layout: the repeated group wraps the element. Older
+                    // writers used a repeated group as the element itself.
+                    ParquetType::GroupType { field_info: inner, fields, .. }
+                        if fields.len() == 1
+                            && inner.name != "array"
+                            && inner.name != format!("{}_tuple", field_info.name) =>
+                    {
+                        Self::build(&fields[0], def + 1, rep + 1, leaf)?
+                    }
+                    _ => Self::inner(repeated, def + 1, rep + 1, leaf)?,
+                };
+                Ok(Node::List { def, filled: def + 1, rep: rep + 1, element: Box::new(element) })
+            }
+            _ if is_list => Err(format!("malformed LIST group '{}'", field_info.name)),
+            [] => Err(format!("group '{}' has no fields", field_info.name)),
+            _ => {
+                let fields = fields
+                    .iter()
+                    .map(|f| Ok((f.get_field_info().name.clone(), Self::build(f, def, rep, leaf)?)))
+                    .collect::<Result<_, String>>()?;
+                Ok(Node::Struct { def, fields })
+            }
+        }
+    }
+
+    /// Index of the first leaf column under this node
+    fn first_leaf(&self) -> usize {
+        match self {
+            Node::Leaf { column, .. } => *column,
+            Node::List { element, .. } => element.first_leaf(),
+            Node::Struct { fields, .. } => fields[0].1.first_leaf(),
+            Node::Map { key, .. } => key.first_leaf(),
+        }
+    }
+
+    /// Rebuild the value at the cursors' position, advancing them past it.
+    /// Maps become JS Maps with `js_maps`, else plain objects.
+    fn assemble(&self, leaves: &mut [LeafCursor], js_maps: bool) -> Result<JsValue, String> {
+        match self {
+            Node::Leaf { column, max_def } => {
+                let leaf = &mut leaves[*column];
+                let def = leaf.def()?;
+                leaf.pos += 1;
+                if def < *max_def {
+                    return Ok(JsValue::NULL);
+                }
+                let v = leaf.values.get(leaf.next_value);
+                leaf.next_value += 1;
+                leaf.conv.try_apply(v)
+            }
+            Node::List { def, filled, rep, element } => {
+                let first = element.first_leaf();
+                let level = leaves[first].def()?;
+                if level < *filled {
+                    self.skip(leaves);
+                    return Ok(if level < *def { JsValue::NULL } else { Array::new().into() });
+                }
+                let items = Array::new();
+                loop {
+                    items.push(&element.assemble(leaves, js_maps)?);
+                    if leaves[first].rep() != Some(*rep) {
+                        break;
+                    }
+                }
+                Ok(items.into())
+            }
+            Node::Struct { def, fields } => {
+                if leaves[self.first_leaf()].def()? < *def {
+                    self.skip(leaves);
+                    return Ok(JsValue::NULL);
+                }
+                let obj = Object::new();
+                for (name, child) in fields {
+                    let v = child.assemble(leaves, js_maps)?;
+                    Reflect::set(&obj, &JsValue::from_str(name), &v).map_err(|_| format!("cannot set field '{}'", name))?;
+                }
+                Ok(obj.into())
+            }
+            Node::Map { def, filled, rep, key, value } => {
+                let first = key.first_leaf();
+                let level = leaves[first].def()?;
+                if level < *def {
+                    self.skip(leaves);
+                    return Ok(JsValue::NULL);
+                }
+                let map = js_sys::Map::new();
+                let obj = Object::new();
+                if level >= *filled {
+                    loop {
+                        let k = key.assemble(leaves, js_maps)?;
+                        let v = value.assemble(leaves, js_maps)?;
+                        if js_maps {
+                            map.set(&k, &v);
+                        } else {
+                            Reflect::set(&obj, &k, &v).map_err(|_| format!("cannot set map key {:?}", k))?;
+                        }
+                        if leaves[first].rep() != Some(*rep) {
+                            break;
+                        }
+                    }
+                } else {
+                    self.skip(leaves);
+                }
+                Ok(if js_maps { map.into() } else { obj.into() })
+            }
+        }
+    }
+
+    /// Step every leaf past an entry that holds no values (a null or empty list or map)
+    fn skip(&self, leaves: &mut [LeafCursor]) {
+        match self {
+            Node::Leaf { column, .. } => leaves[*column].pos += 1,
+            Node::List { element, .. } => element.skip(leaves),
+            Node::Struct { fields, .. } => fields.iter().for_each(|(_, child)| child.skip(leaves)),
+            Node::Map { key, value, .. } => {
+                key.skip(leaves);
+                value.skip(leaves);
+            }
+        }
+    }
+}
+
+/// Decoded levels and present values of one leaf column chunk, with read positions
+struct LeafCursor {
+    levels: Levels,
+    values: ColumnBuf,
+    conv: Convert,
+    /// Next level entry
+    pos: usize,
+    /// Next present value
+    next_value: usize,
+}
+
+impl LeafCursor {
+    fn def(&self) -> Result<u32, String> {
+        self.levels.def.get(self.pos).copied().ok_or_else(|| "levels ended mid-row".to_string())
+    }
+
+    fn rep(&self) -> Option<u32> {
+        self.levels.rep.get(self.pos).copied()
+    }
+
+    fn done(&self) -> bool {
+        self.pos >= self.levels.def.len()
+    }
+}
+
+/// Output buffer for a top-level leaf column under `opts`, and the conversion
+/// its decoded values still need. `geometry` marks a GeoParquet WKB column.
+fn leaf_buf(desc: &ColumnDescriptor, opts: &ReadOptions, geometry: bool) -> (ColumnBuf, Convert) {
+    let prim = &desc.descriptor.primitive_type;
+    let required = prim.field_info.repetition == Repetition::Required;
+    let conv = if geometry && opts.geojson { Convert::Geometry } else { Convert::for_column(prim, opts) };
+    let typed = opts.typed_arrays && required && !opts.rows;
+    let buf = if typed && conv == Convert::Float16 {
+        ColumnBuf::F32(Vec::new())
+    } else {
+        ColumnBuf::for_column(
+            prim.physical_type,
+            typed && conv == Convert::None,
+            // Keep int64 decimals exact until the scale is applied
+            opts.int64_bigint || matches!(conv, Convert::Decimal(..) | Convert::UInt64(_)),
+            is_text(prim),
+        )
+    };
+    (buf, conv)
+}
+
+/// Read a nested field (leaf columns `cols`) into a JS array of rebuilt row
+/// values: up to `limit` rows after skipping the first `skip`. Row groups are
+/// decoded one at a time, all their entries at once.
+#[allow(clippy::too_many_arguments)]
+fn read_nested(
+    file: &FileBytes,
+    metadata: &FileMetaData,
+    name: &str,
+    node: &Node,
+    cols: Range<usize>,
+    row_groups: &[usize],
+    mut skip: usize,
+    limit: usize,
+    opts: &ReadOptions,
+    warnings: &Array,
+) -> Result<ColumnBuf, JsValue> {
+    let out = Array::new();
+    for &ri in row_groups {
+        if out.length() as usize >= limit { break; }
+        let rg = &metadata.row_groups[ri];
+        if rg.num_rows() <= skip {
+            skip -= rg.num_rows();
+            continue;
+        }
+        let mut leaves = Vec::with_capacity(cols.len());
+        for ci in cols.clone() {
+            let desc = &metadata.schema_descr.columns()[ci];
+            let prim = &desc.descriptor.primitive_type;
+            let conv = Convert::for_column(prim, opts);
+            let bigint = opts.int64_bigint || matches!(conv, Convert::Decimal(..) | Convert::UInt64(_));
+            let mut values = ColumnBuf::for_column(prim.physical_type, false, bigint, is_text(prim));
+            let mut levels = Levels::default();
+            let chunk = &rg.columns()[ci];
+            check_chunk(file, chunk, name, ri, opts, warnings)?;
+            read_column_chunk(file.chunk(chunk)?, chunk.into(), desc, &mut values, Some(&mut levels), 0, usize::MAX)?;
+            leaves.push(LeafCursor { levels, values, conv, pos: 0, next_value: 0 });
+        }
+        while !leaves[0].done() && (out.length() as usize) < limit {
+            let row = node
+                .assemble(&mut leaves, opts.js_maps)
+                .map_err(|e| JsValue::from_str(&format!("column '{}' row {}: {}", name, out.length(), e)))?;
+            if skip > 0 {
+                skip -= 1;
+            } else {
+                out.push(&row);
+            }
+        }
+        skip = 0;
+    }
+    Ok(ColumnBuf::Js(out))
+}
+
+// ── Filters ─────────────────────────────────────────────────────────────────
+
+/// Comparison in a `filter` condition
+#[derive(Clone, Copy, PartialEq)]
+enum FilterOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    In,
+    NotIn,
+    IsNull,
+    IsNotNull,
+}
+
+/// One `{ column, op, value }` condition of the `filter` option. Conditions
+/// are ANDed, and nulls only pass `is null` (as in SQL). Values compare like
+/// JS `<` / `>` on the decoded cells, after `dates`, `timestamps`, etc.
+struct Predicate {
+    column: String,
+    op: FilterOp,
+    /// The operand, or the list for `in` / `not in`
+    values: Vec<JsValue>,
+}
+
+/// Equality for values JS can order: numbers, BigInts, strings and Dates
+fn js_eq(a: &JsValue, b: &JsValue) -> bool {
+    a.le(b) && a.ge(b)
+}
+
+impl Predicate {
+    fn from_js(cond: &JsValue) -> Result<Self, JsValue> {
+        let bad = || JsValue::from_str("filter must be an array of { column, op, value } conditions");
+        if !cond.is_object() {
+            return Err(bad());
+        }
+        let column = Reflect::get(cond, &"column".into())?.as_string().ok_or_else(bad)?;
+        let op = match Reflect::get(cond, &"op".into())?.as_string().as_deref() {
+            Some("=") | Some("==") => FilterOp::Eq,
+            Some("!=") => FilterOp::Ne,
+            Some("<") => FilterOp::Lt,
+            Some("<=") => FilterOp::Le,
+            Some(">") => FilterOp::Gt,
+            Some(">=") => FilterOp::Ge,
+            Some("in") => FilterOp::In,
+            Some("not in") => FilterOp::NotIn,
+            Some("is null") => FilterOp::IsNull,
+            Some("is not null") => FilterOp::IsNotNull,
+            _ => {
+                return Err(JsValue::from_str(&format!(
+                    "filter on '{}': op must be '=', '!=', '<', '<=', '>', '>=', 'in', 'not in', 'is null' or 'is not null'",
+                    column
+                )))
+            }
+        };
+        let value = Reflect::get(cond, &"value".into())?;
+        let values = match op {
+            FilterOp::IsNull | FilterOp::IsNotNull => Vec::new(),
+            FilterOp::In | FilterOp::NotIn => value
+                .dyn_ref::<Array>()
+                .ok_or_else(|| JsValue::from_str(&format!("filter on '{}': 'in' and 'not in' need an array value", column)))?
+                .iter()
+                .collect(),
+            _ if value.is_undefined() || value.is_null() => {
+                return Err(JsValue::from_str(&format!("filter on '{}': missing value (use 'is null' for nulls)", column)))
+            }
+            _ => vec![value],
+        };
+        Ok(Predicate { column, op, values })
+    }
+
+    /// Whether one decoded cell passes
+    fn matches(&self, v: &JsValue) -> bool {
+        if v.is_null() || v.is_undefined() {
+            return self.op == FilterOp::IsNull;
+        }
+        match self.op {
+            FilterOp::Eq => js_eq(v, &self.values[0]),
+            FilterOp::Ne => !js_eq(v, &self.values[0]),
+            FilterOp::Lt => v.lt(&self.values[0]),
+            FilterOp::Le => v.le(&self.values[0]),
+            FilterOp::Gt => v.gt(&self.values[0]),
+            FilterOp::Ge => v.ge(&self.values[0]),
+            FilterOp::In => self.values.iter().any(|x| js_eq(v, x)),
+            FilterOp::NotIn => !self.values.iter().any(|x| js_eq(v, x)),
+            FilterOp::IsNull => false,
+            FilterOp::IsNotNull => true,
+        }
+    }
+
+    /// Whether any value of a column chunk or page with these statistics could
+    /// pass: `bounds` are its min and max (converted like its values), and
+    /// `all_null` / `no_nulls` come from its null count where known
+    fn may_match(&self, bounds: Option<(&JsValue, &JsValue)>, all_null: bool, no_nulls: bool) -> bool {
+        match self.op {
+            FilterOp::IsNull => return !no_nulls,
+            FilterOp::IsNotNull => return !all_null,
+            _ if all_null => return false,
+            _ => {}
+        }
+        let Some((lo, hi)) = bounds else { return true };
+        // Written as "not provably out of range", so incomparable values never prune
+        let within = |x: &JsValue| !(x.lt(lo) || x.gt(hi));
+        let only = |x: &JsValue| js_eq(lo, x) && js_eq(hi, x);
+        let undefined = JsValue::UNDEFINED;
+        let x = self.values.first().unwrap_or(&undefined);
+        match self.op {
+            FilterOp::Eq => within(x),
+            FilterOp::Ne => !only(x),
+            FilterOp::Lt => !lo.ge(x),
+            FilterOp::Le => !lo.gt(x),
+            FilterOp::Gt => !hi.le(x),
+            FilterOp::Ge => !hi.lt(x),
+            FilterOp::In => self.values.iter().any(within),
+            FilterOp::NotIn => !self.values.iter().any(only),
+            FilterOp::IsNull | FilterOp::IsNotNull => true,
+        }
+    }
+}
+
+/// A min/max statistic as a JS value comparable with the column's decoded
+/// cells. Plain int64 bounds are BigInts so they stay exact.
+fn filter_bound(prim: &PrimitiveType, conv: Convert, bytes: &[u8]) -> JsValue {
+    match conv {
+        Convert::None if prim.physical_type == PhysicalType::Int64 && bytes.len() == 8 => {
+            JsValue::from(i64::from_le_bytes(bytes.try_into().unwrap()))
+        }
+        Convert::UInt64(_) if bytes.len() == 8 => conv.apply(JsValue::from(i64::from_le_bytes(bytes.try_into().unwrap()))),
+        _ => conv.apply(stat_value(prim.physical_type, is_text(prim), bytes)),
+    }
+}
+
+/// Whether `pred` could pass for some row of a column chunk, by its statistics
+fn chunk_may_match(pred: &Predicate, chunk: &ColumnChunkMetaData, prim: &PrimitiveType, conv: Convert) -> bool {
+    let Some(stats) = &chunk.metadata().statistics else { return true };
+    // Deprecated min/max used signed byte order for binary; only trust them for numbers
+    let legacy_ok = !matches!(prim.physical_type, PhysicalType::ByteArray | PhysicalType::FixedLenByteArray(_));
+    let lo = stats.min_value.as_ref().or(if legacy_ok { stats.min.as_ref() } else { None });
+    let hi = stats.max_value.as_ref().or(if legacy_ok { stats.max.as_ref() } else { None });
+    let bounds = lo.zip(hi).map(|(lo, hi)| (filter_bound(prim, conv, lo), filter_bound(prim, conv, hi)));
+    pred.may_match(
+        bounds.as_ref().map(|(lo, hi)| (lo, hi)),
+        stats.null_count == Some(chunk.num_values()),
+        stats.null_count == Some(0),
+    )
+}
+
+/// Rows of a `num_rows`-row chunk that can hold values passing `pred`, by its
+/// page index: from the first to the end of the last page that may match.
+/// None when the chunk has no page index or it isn't loaded.
+fn page_span(file: &FileBytes, pred: &Predicate, chunk: &ColumnChunkMetaData, prim: &PrimitiveType, conv: Convert, num_rows: usize) -> Option<Range<usize>> {
+    let cc = chunk.column_chunk();
+    let loaded = |offset: Option<i64>, len: Option<i32>| {
+        let start = u64::try_from(offset?).ok()?;
+        file.get(start, start + u64::try_from(len?).ok()?)
+    };
+    // The protocol's limit budgets decoded memory (8 bytes per list element), not input bytes
+    let mut bytes = loaded(cc.column_index_offset, cc.column_index_length)?;
+    let limit = bytes.len().saturating_mul(8);
+    let column_index = ColumnIndex::read_from_in_protocol(&mut TCompactInputProtocol::new(&mut bytes, limit)).ok()?;
+    let mut bytes = loaded(cc.offset_index_offset, cc.offset_index_length)?;
+    let limit = bytes.len().saturating_mul(8);
+    let offset_index = OffsetIndex::read_from_in_protocol(&mut TCompactInputProtocol::new(&mut bytes, limit)).ok()?;
+    let pages = &offset_index.page_locations;
+    if column_index.null_pages.len() != pages.len() {
+        return None;
+    }
+    let mut span: Option<Range<usize>> = None;
+    for (i, page) in pages.iter().enumerate() {
+        let all_null = column_index.null_pages[i];
+        let nulls = column_index.null_counts.as_ref().and_then(|n| n.get(i));
+        let bounds = (!all_null)
+            .then(|| column_index.min_values.get(i).zip(column_index.max_values.get(i)))
+            .flatten()
+            .map(|(lo, hi)| (filter_bound(prim, conv, lo), filter_bound(prim, conv, hi)));
+        if !pred.may_match(bounds.as_ref().map(|(lo, hi)| (lo, hi)), all_null, nulls == Some(&0)) {
+            continue;
+        }
+        let start = page.first_row_index.max(0) as usize;
+        let end = pages.get(i + 1).map_or(num_rows, |next| next.first_row_index.max(0) as usize);
+        span = Some(span.map_or(start..end, |s| s.start.min(start)..s.end.max(end)));
+    }
+    Some(span.unwrap_or(0..0))
+}
+
+// ── Bloom filters ───────────────────────────────────────────────────────────
+//
+// Split-block bloom filters as the parquet spec defines them (and as
+// parquet-writer writes them), read and probed with parquet2's: xxHash64 of a
+// value's PLAIN bytes picks a 32-byte block, and the value may be present only
+// if one bit is set in each of its eight words.
+
+/// `Read + Seek` at file offsets over the loaded range that starts at
+/// `start`. Reading past it is an error rather than EOF, so a filter that
+/// isn't wholly loaded is never taken for a shorter one.
+struct LoadedRange<'a> {
+    start: u64,
+    bytes: &'a [u8],
+    pos: u64,
+}
+
+impl Read for LoadedRange<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let rest = self
+            .pos
+            .checked_sub(self.start)
+            .and_then(|at| self.bytes.get(usize::try_from(at).ok()?..))
+            .filter(|rest| !rest.is_empty() || buf.is_empty())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "bloom filter is not loaded"))?;
+        let n = rest.len().min(buf.len());
+        buf[..n].copy_from_slice(&rest[..n]);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl Seek for LoadedRange<'_> {
+    fn seek(&mut self, to: SeekFrom) -> io::Result<u64> {
+        let pos = match to {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::Current(d) => self.pos.checked_add_signed(d),
+            SeekFrom::End(d) => (self.start + self.bytes.len() as u64).checked_add_signed(d),
+        };
+        self.pos = pos.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "seek before the start of the file"))?;
+        Ok(self.pos)
+    }
+}
+
+/// The bitset of a column chunk's bloom filter, when it has one of the kind
+/// the spec defines (split-block, uncompressed) and it is loaded
+fn bloom_bitset(file: &FileBytes, chunk: &ColumnChunkMetaData) -> Option<Vec<u8>> {
+    let start = u64::try_from(chunk.metadata().bloom_filter_offset?).ok()?;
+    let mut reader = LoadedRange { start, bytes: file.loaded_from(start)?, pos: start };
+    let mut bitset = Vec::new();
+    bloom_filter::read(chunk, &mut reader, &mut bitset).ok()?;
+    // `is_in_set` indexes whole 32-byte blocks; anything else isn't a valid filter
+    (!bitset.is_empty() && bitset.len().is_multiple_of(32)).then_some(bitset)
+}
+
+/// Hex text → bytes
+fn hex_bytes(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok()).collect()
+}
+
+/// The PLAIN bytes of the stored value that decodes (through `conv`) to `v`,
+/// i.e. what a bloom filter hashed for it. None when there's no single such
+/// value — e.g. for converted dates and decimals, whose decoded forms
+/// several stored values may share — so the filter can't rule `v` out.
+fn bloom_key(prim: &PrimitiveType, conv: Convert, v: &JsValue) -> Option<Vec<u8>> {
+    let int = || -> Option<i128> {
+        if v.is_bigint() {
+            return i128::try_from(v.clone()).ok();
+        }
+        let n = v.as_f64()?;
+        (n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER as f64).then_some(n as i128)
+    };
+    // 0 and -0 are equal to filters but hash differently
+    let float = || v.as_f64().filter(|&n| n != 0.0);
+    match (prim.physical_type, conv) {
+        (PhysicalType::Int32, Convert::None) => Some(i32::try_from(int()?).ok()?.to_le_bytes().to_vec()),
+        (PhysicalType::Int32, Convert::UInt32) => Some(u32::try_from(int()?).ok()?.to_le_bytes().to_vec()),
+        (PhysicalType::Int64, Convert::None) => Some(i64::try_from(int()?).ok()?.to_le_bytes().to_vec()),
+        (PhysicalType::Int64, Convert::UInt64(_)) => Some(u64::try_from(int()?).ok()?.to_le_bytes().to_vec()),
+        (PhysicalType::Float, Convert::None) => {
+            let n = float()?;
+            (n as f32 as f64 == n).then(|| (n as f32).to_le_bytes().to_vec())
+        }
+        (PhysicalType::Double, Convert::None) => Some(float()?.to_le_bytes().to_vec()),
+        (PhysicalType::ByteArray, Convert::None) if is_text(prim) => v.as_string().map(String::into_bytes),
+        (PhysicalType::ByteArray | PhysicalType::FixedLenByteArray(_), Convert::None) => {
+            v.dyn_ref::<Uint8Array>().map(Uint8Array::to_vec)
+        }
+        (_, Convert::Hex) => hex_bytes(&v.as_string()?),
+        (_, Convert::Uuid) => hex_bytes(&v.as_string()?.replace('-', "")),
+        _ => None,
+    }
+}
+
+/// Whether a column chunk may hold a value equal to `v`: false only when its
+/// bloom filter is loaded and rules `v` out
+fn bloom_may_contain(file: &FileBytes, chunk: &ColumnChunkMetaData, prim: &PrimitiveType, conv: Convert, v: &JsValue) -> bool {
+    let Some(key) = bloom_key(prim, conv, v) else { return true };
+    bloom_bitset(file, chunk).is_none_or(|bitset| is_in_set(&bitset, hash_byte(&key)))
+}
+
+// ── Read options ────────────────────────────────────────────────────────────
+
+/// What to do with page checksums
+#[derive(Clone, Copy, PartialEq)]
+enum ChecksumMode {
+    Ignore,
+    Fail,
+    Warn,
+}
+
+/// Parsed `readParquet` options
+struct ReadOptions {
+    /// Max rows to decode per column (default 500, for preview)
+    max_rows: usize,
+    verify_checksums: ChecksumMode,
+    /// Column names to decode, in output order (default: all)
+    columns: Option<Vec<String>>,
+    /// Output names of `columns`, from a `select: { newName: 'oldName' }` mapping
+    renames: Option<Vec<String>>,
+    /// Row group indices to decode, in output order (default: all)
+    row_groups: Option<Vec<usize>>,
+    /// Rows to skip before collecting `max_rows`
+    offset: usize,
+    /// Return `data` as an array of row objects instead of column arrays
+    rows: bool,
+    /// Return required numeric/boolean columns as TypedArrays
+    typed_arrays: bool,
+    /// Return int64 values as BigInt instead of (lossy) Numbers
+    int64_bigint: bool,
+    /// How DATE columns are returned (default ISO strings)
+    dates: DateMode,
+    /// Return TIME columns as "HH:MM:SS.fff" strings (default) rather than raw counts
+    times_as_strings: bool,
+    /// Return DECIMAL columns as exact strings instead of Numbers
+    decimals_as_strings: bool,
+    /// Return byte values as hex strings instead of Uint8Arrays
+    binary_hex: bool,
+    /// Return GeoParquet geometry columns as GeoJSON (default) rather than WKB
+    geojson: bool,
+    /// Unit timestamps are normalized to (None = as stored)
+    timestamp_unit: Option<TimeUnit>,
+    /// Return uint64 columns as BigInt (default) rather than checked Numbers
+    uint64_bigint: bool,
+    /// JSON.parse JSON columns instead of returning their text
+    parse_json: bool,
+    /// Return MAP columns as JS Maps instead of plain objects
+    js_maps: bool,
+    /// Only return rows passing all of these (`offset` and `max_rows` count those rows)
+    filter: Vec<Predicate>,
+    /// `where(row, index)` callback; only rows it returns truthy for are kept
+    where_fn: Option<Function>,
+    /// Add footer statistics to schema entries (off for `readParquetSchema`)
+    statistics: bool,
+}
+
+impl ReadOptions {
+    /// Accepts a plain number (max rows, the original signature) or an options
+    /// object; `max_rows` applies when neither sets it
+    fn from_js(options: &JsValue, max_rows: usize) -> Result<Self, JsValue> {
+        let mut opts = ReadOptions { max_rows, verify_checksums: ChecksumMode::Ignore, columns: None, renames: None, row_groups: None, offset: 0, rows: false, typed_arrays: false, int64_bigint: false, dates: DateMode::Iso, times_as_strings: true, decimals_as_strings: false, binary_hex: false, geojson: true, timestamp_unit: Some(TimeUnit::Milliseconds), uint64_bigint: true, parse_json: false, js_maps: false, filter: Vec::new(), where_fn: None, statistics: true };
+        if let Some(n) = options.as_f64() {
+            opts.max_rows = n.max(0.0) as usize;
+            return Ok(opts);
+        }
+        if options.is_undefined() || options.is_null() {
+            return Ok(opts);
+        }
+        if let Some(n) = Reflect::get(options, &"maxRows".into())?.as_f64() {
+            opts.max_rows = n.max(0.0) as usize;
+        }
+        if let Some(n) = Reflect::get(options, &"offset".into())?.as_f64() {
+            opts.offset = n.max(0.0) as usize;
+        }
+        let verify = Reflect::get(options, &"verifyChecksums".into())?;
+        opts.verify_checksums = match verify.as_string().as_deref() {
+            Some("warn") => ChecksumMode::Warn,
+            Some(other) => {
+                return Err(JsValue::from_str(&format!(
+                    "verifyChecksums must be true, false or 'warn', got '{}'",
+                    other
+                )))
+            }
+            None if verify.is_truthy() => ChecksumMode::Fail,
+            None => ChecksumMode::Ignore,
+        };
+        let columns = Reflect::get(options, &"columns".into())?;
+        if !columns.is_undefined() && !columns.is_null() {
+            let arr = columns
+                .dyn_ref::<Array>()
+                .ok_or_else(|| JsValue::from_str("columns must be an array of column names"))?;
+            opts.columns = Some(
+                arr.iter()
+                    .map(|v| v.as_string().ok_or_else(|| JsValue::from_str("columns must be an array of column names")))
+                    .collect::<Result<_, _>>()?,
+            );
+        }
+        let select = Reflect::get(options, &"select".into())?;
+        if !select.is_undefined() && !select.is_null() {
+            if opts.columns.is_some() {
+                return Err(JsValue::from_str("columns and select can't both be set"));
+            }
+            let bad = || JsValue::from_str("select must map new column names to existing ones, like { newName: 'oldName' }");
+            if !select.is_object() || Array::is_array(&select) {
+                return Err(bad());
+            }
+            let (mut renames, mut columns) = (Vec::new(), Vec::new());
+            for entry in Object::entries(select.unchecked_ref()).iter() {
+                let entry: Array = entry.unchecked_into();
+                renames.push(entry.get(0).as_string().ok_or_else(bad)?);
+                columns.push(entry.get(1).as_string().ok_or_else(bad)?);
+            }
+            opts.columns = Some(columns);
+            opts.renames = Some(renames);
+        }
+        opts.typed_arrays = Reflect::get(options, &"typedArrays".into())?.is_truthy();
+        let int64 = Reflect::get(options, &"int64".into())?;
+        opts.int64_bigint = match int64.as_string().as_deref() {
+            None if int64.is_undefined() => false,
+            Some("number") => false,
+            Some("bigint") => true,
+            _ => return Err(JsValue::from_str("int64 must be 'number' or 'bigint'")),
+        };
+        let dates = Reflect::get(options, &"dates".into())?;
+        opts.dates = match dates.as_string().as_deref() {
+            None if dates.is_undefined() => DateMode::Iso,
+            Some("string") => DateMode::Iso,
+            Some("date") => DateMode::Object,
+            Some("number") => DateMode::Days,
+            _ => return Err(JsValue::from_str("dates must be 'string', 'date' or 'number'")),
+        };
+        let times = Reflect::get(options, &"times".into())?;
+        opts.times_as_strings = match times.as_string().as_deref() {
+            None if times.is_undefined() => true,
+            Some("string") => true,
+            Some("number") => false,
+            _ => return Err(JsValue::from_str("times must be 'string' or 'number'")),
+        };
+        let decimals = Reflect::get(options, &"decimals".into())?;
+        opts.decimals_as_strings = match decimals.as_string().as_deref() {
+            None if decimals.is_undefined() => false,
+            Some("number") => false,
+            Some("string") => true,
+            _ => return Err(JsValue::from_str("decimals must be 'number' or 'string'")),
+        };
+        let binary = Reflect::get(options, &"binary".into())?;
+        opts.binary_hex = match binary.as_string().as_deref() {
+            None if binary.is_undefined() => false,
+            Some("bytes") => false,
+            Some("hex") => true,
+            _ => return Err(JsValue::from_str("binary must be 'bytes' or 'hex'")),
+        };
+        let geometry = Reflect::get(options, &"geometry".into())?;
+        opts.geojson = match geometry.as_string().as_deref() {
+            None if geometry.is_undefined() => true,
+            Some("geojson") => true,
+            Some("wkb") => false,
+            _ => return Err(JsValue::from_str("geometry must be 'geojson' or 'wkb'")),
+        };
+        let timestamps = Reflect::get(options, &"timestamps".into())?;
+        opts.timestamp_unit = match timestamps.as_string().as_deref() {
+            None if timestamps.is_undefined() => Some(TimeUnit::Milliseconds),
+            Some("millis") => Some(TimeUnit::Milliseconds),
+            Some("micros") => Some(TimeUnit::Microseconds),
+            Some("nanos") => Some(TimeUnit::Nanoseconds),
+            Some("raw") => None,
+            _ => return Err(JsValue::from_str("timestamps must be 'millis', 'micros', 'nanos' or 'raw'")),
+        };
+        let uint64 = Reflect::get(options, &"uint64".into())?;
+        opts.uint64_bigint = match uint64.as_string().as_deref() {
+            None if uint64.is_undefined() => true,
+            Some("bigint") => true,
+            Some("error") => false,
+            _ => return Err(JsValue::from_str("uint64 must be 'bigint' or 'error'")),
+        };
+        let json = Reflect::get(options, &"json".into())?;
+        opts.parse_json = match json.as_string().as_deref() {
+            None if json.is_undefined() => false,
+            Some("string") => false,
+            Some("parse") => true,
+            _ => return Err(JsValue::from_str("json must be 'string' or 'parse'")),
+        };
+        let maps = Reflect::get(options, &"maps".into())?;
+        opts.js_maps = match maps.as_string().as_deref() {
+            None if maps.is_undefined() => false,
+            Some("object") => false,
+            Some("map") => true,
+            _ => return Err(JsValue::from_str("maps must be 'object' or 'map'")),
+        };
+        let format = Reflect::get(options, &"format".into())?;
+        opts.rows = match format.as_string().as_deref() {
+            None if format.is_undefined() => false,
+            Some("columns") => false,
+            Some("rows") => true,
+            _ => return Err(JsValue::from_str("format must be 'columns' or 'rows'")),
+        };
+        let row_groups = Reflect::get(options, &"rowGroups".into())?;
+        if !row_groups.is_undefined() && !row_groups.is_null() {
+            let bad = || JsValue::from_str("rowGroups must be an array of row group indices");
+            let arr = row_groups.dyn_ref::<Array>().ok_or_else(bad)?;
+            opts.row_groups = Some(
+                arr.iter()
+                    .map(|v| v.as_f64().filter(|n| *n >= 0.0 && n.fract() == 0.0).map(|n| n as usize).ok_or_else(bad))
+                    .collect::<Result<_, _>>()?,
+            );
+        }
+        let filter = Reflect::get(options, &"filter".into())?;
+        if !filter.is_undefined() && !filter.is_null() {
+            let arr = filter
+                .dyn_ref::<Array>()
+                .ok_or_else(|| JsValue::from_str("filter must be an array of { column, op, value } conditions"))?;
+            opts.filter = arr.iter().map(|c| Predicate::from_js(&c)).collect::<Result<_, _>>()?;
+        }
+        let where_fn = Reflect::get(options, &"where".into())?;
+        if !where_fn.is_undefined() && !where_fn.is_null() {
+            opts.where_fn = Some(where_fn.dyn_into().map_err(|_| JsValue::from_str("where must be a function"))?);
+        }
+        Ok(opts)
+    }
+
+    /// Name the `k`th selected column (field `name` in the file) is returned under
+    fn output_name<'a>(&'a self, k: usize, name: &'a str) -> &'a str {
+        self.renames.as_ref().map_or(name, |r| &r[k])
+    }
+}
+
+/// Fill a schema entry for leaf column `ci`: its type and type parameters,
+/// `optional`, field ID and statistics
+fn describe_leaf(obj: &Object, metadata: &FileMetaData, ci: usize, optional: bool, opts: &ReadOptions) -> Result<(), JsValue> {
+    let desc = &metadata.schema_descr.columns()[ci];
+    let prim = &desc.descriptor.primitive_type;
+    Reflect::set(obj, &"type".into(), &JsValue::from_str(type_label(prim)))?;
+    if optional {
+        Reflect::set(obj, &"optional".into(), &JsValue::TRUE)?;
+    }
+    if let PhysicalType::FixedLenByteArray(n) = prim.physical_type {
+        if type_label(prim) == "fixed" {
+            Reflect::set(obj, &"length".into(), &JsValue::from_f64(n as f64))?;
+        }
+    }
+    if let Some(unit) = timestamp_unit(prim) {
+        let unit = opts.timestamp_unit.unwrap_or(unit);
+        Reflect::set(obj, &"unit".into(), &JsValue::from_str(unit_label(unit)))?;
+    }
+    if let Some(utc) = adjusted_to_utc(prim) {
+        Reflect::set(obj, &"isAdjustedToUTC".into(), &JsValue::from_bool(utc))?;
+    }
+    if let Some((precision, scale)) = decimal_spec(prim) {
+        Reflect::set(obj, &"precision".into(), &JsValue::from_f64(precision as f64))?;
+        Reflect::set(obj, &"scale".into(), &JsValue::from_f64(scale as f64))?;
+    }
+    if let Some(id) = prim.field_info.id {
+        Reflect::set(obj, &"id".into(), &JsValue::from_f64(id as f64))?;
+    }
+    if !opts.statistics {
+        return Ok(());
+    }
+    let stats = column_stats(metadata, ci, prim, Convert::for_column(prim, opts))?;
+    if !stats.is_undefined() {
+        Reflect::set(obj, &"statistics".into(), &stats)?;
+    }
+    Ok(())
+}
+
+/// Fill a schema entry for `node`, whose parent is present at level
+/// `parent_def` and whose leaves start at column `first`. Lists describe
+/// their element under `items`, structs their children under `fields` and
+/// maps their `key` and `value`, in the same shape `writeParquet` accepts.
+fn describe_node(
+    obj: &Object,
+    node: &Node,
+    parent_def: u32,
+    first: usize,
+    metadata: &FileMetaData,
+    opts: &ReadOptions,
+) -> Result<(), JsValue> {
+    match node {
+        Node::Leaf { column, max_def } => describe_leaf(obj, metadata, first + column, *max_def > parent_def, opts),
+        Node::List { def, filled, element, .. } => {
+            Reflect::set(obj, &"type".into(), &"list".into())?;
+            if *def > parent_def {
+                Reflect::set(obj, &"optional".into(), &JsValue::TRUE)?;
+            }
+            let items = Object::new();
+            describe_node(&items, element, *filled, first, metadata, opts)?;
+            Reflect::set(obj, &"items".into(), &items)?;
+            Ok(())
+        }
+        Node::Struct { def, fields } => {
+            Reflect::set(obj, &"type".into(), &"struct".into())?;
+            if *def > parent_def {
+                Reflect::set(obj, &"optional".into(), &JsValue::TRUE)?;
+            }
+            let children = Array::new();
+            for (name, child) in fields {
+                let entry = Object::new();
+                Reflect::set(&entry, &"name".into(), &JsValue::from_str(name))?;
+                describe_node(&entry, child, *def, first, metadata, opts)?;
+                children.push(&entry);
+            }
+            Reflect::set(obj, &"fields".into(), &children)?;
+            Ok(())
+        }
+        Node::Map { def, filled, key, value, .. } => {
+            Reflect::set(obj, &"type".into(), &"map".into())?;
+            if *def > parent_def {
+                Reflect::set(obj, &"optional".into(), &JsValue::TRUE)?;
+            }
+            for (label, node) in [("key", key), ("value", value)] {
+                let entry = Object::new();
+                describe_node(&entry, node, *filled, first, metadata, opts)?;
+                Reflect::set(obj, &label.into(), &entry)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Leaf columns of the top-level BYTE_ARRAY fields the GeoParquet `geo`
+/// metadata lists with WKB encoding. Unparseable `geo` entries are ignored.
+fn geometry_columns(metadata: &FileMetaData, fields: &[(String, Node, Range<usize>)]) -> Vec<usize> {
+    let Some(geo) = metadata.key_value_metadata.iter().flatten().find(|kv| kv.key == "geo").and_then(|kv| kv.value.as_deref()) else {
+        return Vec::new();
+    };
+    let Ok(geo_columns) = js_sys::JSON::parse(geo).and_then(|g| Reflect::get(&g, &"columns".into())) else {
+        return Vec::new();
+    };
+    let columns = metadata.schema_descr.columns();
+    fields
+        .iter()
+        .filter(|(name, node, cols)| {
+            let entry = Reflect::get(&geo_columns, &name.into()).unwrap_or(JsValue::UNDEFINED);
+            let encoding = Reflect::get(&entry, &"encoding".into()).ok().and_then(|e| e.as_string());
+            matches!(node, Node::Leaf { .. })
+                && columns[cols.start].descriptor.primitive_type.physical_type == PhysicalType::ByteArray
+                && encoding.is_some_and(|e| e.eq_ignore_ascii_case("WKB"))
+        })
+        .map(|(.., cols)| cols.start)
+        .collect()
+}
+
+/// Footer key_value_metadata as a plain JS object (keys without a value map to null)
+fn key_value_object(metadata: &FileMetaData) -> Result<JsValue, JsValue> {
+    let obj = Object::new();
+    for kv in metadata.key_value_metadata.iter().flatten() {
+        let value = kv.value.as_deref().map_or(JsValue::NULL, JsValue::from_str);
+        Reflect::set(&obj, &JsValue::from_str(&kv.key), &value)?;
+    }
+    Ok(obj.into())
+}
+
+/// A file arriving in chunks (from a ReadableStream or async iterator),
+/// gathered straight into wasm memory so JS never holds it in one piece
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct FileBuffer {
+    bytes: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl FileBuffer {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> FileBuffer {
+        FileBuffer::default()
+    }
+
+    /// Append the next chunk of the file
+    pub fn push(&mut self, chunk: &Uint8Array) {
+        let start = self.bytes.len();
+        self.bytes.resize(start + chunk.length() as usize, 0);
+        chunk.copy_to(&mut self.bytes[start..]);
+    }
+}
+
+/// A Parquet file whose footer has been parsed once. Every read reuses the
+/// metadata and schema tree; only the requested column chunks are decoded.
+/// Remote files start with just the footer and get column chunks through
+/// `addRange`.
+#[wasm_bindgen]
+pub struct ParquetFile {
+    bytes: FileBytes,
+    metadata: FileMetaData,
+    /// Top-level fields with the leaf columns they span
+    fields: Vec<(String, Node, Range<usize>)>,
+    /// Leaf columns the `geo` metadata lists as WKB geometry
+    geometry: Vec<usize>,
+    locked: Locked,
+}
+
+#[wasm_bindgen]
+impl ParquetFile {
+    /// Parse the footer of `data` (copied into wasm memory once), decrypting
+    /// the file with the keys in `options.decryption` if it's encrypted
+    #[wasm_bindgen(constructor)]
+    pub fn new(data: &Uint8Array, options: &JsValue) -> Result<ParquetFile, JsValue> {
+        ParquetFile::from_bytes(data.to_vec(), options)
+    }
+
+    /// Parse a file gathered chunk by chunk in a `FileBuffer`, taking its bytes
+    #[wasm_bindgen(js_name = "fromBuffer")]
+    pub fn from_buffer(buffer: FileBuffer, options: &JsValue) -> Result<ParquetFile, JsValue> {
+        ParquetFile::from_bytes(buffer.bytes, options)
+    }
+
+    /// Parse the footer from the last `tail.length` bytes of a `fileLength`-byte
+    /// file; the tail must hold the footer and its 8-byte trailer. Encrypted
+    /// footers are decrypted with `options.decryption`, but encrypted column
+    /// chunks can't be read this way.
+    #[wasm_bindgen(js_name = "fromTail")]
+    pub fn from_tail(tail: &Uint8Array, file_length: f64, options: &JsValue) -> Result<ParquetFile, JsValue> {
+        let tail = tail.to_vec();
+        let len = file_length as u64;
+        if (tail.len() as u64) > len {
+            return Err(JsValue::from_str(&format!("tail of {} bytes is longer than the file ({})", tail.len(), len)));
+        }
+        let keys = parse_decryption(options)?;
+        let (tail, metadata, locked) = open_file(tail, false, keys.as_ref()).map_err(|e| JsValue::from_str(&format!("metadata: {}", e)))?;
+        let bytes = FileBytes { len, ranges: vec![(len - tail.len() as u64, tail)] };
+        ParquetFile::with_metadata(bytes, metadata, locked)
+    }
+
+    /// Byte ranges `read(options)` needs that aren't loaded yet, as `[start, end)`
+    /// pairs of file offsets. Ranges that touch are merged into one.
+    ///
+    /// Where column chunks have page indexes that aren't loaded, only those
+    /// are returned: once they are, the next call narrows flat columns to the
+    /// pages holding the rows the read covers. Call until it returns none.
+    #[wasm_bindgen(js_name = "missingRanges")]
+    pub fn missing_ranges(&self, options: &JsValue) -> Result<Array, JsValue> {
+        let opts = ReadOptions::from_js(options, 500)?;
+        let selected = self.select(&opts)?;
+        let filter = self.filter_columns(&opts)?;
+        // (leaf, whether it can be read page by page)
+        let mut leaves: Vec<(usize, bool)> = selected
+            .iter()
+            .flat_map(|&fi| {
+                let (_, node, cols) = &self.fields[fi];
+                let flat = matches!(node, Node::Leaf { .. });
+                cols.clone().map(move |ci| (ci, flat))
+            })
+            .collect();
+        leaves.extend(filter.iter().map(|&(ci, ..)| (ci, true)));
+        self.check_readable(leaves.iter().map(|&(ci, _)| ci))?;
+        // Checksums cover whole chunks
+        let by_page = opts.verify_checksums == ChecksumMode::Ignore;
+        let (mut gaps, mut index_gaps): (Vec<Range<u64>>, Vec<Range<u64>>) = (Vec::new(), Vec::new());
+        let mut index_gap = |offset: Option<i64>, len: Option<i32>| {
+            if let (Some(start), Some(len)) = (offset.and_then(|o| u64::try_from(o).ok()), len.and_then(|l| u64::try_from(l).ok())) {
+                index_gaps.extend(self.bytes.missing(start, start + len));
+            }
+        };
+        let (mut skip, mut remaining) = (opts.offset, opts.max_rows);
+        for ri in self.select_row_groups(&opts)? {
+            let rg = &self.metadata.row_groups[ri];
+            // Same row groups and rows as `read_columns` touches. With a filter,
+            // which rows match isn't known yet: every row its statistics allow.
+            let rows = if !filter.is_empty() || opts.where_fn.is_some() {
+                let span = self.filter_span(ri, &filter);
+                if span.is_empty() {
+                    continue;
+                }
+                span
+            } else {
+                if remaining == 0 { break; }
+                if rg.num_rows() <= skip {
+                    skip -= rg.num_rows();
+                    continue;
+                }
+                let rows = skip..rg.num_rows().min(skip.saturating_add(remaining));
+                remaining = remaining.saturating_sub(rg.num_rows() - skip);
+                skip = 0;
+                rows
+            };
+            for &(ci, flat) in &leaves {
+                let chunk = &rg.columns()[ci];
+                let cc = chunk.column_chunk();
+                if flat && by_page {
+                    index_gap(cc.offset_index_offset, cc.offset_index_length);
+                }
+                if filter.iter().any(|&(fci, ..)| fci == ci) {
+                    index_gap(cc.column_index_offset, cc.column_index_length);
+                }
+                match (flat && by_page).then(|| page_selection(&self.bytes, chunk, rg.num_rows(), rows.clone())).flatten() {
+                    Some((pages, _)) => pages.iter().for_each(|r| gaps.extend(self.bytes.missing(r.start, r.end))),
+                    None => {
+                        let (start, len) = chunk.byte_range();
+                        gaps.extend(self.bytes.missing(start, start + len));
+                    }
+                }
+            }
+        }
+        if !index_gaps.is_empty() {
+            gaps = index_gaps;
+        }
+        gaps.sort_by_key(|r| r.start);
+        let out = Array::new();
+        let mut merged: Option<Range<u64>> = None;
+        for gap in gaps {
+            match &mut merged {
+                Some(m) if gap.start <= m.end => m.end = m.end.max(gap.end),
+                _ => {
+                    if let Some(m) = merged.replace(gap) {
+                        out.push(&range_js(m));
+                    }
+                }
+            }
+        }
+        if let Some(m) = merged {
+            out.push(&range_js(m));
+        }
+        Ok(out)
+    }
+
+    /// Add file bytes read at `offset` (e.g. a range from `missingRanges`)
+    #[wasm_bindgen(js_name = "addRange")]
+    pub fn add_range(&mut self, offset: f64, data: &Uint8Array) -> Result<(), JsValue> {
+        let offset = offset as u64;
+        if offset + data.length() as u64 > self.bytes.len {
+            return Err(JsValue::from_str(&format!(
+                "range {}..{} is past the end of the file ({})",
+                offset,
+                offset + data.length() as u64,
+                self.bytes.len
+            )));
+        }
+        self.bytes.insert(offset, data.to_vec());
+        Ok(())
+    }
+
+    /// Total rows in the file
+    #[wasm_bindgen(getter, js_name = "numRows")]
+    pub fn num_rows(&self) -> f64 {
+        self.metadata.num_rows as f64
+    }
+
+    /// Row groups in the file
+    #[wasm_bindgen(getter, js_name = "numRowGroups")]
+    pub fn num_row_groups(&self) -> usize {
+        self.metadata.row_groups.len()
+    }
+
+    /// Footer key-value metadata
+    pub fn metadata(&self) -> Result<JsValue, JsValue> {
+        key_value_object(&self.metadata)
+    }
+
+    /// Schema entries (with statistics), as in `readParquet`'s result. Options
+    /// that change value types (`timestamps`, `decimals`, ...) apply to the
+    /// statistics; `columns` selects entries.
+    pub fn schema(&self, options: &JsValue) -> Result<JsValue, JsValue> {
+        let opts = ReadOptions::from_js(options, 500)?;
+        let selected = self.select(&opts)?;
+        Ok(self.schema_array(&selected, &opts)?.into())
+    }
+
+    /// Footer summary, as returned by `readParquetMetadata`. No pages are read.
+    pub fn info(&self, options: &JsValue) -> Result<JsValue, JsValue> {
+        let opts = ReadOptions::from_js(options, 500)?;
+        let selected = self.select(&opts)?;
+        let all: Vec<usize> = (0..self.metadata.row_groups.len()).collect();
+
+        let result = Object::new();
+        Reflect::set(&result, &"numRows".into(), &JsValue::from_f64(self.metadata.num_rows as f64))?;
+        Reflect::set(&result, &"version".into(), &JsValue::from_f64(self.metadata.version as f64))?;
+        let created_by = self.metadata.created_by.as_deref().map_or(JsValue::NULL, JsValue::from_str);
+        Reflect::set(&result, &"createdBy".into(), &created_by)?;
+        let schema = self.schema_array(&selected, &opts)?;
+        Reflect::set(&result, &"schema".into(), &schema)?;
+        let row_groups = self.row_groups_array(&all)?;
+        Reflect::set(&result, &"rowGroups".into(), &row_groups)?;
+        Reflect::set(&result, &"metadata".into(), &key_value_object(&self.metadata)?)?;
+        Ok(result.into())
+    }
+
+    /// Every page of one leaf column (its dotted path, or the name of a
+    /// top-level column with a single leaf), as `inspectPages` returns them
+    #[wasm_bindgen(js_name = "inspectPages")]
+    pub fn inspect_pages(&self, column: &str) -> Result<Array, JsValue> {
+        let ci = self.leaf_index(column)?;
+        self.check_readable([ci])?;
+        let out = Array::new();
+        for (ri, rg) in self.metadata.row_groups.iter().enumerate() {
+            let chunk = &rg.columns()[ci];
+            inspect_chunk(self.bytes.chunk(chunk)?, chunk.byte_range().0, ri, &out)?;
+        }
+        Ok(out)
+    }
+
+    /// Whether a leaf column (as for `inspectPages`) may hold a value equal to
+    /// `value`, decoded with default options: false only when the bloom filter
+    /// of every row group rules it out. Chunks without a (loaded) filter, and
+    /// values the filter can't be asked about, count as maybe.
+    #[wasm_bindgen(js_name = "mightContain")]
+    pub fn might_contain(&self, column: &str, value: &JsValue) -> Result<JsValue, JsValue> {
+        let ci = self.leaf_index(column)?;
+        self.check_readable([ci])?;
+        let prim = &self.metadata.schema_descr.columns()[ci].descriptor.primitive_type;
+        let conv = Convert::for_column(prim, &ReadOptions::from_js(&JsValue::UNDEFINED, 0)?);
+        let found = self
+            .metadata
+            .row_groups
+            .iter()
+            .any(|rg| bloom_may_contain(&self.bytes, &rg.columns()[ci], prim, conv, value));
+        Ok(JsValue::from_bool(found))
+    }
+
+    /// Same result as `readParquet(bytes, options)`, without re-parsing the footer
+    pub fn read(&self, options: &JsValue) -> Result<JsValue, JsValue> {
+        let opts = ReadOptions::from_js(options, 500)?;
+        let selected = self.select(&opts)?;
+        let row_groups = self.select_row_groups(&opts)?;
+        let warnings = Array::new();
+        let columns = self.read_columns(&selected, &row_groups, &opts, &warnings)?;
+
+        let result = Object::new();
+        let schema = self.schema_array(&selected, &opts)?;
+        Reflect::set(&result, &"schema".into(), &schema)?;
+        Reflect::set(&result, &"data".into(), &data_js(columns, opts.rows)?)?;
+        Reflect::set(&result, &"metadata".into(), &key_value_object(&self.metadata)?)?;
+        let all: Vec<usize> = (0..self.metadata.row_groups.len()).collect();
+        let layout = self.row_groups_array(&all)?;
+        Reflect::set(&result, &"rowGroups".into(), &layout)?;
+        if warnings.length() > 0 {
+            Reflect::set(&result, &"warnings".into(), &warnings)?;
+        }
+        Reflect::set(&result, &"numRows".into(), &JsValue::from_f64(self.metadata.num_rows as f64))?;
+        Ok(result.into())
+    }
+
+    /// The `data` of one whole row group (`maxRows` and `offset` count within it)
+    #[wasm_bindgen(js_name = "readRowGroup")]
+    pub fn read_row_group(&self, index: usize, options: &JsValue) -> Result<JsValue, JsValue> {
+        let mut opts = ReadOptions::from_js(options, usize::MAX)?;
+        opts.row_groups = Some(vec![index]);
+        let selected = self.select(&opts)?;
+        let row_groups = self.select_row_groups(&opts)?;
+        let columns = self.read_columns(&selected, &row_groups, &opts, &Array::new())?;
+        data_js(columns, opts.rows)
+    }
+
+    /// Every value of one column (across `rowGroups`, default all)
+    #[wasm_bindgen(js_name = "readColumn")]
+    pub fn read_column(&self, name: &str, options: &JsValue) -> Result<JsValue, JsValue> {
+        let mut opts = ReadOptions::from_js(options, usize::MAX)?;
+        opts.columns = Some(vec![name.to_string()]);
+        opts.renames = None;
+        opts.rows = false;
+        let selected = self.select(&opts)?;
+        let row_groups = self.select_row_groups(&opts)?;
+        let mut columns = self.read_columns(&selected, &row_groups, &opts, &Array::new())?;
+        Ok(columns.pop().map_or(JsValue::UNDEFINED, |(_, buf)| buf.into_js()))
+    }
+}
+
+impl ParquetFile {
+    fn from_bytes(bytes: Vec<u8>, options: &JsValue) -> Result<ParquetFile, JsValue> {
+        let keys = parse_decryption(options)?;
+        let (bytes, metadata, locked) = open_file(bytes, true, keys.as_ref()).map_err(|e| JsValue::from_str(&format!("metadata: {}", e)))?;
+        ParquetFile::with_metadata(FileBytes::whole(bytes), metadata, locked)
+    }
+
+    fn with_metadata(bytes: FileBytes, metadata: FileMetaData, locked: Locked) -> Result<ParquetFile, JsValue> {
+        let mut next_leaf = 0;
+        let fields = metadata
+            .schema_descr
+            .fields()
+            .iter()
+            .map(|t| {
+                let name = t.get_field_info().name.clone();
+                let first = next_leaf;
+                let mut leaf = 0;
+                let node = Node::build(t, 0, 0, &mut leaf)
+                    .map_err(|e| JsValue::from_str(&format!("column '{}': {}", name, e)))?;
+                next_leaf += leaf;
+                Ok((name, node, first..next_leaf))
+            })
+            .collect::<Result<Vec<_>, JsValue>>()?;
+        let geometry = geometry_columns(&metadata, &fields);
+        Ok(ParquetFile { bytes, metadata, fields, geometry, locked })
+    }
+
+    /// Indices into `fields` of the columns to decode, in output order
+    fn select(&self, opts: &ReadOptions) -> Result<Vec<usize>, JsValue> {
+        match &opts.columns {
+            Some(names) => names
+                .iter()
+                .map(|n| {
+                    self.fields
+                        .iter()
+                        .position(|(name, ..)| name == n)
+                        .ok_or_else(|| JsValue::from_str(&format!("unknown column '{}'", n)))
+                })
+                .collect(),
+            None => Ok((0..self.fields.len()).collect()),
+        }
+    }
+
+    /// Fail with the first of `leaves` whose key wasn't given
+    fn check_readable(&self, leaves: impl IntoIterator<Item = usize>) -> Result<(), JsValue> {
+        for leaf in leaves {
+            if let Some((_, error)) = self.locked.iter().find(|(l, _)| *l == leaf) {
+                return Err(JsValue::from_str(error));
+            }
+        }
+        Ok(())
+    }
+
+    /// Index of a leaf column given its dotted path, or the name of a
+    /// top-level column with a single leaf
+    fn leaf_index(&self, column: &str) -> Result<usize, JsValue> {
+        self.metadata
+            .schema_descr
+            .columns()
+            .iter()
+            .position(|c| c.path_in_schema.join(".") == column)
+            .or_else(|| match self.fields.iter().find(|(name, ..)| name == column) {
+                Some((_, _, leaves)) if leaves.len() == 1 => Some(leaves.start),
+                _ => None,
+            })
+            .ok_or_else(|| JsValue::from_str(&format!("unknown column '{}'", column)))
+    }
+
+    fn select_row_groups(&self, opts: &ReadOptions) -> Result<Vec<usize>, JsValue> {
+        let num = self.metadata.row_groups.len();
+        match &opts.row_groups {
+            Some(indices) => {
+                if let Some(&bad) = indices.iter().find(|&&i| i >= num) {
+                    return Err(JsValue::from_str(&format!("row group {} out of range (file has {})", bad, num)));
+                }
+                Ok(indices.clone())
+            }
+            None => Ok((0..num).collect()),
+        }
+    }
+
+    /// Layout of the row groups numbered in `row_groups`: sizes, and each
+    /// column chunk's codec, encodings and position in the file
+    fn row_groups_array(&self, row_groups: &[usize]) -> Result<Array, JsValue> {
+        let out = Array::new();
+        for &ri in row_groups {
+            let rg = &self.metadata.row_groups[ri];
+            let columns = Array::new();
+            for chunk in rg.columns() {
+                let (offset, _) = chunk.byte_range();
+                let encodings: Array = chunk.column_encoding().iter().map(|&e| JsValue::from_str(encoding_label(e))).collect();
+                let col = Object::new();
+                Reflect::set(&col, &"path".into(), &JsValue::from_str(&chunk.descriptor().path_in_schema.join(".")))?;
+                Reflect::set(&col, &"codec".into(), &JsValue::from_str(codec_label(chunk.compression())))?;
+                Reflect::set(&col, &"numValues".into(), &JsValue::from_f64(chunk.num_values() as f64))?;
+                Reflect::set(&col, &"encodings".into(), &encodings)?;
+                Reflect::set(&col, &"compressedSize".into(), &JsValue::from_f64(chunk.compressed_size() as f64))?;
+                Reflect::set(&col, &"uncompressedSize".into(), &JsValue::from_f64(chunk.uncompressed_size() as f64))?;
+                Reflect::set(&col, &"offset".into(), &JsValue::from_f64(offset as f64))?;
+                let bloom = chunk.metadata().bloom_filter_offset.map_or(JsValue::NULL, |o| JsValue::from_f64(o as f64));
+                Reflect::set(&col, &"bloomFilterOffset".into(), &bloom)?;
+                columns.push(&col);
+            }
+            let obj = Object::new();
+            Reflect::set(&obj, &"numRows".into(), &JsValue::from_f64(rg.num_rows() as f64))?;
+            Reflect::set(&obj, &"totalByteSize".into(), &JsValue::from_f64(rg.total_byte_size() as f64))?;
+            Reflect::set(&obj, &"compressedSize".into(), &JsValue::from_f64(rg.compressed_size() as f64))?;
+            Reflect::set(&obj, &"columns".into(), &columns)?;
+            out.push(&obj);
+        }
+        Ok(out)
+    }
+
+    fn schema_array(&self, selected: &[usize], opts: &ReadOptions) -> Result<Array, JsValue> {
+        let schema_arr = Array::new();
+        for (k, &fi) in selected.iter().enumerate() {
+            let (name, node, cols) = &self.fields[fi];
+            let obj = Object::new();
+            Reflect::set(&obj, &"name".into(), &JsValue::from_str(opts.output_name(k, name)))?;
+            describe_node(&obj, node, 0, cols.start, &self.metadata, opts)?;
+            if self.geometry.contains(&cols.start) {
+                Reflect::set(&obj, &"type".into(), &"geometry".into())?;
+            }
+            if let ParquetType::GroupType { field_info: FieldInfo { id: Some(id), .. }, .. } = &self.metadata.schema_descr.fields()[fi] {
+                Reflect::set(&obj, &"id".into(), &JsValue::from_f64(*id as f64))?;
+            }
+            schema_arr.push(&obj);
+        }
+        Ok(schema_arr)
+    }
+
+    /// Decode the `selected` fields over `row_groups`, honouring `maxRows` and `offset`
+    fn read_columns(
+        &self,
+        selected: &[usize],
+        row_groups: &[usize],
+        opts: &ReadOptions,
+        warnings: &Array,
+    ) -> Result<Vec<(JsValue, ColumnBuf)>, JsValue> {
+        self.check_readable(selected.iter().flat_map(|&fi| self.fields[fi].2.clone()))?;
+        self.check_readable(self.filter_columns(opts)?.iter().map(|&(ci, ..)| ci))?;
+        if !opts.filter.is_empty() || opts.where_fn.is_some() {
+            return self.read_filtered(selected, row_groups, opts, warnings);
+        }
+        let (file, metadata) = (&self.bytes, &self.metadata);
+        let col_descriptors = metadata.schema_descr.columns();
+        let limit = opts.max_rows;
+        let mut columns: Vec<(JsValue, ColumnBuf)> = Vec::with_capacity(selected.len());
+
+        for (k, &fi) in selected.iter().enumerate() {
+            let (name, node, cols) = &self.fields[fi];
+            let out_name = JsValue::from_str(opts.output_name(k, name));
+            if !matches!(node, Node::Leaf { .. }) {
+                let buf = read_nested(file, metadata, name, node, cols.clone(), row_groups, opts.offset, limit, opts, warnings)?;
+                columns.push((out_name, buf));
+                continue;
+            }
+            let ci = cols.start;
+            let desc = &col_descriptors[ci];
+            let (mut buf, conv) = leaf_buf(desc, opts, self.geometry.contains(&ci));
+            let mut total = 0usize;
+            let mut skip = opts.offset;
+
+            for &ri in row_groups {
+                if total >= limit { break; }
+                let rg = &metadata.row_groups[ri];
+                // Whole row groups inside the offset are never touched
+                if rg.num_rows() <= skip {
+                    skip -= rg.num_rows();
+                    continue;
+                }
+                let chunk = &rg.columns()[ci];
+                check_chunk(file, chunk, name, ri, opts, warnings)?;
+                total += read_rows(file, chunk, desc, &mut buf, skip, limit - total)?;
+                skip = 0;
+            }
+
+            let buf = buf.convert(conv).map_err(|e| JsValue::from_str(&format!("column '{}' {}", name, e)))?;
+            columns.push((out_name, buf));
+        }
+        Ok(columns)
+    }
+
+    /// The leaf column each `filter` condition tests, with the conversion its
+    /// values get
+    fn filter_columns<'a>(&self, opts: &'a ReadOptions) -> Result<Vec<(usize, Convert, &'a Predicate)>, JsValue> {
+        opts.filter
+            .iter()
+            .map(|pred| {
+                let (_, node, cols) = self
+                    .fields
+                    .iter()
+                    .find(|(name, ..)| *name == pred.column)
+                    .ok_or_else(|| JsValue::from_str(&format!("unknown filter column '{}'", pred.column)))?;
+                if !matches!(node, Node::Leaf { .. }) {
+                    return Err(JsValue::from_str(&format!("filter column '{}' is not a primitive column", pred.column)));
+                }
+                let prim = &self.metadata.schema_descr.columns()[cols.start].descriptor.primitive_type;
+                Ok((cols.start, Convert::for_column(prim, opts), pred))
+            })
+            .collect()
+    }
+
+    /// Rows of row group `ri` that may pass `filter`, by column-chunk
+    /// statistics and page indexes; empty when the row group can be skipped
+    fn filter_span(&self, ri: usize, filter: &[(usize, Convert, &Predicate)]) -> Range<usize> {
+        let rg = &self.metadata.row_groups[ri];
+        let mut span = 0..rg.num_rows();
+        for &(ci, conv, pred) in filter {
+            let chunk = &rg.columns()[ci];
+            let prim = &self.metadata.schema_descr.columns()[ci].descriptor.primitive_type;
+            if !chunk_may_match(pred, chunk, prim, conv) {
+                return 0..0;
+            }
+            let equality = matches!(pred.op, FilterOp::Eq | FilterOp::In);
+            if equality && !pred.values.iter().any(|v| bloom_may_contain(&self.bytes, chunk, prim, conv, v)) {
+                return 0..0;
+            }
+            if let Some(pages) = page_span(&self.bytes, pred, chunk, prim, conv, rg.num_rows()) {
+                span = span.start.max(pages.start)..span.end.min(pages.end);
+            }
+            if span.is_empty() {
+                return 0..0;
+            }
+        }
+        span
+    }
+
+    /// Rows `span` of field `fi` in row group `ri`, unconverted, in a buffer
+    /// of the same kind as `like`
+    fn read_span(
+        &self,
+        fi: usize,
+        ri: usize,
+        like: &ColumnBuf,
+        span: Range<usize>,
+        opts: &ReadOptions,
+        warnings: &Array,
+    ) -> Result<ColumnBuf, JsValue> {
+        let (file, metadata) = (&self.bytes, &self.metadata);
+        let (name, node, cols) = &self.fields[fi];
+        if !matches!(node, Node::Leaf { .. }) {
+            return read_nested(file, metadata, name, node, cols.clone(), &[ri], span.start, span.len(), opts, warnings);
+        }
+        let ci = cols.start;
+        let chunk = &metadata.row_groups[ri].columns()[ci];
+        let mut values = like.empty_like();
+        check_chunk(file, chunk, name, ri, opts, warnings)?;
+        let desc = &metadata.schema_descr.columns()[ci];
+        read_rows(file, chunk, desc, &mut values, span.start, span.len())?;
+        Ok(values)
+    }
+
+    /// `read_columns` with a `filter`. Row groups and pages whose statistics
+    /// rule out a match are never decoded; in the rest, the filter columns are
+    /// decoded first and only matching rows of the selected columns are kept.
+    /// With `where`, the selected columns of each row group are decoded whole
+    /// and the callback sees rows that passed `filter`, as they'd be returned.
+    /// `offset` and `maxRows` count matching rows.
+    fn read_filtered(
+        &self,
+        selected: &[usize],
+        row_groups: &[usize],
+        opts: &ReadOptions,
+        warnings: &Array,
+    ) -> Result<Vec<(JsValue, ColumnBuf)>, JsValue> {
+        let (file, metadata) = (&self.bytes, &self.metadata);
+        let col_descriptors = metadata.schema_descr.columns();
+        let filter = self.filter_columns(opts)?;
+        let mut out: Vec<(ColumnBuf, Convert)> = selected
+            .iter()
+            .map(|&fi| match &self.fields[fi] {
+                (_, Node::Leaf { .. }, cols) => leaf_buf(&col_descriptors[cols.start], opts, self.geometry.contains(&cols.start)),
+                _ => (ColumnBuf::Js(Array::new()), Convert::None),
+            })
+            .collect();
+        let (mut skip, mut remaining) = (opts.offset, opts.max_rows);
+        let names: Vec<JsValue> =
+            selected.iter().enumerate().map(|(k, &fi)| JsValue::from_str(opts.output_name(k, &self.fields[fi].0))).collect();
+
+        for &ri in row_groups {
+            if remaining == 0 { break; }
+            let span = self.filter_span(ri, &filter);
+            if span.is_empty() {
+                continue;
+            }
+            let rg = &metadata.row_groups[ri];
+            let mut pass = vec![true; span.len()];
+            for &(ci, conv, pred) in &filter {
+                let desc = &col_descriptors[ci];
+                let prim = &desc.descriptor.primitive_type;
+                let bigint = opts.int64_bigint || matches!(conv, Convert::Decimal(..) | Convert::UInt64(_));
+                let mut values = ColumnBuf::for_column(prim.physical_type, false, bigint, is_text(prim));
+                let chunk = &rg.columns()[ci];
+                check_chunk(file, chunk, &pred.column, ri, opts, warnings)?;
+                read_rows(file, chunk, desc, &mut values, span.start, span.len())?;
+                let values = values
+                    .convert(conv)
+                    .map_err(|e| JsValue::from_str(&format!("column '{}' {}", pred.column, e)))?;
+                for (i, ok) in pass.iter_mut().enumerate() {
+                    *ok = *ok && pred.matches(&values.get(i));
+                }
+            }
+
+            // Raw selected columns over the whole span, when `where` needs them
+            let mut decoded = None;
+            if let Some(where_fn) = &opts.where_fn {
+                if !pass.contains(&true) {
+                    continue;
+                }
+                let columns = selected
+                    .iter()
+                    .zip(&out)
+                    .map(|(&fi, (buf, _))| self.read_span(fi, ri, buf, span.clone(), opts, warnings))
+                    .collect::<Result<Vec<_>, JsValue>>()?;
+                // Convert just the candidate rows for the callback
+                let candidates: Vec<usize> = (0..span.len()).filter(|&i| pass[i]).collect();
+                let mut converted = Vec::with_capacity(columns.len());
+                for ((values, &(_, conv)), &fi) in columns.iter().zip(&out).zip(selected) {
+                    let mut buf = values.empty_like();
+                    for &i in &candidates {
+                        buf.push_from(values, i);
+                    }
+                    let buf = buf
+                        .convert(conv)
+                        .map_err(|e| JsValue::from_str(&format!("column '{}' {}", self.fields[fi].0, e)))?;
+                    converted.push(buf);
+                }
+                let first_row: usize = metadata.row_groups[..ri].iter().map(|rg| rg.num_rows()).sum::<usize>() + span.start;
+                for (k, &i) in candidates.iter().enumerate() {
+                    let row = Object::new();
+                    for (name, buf) in names.iter().zip(&converted) {
+                        Reflect::set(&row, name, &buf.get(k))?;
+                    }
+                    let index = JsValue::from_f64((first_row + i) as f64);
+                    pass[i] = where_fn.call2(&JsValue::UNDEFINED, &row, &index)?.is_truthy();
+                }
+                decoded = Some(columns);
+            }
+
+            // Offsets within the span of the matching rows to keep
+            let mut keep = Vec::new();
+            for (i, ok) in pass.into_iter().enumerate() {
+                if !ok { continue; }
+                if skip > 0 {
+                    skip -= 1;
+                } else if keep.len() < remaining {
+                    keep.push(i);
+                }
+            }
+            let Some(&last) = keep.last() else { continue };
+            remaining -= keep.len();
+
+            for (k, (&fi, (buf, _))) in selected.iter().zip(out.iter_mut()).enumerate() {
+                // Rows past the last kept one are never decoded
+                let values = match &decoded {
+                    Some(columns) => &columns[k],
+                    None => &self.read_span(fi, ri, buf, span.start..span.start + last + 1, opts, warnings)?,
+                };
+                for &i in &keep {
+                    buf.push_from(values, i);
+                }
+            }
+        }
+
+        selected
+            .iter()
+            .zip(out)
+            .zip(names)
+            .map(|((&fi, (buf, conv)), out_name)| {
+                let name = &self.fields[fi].0;
+                let buf = buf.convert(conv).map_err(|e| JsValue::from_str(&format!("column '{}' {}", name, e)))?;
+                Ok((out_name, buf))
+            })
+            .collect()
+    }
+}
+
+/// `start..end` as a `[start, end]` array
+fn range_js(r: Range<u64>) -> JsValue {
+    Array::of2(&JsValue::from_f64(r.start as f64), &JsValue::from_f64(r.end as f64)).into()
+}
+
+/// Column buffers as `data`: `{ col: [...] }`, or row objects with `rows`
+fn data_js(columns: Vec<(JsValue, ColumnBuf)>, rows: bool) -> Result<JsValue, JsValue> {
+    if rows {
+        // Pivot to [{ col: value, ... }, ...]
+        let num = columns.first().map_or(0, |(_, buf)| buf.len());
+        let out = Array::new_with_length(num as u32);
+        for i in 0..num {
+            let row = Object::new();
+            for (name, buf) in &columns {
+                Reflect::set(&row, name, &buf.get(i))?;
+            }
+            out.set(i as u32, row.into());
+        }
+        Ok(out.into())
+    } else {
+        let data_obj = Object::new();
+        for (name, buf) in columns {
+            Reflect::set(&data_obj, &name, &buf.into_js())?;
+        }
+        Ok(data_obj.into())
+    }
+}
+
+/// Read a Parquet file from bytes and return { schema, data, metadata, rowGroups, numRows }.
+///
+/// - `data`: raw Uint8Array of the entire Parquet file
+/// - `options`: row limit as a number (default 500, for preview), or
+///   `{ maxRows, offset, columns, select, rowGroups, format, typedArrays, int64, dates, times, decimals, binary, timestamps, uint64, json, maps, verifyChecksums, filter, where }`
+///
+/// Returns a JS object:
+/// ```js
+/// {
+///   schema: [{ name: "col1", type: "string", optional: true,
+///              statistics: { min: "a", max: "z", nullCount: 1 } }, ...],
+///   data:   { col1: ["a", null], col2: [1, 2], ... },
+///   metadata: { "ARROW:schema": "...", ... },  // footer key-value metadata
+///   rowGroups: [{ numRows: 10000, totalByteSize: 81920, compressedSize: 40960,
+///                 columns: [{ path: "col1", codec: "SNAPPY", numValues: 10000,
+///                             encodings: ["PLAIN", "RLE"], ... }, ...] }, ...],
+///   numRows: 12345   // total rows in file (preview may be fewer)
+/// }
+/// ```
+#[wasm_bindgen(js_name = "readParquet")]
+pub fn read_parquet(data: &Uint8Array, options: &JsValue) -> Result<JsValue, JsValue> {
+    // Validate options before copying the file into wasm memory
+    ReadOptions::from_js(options, 500)?;
+    ParquetFile::new(data, options)?.read(options)
+}
+
+/// Parse just the footer of a Parquet file and return
+/// { numRows, version, createdBy, schema, rowGroups, metadata }.
+///
+/// Only the footer is copied into wasm memory and no pages are decoded, so
+/// this is cheap even for large files. `options` are the `readParquet` options
+/// that affect schema entries (`columns`, and value types of statistics).
+#[wasm_bindgen(js_name = "readParquetMetadata")]
+pub fn read_parquet_metadata(data: &Uint8Array, options: &JsValue) -> Result<JsValue, JsValue> {
+    ReadOptions::from_js(options, 500)?;
+    ParquetFile::from_tail(&footer_tail(data)?, data.length() as f64, options)?.info(options)
+}
+
+/// Column names and types of a Parquet file, nested columns included, in the
+/// shape of `readParquet`'s `schema` but without statistics.
+///
+/// `data` can be the whole file or just its end, as long as that holds the
+/// footer (the last 64 KB is enough for all but very wide files).
+#[wasm_bindgen(js_name = "readParquetSchema")]
+pub fn read_parquet_schema(data: &Uint8Array) -> Result<JsValue, JsValue> {
+    let file = ParquetFile::from_tail(&footer_tail(data)?, data.length() as f64, &JsValue::UNDEFINED)?;
+    let mut opts = ReadOptions::from_js(&JsValue::UNDEFINED, 0)?;
+    opts.statistics = false;
+    let selected = file.select(&opts)?;
+    Ok(file.schema_array(&selected, &opts)?.into())
+}
+
+/// The footer and trailer at the end of `data` (a whole file, or its last
+/// bytes), without copying anything before them
+fn footer_tail(data: &Uint8Array) -> Result<Uint8Array, JsValue> {
+    let len = data.length();
+    let mut trailer = [0u8; 8];
+    if len >= 8 {
+        data.subarray(len - 8, len).copy_to(&mut trailer);
+    }
+    if &trailer[4..] != b"PAR1" && &trailer[4..] != b"PARE" {
+        // Not a parquet trailer: let the footer parser report it
+        return Ok(data.clone());
+    }
+    let footer_len = u32::from_le_bytes(trailer[..4].try_into().unwrap()) as u64 + 8;
+    if footer_len > len as u64 {
+        return Err(JsValue::from_str(&format!(
+            "footer is {} bytes but only the last {} were given; pass the whole file or a longer tail",
+            footer_len, len
+        )));
+    }
+    Ok(data.subarray(len - footer_len as u32, len))
+}
+
+/// Page headers of one column across all row groups, for debugging files from
+/// other writers: `[{ rowGroup, offset, type, version, encoding, numValues,
+/// headerSize, compressedSize, uncompressedSize, crc }]`. `column` is a leaf's
+/// dotted path (as in `rowGroups[i].columns[j].path`) or a top-level column
+/// with a single leaf. Pages are not decompressed.
+#[wasm_bindgen(js_name = "inspectPages")]
+pub fn inspect_pages(data: &Uint8Array, column: &str) -> Result<Array, JsValue> {
+    ParquetFile::new(data, &JsValue::UNDEFINED)?.inspect_pages(column)
+}
+
+/// The footer's thrift FileMetaData, every field of it, as a JSON-able object
+/// for parquet debugging tools: schema elements, row groups with their column
+/// chunks' metadata and offsets, key-value metadata. Names follow
+/// parquet.thrift in camelCase, enums are their names and binary statistics
+/// are hex. Like `readParquetMetadata`, only the footer is copied in.
+#[wasm_bindgen(js_name = "dumpFooter")]
+pub fn dump_footer(data: &Uint8Array) -> Result<JsValue, JsValue> {
+    let tail = footer_tail(data)?.to_vec();
+    let (metadata, float16) = thrift_footer(&tail).map_err(|e| JsValue::from_str(&format!("metadata: {}", e)))?;
+    Ok(footer_dump_js(&metadata, &float16)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet_format_safe::{ColumnChunk, RowGroup};
+
+    fn chunk() -> ColumnChunk {
+        let meta = ColumnMetaData::new(Type::INT32, vec![], vec!["x".into()], CompressionCodec::UNCOMPRESSED, 0, 0, 0, None, 4, None, None, None, None, None);
+        ColumnChunk::new(None, 4, meta, None, None, None, None, None, None)
+    }
+
+    #[test]
+    fn decrypt_file_rejects_chunks_past_the_first_row_group() {
+        let key = AesGcm::new(&[0; 16]).unwrap();
+        let row_groups = vec![RowGroup::new(vec![chunk()], 0, 0, None, None, None, None), RowGroup::new(vec![chunk(), chunk()], 0, 0, None, None, None, None)];
+        let mut metadata = TFileMetaData::new(2, vec![], 0, row_groups, None, None, None, None, None);
+        let decryptor = Decryptor { file_aad: Vec::new(), ctr_pages: false };
+        let err = decrypt_file(b"PARE", &mut metadata, &[Some(&key)], &decryptor).unwrap_err();
+        assert_eq!(err, "row group 1: column chunk 1 has no column in the schema");
+    }
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+    }
+
+    /// Written by GNU gzip, with the file name in the header; two members back to back
+    #[test]
+    fn gunzip_reads_gzip_members() {
+        let member = hex("1f8b080840f7d16a0003706167652e62696e002bc9ccabd42d482c2a2c4d2d5128c1c50100f368b88226000000");
+        let text = b"tiny-parquet tiny-parquet tiny-parquet";
+        let mut out = vec![0; text.len() * 2];
+        decompress_into(Compression::Gzip, &[&member[..], &member[..]].concat(), &mut out).unwrap();
+        assert_eq!(out, [&text[..], &text[..]].concat());
+
+        let mut corrupt = member.clone();
+        corrupt[member.len() - 8] ^= 1;
+        assert_eq!(decompress_into(Compression::Gzip, &corrupt, &mut out[..text.len()]).unwrap_err(), "gzip CRC mismatch");
+    }
+
+    /// Every page of the LZ4 fixtures, in Hadoop framing and as raw blocks
+    #[test]
+    fn decompresses_lz4_fixture_pages() {
+        for name in ["hadoop_lz4_compressed.parquet", "non_hadoop_lz4_compressed.parquet"] {
+            let bytes = std::fs::read(format!("{}/../_test/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)).unwrap();
+            let metadata = read_metadata(&mut Cursor::new(&bytes)).unwrap();
+            for chunk in metadata.row_groups.iter().flat_map(|rg| rg.columns()) {
+                let (start, len) = chunk.byte_range();
+                let mut rest = &bytes[start as usize..(start + len) as usize];
+                let mut values = 0;
+                while !rest.is_empty() {
+                    let (header, body) = next_page(&mut rest).unwrap();
+                    values += data_page_values(&header);
+                    let page = decompress_page(header, body, chunk.compression(), &chunk.descriptor().descriptor);
+                    assert!(matches!(page, Ok(Some(_))), "{}: {:?}", name, page.err());
+                }
+                assert_eq!(values as i64, chunk.num_values(), "{}", name);
+            }
+        }
+    }
+
+    /// Frames written by libzstd 1.5.7, spanning several blocks
+    #[test]
+    fn decompresses_libzstd_frames() {
+        let rows: Vec<u8> = (0..10_000).flat_map(|i| format!("{},user{},{}\n", i, i % 97, i % 13 * 250).into_bytes()).collect();
+        for level in [1, 19] {
+            let frame = std::fs::read(format!("{}/../_test/fixtures/zstd_rows_{}.zst", env!("CARGO_MANIFEST_DIR"), level)).unwrap();
+            let mut out = vec![0; rows.len()];
+            decompress_into(Compression::Zstd, &frame, &mut out).unwrap();
+            assert!(out == rows, "level {}", level);
+            assert!(decompress_into(Compression::Zstd, &frame, &mut out[1..]).is_err());
+        }
+    }
+
+    /// The INT96 `timestamp_col` of alltypes_plain.parquet, written by Impala 1.3.0
+    #[test]
+    fn reads_impala_int96_timestamps() {
+        let bytes = std::fs::read(format!("{}/../_test/fixtures/alltypes_plain.parquet", env!("CARGO_MANIFEST_DIR"))).unwrap();
+        let metadata = read_metadata(&mut Cursor::new(&bytes)).unwrap();
+        let chunk = metadata.row_groups[0].columns().iter().find(|c| c.descriptor().path_in_schema == ["timestamp_col"]).unwrap();
+        assert_eq!(chunk.descriptor().descriptor.primitive_type.physical_type, PhysicalType::Int96);
+        let (start, len) = chunk.byte_range();
+        let mut out = ColumnBuf::F64(Vec::new());
+        let n = read_column_chunk(&bytes[start as usize..(start + len) as usize], chunk.into(), chunk.descriptor(), &mut out, None, 0, usize::MAX);
+        assert_eq!(n.ok(), Some(8));
+        let ColumnBuf::F64(millis) = out else { unreachable!() };
+        // Midnight and one minute past (nanos of day 60e9) on the 1st of March, April, February and January 2009
+        let expected: [i64; 8] = [1_235_865_600_000, 1_235_865_660_000, 1_238_544_000_000, 1_238_544_060_000, 1_233_446_400_000, 1_233_446_460_000, 1_230_768_000_000, 1_230_768_060_000];
+        assert_eq!(millis, expected.map(|ms| ms as f64));
+    }
+
+    #[test]
+    fn int96_millis_before_the_epoch() {
+        let int96 = |nanos: i64, julian_day: u32| -> [u8; 12] { [&nanos.to_le_bytes()[..], &julian_day.to_le_bytes()[..]].concat().try_into().unwrap() };
+        // 1969-12-31T23:59:59.999
+        assert_eq!(int96_millis(int96(86_399_999_000_000, 2_440_587)), -1.0);
+        // 1900-01-01T12:00:00.500
+        assert_eq!(int96_millis(int96(43_200_500_000_000, 2_415_021)), -2_208_945_599_500.0);
+        assert_eq!(int96_millis(int96(0, 2_440_588)), 0.0);
+    }
 }
@@ -1,24 +1,49 @@
-use std::io::Cursor;
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::io::{Read, Write};
+use std::ops::Range;
+use std::rc::Rc;
 
-use js_sys::{Array, Reflect};
+use aes_gcm::{
+    aead::{consts::U12, AeadInPlace, KeyInit},
+    aes::Aes192,
+    Aes128Gcm, Aes256Gcm, AesGcm as Gcm, Nonce,
+};
+use js_sys::{
+    Array, BigInt64Array, BigUint64Array, Date, Float32Array, Float64Array, Int16Array, Int32Array, Int8Array, Object, Reflect, Uint16Array,
+    Uint32Array, Uint8Array, Uint8ClampedArray,
+};
 use parquet2::{
-    compression::CompressionOptions,
+    bloom_filter::{hash_byte, insert as bloom_insert},
+    compression::{BrotliLevel, CompressionOptions, GzipLevel, ZstdLevel},
     encoding::Encoding,
-    metadata::{Descriptor, SchemaDescriptor},
-    page::{CompressedPage, DataPage, DataPageHeader, DataPageHeaderV1, DictPage, Page},
+    metadata::{ColumnDescriptor, Descriptor, KeyValue, SchemaDescriptor},
+    FallibleStreamingIterator,
+    page::{CompressedDataPage, CompressedDictPage, CompressedPage, DataPage, DataPageHeader, DataPageHeaderV1, DataPageHeaderV2, DictPage, Page},
     schema::{
         types::{
-            FieldInfo, ParquetType, PhysicalType, PrimitiveConvertedType, PrimitiveLogicalType,
+            FieldInfo, GroupConvertedType, GroupLogicalType, IntegerType, ParquetType, PhysicalType, PrimitiveConvertedType, PrimitiveLogicalType,
             PrimitiveType, TimeUnit,
         },
         Repetition,
     },
-    write::{Compressor, DynIter, DynStreamingIterator, FileWriter, Version, WriteOptions},
+    statistics::ParquetStatistics,
+    write::{DynIter, DynStreamingIterator, FileWriter, Version, WriteOptions},
+};
+use parquet_format_safe::{
+    thrift::protocol::{ReadThrift, TCompactInputProtocol, TCompactOutputProtocol, TInputProtocol, TType},
+    AesGcmV1, BloomFilterAlgorithm, BloomFilterCompression, BloomFilterHash, BloomFilterHeader, BoundaryOrder, ColumnChunk, ColumnCryptoMetaData, ColumnIndex,
+    EncryptionAlgorithm, EncryptionWithColumnKey, EncryptionWithFooterKey, FileCryptoMetaData, FileMetaData, LogicalType, NullType, OffsetIndex,
+    PageHeader, PageType, RowGroup, SchemaElement, SortingColumn, SplitBlockAlgorithm, Uncompressed, XxHash,
 };
 use wasm_bindgen::prelude::*;
 
 /// Supported column types from JS schema
 enum ColType {
+    /// decimal(precision, scale)
+    Decimal(usize, usize),
+    /// fixed(n): FIXED_LEN_BYTE_ARRAY of n bytes
+    Fixed(usize),
     Str,
     Int32,
     Int64,
@@ -26,154 +51,789 @@ enum ColType {
     Float64,
     Boolean,
     TimestampMillis,
+    TimestampMicros,
+    TimestampNanos,
+    Date,
+    TimeMillis,
+    TimeMicros,
+    Uuid,
+    /// Raw bytes: BYTE_ARRAY without the UTF8 annotation
+    Binary,
+    Int8,
+    Int16,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    /// IEEE half precision in a 2-byte FIXED_LEN_BYTE_ARRAY
+    Float16,
+    /// UTF-8 JSON text in a BYTE_ARRAY
+    Json,
+    /// BSON documents, passed through as raw bytes
+    Bson,
+    /// GeoParquet geometry: WKB in a BYTE_ARRAY, described by the `geo` footer entry
+    Geometry,
 }
 
 impl ColType {
-    fn from_str(s: &str) -> Self {
-        match s {
+    fn from_str(s: &str) -> Result<Self, String> {
+        if let Some(args) = s.strip_prefix("decimal(").and_then(|r| r.strip_suffix(')')) {
+            return parse_decimal_type(args);
+        }
+        if let Some(n) = s.strip_prefix("fixed(").and_then(|r| r.strip_suffix(')')) {
+            return match n.trim().parse() {
+                Ok(n) if n > 0 => Ok(ColType::Fixed(n)),
+                _ => Err(format!("invalid type '{}': expected fixed(n) with n ≥ 1", s)),
+            };
+        }
+        Ok(match s {
             "int32" => ColType::Int32,
             "int64" => ColType::Int64,
             "float32" | "float" => ColType::Float32,
             "float64" | "double" => ColType::Float64,
             "boolean" | "bool" => ColType::Boolean,
             "timestamp" | "timestamp_millis" => ColType::TimestampMillis,
+            "timestamp_micros" => ColType::TimestampMicros,
+            "timestamp_nanos" => ColType::TimestampNanos,
+            "date" => ColType::Date,
+            "time_millis" => ColType::TimeMillis,
+            "time_micros" => ColType::TimeMicros,
+            "uuid" => ColType::Uuid,
+            "binary" => ColType::Binary,
+            "int8" => ColType::Int8,
+            "int16" => ColType::Int16,
+            "uint8" => ColType::UInt8,
+            "uint16" => ColType::UInt16,
+            "uint32" => ColType::UInt32,
+            "uint64" => ColType::UInt64,
+            "float16" => ColType::Float16,
+            "json" => ColType::Json,
+            "bson" => ColType::Bson,
+            "geometry" => ColType::Geometry,
             _ => ColType::Str,
-        }
+        })
     }
 
     fn physical_type(&self) -> PhysicalType {
         match self {
-            ColType::Str => PhysicalType::ByteArray,
-            ColType::Int32 => PhysicalType::Int32,
-            ColType::Int64 | ColType::TimestampMillis => PhysicalType::Int64,
+            ColType::Str | ColType::Binary | ColType::Json | ColType::Bson | ColType::Geometry => PhysicalType::ByteArray,
+            ColType::Int32
+            | ColType::Date
+            | ColType::TimeMillis
+            | ColType::Int8
+            | ColType::Int16
+            | ColType::UInt8
+            | ColType::UInt16
+            | ColType::UInt32 => PhysicalType::Int32,
+            ColType::Int64
+            | ColType::TimestampMillis
+            | ColType::TimestampMicros
+            | ColType::TimestampNanos
+            | ColType::TimeMicros
+            | ColType::UInt64 => PhysicalType::Int64,
             ColType::Float32 => PhysicalType::Float,
             ColType::Float64 => PhysicalType::Double,
             ColType::Boolean => PhysicalType::Boolean,
+            ColType::Decimal(p, _) if *p <= 9 => PhysicalType::Int32,
+            ColType::Decimal(p, _) if *p <= 18 => PhysicalType::Int64,
+            ColType::Decimal(p, _) => PhysicalType::FixedLenByteArray(decimal_bytes(*p)),
+            ColType::Uuid => PhysicalType::FixedLenByteArray(16),
+            ColType::Fixed(n) => PhysicalType::FixedLenByteArray(*n),
+            ColType::Float16 => PhysicalType::FixedLenByteArray(2),
+        }
+    }
+
+    /// Name, min and max of the narrow integer types
+    fn int_range(&self) -> Option<(&'static str, i64, i64)> {
+        match self {
+            ColType::Int8 => Some(("int8", i8::MIN as i64, i8::MAX as i64)),
+            ColType::Int16 => Some(("int16", i16::MIN as i64, i16::MAX as i64)),
+            ColType::UInt8 => Some(("uint8", 0, u8::MAX as i64)),
+            ColType::UInt16 => Some(("uint16", 0, u16::MAX as i64)),
+            ColType::UInt32 => Some(("uint32", 0, u32::MAX as i64)),
+            _ => None,
+        }
+    }
+
+    /// What strict mode takes for a cell of this type (`None`: any value)
+    fn expected(&self) -> Option<&'static str> {
+        match self {
+            ColType::Int32 | ColType::TimeMillis | ColType::Float32 | ColType::Float64 | ColType::Float16 => Some("a number"),
+            ColType::TimestampMillis | ColType::TimestampMicros | ColType::TimestampNanos => {
+                Some("a Date, number or BigInt")
+            }
+            ColType::Int64
+            | ColType::TimeMicros
+            | ColType::Int8
+            | ColType::Int16
+            | ColType::UInt8
+            | ColType::UInt16
+            | ColType::UInt32
+            | ColType::UInt64 => Some("a number or BigInt"),
+            ColType::Date => Some("a Date or day count"),
+            ColType::Boolean => Some("a boolean"),
+            ColType::Str | ColType::Uuid => Some("a string"),
+            ColType::Decimal(..) => Some("a number, BigInt or string"),
+            ColType::Fixed(_) | ColType::Bson => Some("a Uint8Array"),
+            ColType::Binary => Some("a Uint8Array or string"),
+            ColType::Geometry => Some("a GeoJSON geometry or WKB Uint8Array"),
+            ColType::Json => None,
         }
     }
 
-    fn converted_type(&self) -> Option<PrimitiveConvertedType> {
+    /// Whether strict mode takes non-null cell `x` (see `expected`)
+    fn accepts(&self, x: &JsValue) -> bool {
+        let number = x.as_f64().is_some();
         match self {
+            ColType::Int32 | ColType::TimeMillis | ColType::Float32 | ColType::Float64 | ColType::Float16 => number,
+            ColType::Date => number || x.is_instance_of::<Date>(),
+            ColType::TimestampMillis | ColType::TimestampMicros | ColType::TimestampNanos => {
+                number || x.is_bigint() || x.is_instance_of::<Date>()
+            }
+            ColType::Boolean => x.as_bool().is_some(),
+            ColType::Str | ColType::Uuid => x.is_string(),
+            ColType::Decimal(..) => number || x.is_bigint() || x.is_string(),
+            ColType::Fixed(_) | ColType::Bson => x.is_instance_of::<Uint8Array>(),
+            ColType::Binary => x.is_instance_of::<Uint8Array>() || x.is_string(),
+            ColType::Geometry => x.is_object() && !x.is_array(),
+            ColType::Json => true,
+            _ => number || x.is_bigint(),
+        }
+    }
+
+    /// Unit of timestamp and time-of-day columns
+    fn time_unit(&self) -> Option<TimeUnit> {
+        match self {
+            ColType::TimestampMillis | ColType::TimeMillis => Some(TimeUnit::Milliseconds),
+            ColType::TimestampMicros | ColType::TimeMicros => Some(TimeUnit::Microseconds),
+            ColType::TimestampNanos => Some(TimeUnit::Nanoseconds),
+            _ => None,
+        }
+    }
+
+    /// `utc` is isAdjustedToUTC for timestamp/time columns. The legacy
+    /// converted types imply UTC, so local (naive) columns go without one.
+    fn converted_type(&self, utc: bool) -> Option<PrimitiveConvertedType> {
+        match self {
+            _ if !utc && self.time_unit().is_some() => None,
             ColType::Str => Some(PrimitiveConvertedType::Utf8),
+            ColType::Json => Some(PrimitiveConvertedType::Json),
+            ColType::Bson => Some(PrimitiveConvertedType::Bson),
             ColType::TimestampMillis => Some(PrimitiveConvertedType::TimestampMillis),
+            ColType::TimestampMicros => Some(PrimitiveConvertedType::TimestampMicros),
+            ColType::Date => Some(PrimitiveConvertedType::Date),
+            ColType::TimeMillis => Some(PrimitiveConvertedType::TimeMillis),
+            ColType::TimeMicros => Some(PrimitiveConvertedType::TimeMicros),
+            ColType::Decimal(p, s) => Some(PrimitiveConvertedType::Decimal(*p, *s)),
+            ColType::Int8 => Some(PrimitiveConvertedType::Int8),
+            ColType::Int16 => Some(PrimitiveConvertedType::Int16),
+            ColType::UInt8 => Some(PrimitiveConvertedType::Uint8),
+            ColType::UInt16 => Some(PrimitiveConvertedType::Uint16),
+            ColType::UInt32 => Some(PrimitiveConvertedType::Uint32),
+            ColType::UInt64 => Some(PrimitiveConvertedType::Uint64),
             _ => None,
         }
     }
 
-    fn logical_type(&self) -> Option<PrimitiveLogicalType> {
+    fn logical_type(&self, utc: bool) -> Option<PrimitiveLogicalType> {
         match self {
-            ColType::TimestampMillis => Some(PrimitiveLogicalType::Timestamp {
-                unit: TimeUnit::Milliseconds,
-                is_adjusted_to_utc: true,
-            }),
+            ColType::TimestampMillis | ColType::TimestampMicros | ColType::TimestampNanos => {
+                Some(PrimitiveLogicalType::Timestamp { unit: self.time_unit()?, is_adjusted_to_utc: utc })
+            }
+            ColType::TimeMillis | ColType::TimeMicros => {
+                Some(PrimitiveLogicalType::Time { unit: self.time_unit()?, is_adjusted_to_utc: utc })
+            }
+            ColType::Date => Some(PrimitiveLogicalType::Date),
+            ColType::Decimal(p, s) => Some(PrimitiveLogicalType::Decimal(*p, *s)),
+            ColType::Uuid => Some(PrimitiveLogicalType::Uuid),
+            ColType::Json => Some(PrimitiveLogicalType::Json),
+            ColType::Bson => Some(PrimitiveLogicalType::Bson),
+            ColType::Int8 => Some(PrimitiveLogicalType::Integer(IntegerType::Int8)),
+            ColType::Int16 => Some(PrimitiveLogicalType::Integer(IntegerType::Int16)),
+            ColType::UInt8 => Some(PrimitiveLogicalType::Integer(IntegerType::UInt8)),
+            ColType::UInt16 => Some(PrimitiveLogicalType::Integer(IntegerType::UInt16)),
+            ColType::UInt32 => Some(PrimitiveLogicalType::Integer(IntegerType::UInt32)),
+            ColType::UInt64 => Some(PrimitiveLogicalType::Integer(IntegerType::UInt64)),
             _ => None,
         }
     }
 }
 
-fn plain_header(n: usize) -> DataPageHeader {
-    DataPageHeader::V1(DataPageHeaderV1 {
-        num_values: n as i32,
-        encoding: Encoding::Plain.into(),
-        definition_level_encoding: Encoding::Plain.into(),
-        repetition_level_encoding: Encoding::Plain.into(),
-        statistics: None,
-    })
+/// Largest decimal precision (unscaled values are held in an i128)
+const MAX_DECIMAL_PRECISION: usize = 38;
+
+/// Parse the "p,s" of "decimal(p,s)"
+fn parse_decimal_type(args: &str) -> Result<ColType, String> {
+    let bad = || format!("invalid type 'decimal({})': expected decimal(precision, scale)", args);
+    let (p, s) = args.split_once(',').ok_or_else(bad)?;
+    let p: usize = p.trim().parse().map_err(|_| bad())?;
+    let s: usize = s.trim().parse().map_err(|_| bad())?;
+    if p == 0 || p > MAX_DECIMAL_PRECISION || s > p {
+        return Err(format!(
+            "invalid type 'decimal({})': precision must be 1-{} and scale at most the precision",
+            args, MAX_DECIMAL_PRECISION
+        ));
+    }
+    Ok(ColType::Decimal(p, s))
 }
 
-fn dict_header(n: usize) -> DataPageHeader {
-    DataPageHeader::V1(DataPageHeaderV1 {
-        num_values: n as i32,
-        encoding: Encoding::RleDictionary.into(),
-        definition_level_encoding: Encoding::Plain.into(),
-        repetition_level_encoding: Encoding::Plain.into(),
-        statistics: None,
-    })
+/// Smallest FIXED_LEN_BYTE_ARRAY width that holds every unscaled value of
+/// the given precision as big-endian two's complement
+fn decimal_bytes(precision: usize) -> usize {
+    let max = 10i128.pow(precision as u32) - 1;
+    (1..16).find(|&n| max < 1i128 << (8 * n - 1)).unwrap_or(16)
+}
+
+/// Build a data page from encoded values plus the page's repetition and
+/// definition levels (if the column has any).
+///
+/// V1 pages prefix the levels (each with a 4-byte length) to the values and
+/// are compressed as a whole; V2 pages keep the bare RLE levels up front and
+/// only the values section is compressed.
+fn data_page(
+    values: Vec<u8>,
+    encoding: Encoding,
+    levels: Levels,
+    stats: Option<ParquetStatistics>,
+    cfg: &WriteConfig,
+    d: &Descriptor,
+) -> Page {
+    let num_rows = levels.num_rows();
+    let num_values = levels.num_entries();
+    let rep = levels.encode_rep();
+    let def = levels.encode_def();
+    let (header, buf) = match cfg.version {
+        Version::V1 => {
+            let header = DataPageHeader::V1(DataPageHeaderV1 {
+                num_values: num_values as i32,
+                encoding: encoding.into(),
+                definition_level_encoding: Encoding::Rle.into(),
+                repetition_level_encoding: Encoding::Rle.into(),
+                statistics: stats,
+            });
+            let mut buf = Vec::with_capacity(8 + rep.as_ref().map_or(0, Vec::len) + def.as_ref().map_or(0, Vec::len) + values.len());
+            for levels in [rep, def].into_iter().flatten() {
+                buf.extend_from_slice(&(levels.len() as u32).to_le_bytes());
+                buf.extend_from_slice(&levels);
+            }
+            buf.extend_from_slice(&values);
+            (header, buf)
+        }
+        Version::V2 => {
+            let (rep, def) = (rep.unwrap_or_default(), def.unwrap_or_default());
+            let header = DataPageHeader::V2(DataPageHeaderV2 {
+                num_values: num_values as i32,
+                num_nulls: (num_values - levels.num_values()) as i32,
+                num_rows: num_rows as i32,
+                encoding: encoding.into(),
+                definition_levels_byte_length: def.len() as i32,
+                repetition_levels_byte_length: rep.len() as i32,
+                is_compressed: Some(cfg.compression.any()),
+                statistics: stats,
+            });
+            let mut buf = rep;
+            buf.extend_from_slice(&def);
+            buf.extend_from_slice(&values);
+            (header, buf)
+        }
+    };
+    Page::Data(DataPage::new(header, buf, d.clone(), Some(num_rows)))
+}
+
+/// Values that can be PLAIN-encoded and dictionary-encoded
+trait PlainValue {
+    fn write_plain(&self, buf: &mut Vec<u8>);
+    /// Bytes `write_plain` appends
+    fn plain_size(&self) -> usize;
+    /// Equality used for dictionary lookups (bitwise for floats, so -0.0 and NaN survive)
+    fn same(&self, other: &Self) -> bool;
+    /// Bytes a bloom filter hashes: the PLAIN encoding, minus any length prefix
+    fn bloom_bytes(&self, buf: &mut Vec<u8>) {
+        self.write_plain(buf);
+    }
+}
+
+macro_rules! impl_plain_int {
+    ($($t:ty),*) => {$(
+        impl PlainValue for $t {
+            fn write_plain(&self, buf: &mut Vec<u8>) { buf.extend_from_slice(&self.to_le_bytes()); }
+            fn plain_size(&self) -> usize { std::mem::size_of::<$t>() }
+            fn same(&self, other: &Self) -> bool { self == other }
+        }
+    )*};
+}
+impl_plain_int!(i32, i64, u32, u64);
+
+macro_rules! impl_plain_float {
+    ($($t:ty),*) => {$(
+        impl PlainValue for $t {
+            fn write_plain(&self, buf: &mut Vec<u8>) { buf.extend_from_slice(&self.to_le_bytes()); }
+            fn plain_size(&self) -> usize { std::mem::size_of::<$t>() }
+            fn same(&self, other: &Self) -> bool { self.to_bits() == other.to_bits() }
+        }
+    )*};
+}
+impl_plain_float!(f32, f64);
+
+impl PlainValue for Vec<u8> {
+    fn write_plain(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&(self.len() as u32).to_le_bytes());
+        buf.extend_from_slice(self);
+    }
+    fn plain_size(&self) -> usize { 4 + self.len() }
+    fn same(&self, other: &Self) -> bool { self == other }
+    fn bloom_bytes(&self, buf: &mut Vec<u8>) { buf.extend_from_slice(self); }
+}
+
+/// Decimal stored as a big-endian two's complement FIXED_LEN_BYTE_ARRAY of `size` bytes
+#[derive(Clone, Copy)]
+struct FixedDecimal {
+    unscaled: i128,
+    size: usize,
+}
+
+impl FixedDecimal {
+    fn be_bytes(&self) -> Vec<u8> {
+        self.unscaled.to_be_bytes()[16 - self.size..].to_vec()
+    }
+}
+
+impl PlainValue for FixedDecimal {
+    fn write_plain(&self, buf: &mut Vec<u8>) { buf.extend_from_slice(&self.be_bytes()); }
+    fn plain_size(&self) -> usize { self.size }
+    fn same(&self, other: &Self) -> bool { self.unscaled == other.unscaled }
+}
+
+/// FIXED_LEN_BYTE_ARRAY value: PLAIN has no length prefix, all values share one width
+#[derive(Clone)]
+struct FixedBytes(Vec<u8>);
+
+impl PlainValue for FixedBytes {
+    fn write_plain(&self, buf: &mut Vec<u8>) { buf.extend_from_slice(&self.0); }
+    fn plain_size(&self) -> usize { self.0.len() }
+    fn same(&self, other: &Self) -> bool { self.0 == other.0 }
 }
 
-fn encode_i32(vals: &[i32], d: &Descriptor) -> Page {
-    let mut b = Vec::with_capacity(vals.len() * 4);
-    for v in vals { b.extend_from_slice(&v.to_le_bytes()); }
-    Page::Data(DataPage::new(plain_header(vals.len()), b, d.clone(), Some(vals.len())))
+/// FLOAT16 value: the IEEE half-precision bits, stored little-endian
+#[derive(Clone, Copy)]
+struct Half(u16);
+
+impl Half {
+    /// Round a Number to the nearest half (ties to even); out-of-range values
+    /// become ±Infinity and tiny ones subnormals or ±0
+    fn from_f64(x: f64) -> Self {
+        let b = x.to_bits();
+        let sign = ((b >> 48) & 0x8000) as u16;
+        let exp = ((b >> 52) & 0x7ff) as i32;
+        let man = b & ((1 << 52) - 1);
+        if exp == 0x7ff {
+            return Half(sign | 0x7c00 | if man != 0 { 0x200 } else { 0 });
+        }
+        let e = exp - 1023 + 15;
+        if e >= 0x1f {
+            return Half(sign | 0x7c00);
+        }
+        // normals keep the top 10 mantissa bits; subnormals shift the implicit bit in
+        let (bits, shift) = match e {
+            _ if e < -10 => return Half(sign),
+            _ if e <= 0 => (man | 1 << 52, (43 - e) as u32),
+            _ => (((e as u64) << 52) | man, 42),
+        };
+        let kept = bits >> shift;
+        let rem = bits & ((1 << shift) - 1);
+        let mid = 1 << (shift - 1);
+        // a carry out of the mantissa correctly bumps the exponent (up to Infinity)
+        let rounded = kept + (rem > mid || rem == mid && kept & 1 == 1) as u64;
+        Half(sign | rounded as u16)
+    }
+
+    fn order_key(&self) -> u16 {
+        if self.0 & 0x8000 != 0 { !self.0 } else { self.0 | 0x8000 }
+    }
 }
 
-fn encode_i64(vals: &[i64], d: &Descriptor) -> Page {
-    let mut b = Vec::with_capacity(vals.len() * 8);
-    for v in vals { b.extend_from_slice(&v.to_le_bytes()); }
-    Page::Data(DataPage::new(plain_header(vals.len()), b, d.clone(), Some(vals.len())))
+impl PlainValue for Half {
+    fn write_plain(&self, buf: &mut Vec<u8>) { buf.extend_from_slice(&self.0.to_le_bytes()); }
+    fn plain_size(&self) -> usize { 2 }
+    fn same(&self, other: &Self) -> bool { self.0 == other.0 }
 }
 
-fn encode_f32(vals: &[f32], d: &Descriptor) -> Page {
-    let mut b = Vec::with_capacity(vals.len() * 4);
-    for v in vals { b.extend_from_slice(&v.to_le_bytes()); }
-    Page::Data(DataPage::new(plain_header(vals.len()), b, d.clone(), Some(vals.len())))
+fn encode_plain<'a, T: PlainValue + 'a>(vals: impl IntoIterator<Item = &'a T>) -> Vec<u8> {
+    let mut b = Vec::new();
+    for v in vals { v.write_plain(&mut b); }
+    b
 }
 
-fn encode_f64(vals: &[f64], d: &Descriptor) -> Page {
-    let mut b = Vec::with_capacity(vals.len() * 8);
-    for v in vals { b.extend_from_slice(&v.to_le_bytes()); }
-    Page::Data(DataPage::new(plain_header(vals.len()), b, d.clone(), Some(vals.len())))
+/// RLE-encoded booleans: a 4-byte length, then the RLE/bit-packed hybrid
+/// with a bit width of 1
+fn encode_bool_rle(vals: &[bool]) -> Vec<u8> {
+    let bits: Vec<u32> = vals.iter().map(|&v| v as u32).collect();
+    let mut buf = vec![0; 4];
+    rle_hybrid_encode(&bits, 1, &mut buf);
+    let len = (buf.len() - 4) as u32;
+    buf[..4].copy_from_slice(&len.to_le_bytes());
+    buf
 }
 
-fn encode_bool(vals: &[bool], d: &Descriptor) -> Page {
-    let mut b = vec![0u8; (vals.len() + 7) / 8];
+fn encode_bool(vals: &[bool]) -> Vec<u8> {
+    let mut b = vec![0u8; vals.len().div_ceil(8)];
     for (i, &v) in vals.iter().enumerate() {
         if v { b[i / 8] |= 1 << (i % 8); }
     }
-    Page::Data(DataPage::new(plain_header(vals.len()), b, d.clone(), Some(vals.len())))
+    b
+}
+
+// ── Delta encoding ──────────────────────────────────────────────────────────
+//
+// DELTA_BINARY_PACKED: a header (block size, miniblocks per block, value count,
+// first value), then blocks of the deltas between consecutive values, each
+// stored as the block's smallest delta plus a bit-packed offset from it, with
+// one bit width per miniblock. Sorted or slowly changing integers (IDs,
+// timestamps) take a few bits per value.
+//
+// Byte arrays build on it: DELTA_LENGTH_BYTE_ARRAY stores all lengths that
+// way, then the bytes back to back; DELTA_BYTE_ARRAY stores how many leading
+// bytes each value shares with the one before, then the rest of each value as
+// DELTA_LENGTH_BYTE_ARRAY. Sorted keys and URLs keep little but their tails.
+
+const DELTA_BLOCK_SIZE: usize = 128;
+const DELTA_MINIBLOCKS: usize = 4;
+
+/// How a column's values are encoded
+#[derive(Clone, Copy, PartialEq)]
+enum ValueEncoding {
+    /// Dictionary when `dictionary` is on and the chunk has few distinct
+    /// values, else PLAIN (booleans: RLE when that's smaller)
+    Default,
+    /// Dictionary whatever `dictionary` says, while the chunk has few
+    /// distinct values
+    Dictionary,
+    /// PLAIN only
+    Plain,
+    /// DELTA_BINARY_PACKED for int32 and int64 columns, DELTA_BYTE_ARRAY for
+    /// byte arrays
+    Delta,
+    /// DELTA_LENGTH_BYTE_ARRAY (byte arrays)
+    DeltaLength,
+    /// BYTE_STREAM_SPLIT (float32 and float64 columns)
+    ByteStreamSplit,
+}
+
+/// Integers DELTA_BINARY_PACKED can store. Deltas wrap around at the physical
+/// width, as readers add them back with wrapping arithmetic.
+trait DeltaValue: Copy {
+    /// Bits of the physical type
+    const BITS: u32;
+    /// The value as its signed physical type, sign-extended
+    fn physical(self) -> i64;
+}
+
+impl DeltaValue for i32 {
+    const BITS: u32 = 32;
+    fn physical(self) -> i64 { self as i64 }
+}
+
+impl DeltaValue for u32 {
+    const BITS: u32 = 32;
+    fn physical(self) -> i64 { self as i32 as i64 }
+}
+
+impl DeltaValue for i64 {
+    const BITS: u32 = 64;
+    fn physical(self) -> i64 { self }
+}
+
+impl DeltaValue for u64 {
+    const BITS: u32 = 64;
+    fn physical(self) -> i64 { self as i64 }
+}
+
+/// Append a zigzag ULEB128-encoded signed integer
+fn push_zigzag(buf: &mut Vec<u8>, v: i64) {
+    push_uleb128(buf, ((v << 1) ^ (v >> 63)) as u64);
+}
+
+fn encode_delta<T: DeltaValue>(vals: &[T]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    push_uleb128(&mut buf, DELTA_BLOCK_SIZE as u64);
+    push_uleb128(&mut buf, DELTA_MINIBLOCKS as u64);
+    push_uleb128(&mut buf, vals.len() as u64);
+    push_zigzag(&mut buf, vals.first().map_or(0, |v| v.physical()));
+
+    let wrap = |x: i64| if T::BITS == 32 { x as i32 as i64 } else { x };
+    let mask = if T::BITS == 32 { u32::MAX as u64 } else { u64::MAX };
+    let deltas: Vec<i64> = vals.windows(2).map(|w| wrap(w[1].physical().wrapping_sub(w[0].physical()))).collect();
+    let per_miniblock = DELTA_BLOCK_SIZE / DELTA_MINIBLOCKS;
+
+    for block in deltas.chunks(DELTA_BLOCK_SIZE) {
+        let min = block.iter().copied().min().unwrap_or(0);
+        push_zigzag(&mut buf, min);
+        let offsets: Vec<u64> = block.iter().map(|&d| d.wrapping_sub(min) as u64 & mask).collect();
+        let miniblocks: Vec<&[u64]> = offsets.chunks(per_miniblock).collect();
+
+        // A width byte for every miniblock, even ones past the last value
+        let widths: Vec<usize> = (0..DELTA_MINIBLOCKS)
+            .map(|m| miniblocks.get(m).map_or(0, |mb| 64 - mb.iter().max().unwrap_or(&0).leading_zeros() as usize))
+            .collect();
+        buf.extend(widths.iter().map(|&w| w as u8));
+
+        // Miniblocks are bit-packed LSB first and padded to full length; the
+        // unused ones after the last value take no bytes at all
+        for (mb, &width) in miniblocks.iter().zip(&widths) {
+            let start = buf.len();
+            buf.resize(start + per_miniblock * width / 8, 0);
+            for (i, &v) in mb.iter().enumerate() {
+                let bit_offset = i * width;
+                let mut val = (v as u128) << (bit_offset % 8);
+                for b in buf[start + bit_offset / 8..].iter_mut().take((bit_offset % 8 + width).div_ceil(8)) {
+                    *b |= val as u8;
+                    val >>= 8;
+                }
+            }
+        }
+    }
+    buf
 }
 
-fn encode_binary(vals: &[Vec<u8>], d: &Descriptor) -> Page {
-    let total: usize = vals.iter().map(|v| 4 + v.len()).sum();
-    let mut b = Vec::with_capacity(total);
+fn encode_delta_length(vals: &[&[u8]]) -> Vec<u8> {
+    let lengths: Vec<i32> = vals.iter().map(|v| v.len() as i32).collect();
+    let mut buf = encode_delta(&lengths);
     for v in vals {
-        b.extend_from_slice(&(v.len() as u32).to_le_bytes());
-        b.extend_from_slice(v);
+        buf.extend_from_slice(v);
     }
-    Page::Data(DataPage::new(plain_header(vals.len()), b, d.clone(), Some(vals.len())))
+    buf
 }
 
-// ── Dictionary encoding helpers ─────────────────────────────────────────────
+fn encode_delta_byte_array(vals: &[Vec<u8>]) -> Vec<u8> {
+    let mut prefixes = Vec::with_capacity(vals.len());
+    let mut suffixes = Vec::with_capacity(vals.len());
+    let mut prev: &[u8] = &[];
+    for v in vals {
+        let shared = prev.iter().zip(v).take_while(|(a, b)| a == b).count();
+        prefixes.push(shared as i32);
+        suffixes.push(&v[shared..]);
+        prev = v;
+    }
+    let mut buf = encode_delta(&prefixes);
+    buf.extend(encode_delta_length(&suffixes));
+    buf
+}
 
-/// Number of bits needed to represent values 0..n-1
-fn num_bits(n: usize) -> u32 {
-    if n <= 1 { return 0; }
-    (usize::BITS - (n - 1).leading_zeros())
+/// BYTE_STREAM_SPLIT: the first byte of every value, then every second byte,
+/// and so on. Floats that change slowly share their sign, exponent and top
+/// mantissa bytes, which then form long, compressible runs.
+fn encode_byte_stream_split<T: PlainValue>(vals: &[T]) -> Vec<u8> {
+    let plain = encode_plain(vals);
+    let width = vals.first().map_or(0, |v| v.plain_size());
+    let mut buf = vec![0; plain.len()];
+    for (i, value) in plain.chunks_exact(width.max(1)).enumerate() {
+        for (b, &byte) in value.iter().enumerate() {
+            buf[b * vals.len() + i] = byte;
+        }
+    }
+    buf
 }
 
-/// Encode u32 indices using RLE/bit-packed hybrid encoding (parquet spec)
-fn rle_encode_indices(indices: &[u32], bit_width: u32) -> Vec<u8> {
-    let mut buf = Vec::new();
-    // bit_width byte prefix (required by Parquet for dict pages)
-    buf.push(bit_width as u8);
+// ── Statistics ──────────────────────────────────────────────────────────────
+
+/// Values that can be summarized into min/max statistics
+trait StatValue {
+    /// Ordering used for min/max (signed for ints, unsigned bytes for binary)
+    fn stat_cmp(&self, other: &Self) -> Ordering;
+    /// PLAIN encoding of a single value, without a length prefix
+    fn stat_bytes(&self) -> Vec<u8>;
+    /// NaN is excluded from float min/max
+    fn is_nan(&self) -> bool { false }
+}
+
+macro_rules! impl_stat_int {
+    ($($t:ty),*) => {$(
+        impl StatValue for $t {
+            fn stat_cmp(&self, other: &Self) -> Ordering { self.cmp(other) }
+            fn stat_bytes(&self) -> Vec<u8> { self.to_le_bytes().to_vec() }
+        }
+    )*};
+}
+impl_stat_int!(i32, i64, u32, u64);
+
+macro_rules! impl_stat_float {
+    ($($t:ty),*) => {$(
+        impl StatValue for $t {
+            // total_cmp orders -0.0 before +0.0, as the spec asks for zero bounds
+            fn stat_cmp(&self, other: &Self) -> Ordering { self.total_cmp(other) }
+            fn stat_bytes(&self) -> Vec<u8> { self.to_le_bytes().to_vec() }
+            fn is_nan(&self) -> bool { <$t>::is_nan(*self) }
+        }
+    )*};
+}
+impl_stat_float!(f32, f64);
+
+impl StatValue for Vec<u8> {
+    fn stat_cmp(&self, other: &Self) -> Ordering { self.cmp(other) }
+    fn stat_bytes(&self) -> Vec<u8> { self.clone() }
+}
+
+impl StatValue for FixedDecimal {
+    // Decimals compare as signed numbers
+    fn stat_cmp(&self, other: &Self) -> Ordering { self.unscaled.cmp(&other.unscaled) }
+    fn stat_bytes(&self) -> Vec<u8> { self.be_bytes() }
+}
+
+impl StatValue for FixedBytes {
+    fn stat_cmp(&self, other: &Self) -> Ordering { self.0.cmp(&other.0) }
+    fn stat_bytes(&self) -> Vec<u8> { self.0.clone() }
+}
 
-    if bit_width == 0 {
-        // All values are the same (single dictionary entry), write RLE run
-        // RLE header: (count << 1) | 0
-        let count = indices.len() as u64;
-        let header = count << 1;
-        let mut h = header;
-        loop {
-            let byte = (h & 0x7F) as u8;
-            h >>= 7;
-            if h == 0 {
-                buf.push(byte);
-                break;
+impl StatValue for Half {
+    // Sign-magnitude bits mapped onto an unsigned key: numeric order, -0 before +0
+    fn stat_cmp(&self, other: &Self) -> Ordering { self.order_key().cmp(&other.order_key()) }
+    fn stat_bytes(&self) -> Vec<u8> { self.0.to_le_bytes().to_vec() }
+    fn is_nan(&self) -> bool { self.0 & 0x7fff > 0x7c00 }
+}
+
+impl StatValue for bool {
+    fn stat_cmp(&self, other: &Self) -> Ordering { self.cmp(other) }
+    fn stat_bytes(&self) -> Vec<u8> { vec![*self as u8] }
+}
+
+/// min/max/null_count over the non-null values of a page
+fn page_stats<T: StatValue>(vals: &[T], null_count: usize) -> ParquetStatistics {
+    let mut min: Option<&T> = None;
+    let mut max: Option<&T> = None;
+    for v in vals.iter().filter(|v| !v.is_nan()) {
+        if min.is_none_or(|m| v.stat_cmp(m) == Ordering::Less) { min = Some(v); }
+        if max.is_none_or(|m| v.stat_cmp(m) == Ordering::Greater) { max = Some(v); }
+    }
+    ParquetStatistics {
+        max: None,
+        min: None,
+        null_count: Some(null_count as i64),
+        distinct_count: None,
+        max_value: max.map(|v| v.stat_bytes()),
+        min_value: min.map(|v| v.stat_bytes()),
+    }
+}
+
+// ── Levels ──────────────────────────────────────────────────────────────────
+
+/// Encode definition levels for a flat optional column (max level 1) as
+/// RLE/bit-packed data. V1 pages add a 4-byte length prefix, V2 pages don't.
+fn encode_def_levels(validity: &[bool]) -> Vec<u8> {
+    let levels: Vec<u32> = validity.iter().map(|&v| v as u32).collect();
+    let mut rle = Vec::new();
+    rle_hybrid_encode(&levels, 1, &mut rle);
+    rle
+}
+
+/// Repetition/definition levels of a nested leaf column (Dremel encoding),
+/// one entry per value slot: a value, a null, or an empty/null list
+struct Nested {
+    def: Vec<u32>,
+    rep: Vec<u32>,
+    max_def: u32,
+    max_rep: u32,
+    /// First entry of each row, plus one past the last entry
+    row_starts: Vec<usize>,
+}
+
+/// Levels of some rows of a column: flat columns have one entry per row (with
+/// `validity` when optional), nested ones any number per row
+#[derive(Clone, Copy)]
+enum Levels<'a> {
+    Flat { validity: Option<&'a [bool]>, num_rows: usize },
+    Nested { def: &'a [u32], rep: &'a [u32], max_def: u32, max_rep: u32, row_starts: &'a [usize] },
+}
+
+impl<'a> Levels<'a> {
+    fn num_rows(&self) -> usize {
+        match self {
+            Levels::Flat { num_rows, .. } => *num_rows,
+            Levels::Nested { row_starts, .. } => row_starts.len() - 1,
+        }
+    }
+
+    /// Level entries (a page header's num_values)
+    fn num_entries(&self) -> usize {
+        match self {
+            Levels::Flat { num_rows, .. } => *num_rows,
+            Levels::Nested { def, .. } => def.len(),
+        }
+    }
+
+    /// Non-null values in row `row`
+    fn row_values(&self, row: usize) -> usize {
+        match self {
+            Levels::Flat { validity, .. } => validity.is_none_or(|v| v[row]) as usize,
+            Levels::Nested { def, max_def, row_starts, .. } => {
+                let base = row_starts[0];
+                def[row_starts[row] - base..row_starts[row + 1] - base].iter().filter(|&&d| d == *max_def).count()
             }
-            buf.push(byte | 0x80);
         }
-        // value = 0 in ceil(bit_width/8) bytes, but bit_width=0 means 0 bytes... 
-        // Actually per spec, min bit_width for RLE is 1
-        return buf;
     }
 
-    // Use bit-packed encoding (simpler, works well for random indices)
-    let num_values = indices.len();
-    let num_groups = (num_values + 7) / 8; // groups of 8
+    /// Non-null values in all rows
+    fn num_values(&self) -> usize {
+        match self {
+            Levels::Flat { validity, num_rows } => validity.map_or(*num_rows, |v| v.iter().filter(|&&b| b).count()),
+            Levels::Nested { def, max_def, .. } => def.iter().filter(|&&d| d == *max_def).count(),
+        }
+    }
 
-    // bit-packed header: (num_groups << 1) | 1
-    let header = ((num_groups as u64) << 1) | 1;
-    let mut h = header;
+    fn rows(&self, rows: Range<usize>) -> Levels<'a> {
+        match *self {
+            Levels::Flat { validity, .. } => {
+                Levels::Flat { validity: validity.map(|v| &v[rows.clone()]), num_rows: rows.len() }
+            }
+            Levels::Nested { def, rep, max_def, max_rep, row_starts } => {
+                let base = row_starts[0];
+                let entries = row_starts[rows.start] - base..row_starts[rows.end] - base;
+                Levels::Nested {
+                    def: &def[entries.clone()],
+                    rep: &rep[entries],
+                    max_def,
+                    max_rep,
+                    row_starts: &row_starts[rows.start..=rows.end],
+                }
+            }
+        }
+    }
+
+    /// RLE-encoded repetition levels, if the column has any
+    fn encode_rep(&self) -> Option<Vec<u8>> {
+        match self {
+            Levels::Nested { rep, max_rep, .. } if *max_rep > 0 => {
+                let mut rle = Vec::new();
+                rle_hybrid_encode(rep, num_bits(*max_rep as usize + 1), &mut rle);
+                Some(rle)
+            }
+            _ => None,
+        }
+    }
+
+    /// RLE-encoded definition levels, if the column has any
+    fn encode_def(&self) -> Option<Vec<u8>> {
+        match self {
+            Levels::Flat { validity, .. } => validity.map(encode_def_levels),
+            Levels::Nested { def, max_def, .. } if *max_def > 0 => {
+                let mut rle = Vec::new();
+                rle_hybrid_encode(def, num_bits(*max_def as usize + 1), &mut rle);
+                Some(rle)
+            }
+            Levels::Nested { .. } => None,
+        }
+    }
+}
+
+// ── Dictionary encoding helpers ─────────────────────────────────────────────
+
+/// Number of bits needed to represent values 0..n-1
+fn num_bits(n: usize) -> u32 {
+    if n <= 1 { return 0; }
+    usize::BITS - (n - 1).leading_zeros()
+}
+
+/// Append a ULEB128-encoded run header
+fn push_uleb128(buf: &mut Vec<u8>, mut h: u64) {
     loop {
         let byte = (h & 0x7F) as u8;
         h >>= 7;
@@ -183,21 +843,55 @@ fn rle_encode_indices(indices: &[u32], bit_width: u32) -> Vec<u8> {
         }
         buf.push(byte | 0x80);
     }
+}
+
+/// Encode u32 values using RLE/bit-packed hybrid encoding (parquet spec).
+/// Runs of at least 8 equal values become RLE runs, the values between them
+/// bit-packed runs. A bit-packed run holds whole groups of 8, so one in the
+/// middle is topped up from the start of the RLE run after it.
+fn rle_hybrid_encode(values: &[u32], bit_width: u32, buf: &mut Vec<u8>) {
+    let mut packed_start = 0;
+    let mut i = 0;
+    while i < values.len() {
+        let run = values[i..].iter().take_while(|&&v| v == values[i]).count();
+        let fill = (8 - (i - packed_start) % 8) % 8;
+        if run >= fill + 8 {
+            if i + fill > packed_start {
+                bit_pack_run(&values[packed_start..i + fill], bit_width, buf);
+            }
+            push_uleb128(buf, ((run - fill) as u64) << 1);
+            buf.extend_from_slice(&values[i].to_le_bytes()[..bit_width.div_ceil(8) as usize]);
+            packed_start = i + run;
+        }
+        i += run;
+    }
+    if packed_start < values.len() || values.is_empty() {
+        bit_pack_run(&values[packed_start..], bit_width, buf);
+    }
+}
+
+/// Append one bit-packed run of `values`, padded to a multiple of 8
+fn bit_pack_run(values: &[u32], bit_width: u32, buf: &mut Vec<u8>) {
+    let num_values = values.len();
+    let num_groups = num_values.div_ceil(8); // groups of 8
+
+    // bit-packed header: (num_groups << 1) | 1
+    push_uleb128(buf, ((num_groups as u64) << 1) | 1);
 
     // bit-pack the values
     let total_bits = num_groups * 8 * bit_width as usize;
-    let total_bytes = (total_bits + 7) / 8;
+    let total_bytes = total_bits.div_ceil(8);
     let start = buf.len();
     buf.resize(start + total_bytes, 0);
 
-    for (i, &idx) in indices.iter().enumerate() {
+    for (i, &v) in values.iter().enumerate() {
         let bit_offset = i * bit_width as usize;
         let byte_offset = start + bit_offset / 8;
         let bit_shift = bit_offset % 8;
-        
+
         // Write the value across potentially multiple bytes
-        let mut val = (idx as u64) << bit_shift;
-        let bytes_needed = ((bit_shift + bit_width as usize) + 7) / 8;
+        let mut val = (v as u64) << bit_shift;
+        let bytes_needed = (bit_shift + bit_width as usize).div_ceil(8);
         for b in 0..bytes_needed {
             if byte_offset + b < buf.len() {
                 buf[byte_offset + b] |= (val & 0xFF) as u8;
@@ -205,210 +899,4196 @@ fn rle_encode_indices(indices: &[u32], bit_width: u32) -> Vec<u8> {
             val >>= 8;
         }
     }
+}
 
+/// Encode dictionary indices: 1-byte bit_width prefix, then RLE/bit-packed data
+fn rle_encode_indices(indices: &[u32], bit_width: u32) -> Vec<u8> {
+    let mut buf = Vec::new();
+    // bit_width byte prefix (required by Parquet for dict pages)
+    buf.push(bit_width as u8);
+    rle_hybrid_encode(indices, bit_width, &mut buf);
     buf
 }
 
-/// Try to dictionary-encode a binary column in a single pass.
-/// Uses Vec linear search (cache-friendly for <256 unique values).
-/// Returns None and falls back to plain if cardinality is too high.
-fn try_encode_dict(vals: &[Vec<u8>], d: &Descriptor) -> Option<Vec<Page>> {
-    let n = vals.len();
-    if n == 0 { return None; }
-
+/// Try to dictionary-encode a column chunk in a single pass, page by page
+/// (`page_lens` non-null values each).
+///
+/// Like parquet-mr, a page whose values would take the dictionary past
+/// `cfg.dictionary_page_size` bytes falls back to plain, and so do all pages
+/// after it; the pages before keep their indices. Unlike parquet-mr, the
+/// same happens past 256 entries: lookups are a linear search of the
+/// dictionary, which this cap keeps short, and indices stay within 8 bits.
+/// If the first page has more than `cfg.dictionary_ratio` distinct values
+/// per value, the whole chunk is plain.
+/// Returns the dictionary page, one index per value of the dictionary-encoded
+/// pages and how many pages those are, or None if no page is.
+fn try_build_dict<T: PlainValue>(vals: &[T], page_lens: &[usize], cfg: &WriteConfig) -> Option<(Page, Vec<u32>, u32, usize)> {
     // Max 256 unique values — keeps Vec linear search fast (≤256 comparisons)
     // and produces optimal RLE encoding (≤8 bits per index)
     let max_unique: usize = 256;
 
     // Single pass: build dictionary + indices simultaneously
-    let mut dict_values: Vec<&[u8]> = Vec::with_capacity(64);
-    let mut indices: Vec<u32> = Vec::with_capacity(n);
+    let mut dict_values: Vec<&T> = Vec::with_capacity(64);
+    let mut dict_size = 0;
+    let mut indices: Vec<u32> = Vec::with_capacity(vals.len());
+    let mut num_pages = 0;
+    let mut k = 0;
 
-    for v in vals {
-        // Linear search in dict (fast for <256 entries, cache-friendly)
-        let idx = dict_values.iter().position(|d| *d == v.as_slice());
-        match idx {
-            Some(i) => indices.push(i as u32),
-            None => {
-                if dict_values.len() >= max_unique {
-                    return None; // too many unique values, fall back to plain
+    'pages: for &len in page_lens {
+        let (entries, kept) = (dict_values.len(), indices.len());
+        for v in &vals[k..k + len] {
+            // Linear search in dict (fast for <256 entries, cache-friendly)
+            let idx = dict_values.iter().position(|d| d.same(v));
+            match idx {
+                Some(i) => indices.push(i as u32),
+                None => {
+                    dict_size += v.plain_size();
+                    if dict_values.len() >= max_unique || dict_size > cfg.dictionary_page_size {
+                        // Dictionary full: this page and the rest are plain
+                        dict_values.truncate(entries);
+                        indices.truncate(kept);
+                        break 'pages;
+                    }
+                    indices.push(dict_values.len() as u32);
+                    dict_values.push(v);
                 }
-                indices.push(dict_values.len() as u32);
-                dict_values.push(v.as_slice());
             }
         }
+        if num_pages == 0 && dict_values.len() as f64 > cfg.dictionary_ratio * len as f64 {
+            return None;
+        }
+        k += len;
+        num_pages += 1;
+    }
+    if dict_values.is_empty() {
+        return None;
     }
 
     let num_dict = dict_values.len();
     let bits = num_bits(num_dict).max(1);
 
     // Dictionary page: PLAIN-encoded unique values
-    let dict_total: usize = dict_values.iter().map(|v| 4 + v.len()).sum();
-    let mut dict_buf = Vec::with_capacity(dict_total);
-    for v in &dict_values {
-        dict_buf.extend_from_slice(&(v.len() as u32).to_le_bytes());
-        dict_buf.extend_from_slice(v);
-    }
+    let dict_buf = encode_plain(dict_values.iter().copied());
     let dict_page = Page::Dict(DictPage::new(dict_buf, num_dict, false));
 
-    // Data page: RLE/bit-packed encoded indices
-    let rle_buf = rle_encode_indices(&indices, bits);
-    let data_page = Page::Data(DataPage::new(
-        dict_header(n),
-        rle_buf,
-        d.clone(),
-        Some(n),
-    ));
-
-    Some(vec![dict_page, data_page])
+    Some((dict_page, indices, bits, num_pages))
 }
 
-#[wasm_bindgen(js_name = "writeParquet")]
-pub fn write_parquet(
-    schema_js: &JsValue,
-    data_js: &JsValue,
-    config_js: &JsValue,
-) -> Result<js_sys::Uint8Array, JsValue> {
-    let compression = if let Ok(comp) = Reflect::get(config_js, &"compression".into()) {
-        match comp.as_string().as_deref() {
-            Some("snappy") => CompressionOptions::Snappy,
-            Some("none") => CompressionOptions::Uncompressed,
-            _ => CompressionOptions::Snappy,
+/// Split a chunk's rows into pages of roughly `cfg.data_page_size` PLAIN bytes
+/// (and at most `cfg.data_page_rows` rows). `value_size(k)` is the encoded
+/// size of the k-th non-null value.
+fn page_ranges(levels: Levels, cfg: &WriteConfig, value_size: impl Fn(usize) -> usize) -> Vec<Range<usize>> {
+    let num_rows = levels.num_rows();
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    let mut bytes = 0;
+    let mut k = 0;
+    for row in 0..num_rows {
+        for _ in 0..levels.row_values(row) {
+            bytes += value_size(k);
+            k += 1;
         }
-    } else {
-        CompressionOptions::Snappy
-    };
-
-    // Dictionary config: default true
-    // Note: Reflect::get returns Ok(undefined) when key is missing, NOT Err
-    let use_dict = if let Ok(d) = Reflect::get(config_js, &"dictionary".into()) {
-        if d.is_undefined() {
-            true // key not present → default ON
-        } else {
-            d.is_truthy() // explicit true/false
+        if bytes >= cfg.data_page_size || row + 1 - start >= cfg.data_page_rows {
+            ranges.push(start..row + 1);
+            start = row + 1;
+            bytes = 0;
         }
+    }
+    if start < num_rows || ranges.is_empty() {
+        ranges.push(start..num_rows);
+    }
+    ranges
+}
+
+/// Encode one column chunk: an optional dictionary page, then one data page per
+/// row range from `page_ranges`. `vals` holds only non-null values; `levels`
+/// covers all rows.
+fn encode_chunk<T: PlainValue + StatValue>(vals: &[T], levels: Levels, cfg: &WriteConfig, d: &Descriptor, dictionary: bool) -> Vec<Page> {
+    let ranges = page_ranges(levels, cfg, |k| vals[k].plain_size());
+    let dict = if dictionary {
+        let page_lens: Vec<usize> = ranges.iter().map(|rows| levels.rows(rows.clone()).num_values()).collect();
+        try_build_dict(vals, &page_lens, cfg)
     } else {
-        true
+        None
     };
 
-    let schema_arr: &Array = schema_js
-        .dyn_ref::<Array>()
-        .ok_or_else(|| JsValue::from_str("schema must be an array"))?;
+    let mut pages = Vec::with_capacity(ranges.len() + 1);
+    let mut indices: Option<(Vec<u32>, u32)> = None;
+    let mut dict_pages = 0;
+    if let Some((dict_page, idx, bits, num_pages)) = dict {
+        pages.push(dict_page);
+        indices = Some((idx, bits));
+        dict_pages = num_pages;
+    }
 
-    let num_cols = schema_arr.length() as usize;
-    let mut col_names: Vec<String> = Vec::with_capacity(num_cols);
-    let mut col_types: Vec<ColType> = Vec::with_capacity(num_cols);
-    let mut parquet_fields: Vec<ParquetType> = Vec::with_capacity(num_cols);
+    let mut k = 0; // first non-null value of the current page
+    for (i, rows) in ranges.into_iter().enumerate() {
+        let page_levels = levels.rows(rows);
+        let count = page_levels.num_values();
+        let page_vals = &vals[k..k + count];
+        let stats = cfg.statistics.then(|| page_stats(page_vals, page_levels.num_entries() - count));
 
-    for i in 0..num_cols {
-        let col = schema_arr.get(i as u32);
-        let name = Reflect::get(&col, &"name".into())
-            .ok()
-            .and_then(|v| v.as_string())
-            .ok_or_else(|| JsValue::from_str("schema element must have string 'name'"))?;
+        let page = match &indices {
+            // Data page: RLE/bit-packed encoded indices
+            Some((idx, bits)) if i < dict_pages => data_page(
+                rle_encode_indices(&idx[k..k + count], *bits),
+                Encoding::RleDictionary,
+                page_levels,
+                stats,
+                cfg,
+                d,
+            ),
+            _ => data_page(encode_plain(page_vals), Encoding::Plain, page_levels, stats, cfg, d),
+        };
+        pages.push(page);
+        k += count;
+    }
+    pages
+}
 
-        let type_str = Reflect::get(&col, &"type".into())
-            .ok()
-            .and_then(|v| v.as_string())
-            .unwrap_or_else(|| "string".to_string());
+/// Encode a boolean column chunk, no dictionary: each page RLE-encoded when
+/// `rle` is set and that's smaller (long runs of one value), else bit-packed
+/// PLAIN
+fn encode_bool_chunk(vals: &[bool], levels: Levels, cfg: &WriteConfig, d: &Descriptor, rle: bool) -> Vec<Page> {
+    let mut k = 0;
+    page_ranges(levels, cfg, |_| 1)
+        .into_iter()
+        .map(|rows| {
+            let page_levels = levels.rows(rows);
+            let count = page_levels.num_values();
+            let page_vals = &vals[k..k + count];
+            k += count;
+            let stats = cfg.statistics.then(|| page_stats(page_vals, page_levels.num_entries() - count));
+            let plain = encode_bool(page_vals);
+            match rle.then(|| encode_bool_rle(page_vals)) {
+                Some(runs) if runs.len() < plain.len() => data_page(runs, Encoding::Rle, page_levels, stats, cfg, d),
+                _ => data_page(plain, Encoding::Plain, page_levels, stats, cfg, d),
+            }
+        })
+        .collect()
+}
+
+/// Encode a column chunk without a dictionary: one data page per row range
+/// from `page_ranges`, its values encoded by `encode` as `encoding`
+fn encode_chunk_as<T: PlainValue + StatValue>(
+    vals: &[T],
+    levels: Levels,
+    cfg: &WriteConfig,
+    d: &Descriptor,
+    encoding: Encoding,
+    encode: impl Fn(&[T]) -> Vec<u8>,
+) -> Vec<Page> {
+    let mut k = 0;
+    page_ranges(levels, cfg, |k| vals[k].plain_size())
+        .into_iter()
+        .map(|rows| {
+            let page_levels = levels.rows(rows);
+            let count = page_levels.num_values();
+            let page_vals = &vals[k..k + count];
+            k += count;
+            let stats = cfg.statistics.then(|| page_stats(page_vals, page_levels.num_entries() - count));
+            data_page(encode(page_vals), encoding, page_levels, stats, cfg, d)
+        })
+        .collect()
+}
 
-        let ct = ColType::from_str(&type_str);
+/// Column name for cell errors, plus the row of each cell when that isn't its
+/// index (the values of a leaf inside a nested column)
+struct CellName<'a> {
+    name: &'a str,
+    rows: Option<&'a [usize]>,
+}
 
-        let ptype = PrimitiveType {
-            field_info: FieldInfo {
-                name: name.clone(),
-                repetition: Repetition::Required,
-                id: None,
-            },
-            logical_type: ct.logical_type(),
-            converted_type: ct.converted_type(),
-            physical_type: ct.physical_type(),
-        };
+/// How `collect_values` treats the cells of a column that it can't take as
+/// they are (with the default `Coercion::Coerce`, there's no check at all)
+struct CellCheck<'a> {
+    ct: &'a ColType,
+    coercion: Coercion,
+    /// The column's `default`
+    default: Option<&'a JsValue>,
+}
+
+impl CellCheck<'_> {
+    /// What's wrong with cell `x`, if anything: null or undefined in a
+    /// required column, NaN outside float columns, or the wrong JS type
+    fn problem(&self, x: &JsValue, optional: bool) -> Option<String> {
+        if x.is_null() || x.is_undefined() {
+            return (!optional).then(|| format!("got {} in a required column", js_type(x)));
+        }
+        let float = matches!(self.ct, ColType::Float32 | ColType::Float64 | ColType::Float16);
+        if !float && x.as_f64().is_some_and(f64::is_nan) {
+            return Some("got NaN".to_string());
+        }
+        if !self.ct.accepts(x) {
+            return Some(format!("expected {}, got {}", self.ct.expected().unwrap_or("a value"), js_type(x)));
+        }
+        None
+    }
 
-        parquet_fields.push(ParquetType::PrimitiveType(ptype));
-        col_names.push(name);
-        col_types.push(ct);
+    /// The cell to write in place of `x`, or the error for it
+    fn resolve(&self, x: JsValue, optional: bool) -> Result<JsValue, String> {
+        let Some(problem) = self.problem(&x, optional) else { return Ok(x) };
+        match (self.coercion, self.default) {
+            (Coercion::Default, Some(d)) => Ok(d.clone()),
+            (Coercion::Null | Coercion::Default, _) if optional => Ok(JsValue::NULL),
+            _ => Err(problem),
+        }
     }
+}
 
-    let schema_desc = SchemaDescriptor::new("schema".to_string(), parquet_fields);
-    let columns = schema_desc.columns();
+/// JS type of a cell for errors: `typeof`, or the constructor name of objects
+fn js_type(x: &JsValue) -> String {
+    if x.is_null() {
+        return "null".to_string();
+    }
+    if !x.is_object() {
+        return x.js_typeof().as_string().unwrap_or_default();
+    }
+    Reflect::get(x, &"constructor".into())
+        .ok()
+        .and_then(|c| c.dyn_into::<js_sys::Function>().ok())
+        .map_or_else(|| "object".to_string(), |c| String::from(c.name()))
+}
 
-    let options = WriteOptions {
-        write_statistics: false,
-        version: Version::V1,
+/// Collect the values of a JS array column. For optional columns, null and
+/// undefined cells are left out of the values and recorded as `false` in the
+/// returned validity; required columns pass every cell through `f`.
+/// With a `check`, cells the column can't take as they are (wrong JS type,
+/// NaN, missing in a required column) are replaced or rejected first, as its
+/// coercion policy says. Errors are reported with the column name and row index.
+fn collect_values<T>(
+    arr: &Array,
+    name: &CellName,
+    optional: bool,
+    check: Option<&CellCheck>,
+    f: impl Fn(JsValue) -> Result<T, String>,
+) -> Result<(Vec<T>, Option<Vec<bool>>), JsValue> {
+    let len = arr.length() as usize;
+    let mut vals = Vec::with_capacity(len);
+    let at = |j: usize, e: String| {
+        let row = name.rows.map_or(j, |rows| rows[j]);
+        JsValue::from_str(&format!("column '{}' row {}: {}", name.name, row, e))
+    };
+    let cell = |j: usize| match check {
+        Some(c) => c.resolve(arr.get(j as u32), optional).map_err(|e| at(j, e)),
+        None => Ok(arr.get(j as u32)),
     };
+    if !optional {
+        for j in 0..len { vals.push(f(cell(j)?).map_err(|e| at(j, e))?); }
+        return Ok((vals, None));
+    }
+    let mut validity = Vec::with_capacity(len);
+    for j in 0..len {
+        let v = cell(j)?;
+        if v.is_null() || v.is_undefined() {
+            validity.push(false);
+        } else {
+            validity.push(true);
+            vals.push(f(v).map_err(|e| at(j, e))?);
+        }
+    }
+    Ok((vals, Some(validity)))
+}
 
-    // Build compressed column iterators (FileWriter expects CompressedPage)
-    let mut col_iters: Vec<
-        Result<
-            DynStreamingIterator<'static, CompressedPage, parquet2::error::Error>,
-            parquet2::error::Error,
-        >,
-    > = Vec::with_capacity(num_cols);
-
-    for (i, (name, ct)) in col_names.iter().zip(col_types.iter()).enumerate() {
-        let arr_val = Reflect::get(data_js, &JsValue::from_str(name))
-            .map_err(|_| JsValue::from_str(&format!("missing column '{}'", name)))?;
-        let arr: &Array = arr_val
-            .dyn_ref::<Array>()
-            .ok_or_else(|| JsValue::from_str(&format!("column '{}' must be array", name)))?;
-        let len = arr.length() as usize;
-        let desc = columns[i].descriptor.clone();
+/// Largest integer a JS Number holds exactly (Number.MAX_SAFE_INTEGER)
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
 
-        let pages: Vec<Page> = match ct {
-            ColType::Int32 => {
-                let v: Vec<i32> = (0..len).map(|j| arr.get(j as u32).as_f64().unwrap_or(0.0) as i32).collect();
-                vec![encode_i32(&v, &desc)]
-            }
-            ColType::Int64 | ColType::TimestampMillis => {
-                let v: Vec<i64> = (0..len).map(|j| arr.get(j as u32).as_f64().unwrap_or(0.0) as i64).collect();
-                vec![encode_i64(&v, &desc)]
-            }
+/// Convert an int64 cell: BigInts are taken exactly; Numbers are truncated,
+/// and in strict mode must be safe integers (beyond 2^53 they have already
+/// lost precision).
+fn int64_value(x: JsValue, strict: bool) -> Result<i64, String> {
+    if x.is_bigint() {
+        return i64::try_from(x).map_err(|_| "BigInt is out of int64 range".to_string());
+    }
+    let n = x.as_f64().unwrap_or(0.0);
+    if strict && !(n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER) {
+        return Err(format!("{} is not a safe integer; pass a BigInt for int64 values beyond 2^53", n));
+    }
+    Ok(n as i64)
+}
+
+/// Convert a timestamp cell: a JS Date becomes its epoch time in the
+/// column's unit, anything else is taken as an int64 count of that unit.
+fn timestamp_value(x: JsValue, ct: &ColType, strict: bool) -> Result<i64, String> {
+    let Some(d) = x.dyn_ref::<Date>() else {
+        return int64_value(x, strict);
+    };
+    let ms = d.get_time();
+    if ms.is_nan() {
+        return Err("invalid Date".to_string());
+    }
+    let (per_ms, name) = match ct.time_unit() {
+        Some(TimeUnit::Microseconds) => (1_000, "timestamp_micros"),
+        Some(TimeUnit::Nanoseconds) => (1_000_000, "timestamp_nanos"),
+        _ => (1, "timestamp_millis"),
+    };
+    (ms as i64)
+        .checked_mul(per_ms)
+        .ok_or_else(|| format!("{} is out of range for {}", String::from(d.to_iso_string()), name))
+}
+
+/// Convert a uint64 cell. BigInts are taken exactly; Numbers are truncated
+/// (strict mode requires safe integers). Negative values are always rejected.
+fn uint64_value(x: JsValue, strict: bool) -> Result<u64, String> {
+    if x.is_bigint() {
+        return u64::try_from(x.clone()).map_err(|_| format!("{:?} is out of range for uint64", x));
+    }
+    let n = x.as_f64().unwrap_or(0.0);
+    if strict && !(n.fract() == 0.0 && n <= MAX_SAFE_INTEGER) {
+        return Err(format!("{} is not a safe integer; pass a BigInt for uint64 values beyond 2^53", n));
+    }
+    if !(n.trunc() >= 0.0 && n < u64::MAX as f64) {
+        return Err(format!("{} is out of range for uint64", n));
+    }
+    Ok(n as u64)
+}
+
+/// Convert a cell of a narrow integer column (`range` from `ColType::int_range`).
+/// Numbers are truncated (strict mode requires integers); values outside the
+/// type's range are always rejected.
+fn ranged_int_value(x: JsValue, range: (&str, i64, i64), strict: bool) -> Result<i64, String> {
+    let (name, lo, hi) = range;
+    let v = if x.is_bigint() {
+        i64::try_from(x.clone()).map_err(|_| format!("{:?} is out of range for {}", x, name))?
+    } else {
+        let n = x.as_f64().unwrap_or(0.0);
+        if strict && n.fract() != 0.0 {
+            return Err(format!("{} is not an integer", n));
+        }
+        if !(n.trunc() >= lo as f64 && n.trunc() <= hi as f64) {
+            return Err(format!("{} is out of range for {}", n, name));
+        }
+        n as i64
+    };
+    if v < lo || v > hi {
+        return Err(format!("{} is out of range for {}", v, name));
+    }
+    Ok(v)
+}
+
+/// Convert an int32 (or time_millis) cell. Numbers are truncated and
+/// saturate; strict mode requires integers in range.
+fn int32_value(x: JsValue, strict: bool) -> Result<i32, String> {
+    let n = x.as_f64().unwrap_or(0.0);
+    if strict && n.fract() != 0.0 {
+        return Err(format!("{} is not an integer", n));
+    }
+    if strict && !(n >= i32::MIN as f64 && n <= i32::MAX as f64) {
+        return Err(format!("{} is out of range for int32", n));
+    }
+    Ok(n as i32)
+}
+
+/// Milliseconds per day (DATE columns store days since the Unix epoch)
+const MS_PER_DAY: f64 = 86_400_000.0;
+
+/// Convert a date cell: a JS Date becomes the UTC day it falls on, a Number
+/// is taken as a day count (strict mode requires an integer one).
+fn date_value(x: JsValue, strict: bool) -> Result<i32, String> {
+    match x.dyn_ref::<Date>() {
+        Some(d) if d.get_time().is_nan() => Err("invalid Date".to_string()),
+        Some(d) => Ok((d.get_time() / MS_PER_DAY).floor() as i32),
+        None => int32_value(x, strict),
+    }
+}
+
+/// Convert a decimal cell (string, Number or BigInt) to its unscaled integer
+/// at `scale`: "12.345" at scale 2 → 1235. Extra fraction digits are rounded
+/// half away from zero, or rejected in strict mode.
+fn decimal_value(x: JsValue, precision: usize, scale: usize, strict: bool) -> Result<i128, String> {
+    let text = if let Some(s) = x.as_string() {
+        s
+    } else if let Some(b) = x.dyn_ref::<js_sys::BigInt>() {
+        String::from(b.to_string(10).map_err(|_| "invalid BigInt".to_string())?)
+    } else {
+        match x.as_f64() {
+            Some(n) if n.is_finite() => n.to_string(),
+            Some(n) => return Err(format!("{} is not a decimal number", n)),
+            None => "0".to_string(),
+        }
+    };
+    let bad = || format!("'{}' is not a decimal number", text);
+    let t = text.trim();
+    let (negative, digits) = match t.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, t.strip_prefix('+').unwrap_or(t)),
+    };
+    let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+    if int_part.is_empty() && frac_part.is_empty()
+        || !int_part.bytes().chain(frac_part.bytes()).all(|b| b.is_ascii_digit())
+    {
+        return Err(bad());
+    }
+    let (kept, dropped) = frac_part.split_at(frac_part.len().min(scale));
+    if strict && dropped.bytes().any(|b| b != b'0') {
+        return Err(format!("'{}' has more than {} fraction digits", text, scale));
+    }
+    let too_big = || format!("'{}' does not fit decimal({}, {})", text, precision, scale);
+    let mut unscaled: i128 = 0;
+    let padding = std::iter::repeat_n(b'0', scale - kept.len());
+    for b in int_part.bytes().chain(kept.bytes()).chain(padding) {
+        unscaled = unscaled
+            .checked_mul(10)
+            .and_then(|u| u.checked_add((b - b'0') as i128))
+            .ok_or_else(too_big)?;
+    }
+    if dropped.as_bytes().first().is_some_and(|&b| b >= b'5') {
+        unscaled += 1;
+    }
+    if unscaled >= 10i128.pow(precision as u32) {
+        return Err(too_big());
+    }
+    Ok(if negative { -unscaled } else { unscaled })
+}
+
+/// Parse a UUID cell ("123e4567-e89b-12d3-a456-426614174000", any case) into
+/// its 16 bytes. Missing cells in required columns become the nil UUID.
+fn uuid_value(x: JsValue) -> Result<FixedBytes, String> {
+    if x.is_null() || x.is_undefined() {
+        return Ok(FixedBytes(vec![0; 16]));
+    }
+    let text = x.as_string().ok_or_else(|| format!("expected a UUID string, got {:?}", x))?;
+    let bad = || format!("'{}' is not a UUID", text);
+    let b = text.as_bytes();
+    if b.len() != 36 || [8, 13, 18, 23].iter().any(|&i| b[i] != b'-') {
+        return Err(bad());
+    }
+    let hex: Vec<u8> = b.iter().copied().filter(|&c| c != b'-').collect();
+    if hex.len() != 32 || !hex.iter().all(u8::is_ascii_hexdigit) {
+        return Err(bad());
+    }
+    let nibble = |c: u8| (c as char).to_digit(16).unwrap() as u8;
+    Ok(FixedBytes(hex.chunks(2).map(|p| nibble(p[0]) << 4 | nibble(p[1])).collect()))
+}
+
+/// Convert a fixed(n) cell: a Uint8Array of exactly `n` bytes. Missing cells
+/// in required columns become `n` zero bytes.
+fn fixed_value(x: JsValue, n: usize) -> Result<FixedBytes, String> {
+    if x.is_null() || x.is_undefined() {
+        return Ok(FixedBytes(vec![0; n]));
+    }
+    let bytes = x.dyn_ref::<Uint8Array>().ok_or_else(|| format!("expected a Uint8Array, got {:?}", x))?;
+    if bytes.length() as usize != n {
+        return Err(format!("expected {} bytes, got {}", n, bytes.length()));
+    }
+    Ok(FixedBytes(bytes.to_vec()))
+}
+
+/// Convert a binary cell: a Uint8Array's bytes, or a string's UTF-8 bytes.
+/// Missing cells in required columns become empty.
+fn binary_value(x: JsValue) -> Result<Vec<u8>, String> {
+    if let Some(bytes) = x.dyn_ref::<Uint8Array>() {
+        return Ok(bytes.to_vec());
+    }
+    if let Some(s) = x.as_string() {
+        return Ok(s.into_bytes());
+    }
+    if x.is_null() || x.is_undefined() {
+        return Ok(Vec::new());
+    }
+    Err(format!("expected a Uint8Array, got {:?}", x))
+}
+
+/// Convert a BSON cell: an encoded document as a Uint8Array. Missing cells in
+/// required columns become empty.
+fn bson_value(x: JsValue) -> Result<Vec<u8>, String> {
+    match x.dyn_ref::<Uint8Array>() {
+        Some(bytes) => Ok(bytes.to_vec()),
+        None if x.is_null() || x.is_undefined() => Ok(Vec::new()),
+        None => Err(format!("expected an encoded BSON document (Uint8Array), got {:?}", x)),
+    }
+}
+
+/// Convert a JSON cell: strings are taken as already serialized, anything
+/// else goes through JSON.stringify. Missing cells in required columns become `null`.
+fn json_value(x: JsValue) -> Result<Vec<u8>, String> {
+    if let Some(s) = x.as_string() {
+        return Ok(s.into_bytes());
+    }
+    if x.is_undefined() {
+        return Ok(b"null".to_vec());
+    }
+    match js_sys::JSON::stringify(&x) {
+        Ok(s) => s.as_string().map(String::into_bytes).ok_or_else(|| format!("{:?} is not JSON-serializable", x)),
+        Err(e) => Err(match e.dyn_ref::<js_sys::Error>() {
+            Some(err) => format!("JSON.stringify failed: {}", String::from(err.message())),
+            None => "JSON.stringify failed".to_string(),
+        }),
+    }
+}
+
+/// Gather one column out of an array of row objects.
+/// Missing keys come through as `undefined` (null in optional columns).
+fn row_column(rows: &Array, name: &str) -> Array {
+    let key = JsValue::from_str(name);
+    let col = Array::new_with_length(rows.length());
+    for (j, row) in rows.iter().enumerate() {
+        let v = if row.is_object() { Reflect::get(&row, &key).unwrap_or(JsValue::UNDEFINED) } else { JsValue::UNDEFINED };
+        col.set(j as u32, v);
+    }
+    col
+}
+
+/// Copy any numeric TypedArray (except the BigInt ones) as f64s
+fn typed_f64(v: &JsValue) -> Option<Vec<f64>> {
+    fn widen<T: Into<f64>>(v: Vec<T>) -> Vec<f64> {
+        v.into_iter().map(Into::into).collect()
+    }
+    macro_rules! copy_from {
+        ($($t:ty),*) => {
+            $(if let Some(a) = v.dyn_ref::<$t>() { return Some(widen(a.to_vec())); })*
+        };
+    }
+    copy_from!(
+        Float64Array, Float32Array, Int32Array, Uint32Array, Int16Array, Uint16Array, Int8Array, Uint8Array,
+        Uint8ClampedArray
+    );
+    None
+}
+
+// ── Schema inference ────────────────────────────────────────────────────────
+
+/// Schema type of a TypedArray column
+fn typed_type(v: &JsValue) -> Option<&'static str> {
+    macro_rules! typed {
+        ($($t:ty => $name:literal),*) => {
+            $(if v.is_instance_of::<$t>() { return Some($name); })*
+        };
+    }
+    typed!(
+        Float64Array => "float64", Float32Array => "float32", Int32Array => "int32", Uint32Array => "uint32",
+        Int16Array => "int16", Uint16Array => "uint16", Int8Array => "int8", Uint8Array => "uint8",
+        Uint8ClampedArray => "uint8", BigInt64Array => "int64", BigUint64Array => "uint64"
+    );
+    None
+}
+
+/// Schema entry for a column (or list element, or struct field) holding
+/// `values`. Every value is looked at, so a late 1.5 still makes a float64
+/// column. `path` names the column in errors.
+fn infer_field(name: Option<&str>, values: &[JsValue], path: &str) -> Result<Object, JsValue> {
+    let err = |msg: String| JsValue::from_str(&format!("column '{}': {}", path, msg));
+    let mut optional = false;
+    let mut kind: Option<&'static str> = None;
+    let mut nested: Vec<JsValue> = Vec::new();
+    for v in values {
+        let k = if v.is_null() || v.is_undefined() {
+            optional = true;
+            continue;
+        } else if let Some(n) = v.as_f64() {
+            if n.fract() == 0.0 && n >= i32::MIN as f64 && n <= i32::MAX as f64 { "int32" } else { "float64" }
+        } else if v.is_bigint() {
+            "int64"
+        } else if v.as_bool().is_some() {
+            "boolean"
+        } else if v.is_string() {
+            "string"
+        } else if v.is_instance_of::<Date>() {
+            "timestamp"
+        } else if v.is_instance_of::<Uint8Array>() {
+            "binary"
+        } else if Array::is_array(v) {
+            nested.extend(v.unchecked_ref::<Array>().iter());
+            "list"
+        } else if v.is_object() {
+            nested.push(v.clone());
+            "struct"
+        } else {
+            return Err(err(format!("can't infer a type for {:?}", v)));
+        };
+        kind = Some(match (kind, k) {
+            (None, k) => k,
+            (Some(a), b) if a == b => a,
+            (Some("int32"), "float64") | (Some("float64"), "int32") => "float64",
+            (Some(a), b) => return Err(err(format!("mixes {} and {} values", a, b))),
+        });
+    }
+
+    let entry = Object::new();
+    if let Some(name) = name {
+        Reflect::set(&entry, &"name".into(), &name.into())?;
+    }
+    // All-null columns can hold anything: strings are the safe default
+    let kind = kind.unwrap_or("string");
+    Reflect::set(&entry, &"type".into(), &kind.into())?;
+    if optional {
+        Reflect::set(&entry, &"optional".into(), &JsValue::TRUE)?;
+    }
+    match kind {
+        "list" => {
+            let items = infer_field(None, &nested, &format!("{}.list.element", path))?;
+            Reflect::set(&entry, &"items".into(), &items)?;
+        }
+        "struct" => {
+            let fields = infer_rows(&nested, Some(path))?;
+            Reflect::set(&entry, &"fields".into(), &fields)?;
+        }
+        _ => {}
+    }
+    Ok(entry)
+}
+
+/// Schema entries for the keys of row objects, in order of first appearance.
+/// Keys missing from some rows make optional columns.
+fn infer_rows(rows: &[JsValue], parent: Option<&str>) -> Result<Array, JsValue> {
+    let mut names: Vec<String> = Vec::new();
+    for row in rows.iter().filter(|r| r.is_object()) {
+        for key in Object::keys(row.unchecked_ref::<Object>()).iter() {
+            let key = key.as_string().unwrap_or_default();
+            if !names.contains(&key) {
+                names.push(key);
+            }
+        }
+    }
+    let fields = Array::new();
+    for name in &names {
+        let key = JsValue::from_str(name);
+        let values: Vec<JsValue> = rows
+            .iter()
+            .map(|row| if row.is_object() { Reflect::get(row, &key).unwrap_or(JsValue::UNDEFINED) } else { JsValue::UNDEFINED })
+            .collect();
+        let path = parent.map_or_else(|| name.clone(), |p| format!("{}.{}", p, name));
+        let field = infer_field(Some(name), &values, &path)?;
+        fields.push(&field);
+    }
+    Ok(fields)
+}
+
+/// Infer a `writeParquet` schema from columnar data or an array of row objects
+#[wasm_bindgen(js_name = "inferSchema")]
+pub fn infer_schema(data: &JsValue) -> Result<Array, JsValue> {
+    if let Some(rows) = data.dyn_ref::<Array>() {
+        return infer_rows(&rows.to_vec(), None);
+    }
+    if !data.is_object() {
+        return Err(JsValue::from_str("data must be an object of column arrays or an array of row objects"));
+    }
+    let schema = Array::new();
+    for entry in Object::entries(data.unchecked_ref()).iter() {
+        let entry: Array = entry.unchecked_into();
+        let name = entry.get(0).as_string().unwrap_or_default();
+        let column = entry.get(1);
+        let field = match typed_type(&column) {
+            Some(ty) => {
+                let field = Object::new();
+                Reflect::set(&field, &"name".into(), &name.as_str().into())?;
+                Reflect::set(&field, &"type".into(), &ty.into())?;
+                field
+            }
+            None => {
+                let values = column
+                    .dyn_into::<Array>()
+                    .map_err(|_| JsValue::from_str(&format!("column '{}' must be array", name)))?;
+                infer_field(Some(&name), &values.to_vec(), &name)?
+            }
+        };
+        schema.push(&field);
+    }
+    Ok(schema)
+}
+
+// ── Column data ─────────────────────────────────────────────────────────────
+
+/// Non-null values of a column, by physical representation
+enum Values {
+    Int32(Vec<i32>),
+    Int64(Vec<i64>),
+    Float32(Vec<f32>),
+    Float64(Vec<f64>),
+    Boolean(Vec<bool>),
+    Binary(Vec<Vec<u8>>),
+    Decimal(Vec<FixedDecimal>),
+    Fixed(Vec<FixedBytes>),
+    UInt32(Vec<u32>),
+    UInt64(Vec<u64>),
+    Float16(Vec<Half>),
+}
+
+/// A column pulled out of JS once, then sliced into row groups
+struct ColumnData {
+    values: Values,
+    /// Per-row validity for optional columns (None = required)
+    validity: Option<Vec<bool>>,
+    /// Levels of a leaf inside a nested column (`validity` is then unused)
+    nested: Option<Nested>,
+    num_rows: usize,
+}
+
+impl ColumnData {
+    fn from_js(
+        arr: &Array,
+        name: &CellName,
+        ct: &ColType,
+        optional: bool,
+        default: Option<&JsValue>,
+        cfg: &WriteConfig,
+    ) -> Result<Self, JsValue> {
+        let num_rows = arr.length() as usize;
+        let check = CellCheck { ct, coercion: cfg.coercion, default };
+        let check = (cfg.coercion != Coercion::Coerce).then_some(&check);
+        let (values, validity) = match ct {
+            ColType::Int32 | ColType::TimeMillis => {
+                let (v, valid) = collect_values(arr, name, optional, check, |x| int32_value(x, cfg.strict))?;
+                (Values::Int32(v), valid)
+            }
+            ColType::Date => {
+                let (v, valid) = collect_values(arr, name, optional, check, |x| date_value(x, cfg.strict))?;
+                (Values::Int32(v), valid)
+            }
+            ColType::Int64 | ColType::TimeMicros => {
+                let (v, valid) = collect_values(arr, name, optional, check, |x| int64_value(x, cfg.strict))?;
+                (Values::Int64(v), valid)
+            }
+            ColType::TimestampMillis | ColType::TimestampMicros | ColType::TimestampNanos => {
+                let (v, valid) = collect_values(arr, name, optional, check, |x| timestamp_value(x, ct, cfg.strict))?;
+                (Values::Int64(v), valid)
+            }
             ColType::Float32 => {
-                let v: Vec<f32> = (0..len).map(|j| arr.get(j as u32).as_f64().unwrap_or(0.0) as f32).collect();
-                vec![encode_f32(&v, &desc)]
+                let (v, valid) = collect_values(arr, name, optional, check, |x| Ok(x.as_f64().unwrap_or(0.0) as f32))?;
+                (Values::Float32(v), valid)
             }
             ColType::Float64 => {
-                let v: Vec<f64> = (0..len).map(|j| arr.get(j as u32).as_f64().unwrap_or(0.0)).collect();
-                vec![encode_f64(&v, &desc)]
+                let (v, valid) = collect_values(arr, name, optional, check, |x| Ok(x.as_f64().unwrap_or(0.0)))?;
+                (Values::Float64(v), valid)
+            }
+            ColType::Float16 => {
+                let (v, valid) = collect_values(arr, name, optional, check, |x| Ok(Half::from_f64(x.as_f64().unwrap_or(0.0))))?;
+                (Values::Float16(v), valid)
             }
             ColType::Boolean => {
-                let v: Vec<bool> = (0..len).map(|j| arr.get(j as u32).is_truthy()).collect();
-                vec![encode_bool(&v, &desc)]
+                let (v, valid) = collect_values(arr, name, optional, check, |x| Ok(x.is_truthy()))?;
+                (Values::Boolean(v), valid)
+            }
+            ColType::Decimal(p, s) => {
+                let (v, valid) = collect_values(arr, name, optional, check, |x| decimal_value(x, *p, *s, cfg.strict))?;
+                let values = match ct.physical_type() {
+                    PhysicalType::Int32 => Values::Int32(v.into_iter().map(|u| u as i32).collect()),
+                    PhysicalType::Int64 => Values::Int64(v.into_iter().map(|u| u as i64).collect()),
+                    PhysicalType::FixedLenByteArray(size) => {
+                        Values::Decimal(v.into_iter().map(|unscaled| FixedDecimal { unscaled, size }).collect())
+                    }
+                    _ => unreachable!("decimal storage is int32, int64 or fixed_len_byte_array"),
+                };
+                (values, valid)
+            }
+            ColType::Uuid => {
+                let (v, valid) = collect_values(arr, name, optional, check, uuid_value)?;
+                (Values::Fixed(v), valid)
+            }
+            ColType::Fixed(n) => {
+                let (v, valid) = collect_values(arr, name, optional, check, |x| fixed_value(x, *n))?;
+                (Values::Fixed(v), valid)
+            }
+            ColType::Json => {
+                let (v, valid) = collect_values(arr, name, optional, check, json_value)?;
+                (Values::Binary(v), valid)
+            }
+            ColType::Bson => {
+                let (v, valid) = collect_values(arr, name, optional, check, bson_value)?;
+                (Values::Binary(v), valid)
+            }
+            ColType::Geometry => {
+                let (v, valid) = collect_values(arr, name, optional, check, geometry_value)?;
+                (Values::Binary(v), valid)
+            }
+            ColType::Binary => {
+                let (v, valid) = collect_values(arr, name, optional, check, binary_value)?;
+                (Values::Binary(v), valid)
+            }
+            ColType::UInt32 => {
+                let range = ct.int_range().unwrap();
+                let (v, valid) = collect_values(arr, name, optional, check, |x| ranged_int_value(x, range, cfg.strict))?;
+                (Values::UInt32(v.into_iter().map(|x| x as u32).collect()), valid)
+            }
+            ColType::UInt64 => {
+                let (v, valid) = collect_values(arr, name, optional, check, |x| uint64_value(x, cfg.strict))?;
+                (Values::UInt64(v), valid)
+            }
+            ColType::Int8 | ColType::Int16 | ColType::UInt8 | ColType::UInt16 => {
+                let range = ct.int_range().unwrap();
+                let (v, valid) = collect_values(arr, name, optional, check, |x| ranged_int_value(x, range, cfg.strict))?;
+                (Values::Int32(v.into_iter().map(|x| x as i32).collect()), valid)
             }
             ColType::Str => {
-                let v: Vec<Vec<u8>> = (0..len)
-                    .map(|j| arr.get(j as u32).as_string().unwrap_or_default().into_bytes())
-                    .collect();
-                
-                if use_dict {
-                    try_encode_dict(&v, &desc).unwrap_or_else(|| vec![encode_binary(&v, &desc)])
-                } else {
-                    vec![encode_binary(&v, &desc)]
+                let (v, valid) = collect_values(arr, name, optional, check, |x| {
+                    Ok(x.as_string().unwrap_or_default().into_bytes())
+                })?;
+                (Values::Binary(v), valid)
+            }
+        };
+        Ok(ColumnData { values, validity, nested: None, num_rows })
+    }
+
+    /// Copy a numeric or boolean column out of a TypedArray in one call instead
+    /// of reading it value by value. TypedArrays hold no nulls. Returns None if
+    /// `v` isn't a TypedArray (or the column is a string column, or a narrow
+    /// integer column with out-of-range values — those go through `from_js`
+    /// for a per-row error). uint64 takes a BigUint64Array as is.
+    fn from_typed(v: &JsValue, ct: &ColType, optional: bool) -> Option<Self> {
+        let values = match ct {
+            ColType::Int8 | ColType::Int16 | ColType::UInt8 | ColType::UInt16 | ColType::UInt32 => {
+                let (_, lo, hi) = ct.int_range()?;
+                let vals = typed_f64(v)?;
+                if !vals.iter().all(|&x| x.trunc() >= lo as f64 && x.trunc() <= hi as f64) {
+                    return None;
+                }
+                match ct {
+                    ColType::UInt32 => Values::UInt32(vals.into_iter().map(|x| x as u32).collect()),
+                    _ => Values::Int32(vals.into_iter().map(|x| x as i32).collect()),
+                }
+            }
+            ColType::UInt64 => Values::UInt64(match v.dyn_ref::<BigUint64Array>() {
+                Some(a) => a.to_vec(),
+                None => {
+                    let vals = typed_f64(v)?;
+                    if !vals.iter().all(|&x| x.trunc() >= 0.0) {
+                        return None;
+                    }
+                    vals.into_iter().map(|x| x as u64).collect()
                 }
+            }),
+            ColType::Int32 | ColType::Date | ColType::TimeMillis => Values::Int32(match v.dyn_ref::<Int32Array>() {
+                Some(a) => a.to_vec(),
+                None => typed_f64(v)?.into_iter().map(|x| x as i32).collect(),
+            }),
+            ColType::Int64
+            | ColType::TimestampMillis
+            | ColType::TimestampMicros
+            | ColType::TimestampNanos
+            | ColType::TimeMicros => {
+                Values::Int64(match v.dyn_ref::<BigInt64Array>() {
+                    Some(a) => a.to_vec(),
+                    None => typed_f64(v)?.into_iter().map(|x| x as i64).collect(),
+                })
             }
+            ColType::Float32 => Values::Float32(match v.dyn_ref::<Float32Array>() {
+                Some(a) => a.to_vec(),
+                None => typed_f64(v)?.into_iter().map(|x| x as f32).collect(),
+            }),
+            ColType::Float64 => Values::Float64(typed_f64(v)?),
+            ColType::Float16 => Values::Float16(typed_f64(v)?.into_iter().map(Half::from_f64).collect()),
+            ColType::Boolean => Values::Boolean(typed_f64(v)?.into_iter().map(|x| x != 0.0).collect()),
+            ColType::Str
+            | ColType::Binary
+            | ColType::Json
+            | ColType::Bson
+            | ColType::Geometry
+            | ColType::Decimal(..)
+            | ColType::Uuid
+            | ColType::Fixed(_) => return None,
         };
+        let num_rows = match &values {
+            Values::Int32(v) => v.len(),
+            Values::Int64(v) => v.len(),
+            Values::Float32(v) => v.len(),
+            Values::Float64(v) => v.len(),
+            Values::Boolean(v) => v.len(),
+            Values::Binary(v) => v.len(),
+            Values::Decimal(v) => v.len(),
+            Values::Fixed(v) => v.len(),
+            Values::UInt32(v) => v.len(),
+            Values::UInt64(v) => v.len(),
+            Values::Float16(v) => v.len(),
+        };
+        let validity = optional.then(|| vec![true; num_rows]);
+        Some(ColumnData { values, validity, nested: None, num_rows })
+    }
 
-        let compressed_pages = DynStreamingIterator::new(Compressor::new_from_vec(
-            DynIter::new(pages.into_iter().map(Ok)),
-            compression,
-            vec![],
-        ));
-        col_iters.push(Ok(compressed_pages));
+    /// Map a row range onto its levels and non-null value range
+    fn value_range(&self, rows: Range<usize>) -> (Levels<'_>, Range<usize>) {
+        if let Some(n) = &self.nested {
+            let all = Levels::Nested {
+                def: &n.def,
+                rep: &n.rep,
+                max_def: n.max_def,
+                max_rep: n.max_rep,
+                row_starts: &n.row_starts,
+            };
+            let start = all.rows(0..rows.start).num_values();
+            let levels = all.rows(rows);
+            return (levels, start..start + levels.num_values());
+        }
+        match &self.validity {
+            Some(valid) => {
+                let start = valid[..rows.start].iter().filter(|&&v| v).count();
+                let count = valid[rows.clone()].iter().filter(|&&v| v).count();
+                (Levels::Flat { validity: Some(&valid[rows.clone()]), num_rows: rows.len() }, start..start + count)
+            }
+            None => (Levels::Flat { validity: None, num_rows: rows.len() }, rows),
+        }
     }
 
-    let mut writer = FileWriter::new(Cursor::new(Vec::new()), schema_desc, options, None);
+    /// Column-chunk min/max where parquet2's reduction of the page
+    /// statistics would be wrong: it compares fixed-length values as unsigned
+    /// bytes (decimals are signed, float16 numeric) and integers as signed
+    /// (uint32/uint64 aren't).
+    fn chunk_bounds(&self, rows: Range<usize>) -> Option<ParquetStatistics> {
+        match &self.values {
+            Values::Decimal(v) => Some(page_stats(&v[self.value_range(rows).1], 0)),
+            Values::UInt32(v) => Some(page_stats(&v[self.value_range(rows).1], 0)),
+            Values::UInt64(v) => Some(page_stats(&v[self.value_range(rows).1], 0)),
+            Values::Float16(v) => Some(page_stats(&v[self.value_range(rows).1], 0)),
+            _ => None,
+        }
+    }
 
-    writer
-        .write(DynIter::new(col_iters.into_iter()))
-        .map_err(|e| JsValue::from_str(&format!("write error: {}", e)))?;
+    /// Bloom filter hashes of the non-null values in `rows` (None for booleans,
+    /// which bloom filters don't cover)
+    fn bloom_hashes(&self, rows: Range<usize>) -> Option<Vec<u64>> {
+        let vals = self.value_range(rows).1;
+        Some(match &self.values {
+            Values::Int32(v) => bloom_hashes(&v[vals]),
+            Values::Int64(v) => bloom_hashes(&v[vals]),
+            Values::Float32(v) => bloom_hashes(&v[vals]),
+            Values::Float64(v) => bloom_hashes(&v[vals]),
+            Values::Boolean(_) => return None,
+            Values::Binary(v) => bloom_hashes(&v[vals]),
+            Values::Decimal(v) => bloom_hashes(&v[vals]),
+            Values::Fixed(v) => bloom_hashes(&v[vals]),
+            Values::UInt32(v) => bloom_hashes(&v[vals]),
+            Values::UInt64(v) => bloom_hashes(&v[vals]),
+            Values::Float16(v) => bloom_hashes(&v[vals]),
+        })
+    }
 
-    writer
-        .end(None)
-        .map_err(|e| JsValue::from_str(&format!("finalize error: {}", e)))?;
+    /// Encode rows `rows` of this column into pages (one column chunk)
+    fn pages(&self, rows: Range<usize>, cfg: &WriteConfig, d: &Descriptor, encoding: ValueEncoding) -> Vec<Page> {
+        let (levels, vals) = self.value_range(rows);
+        let dictionary = match encoding {
+            ValueEncoding::Dictionary => true,
+            ValueEncoding::Default => cfg.dictionary,
+            _ => false,
+        };
 
-    let bytes = writer.into_inner().into_inner();
-    let out = js_sys::Uint8Array::new_with_length(bytes.len() as u32);
-    out.copy_from(&bytes);
-    Ok(out)
+        match &self.values {
+            Values::Int32(v) if encoding == ValueEncoding::Delta => encode_chunk_as(&v[vals], levels, cfg, d, Encoding::DeltaBinaryPacked, encode_delta),
+            Values::Int64(v) if encoding == ValueEncoding::Delta => encode_chunk_as(&v[vals], levels, cfg, d, Encoding::DeltaBinaryPacked, encode_delta),
+            Values::UInt32(v) if encoding == ValueEncoding::Delta => encode_chunk_as(&v[vals], levels, cfg, d, Encoding::DeltaBinaryPacked, encode_delta),
+            Values::UInt64(v) if encoding == ValueEncoding::Delta => encode_chunk_as(&v[vals], levels, cfg, d, Encoding::DeltaBinaryPacked, encode_delta),
+            Values::Float32(v) if encoding == ValueEncoding::ByteStreamSplit => {
+                encode_chunk_as(&v[vals], levels, cfg, d, Encoding::ByteStreamSplit, encode_byte_stream_split)
+            }
+            Values::Float64(v) if encoding == ValueEncoding::ByteStreamSplit => {
+                encode_chunk_as(&v[vals], levels, cfg, d, Encoding::ByteStreamSplit, encode_byte_stream_split)
+            }
+            Values::Binary(v) if encoding == ValueEncoding::Delta => encode_chunk_as(&v[vals], levels, cfg, d, Encoding::DeltaByteArray, encode_delta_byte_array),
+            Values::Binary(v) if encoding == ValueEncoding::DeltaLength => encode_chunk_as(&v[vals], levels, cfg, d, Encoding::DeltaLengthByteArray, |v| {
+                encode_delta_length(&v.iter().map(Vec::as_slice).collect::<Vec<_>>())
+            }),
+            Values::Int32(v) => encode_chunk(&v[vals], levels, cfg, d, dictionary),
+            Values::Int64(v) => encode_chunk(&v[vals], levels, cfg, d, dictionary),
+            Values::Float32(v) => encode_chunk(&v[vals], levels, cfg, d, dictionary),
+            Values::Float64(v) => encode_chunk(&v[vals], levels, cfg, d, dictionary),
+            Values::Boolean(v) => encode_bool_chunk(&v[vals], levels, cfg, d, encoding != ValueEncoding::Plain),
+            Values::Binary(v) => encode_chunk(&v[vals], levels, cfg, d, dictionary),
+            Values::Decimal(v) => encode_chunk(&v[vals], levels, cfg, d, dictionary),
+            Values::Fixed(v) => encode_chunk(&v[vals], levels, cfg, d, dictionary),
+            Values::UInt32(v) => encode_chunk(&v[vals], levels, cfg, d, dictionary),
+            Values::UInt64(v) => encode_chunk(&v[vals], levels, cfg, d, dictionary),
+            Values::Float16(v) => encode_chunk(&v[vals], levels, cfg, d, dictionary),
+        }
+    }
+}
+
+// ── Sorting rows ────────────────────────────────────────────────────────────
+//
+// `sortBy` reorders the rows of every column before anything is encoded, so
+// runs of equal keys feed the dictionary and RLE, and each page's min/max
+// covers a narrow range. Keys compare like column statistics; nulls sort last
+// in both directions and ties keep their input order.
+
+impl Values {
+    /// Compare the values at `a` and `b` as statistics would
+    fn cmp_at(&self, a: usize, b: usize) -> Ordering {
+        match self {
+            Values::Int32(v) => v[a].stat_cmp(&v[b]),
+            Values::Int64(v) => v[a].stat_cmp(&v[b]),
+            Values::Float32(v) => v[a].stat_cmp(&v[b]),
+            Values::Float64(v) => v[a].stat_cmp(&v[b]),
+            Values::Boolean(v) => v[a].stat_cmp(&v[b]),
+            Values::Binary(v) => v[a].stat_cmp(&v[b]),
+            Values::Decimal(v) => v[a].stat_cmp(&v[b]),
+            Values::Fixed(v) => v[a].stat_cmp(&v[b]),
+            Values::UInt32(v) => v[a].stat_cmp(&v[b]),
+            Values::UInt64(v) => v[a].stat_cmp(&v[b]),
+            Values::Float16(v) => v[a].stat_cmp(&v[b]),
+        }
+    }
+
+    /// The values at `idx`, in that order
+    fn take(&self, idx: &[usize]) -> Values {
+        fn take<T: Clone>(v: &[T], idx: &[usize]) -> Vec<T> {
+            idx.iter().map(|&i| v[i].clone()).collect()
+        }
+        match self {
+            Values::Int32(v) => Values::Int32(take(v, idx)),
+            Values::Int64(v) => Values::Int64(take(v, idx)),
+            Values::Float32(v) => Values::Float32(take(v, idx)),
+            Values::Float64(v) => Values::Float64(take(v, idx)),
+            Values::Boolean(v) => Values::Boolean(take(v, idx)),
+            Values::Binary(v) => Values::Binary(take(v, idx)),
+            Values::Decimal(v) => Values::Decimal(take(v, idx)),
+            Values::Fixed(v) => Values::Fixed(take(v, idx)),
+            Values::UInt32(v) => Values::UInt32(take(v, idx)),
+            Values::UInt64(v) => Values::UInt64(take(v, idx)),
+            Values::Float16(v) => Values::Float16(take(v, idx)),
+        }
+    }
+}
+
+impl ColumnData {
+    /// Index of each row's value in a flat column (None for nulls)
+    fn row_values(&self) -> Vec<Option<usize>> {
+        match &self.validity {
+            Some(valid) => {
+                let mut k = 0;
+                valid
+                    .iter()
+                    .map(|&v| {
+                        k += v as usize;
+                        v.then_some(k - 1)
+                    })
+                    .collect()
+            }
+            None => (0..self.num_rows).map(Some).collect(),
+        }
+    }
+
+    /// Put the rows in `order` (old row index of every new row)
+    fn reorder(&mut self, order: &[usize]) {
+        if let Some(n) = &mut self.nested {
+            // Values of a row are the entries at max_def
+            let mut value_starts = Vec::with_capacity(n.row_starts.len());
+            let mut k = 0;
+            for w in n.row_starts.windows(2) {
+                value_starts.push(k);
+                k += n.def[w[0]..w[1]].iter().filter(|&&d| d == n.max_def).count();
+            }
+            value_starts.push(k);
+
+            let (mut def, mut rep, mut idx) = (Vec::with_capacity(n.def.len()), Vec::with_capacity(n.rep.len()), Vec::with_capacity(k));
+            let mut row_starts = Vec::with_capacity(n.row_starts.len());
+            for &r in order {
+                row_starts.push(def.len());
+                def.extend_from_slice(&n.def[n.row_starts[r]..n.row_starts[r + 1]]);
+                rep.extend_from_slice(&n.rep[n.row_starts[r]..n.row_starts[r + 1]]);
+                idx.extend(value_starts[r]..value_starts[r + 1]);
+            }
+            row_starts.push(def.len());
+            (n.def, n.rep, n.row_starts) = (def, rep, row_starts);
+            self.values = self.values.take(&idx);
+            return;
+        }
+        let rows = self.row_values();
+        let idx: Vec<usize> = order.iter().filter_map(|&r| rows[r]).collect();
+        self.values = self.values.take(&idx);
+        if let Some(valid) = &mut self.validity {
+            *valid = order.iter().map(|&r| valid[r]).collect();
+        }
+    }
+}
+
+/// Sort the rows of all `columns` by the key columns in `keys` (leaf index,
+/// descending)
+fn sort_rows(columns: &mut [ColumnData], keys: &[(usize, bool)], num_rows: usize) {
+    let rows: Vec<Vec<Option<usize>>> = keys.iter().map(|&(i, _)| columns[i].row_values()).collect();
+    let mut order: Vec<usize> = (0..num_rows).collect();
+    order.sort_by(|&a, &b| {
+        keys.iter()
+            .zip(&rows)
+            .map(|(&(i, descending), rows)| match (rows[a], rows[b]) {
+                (Some(x), Some(y)) if descending => columns[i].values.cmp_at(y, x),
+                (Some(x), Some(y)) => columns[i].values.cmp_at(x, y),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            })
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+    if order.iter().enumerate().all(|(i, &r)| i == r) {
+        return;
+    }
+    for c in columns {
+        c.reorder(&order);
+    }
+}
+
+// ── Nested columns ──────────────────────────────────────────────────────────
+
+/// A schema entry: a primitive column, or a nested type over child fields
+struct Field {
+    name: String,
+    optional: bool,
+    /// Iceberg/Delta field ID
+    id: Option<i32>,
+    kind: FieldKind,
+    /// Value for invalid cells with `coercion: 'default'` (primitive columns)
+    default: Option<JsValue>,
+}
+
+enum FieldKind {
+    /// Primitive column, with its isAdjustedToUTC flag
+    Leaf(ColType, bool),
+    /// LIST of `element`, written with the standard three-level layout
+    List(Box<Field>),
+    /// Group of named child fields
+    Struct(Vec<Field>),
+    /// MAP of `key` → `value` entries (the key is a required primitive)
+    Map(Box<Field>, Box<Field>),
+}
+
+impl Field {
+    /// Parse a schema entry named `name`; `path` names it in errors
+    fn from_js(col: &JsValue, name: String, path: &str) -> Result<Field, JsValue> {
+        let err = |msg: &str| JsValue::from_str(&format!("column '{}': {}", path, msg));
+        let type_str = Reflect::get(col, &"type".into())
+            .ok()
+            .and_then(|v| v.as_string())
+            .unwrap_or_else(|| "string".to_string());
+
+        // optional: true → nullable column with definition levels
+        let optional = Reflect::get(col, &"optional".into())
+            .map(|v| v.is_truthy())
+            .unwrap_or(false);
+
+        // id: Iceberg/Delta field ID, stored as the schema element's field_id
+        let id = match Reflect::get(col, &"id".into()).ok().filter(|v| !v.is_undefined()) {
+            None => None,
+            Some(v) => match v.as_f64() {
+                Some(n) if n.fract() == 0.0 && n >= i32::MIN as f64 && n <= i32::MAX as f64 => Some(n as i32),
+                _ => return Err(err("id must be a 32-bit integer")),
+            },
+        };
+
+        let kind = match type_str.as_str() {
+            "list" => {
+                let items = Reflect::get(col, &"items".into())?;
+                if items.is_undefined() || items.is_null() {
+                    return Err(err("list columns need 'items'"));
+                }
+                let element = Field::from_js(&type_entry(items)?, "element".to_string(), &format!("{}.list.element", path))?;
+                FieldKind::List(Box::new(element))
+            }
+            "map" => {
+                let (key, value) = (Reflect::get(col, &"key".into())?, Reflect::get(col, &"value".into())?);
+                if key.is_undefined() || key.is_null() || value.is_undefined() || value.is_null() {
+                    return Err(err("map columns need 'key' and 'value'"));
+                }
+                let key = Field::from_js(&type_entry(key)?, "key".to_string(), &format!("{}.key_value.key", path))?;
+                if key.optional || !matches!(key.kind, FieldKind::Leaf(..)) {
+                    return Err(err("map keys must be a required primitive type"));
+                }
+                let value = Field::from_js(&type_entry(value)?, "value".to_string(), &format!("{}.key_value.value", path))?;
+                FieldKind::Map(Box::new(key), Box::new(value))
+            }
+            "struct" => {
+                let children = Reflect::get(col, &"fields".into())?
+                    .dyn_into::<Array>()
+                    .map_err(|_| err("struct columns need a 'fields' array"))?;
+                if children.length() == 0 {
+                    return Err(err("struct columns need at least one field"));
+                }
+                let mut fields: Vec<Field> = Vec::with_capacity(children.length() as usize);
+                for child in children.iter() {
+                    let name = Reflect::get(&child, &"name".into())
+                        .ok()
+                        .and_then(|v| v.as_string())
+                        .ok_or_else(|| err("struct fields must have string 'name'"))?;
+                    if fields.iter().any(|f| f.name == name) {
+                        return Err(err(&format!("duplicate field '{}'", name)));
+                    }
+                    let child_path = format!("{}.{}", path, name);
+                    fields.push(Field::from_js(&child, name, &child_path)?);
+                }
+                FieldKind::Struct(fields)
+            }
+            _ => {
+                let ct = ColType::from_str(&type_str).map_err(|e| err(&e))?;
+                // isAdjustedToUTC: false → local (naive) timestamps/times
+                let utc = match Reflect::get(col, &"isAdjustedToUTC".into()).ok().filter(|v| !v.is_undefined()) {
+                    None => true,
+                    Some(_) if ct.time_unit().is_none() => {
+                        return Err(err("isAdjustedToUTC only applies to timestamp and time columns"))
+                    }
+                    Some(v) => v.is_truthy(),
+                };
+                FieldKind::Leaf(ct, utc)
+            }
+        };
+        let default = match (Reflect::get(col, &"default".into())?, &kind) {
+            (d, _) if d.is_undefined() => None,
+            (d, FieldKind::Leaf(ct, _)) if !d.is_null() && ct.accepts(&d) => Some(d),
+            (_, FieldKind::Leaf(ct, _)) => {
+                return Err(err(&format!("default must be {}", ct.expected().unwrap_or("a non-null value"))))
+            }
+            _ => return Err(err("default only applies to primitive columns")),
+        };
+        Ok(Field { name, optional, id, kind, default })
+    }
+
+    fn parquet_type(&self) -> ParquetType {
+        let field_info = FieldInfo {
+            name: self.name.clone(),
+            repetition: if self.optional { Repetition::Optional } else { Repetition::Required },
+            id: self.id,
+        };
+        match &self.kind {
+            FieldKind::Leaf(ct, utc) => ParquetType::PrimitiveType(PrimitiveType {
+                field_info,
+                logical_type: ct.logical_type(*utc),
+                converted_type: ct.converted_type(*utc),
+                physical_type: ct.physical_type(),
+            }),
+            FieldKind::List(element) => ParquetType::GroupType {
+                field_info,
+                logical_type: Some(GroupLogicalType::List),
+                converted_type: Some(GroupConvertedType::List),
+                fields: vec![ParquetType::GroupType {
+                    field_info: FieldInfo { name: "list".to_string(), repetition: Repetition::Repeated, id: None },
+                    logical_type: None,
+                    converted_type: None,
+                    fields: vec![element.parquet_type()],
+                }],
+            },
+            FieldKind::Struct(fields) => ParquetType::GroupType {
+                field_info,
+                logical_type: None,
+                converted_type: None,
+                fields: fields.iter().map(Field::parquet_type).collect(),
+            },
+            FieldKind::Map(key, value) => ParquetType::GroupType {
+                field_info,
+                logical_type: Some(GroupLogicalType::Map),
+                converted_type: Some(GroupConvertedType::Map),
+                fields: vec![ParquetType::GroupType {
+                    field_info: FieldInfo { name: "key_value".to_string(), repetition: Repetition::Repeated, id: None },
+                    logical_type: None,
+                    converted_type: None,
+                    fields: vec![key.parquet_type(), value.parquet_type()],
+                }],
+            },
+        }
+    }
+
+    /// Number of primitive columns under this field
+    fn num_leaves(&self) -> usize {
+        match &self.kind {
+            FieldKind::Leaf(..) => 1,
+            FieldKind::List(element) => element.num_leaves(),
+            FieldKind::Struct(fields) => fields.iter().map(Field::num_leaves).sum(),
+            FieldKind::Map(_, value) => 1 + value.num_leaves(),
+        }
+    }
+
+    /// Dotted path and type of every primitive column under this field, in
+    /// schema order
+    fn leaves<'a>(&'a self, path: &str, out: &mut Vec<(String, &'a ColType)>) {
+        match &self.kind {
+            FieldKind::Leaf(ct, _) => out.push((path.to_string(), ct)),
+            FieldKind::List(element) => element.leaves(&format!("{}.list.element", path), out),
+            FieldKind::Struct(fields) => {
+                for f in fields {
+                    f.leaves(&format!("{}.{}", path, f.name), out);
+                }
+            }
+            FieldKind::Map(key, value) => {
+                key.leaves(&format!("{}.key_value.key", path), out);
+                value.leaves(&format!("{}.key_value.value", path), out);
+            }
+        }
+    }
+
+    /// Append one value of this field to the levels of its leaves (`bufs`).
+    /// `rep` is the repetition level of the first entry, `def` the definition
+    /// level reached by the parent and `depth` the number of repeated ancestors.
+    fn shred(&self, v: JsValue, rep: u32, def: u32, depth: u32, bufs: &mut [Shredded], row: usize) -> Result<(), String> {
+        if v.is_null() || v.is_undefined() {
+            if !self.optional {
+                return Err(format!("null value for required field '{}'", self.name));
+            }
+            bufs.iter_mut().for_each(|b| b.push_empty(rep, def));
+            return Ok(());
+        }
+        let def = def + self.optional as u32;
+        match &self.kind {
+            FieldKind::Leaf(..) => bufs[0].push(v, rep, def, row),
+            FieldKind::List(element) => {
+                let items = if Array::is_array(&v) || js_sys::ArrayBuffer::is_view(&v) {
+                    Array::from(&v)
+                } else {
+                    return Err(format!("expected an array for list '{}', got {:?}", self.name, v));
+                };
+                if items.length() == 0 {
+                    bufs.iter_mut().for_each(|b| b.push_empty(rep, def));
+                }
+                for (i, item) in items.iter().enumerate() {
+                    let rep = if i == 0 { rep } else { depth + 1 };
+                    element.shred(item, rep, def + 1, depth + 1, bufs, row)?;
+                }
+            }
+            FieldKind::Struct(fields) => {
+                if !v.is_object() || Array::is_array(&v) {
+                    return Err(format!("expected an object for struct '{}', got {:?}", self.name, v));
+                }
+                let mut bufs = &mut bufs[..];
+                for f in fields {
+                    let (mine, rest) = bufs.split_at_mut(f.num_leaves());
+                    f.shred(Reflect::get(&v, &JsValue::from_str(&f.name)).unwrap_or(JsValue::UNDEFINED), rep, def, depth, mine, row)?;
+                    bufs = rest;
+                }
+            }
+            FieldKind::Map(key, value) => {
+                // JS Maps keep their keys' types; plain objects have string keys
+                let entries = match v.dyn_ref::<js_sys::Map>() {
+                    Some(map) => Array::from(&map.entries()),
+                    None if v.is_object() && !Array::is_array(&v) => Object::entries(v.unchecked_ref()),
+                    None => return Err(format!("expected an object or Map for map '{}', got {:?}", self.name, v)),
+                };
+                if entries.length() == 0 {
+                    bufs.iter_mut().for_each(|b| b.push_empty(rep, def));
+                }
+                let (keys, values) = bufs.split_at_mut(1);
+                for (i, entry) in entries.iter().enumerate() {
+                    let entry: Array = entry.unchecked_into();
+                    let rep = if i == 0 { rep } else { depth + 1 };
+                    key.shred(entry.get(0), rep, def + 1, depth + 1, keys, row)?;
+                    value.shred(entry.get(1), rep, def + 1, depth + 1, values, row)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A nested schema entry; a type name is shorthand for `{ type }`
+fn type_entry(v: JsValue) -> Result<JsValue, JsValue> {
+    if v.as_string().is_none() {
+        return Ok(v);
+    }
+    let obj = Object::new();
+    Reflect::set(&obj, &"type".into(), &v)?;
+    Ok(obj.into())
+}
+
+/// A leaf of a nested column while its rows are being shredded
+#[derive(Default)]
+struct Shredded {
+    /// Non-null values, and the row each came from
+    values: Vec<JsValue>,
+    rows: Vec<usize>,
+    def: Vec<u32>,
+    rep: Vec<u32>,
+    row_starts: Vec<usize>,
+}
+
+impl Shredded {
+    fn push(&mut self, v: JsValue, rep: u32, def: u32, row: usize) {
+        self.values.push(v);
+        self.rows.push(row);
+        self.push_empty(rep, def);
+    }
+
+    /// A null or empty list: levels without a value
+    fn push_empty(&mut self, rep: u32, def: u32) {
+        self.rep.push(rep);
+        self.def.push(def);
+    }
+}
+
+impl ColumnData {
+    /// Shred the rows of nested column `field` (`arr`) into one ColumnData per
+    /// leaf. `descs` starts with the leaves' descriptors, for their max levels.
+    fn from_nested(field: &Field, arr: &Array, descs: &[ColumnDescriptor], cfg: &WriteConfig) -> Result<Vec<Self>, JsValue> {
+        let num_rows = arr.length() as usize;
+        let mut leaves = Vec::new();
+        field.leaves(&field.name, &mut leaves);
+        let mut bufs: Vec<Shredded> = leaves.iter().map(|_| Shredded::default()).collect();
+        for row in 0..num_rows {
+            bufs.iter_mut().for_each(|b| b.row_starts.push(b.def.len()));
+            field
+                .shred(arr.get(row as u32), 0, 0, 0, &mut bufs, row)
+                .map_err(|e| JsValue::from_str(&format!("column '{}' row {}: {}", field.name, row, e)))?;
+        }
+        let mut out = Vec::with_capacity(bufs.len());
+        for ((mut buf, (path, ct)), desc) in bufs.into_iter().zip(leaves).zip(descs) {
+            buf.row_starts.push(buf.def.len());
+            let values: Array = buf.values.iter().collect();
+            let name = CellName { name: &path, rows: Some(&buf.rows) };
+            let mut c = ColumnData::from_js(&values, &name, ct, false, None, cfg)?;
+            c.nested = Some(Nested {
+                def: buf.def,
+                rep: buf.rep,
+                max_def: desc.descriptor.max_def_level as u32,
+                max_rep: desc.descriptor.max_rep_level as u32,
+                row_starts: buf.row_starts,
+            });
+            c.num_rows = num_rows;
+            out.push(c);
+        }
+        Ok(out)
+    }
+}
+
+/// Rows per row group unless `rowGroupSize` says otherwise (arrow-rs default)
+const DEFAULT_ROW_GROUP_SIZE: usize = 1024 * 1024;
+
+/// Target uncompressed data page size unless `dataPageSize` says otherwise (parquet-mr default)
+const DEFAULT_DATA_PAGE_SIZE: usize = 1024 * 1024;
+
+/// Max dictionary page size unless `dictionaryPageSize` says otherwise (parquet-mr default)
+const DEFAULT_DICTIONARY_PAGE_SIZE: usize = 1024 * 1024;
+
+/// Parsed `writeParquet` config
+struct WriteConfig {
+    compression: Compression,
+    /// Dictionary-encode string and numeric columns (default true)
+    dictionary: bool,
+    /// Max PLAIN bytes of a dictionary before pages fall back to PLAIN
+    dictionary_page_size: usize,
+    /// Max distinct values per value in a chunk's first page for it to get
+    /// a dictionary (default 1: any)
+    dictionary_ratio: f64,
+    /// Attach min/max/null_count to pages and column chunks (default true)
+    statistics: bool,
+    /// Max rows per row group
+    row_group_size: usize,
+    /// Target uncompressed bytes per data page
+    data_page_size: usize,
+    /// Max rows per data page
+    data_page_rows: usize,
+    /// Data page header format (default V1)
+    version: Version,
+    /// Footer key_value_metadata
+    metadata: Option<Vec<KeyValue>>,
+    /// Footer created_by string (default unset)
+    created_by: Option<String>,
+    /// Name of the root schema node (default "schema")
+    schema_name: String,
+    /// Store a CRC-32 in every page header (default false)
+    page_checksums: bool,
+    /// Reject cells that can't be stored exactly (default false)
+    strict: bool,
+    /// What happens to cells a column can't take as they are (default
+    /// `Coerce`, or `Error` with `strict`)
+    coercion: Coercion,
+    /// Embed the schema as Arrow types under `ARROW:schema` (default false)
+    arrow_schema: bool,
+    /// Called with the bytes of each finished row group and then the footer,
+    /// instead of collecting the file (default unset)
+    sink: Option<js_sys::Function>,
+}
+
+impl WriteConfig {
+    fn from_js(config_js: &JsValue) -> Result<Self, JsValue> {
+        Ok(WriteConfig {
+            compression: parse_compression(config_js)?,
+            dictionary: bool_option(config_js, "dictionary", true),
+            dictionary_page_size: positive_option(config_js, "dictionaryPageSize")?.unwrap_or(DEFAULT_DICTIONARY_PAGE_SIZE),
+            dictionary_ratio: parse_dictionary_ratio(config_js)?,
+            statistics: bool_option(config_js, "statistics", true),
+            row_group_size: positive_option(config_js, "rowGroupSize")?.unwrap_or(DEFAULT_ROW_GROUP_SIZE),
+            data_page_size: positive_option(config_js, "dataPageSize")?.unwrap_or(DEFAULT_DATA_PAGE_SIZE),
+            data_page_rows: positive_option(config_js, "dataPageRows")?.unwrap_or(usize::MAX),
+            version: parse_version(config_js)?,
+            metadata: parse_metadata(config_js)?,
+            created_by: string_option(config_js, "createdBy")?,
+            schema_name: string_option(config_js, "schemaName")?.unwrap_or_else(|| "schema".to_string()),
+            page_checksums: bool_option(config_js, "pageChecksums", false),
+            strict: bool_option(config_js, "strict", false),
+            coercion: parse_coercion(config_js)?,
+            arrow_schema: bool_option(config_js, "arrowSchema", false),
+            sink: parse_sink(config_js)?,
+        })
+    }
+}
+
+/// Read the options that pick value encodings, one per leaf column:
+/// `deltaEncoding` (leaf column paths, or `{ path: true | 'length' }`),
+/// `byteStreamSplit` (leaf column paths), then
+/// `encodings: { path: 'dictionary' | 'plain' | 'delta' | 'byte_stream_split' }`.
+fn parse_value_encodings(config_js: &JsValue, leaves: &[(String, &ColType)]) -> Result<Vec<ValueEncoding>, JsValue> {
+    let mut encodings = vec![ValueEncoding::Default; leaves.len()];
+
+    let v = Reflect::get(config_js, &"deltaEncoding".into()).unwrap_or(JsValue::UNDEFINED);
+    if !v.is_undefined() && !v.is_null() {
+        let bad = || JsValue::from_str("deltaEncoding must be an array of column names, or an object like { column: true | 'length' }");
+        let entries: Vec<(String, JsValue)> = match v.dyn_ref::<Array>() {
+            Some(arr) => arr.iter().map(|c| c.as_string().map(|s| (s, JsValue::TRUE))).collect::<Option<_>>().ok_or_else(bad)?,
+            None if v.is_object() => object_entries(&v),
+            None => return Err(bad()),
+        };
+        for (column, mode) in entries {
+            let encoding = match mode.as_string().as_deref() {
+                _ if mode.is_undefined() || mode.is_null() || mode == JsValue::FALSE => continue,
+                None if mode == JsValue::TRUE => ValueEncoding::Delta,
+                Some("length") => ValueEncoding::DeltaLength,
+                _ => return Err(JsValue::from_str(&format!("deltaEncoding: '{}' must map to true or 'length'", column))),
+            };
+            set_value_encoding(&mut encodings, leaves, "deltaEncoding", &column, encoding)?;
+        }
+    }
+
+    let v = Reflect::get(config_js, &"byteStreamSplit".into()).unwrap_or(JsValue::UNDEFINED);
+    if !v.is_undefined() && !v.is_null() {
+        let columns: Vec<String> = v
+            .dyn_ref::<Array>()
+            .and_then(|arr| arr.iter().map(|c| c.as_string()).collect())
+            .ok_or_else(|| JsValue::from_str("byteStreamSplit must be an array of column names"))?;
+        for column in columns {
+            set_value_encoding(&mut encodings, leaves, "byteStreamSplit", &column, ValueEncoding::ByteStreamSplit)?;
+        }
+    }
+
+    let v = Reflect::get(config_js, &"encodings".into()).unwrap_or(JsValue::UNDEFINED);
+    if !v.is_undefined() && !v.is_null() {
+        if !v.is_object() || v.is_array() {
+            return Err(JsValue::from_str("encodings must be an object like { column: 'dictionary' | 'plain' | 'delta' | 'byte_stream_split' }"));
+        }
+        for (column, name) in object_entries(&v) {
+            let encoding = match name.as_string().as_deref() {
+                _ if name.is_undefined() || name.is_null() => continue,
+                Some("dictionary") => ValueEncoding::Dictionary,
+                Some("plain") => ValueEncoding::Plain,
+                Some("delta") => ValueEncoding::Delta,
+                Some("byte_stream_split") => ValueEncoding::ByteStreamSplit,
+                _ => {
+                    return Err(JsValue::from_str(&format!(
+                        "encodings: '{}' must map to 'dictionary', 'plain', 'delta' or 'byte_stream_split'",
+                        column
+                    )))
+                }
+            };
+            set_value_encoding(&mut encodings, leaves, "encodings", &column, encoding)?;
+        }
+    }
+    Ok(encodings)
+}
+
+/// Entries of a plain JS object, keys as strings
+fn object_entries(v: &JsValue) -> Vec<(String, JsValue)> {
+    Object::entries(v.unchecked_ref::<Object>())
+        .iter()
+        .map(|e| {
+            let e: Array = e.into();
+            (e.get(0).as_string().unwrap_or_default(), e.get(1))
+        })
+        .collect()
+}
+
+/// Give leaf column `column` the `encoding` that config option `option` asks
+/// for, if its type takes it and no other option gave it a different one
+fn set_value_encoding(
+    encodings: &mut [ValueEncoding],
+    leaves: &[(String, &ColType)],
+    option: &str,
+    column: &str,
+    encoding: ValueEncoding,
+) -> Result<(), JsValue> {
+    let idx = leaves
+        .iter()
+        .position(|(path, _)| path == column)
+        .ok_or_else(|| JsValue::from_str(&format!("{}: unknown column '{}'", option, column)))?;
+    let (ct, physical) = (leaves[idx].1, leaves[idx].1.physical_type());
+    let problem = match encoding {
+        ValueEncoding::Dictionary if matches!(ct, ColType::Boolean) => Some("is boolean, which has no dictionary encoding"),
+        ValueEncoding::Delta if !matches!(physical, PhysicalType::Int32 | PhysicalType::Int64 | PhysicalType::ByteArray) => {
+            Some("isn't stored as int32, int64 or a byte array")
+        }
+        ValueEncoding::DeltaLength if physical != PhysicalType::ByteArray => Some("isn't a byte array, which 'length' is for"),
+        ValueEncoding::ByteStreamSplit if !matches!(ct, ColType::Float32 | ColType::Float64) => Some("isn't float32 or float64"),
+        _ => None,
+    };
+    if let Some(problem) = problem {
+        return Err(JsValue::from_str(&format!("{}: column '{}' {}", option, column, problem)));
+    }
+    if encodings[idx] != ValueEncoding::Default && encodings[idx] != encoding {
+        return Err(JsValue::from_str(&format!("{}: column '{}' already has an encoding from another option", option, column)));
+    }
+    encodings[idx] = encoding;
+    Ok(())
+}
+
+/// Read a size/count config option that must be >= 1
+fn positive_option(config_js: &JsValue, key: &str) -> Result<Option<usize>, JsValue> {
+    match Reflect::get(config_js, &JsValue::from_str(key)).ok().and_then(|v| v.as_f64()) {
+        Some(n) if n >= 1.0 => Ok(Some(n as usize)),
+        Some(n) => Err(JsValue::from_str(&format!("{} must be >= 1, got {}", key, n))),
+        None => Ok(None),
+    }
+}
+
+/// Read `dictionaryRatio`: a fraction in (0, 1], default 1
+fn parse_dictionary_ratio(config_js: &JsValue) -> Result<f64, JsValue> {
+    let v = Reflect::get(config_js, &"dictionaryRatio".into()).unwrap_or(JsValue::UNDEFINED);
+    if v.is_undefined() || v.is_null() {
+        return Ok(1.0);
+    }
+    match v.as_f64() {
+        Some(r) if r > 0.0 && r <= 1.0 => Ok(r),
+        Some(r) => Err(JsValue::from_str(&format!("dictionaryRatio must be in (0, 1], got {}", r))),
+        None => Err(JsValue::from_str("dictionaryRatio must be a number")),
+    }
+}
+
+/// Read an optional string config option
+fn string_option(config_js: &JsValue, key: &str) -> Result<Option<String>, JsValue> {
+    let v = Reflect::get(config_js, &JsValue::from_str(key)).unwrap_or(JsValue::UNDEFINED);
+    if v.is_undefined() {
+        return Ok(None);
+    }
+    v.as_string()
+        .map(Some)
+        .ok_or_else(|| JsValue::from_str(&format!("{} must be a string", key)))
+}
+
+/// Read `sink`: a function taking each chunk of the file as a Uint8Array
+fn parse_sink(config_js: &JsValue) -> Result<Option<js_sys::Function>, JsValue> {
+    let v = Reflect::get(config_js, &"sink".into()).unwrap_or(JsValue::UNDEFINED);
+    if v.is_undefined() || v.is_null() {
+        return Ok(None);
+    }
+    v.dyn_into::<js_sys::Function>()
+        .map(Some)
+        .map_err(|_| JsValue::from_str("sink must be a function or a WritableStream"))
+}
+
+/// Read a boolean config flag.
+/// Note: Reflect::get returns Ok(undefined) when key is missing, NOT Err
+fn bool_option(config_js: &JsValue, key: &str, default: bool) -> bool {
+    match Reflect::get(config_js, &JsValue::from_str(key)) {
+        Ok(v) if !v.is_undefined() => v.is_truthy(), // explicit true/false
+        _ => default, // key not present → default
+    }
+}
+
+/// Page codecs: one for the file, and overrides for some leaf columns
+struct Compression {
+    default: CompressionOptions,
+    /// (dotted leaf path, codec)
+    per_column: Vec<(String, CompressionOptions)>,
+}
+
+impl Compression {
+    /// Codec for the leaf column at `path`
+    fn column(&self, path: &str) -> CompressionOptions {
+        self.per_column
+            .iter()
+            .find(|(p, _)| p == path)
+            .map_or(self.default, |(_, c)| *c)
+    }
+
+    /// Whether any column is compressed. V2 pages carry this rather than
+    /// their own column's codec: an UNCOMPRESSED page flagged as compressed
+    /// still reads back as it is.
+    fn any(&self) -> bool {
+        self.default != CompressionOptions::Uncompressed
+            || self.per_column.iter().any(|(_, c)| *c != CompressionOptions::Uncompressed)
+    }
+}
+
+/// Read `compression` (+ optional `compressionLevel`) from the write config:
+/// a codec name, or `{ default, perColumn: { path: codec } }`. Unknown or
+/// missing codec names fall back to snappy, except in `perColumn`. Levels are
+/// validated against the codec's range: gzip 0-10, zstd 1-22, brotli 0-11
+/// (ignored by the rest) and apply to every column.
+fn parse_compression(config_js: &JsValue) -> Result<Compression, JsValue> {
+    let comp = Reflect::get(config_js, &"compression".into()).unwrap_or(JsValue::UNDEFINED);
+    if !comp.is_object() {
+        return Ok(Compression {
+            default: parse_codec(comp.as_string().as_deref(), config_js)?.unwrap_or(CompressionOptions::Snappy),
+            per_column: Vec::new(),
+        });
+    }
+    if comp.is_array() {
+        return Err(JsValue::from_str("compression must be a codec name or { default, perColumn }"));
+    }
+
+    let default = Reflect::get(&comp, &"default".into()).unwrap_or(JsValue::UNDEFINED);
+    let default = parse_codec(default.as_string().as_deref(), config_js)?.unwrap_or(CompressionOptions::Snappy);
+    let mut per_column = Vec::new();
+    let columns = Reflect::get(&comp, &"perColumn".into()).unwrap_or(JsValue::UNDEFINED);
+    if !columns.is_undefined() && !columns.is_null() {
+        if !columns.is_object() || columns.is_array() {
+            return Err(JsValue::from_str("compression.perColumn must be an object like { column: 'zstd' }"));
+        }
+        for (column, name) in object_entries(&columns) {
+            let codec = parse_codec(name.as_string().as_deref(), config_js)?.ok_or_else(|| {
+                JsValue::from_str(&format!(
+                    "compression.perColumn: '{}' must map to 'snappy', 'gzip', 'zstd', 'lz4', 'brotli' or 'none'",
+                    column
+                ))
+            })?;
+            per_column.push((column, codec));
+        }
+    }
+    Ok(Compression { default, per_column })
+}
+
+/// Codec called `name`, at `compressionLevel` if the config sets one; None
+/// for unknown names. Only snappy and none are built in without the `codecs`
+/// feature.
+fn parse_codec(name: Option<&str>, config_js: &JsValue) -> Result<Option<CompressionOptions>, JsValue> {
+    let level = match Reflect::get(config_js, &"compressionLevel".into())
+        .ok()
+        .and_then(|v| v.as_f64())
+    {
+        Some(l) if l < 0.0 || l.fract() != 0.0 => {
+            return Err(JsValue::from_str(&format!(
+                "compressionLevel must be a non-negative integer, got {}",
+                l
+            )))
+        }
+        l => l.map(|l| l as u32),
+    };
+    let bad_level = |e: parquet2::error::Error| JsValue::from_str(&format!("compressionLevel: {}", e));
+    #[cfg(not(feature = "codecs"))]
+    if let Some(name @ ("gzip" | "zstd" | "lz4" | "lz4_raw" | "brotli")) = name {
+        return Err(JsValue::from_str(&format!(
+            "compression '{}' needs the full writer: import from 'tiny-parquet/writer-full'",
+            name
+        )));
+    }
+
+    Ok(Some(match name {
+        Some("snappy") => CompressionOptions::Snappy,
+        Some("gzip") => CompressionOptions::Gzip(
+            level
+                .map(|l| GzipLevel::try_new(l.min(u8::MAX as u32) as u8))
+                .transpose()
+                .map_err(bad_level)?,
+        ),
+        Some("zstd") => CompressionOptions::Zstd(
+            level.map(|l| ZstdLevel::try_new(l as i32)).transpose().map_err(bad_level)?,
+        ),
+        Some("lz4") | Some("lz4_raw") => CompressionOptions::Lz4Raw,
+        Some("brotli") => CompressionOptions::Brotli(
+            level.map(BrotliLevel::try_new).transpose().map_err(bad_level)?,
+        ),
+        Some("none") => CompressionOptions::Uncompressed,
+        _ => return Ok(None),
+    }))
+}
+
+/// Policy for cells of the wrong JS type, NaN in non-float columns, and null
+/// or undefined in required ones
+#[derive(Clone, Copy, PartialEq)]
+enum Coercion {
+    /// Convert them like JS would (0, '', false, ...)
+    Coerce,
+    /// Write null (optional columns; an error in required ones)
+    Null,
+    /// Write the column's `default`, else null in optional columns
+    Default,
+    /// Reject them
+    Error,
+}
+
+/// Parse `coercion`; the default is `Coerce`, or `Error` with `strict: true`
+fn parse_coercion(config_js: &JsValue) -> Result<Coercion, JsValue> {
+    let coercion = Reflect::get(config_js, &"coercion".into()).unwrap_or(JsValue::UNDEFINED);
+    if coercion.is_undefined() {
+        let strict = bool_option(config_js, "strict", false);
+        return Ok(if strict { Coercion::Error } else { Coercion::Coerce });
+    }
+    match coercion.as_string().as_deref() {
+        Some("coerce") => Ok(Coercion::Coerce),
+        Some("null") => Ok(Coercion::Null),
+        Some("default") => Ok(Coercion::Default),
+        Some("error") => Ok(Coercion::Error),
+        _ => Err(JsValue::from_str(&format!(
+            "coercion must be 'coerce', 'null', 'default' or 'error', got {:?}",
+            coercion
+        ))),
+    }
+}
+
+/// Parse `version`: "v1" (default) or "v2" data pages
+fn parse_version(config_js: &JsValue) -> Result<Version, JsValue> {
+    let version = Reflect::get(config_js, &"version".into()).unwrap_or(JsValue::UNDEFINED);
+    if version.is_undefined() {
+        return Ok(Version::V1);
+    }
+    match version.as_string().as_deref() {
+        Some("v1") => Ok(Version::V1),
+        Some("v2") => Ok(Version::V2),
+        _ => Err(JsValue::from_str(&format!("version must be 'v1' or 'v2', got {:?}", version))),
+    }
+}
+
+/// Parse `metadata: { key: value }` into footer key-value pairs.
+/// Values must be strings; `null`/`undefined` store a key without a value.
+fn parse_metadata(config_js: &JsValue) -> Result<Option<Vec<KeyValue>>, JsValue> {
+    let meta = Reflect::get(config_js, &"metadata".into()).unwrap_or(JsValue::UNDEFINED);
+    if meta.is_undefined() || meta.is_null() {
+        return Ok(None);
+    }
+    let obj = meta
+        .dyn_ref::<Object>()
+        .ok_or_else(|| JsValue::from_str("metadata must be an object"))?;
+    let mut kvs = Vec::new();
+    for entry in Object::entries(obj).iter() {
+        let entry = Array::from(&entry);
+        let key = entry.get(0).as_string().unwrap_or_default();
+        let value = entry.get(1);
+        let value = if value.is_undefined() || value.is_null() {
+            None
+        } else {
+            Some(value.as_string().ok_or_else(|| {
+                JsValue::from_str(&format!("metadata['{}'] must be a string", key))
+            })?)
+        };
+        kvs.push(KeyValue { key, value });
+    }
+    Ok(Some(kvs))
+}
+
+/// Parse `sortedBy: [{ column, descending?, nullsFirst? }]` into sorting_columns.
+/// Only records the order; rows are written as given.
+fn parse_sorted_by(config_js: &JsValue, col_names: &[String]) -> Result<Option<Vec<SortingColumn>>, JsValue> {
+    let sorted = Reflect::get(config_js, &"sortedBy".into()).unwrap_or(JsValue::UNDEFINED);
+    if sorted.is_undefined() || sorted.is_null() {
+        return Ok(None);
+    }
+    let arr = sorted
+        .dyn_ref::<Array>()
+        .ok_or_else(|| JsValue::from_str("sortedBy must be an array"))?;
+    let mut cols = Vec::with_capacity(arr.length() as usize);
+    for entry in arr.iter() {
+        let column = Reflect::get(&entry, &"column".into())
+            .ok()
+            .and_then(|v| v.as_string())
+            .ok_or_else(|| JsValue::from_str("sortedBy entries need a string 'column'"))?;
+        let idx = col_names
+            .iter()
+            .position(|n| *n == column)
+            .ok_or_else(|| JsValue::from_str(&format!("sortedBy: unknown column '{}'", column)))?;
+        cols.push(SortingColumn {
+            column_idx: idx as i32,
+            descending: bool_option(&entry, "descending", false),
+            nulls_first: bool_option(&entry, "nullsFirst", false),
+        });
+    }
+    Ok(Some(cols))
+}
+
+/// Parse `sortBy`: top-level primitive columns to sort rows by, each name
+/// prefixed with `-` for descending order. Returns (leaf index, descending).
+fn parse_sort_by(config_js: &JsValue, fields: &[Field], leaf_paths: &[String]) -> Result<Option<Vec<(usize, bool)>>, JsValue> {
+    let v = Reflect::get(config_js, &"sortBy".into()).unwrap_or(JsValue::UNDEFINED);
+    if v.is_undefined() || v.is_null() {
+        return Ok(None);
+    }
+    let names: Vec<String> = v
+        .dyn_ref::<Array>()
+        .and_then(|arr| arr.iter().map(|c| c.as_string()).collect())
+        .ok_or_else(|| JsValue::from_str("sortBy must be an array of column names, '-' first for descending"))?;
+    let mut keys = Vec::with_capacity(names.len());
+    for name in names {
+        let (column, descending) = match name.strip_prefix('-') {
+            Some(column) => (column, true),
+            None => (name.as_str(), false),
+        };
+        let idx = leaf_paths
+            .iter()
+            .position(|p| p == column)
+            .ok_or_else(|| JsValue::from_str(&format!("sortBy: unknown column '{}'", column)))?;
+        if !fields.iter().any(|f| f.name == column && matches!(f.kind, FieldKind::Leaf(..))) {
+            return Err(JsValue::from_str(&format!("sortBy: column '{}' is nested; only top-level columns can be sort keys", column)));
+        }
+        keys.push((idx, descending));
+    }
+    Ok(Some(keys))
+}
+
+// ── GeoParquet ──────────────────────────────────────────────────────────────
+//
+// `geometry` columns hold ISO WKB (GeoJSON geometries are encoded on the way
+// in) and are listed in the `geo` footer entry of the GeoParquet 1.1 spec,
+// with the geometry types and bounding box of everything written to them.
+
+/// WKB geometry type codes 1-7, as GeoParquet names them
+const GEOMETRY_TYPES: [&str; 7] = ["Point", "LineString", "Polygon", "MultiPoint", "MultiLineString", "MultiPolygon", "GeometryCollection"];
+
+/// Deepest nesting of geometry collections taken
+const MAX_GEOMETRY_DEPTH: usize = 32;
+
+/// A top-level `geometry` column and what has been written to it
+struct GeoColumn {
+    /// Leaf column index
+    leaf: usize,
+    name: String,
+    /// The schema entry's `crs` (undefined: OGC:CRS84, the spec's default)
+    crs: JsValue,
+    /// 'planar' or 'spherical' (unset: planar)
+    edges: Option<String>,
+    /// GeoParquet names of the geometry types seen, e.g. "Polygon Z"
+    types: Vec<String>,
+    /// [xmin, ymin, xmax, ymax] of every coordinate seen
+    bbox: [f64; 4],
+}
+
+impl GeoColumn {
+    /// Record the types and extent of this column's values in `values`
+    fn add(&mut self, values: &Values) {
+        let Values::Binary(v) = values else { return };
+        for wkb in v {
+            if let Ok(name) = wkb_summary(wkb, &mut self.bbox) {
+                if !self.types.contains(&name) {
+                    self.types.push(name);
+                }
+            }
+        }
+    }
+
+    /// This column's entry under `columns` in the `geo` metadata
+    fn to_js(&self) -> Result<JsValue, JsValue> {
+        let obj = Object::new();
+        Reflect::set(&obj, &"encoding".into(), &"WKB".into())?;
+        let mut types = self.types.clone();
+        types.sort();
+        Reflect::set(&obj, &"geometry_types".into(), &types.iter().map(JsValue::from).collect::<Array>())?;
+        if self.bbox[0] <= self.bbox[2] {
+            Reflect::set(&obj, &"bbox".into(), &self.bbox.iter().map(|&c| JsValue::from_f64(c)).collect::<Array>())?;
+        }
+        if !self.crs.is_undefined() {
+            Reflect::set(&obj, &"crs".into(), &self.crs)?;
+        }
+        if let Some(edges) = &self.edges {
+            Reflect::set(&obj, &"edges".into(), &edges.into())?;
+        }
+        Ok(obj.into())
+    }
+}
+
+/// Find the `geometry` columns among the top-level schema entries, with their
+/// `crs` and `edges`. Geometry columns inside lists, structs and maps are an
+/// error: GeoParquet only describes top-level ones.
+fn parse_geo_columns(schema_arr: &Array, fields: &[Field], leaves: &[(String, &ColType)]) -> Result<Vec<GeoColumn>, JsValue> {
+    let mut geo = Vec::new();
+    for (leaf, (path, ct)) in leaves.iter().enumerate() {
+        if !matches!(ct, ColType::Geometry) {
+            continue;
+        }
+        let Some(i) = fields.iter().position(|f| f.name == *path && matches!(f.kind, FieldKind::Leaf(..))) else {
+            return Err(JsValue::from_str(&format!("column '{}': geometry columns must be top-level", path)));
+        };
+        let col = schema_arr.get(i as u32);
+        let crs = Reflect::get(&col, &"crs".into())?;
+        let edges = match Reflect::get(&col, &"edges".into())?.as_string().as_deref() {
+            None => None,
+            Some(e @ ("planar" | "spherical")) => Some(e.to_string()),
+            Some(_) => return Err(JsValue::from_str(&format!("column '{}': edges must be 'planar' or 'spherical'", path))),
+        };
+        geo.push(GeoColumn { leaf, name: path.clone(), crs, edges, types: Vec::new(), bbox: [f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY] });
+    }
+    Ok(geo)
+}
+
+/// The `geo` footer entry for `columns`, the first being the primary column
+fn geo_metadata(columns: &[GeoColumn]) -> Result<String, JsValue> {
+    let cols = Object::new();
+    for c in columns {
+        Reflect::set(&cols, &c.name.as_str().into(), &c.to_js()?)?;
+    }
+    let geo = Object::new();
+    Reflect::set(&geo, &"version".into(), &"1.1.0".into())?;
+    Reflect::set(&geo, &"primary_column".into(), &columns[0].name.as_str().into())?;
+    Reflect::set(&geo, &"columns".into(), &cols)?;
+    Ok(String::from(js_sys::JSON::stringify(&geo)?))
+}
+
+/// Convert a geometry cell: WKB bytes are checked and taken as they are,
+/// GeoJSON geometry objects are encoded as little-endian ISO WKB
+fn geometry_value(x: JsValue) -> Result<Vec<u8>, String> {
+    if let Some(bytes) = x.dyn_ref::<Uint8Array>() {
+        let wkb = bytes.to_vec();
+        wkb_summary(&wkb, &mut [0.0; 4])?;
+        return Ok(wkb);
+    }
+    if !x.is_object() || x.is_array() {
+        return Err(format!("expected a GeoJSON geometry or WKB Uint8Array, got {:?}", x));
+    }
+    let mut wkb = Vec::new();
+    geojson_wkb(&x, &mut wkb, 0)?;
+    Ok(wkb)
+}
+
+/// Append GeoJSON geometry `g` to `out` as WKB
+fn geojson_wkb(g: &JsValue, out: &mut Vec<u8>, depth: usize) -> Result<(), String> {
+    let get = |key: &str| Reflect::get(g, &key.into()).unwrap_or(JsValue::UNDEFINED);
+    let kind = get("type").as_string().unwrap_or_default();
+    let code = GEOMETRY_TYPES
+        .iter()
+        .position(|t| *t == kind)
+        .ok_or_else(|| format!("unknown GeoJSON geometry type {:?}", kind))? as u32
+        + 1;
+    if code == 7 {
+        if depth >= MAX_GEOMETRY_DEPTH {
+            return Err("geometry collections nested too deeply".to_string());
+        }
+        let members = get("geometries").dyn_into::<Array>().map_err(|_| "GeometryCollection needs a 'geometries' array")?;
+        let mut body = Vec::new();
+        let mut z = None;
+        for m in members.iter() {
+            let start = body.len();
+            geojson_wkb(&m, &mut body, depth + 1)?;
+            z.get_or_insert(u32::from_le_bytes(body[start + 1..start + 5].try_into().unwrap()) > 1000);
+        }
+        out.push(1);
+        out.extend_from_slice(&(code + if z == Some(true) { 1000 } else { 0 }).to_le_bytes());
+        out.extend_from_slice(&members.length().to_le_bytes());
+        out.extend_from_slice(&body);
+        return Ok(());
+    }
+
+    // Nesting of `coordinates`: a position, or arrays of them 1-3 deep
+    let nesting = [0, 1, 2, 1, 2, 3][code as usize - 1];
+    let coords = get("coordinates");
+    if !coords.is_array() {
+        return Err(format!("{} needs a 'coordinates' array", kind));
+    }
+    // Z when the first position has a third number
+    let mut first = coords.clone();
+    for _ in 0..nesting {
+        first = first.dyn_ref::<Array>().map_or(JsValue::UNDEFINED, |a| a.get(0));
+    }
+    let z = first.dyn_ref::<Array>().is_some_and(|p| p.length() >= 3);
+    out.push(1);
+    out.extend_from_slice(&(code + if z { 1000 } else { 0 }).to_le_bytes());
+    match code {
+        4..=6 => {
+            // Multi*: each part is a whole WKB geometry of the single type
+            let parts: Array = coords.unchecked_into();
+            out.extend_from_slice(&parts.length().to_le_bytes());
+            for part in parts.iter() {
+                out.push(1);
+                out.extend_from_slice(&(code - 3 + if z { 1000 } else { 0 }).to_le_bytes());
+                wkb_coordinates(&part, nesting - 1, z, out)?;
+            }
+            Ok(())
+        }
+        _ => wkb_coordinates(&coords, nesting, z, out),
+    }
+}
+
+/// Append the WKB body of `coords` nested `nesting` arrays deep: a position,
+/// a counted list of positions, or a counted list of those
+fn wkb_coordinates(coords: &JsValue, nesting: usize, z: bool, out: &mut Vec<u8>) -> Result<(), String> {
+    let arr = coords.dyn_ref::<Array>().ok_or("coordinates must be arrays")?;
+    if nesting > 0 {
+        out.extend_from_slice(&arr.length().to_le_bytes());
+        return arr.iter().try_for_each(|c| wkb_coordinates(&c, nesting - 1, z, out));
+    }
+    // An empty point is all NaN
+    let dims = if z { 3 } else { 2 };
+    if arr.length() == 0 {
+        (0..dims).for_each(|_| out.extend_from_slice(&f64::NAN.to_le_bytes()));
+        return Ok(());
+    }
+    if arr.length() < 2 || (arr.length() >= 3) != z {
+        return Err(format!("positions must all have {} numbers", if z { "3" } else { "2" }));
+    }
+    for i in 0..dims {
+        let c = arr.get(i).as_f64().ok_or("positions must hold numbers")?;
+        out.extend_from_slice(&c.to_le_bytes());
+    }
+    Ok(())
+}
+
+/// Check WKB `wkb` (ISO or EWKB, either byte order), widen `bbox` to its
+/// coordinates and return its GeoParquet type name
+fn wkb_summary(wkb: &[u8], bbox: &mut [f64; 4]) -> Result<String, String> {
+    let mut pos = 0;
+    let (code, z) = wkb_geometry(wkb, &mut pos, bbox, 0).map_err(|e| format!("invalid WKB: {}", e))?;
+    if pos != wkb.len() {
+        return Err(format!("invalid WKB: {} bytes after the geometry", wkb.len() - pos));
+    }
+    Ok(format!("{}{}", GEOMETRY_TYPES[code as usize - 1], if z { " Z" } else { "" }))
+}
+
+/// Walk the WKB geometry at `pos`, returning its type code and whether it has Z
+fn wkb_geometry(wkb: &[u8], pos: &mut usize, bbox: &mut [f64; 4], depth: usize) -> Result<(u32, bool), String> {
+    let take = |pos: &mut usize, n: usize| -> Result<&[u8], String> {
+        let bytes = wkb.get(*pos..*pos + n).ok_or("ends early")?;
+        *pos += n;
+        Ok(bytes)
+    };
+    let little = match take(pos, 1)?[0] {
+        0 => false,
+        1 => true,
+        b => return Err(format!("byte order {}", b)),
+    };
+    let u32_at = |pos: &mut usize| -> Result<u32, String> {
+        let b: [u8; 4] = take(pos, 4)?.try_into().unwrap();
+        Ok(if little { u32::from_le_bytes(b) } else { u32::from_be_bytes(b) })
+    };
+    let raw = u32_at(pos)?;
+    // EWKB flags, else ISO thousands
+    let (code, mut z, mut m) = ((raw & 0x0fff_ffff) % 1000, raw & 0x8000_0000 != 0, raw & 0x4000_0000 != 0);
+    match (raw & 0x0fff_ffff) / 1000 {
+        0 => {}
+        1 => z = true,
+        2 => m = true,
+        3 => (z, m) = (true, true),
+        _ => return Err(format!("geometry type {}", raw)),
+    }
+    if raw & 0x2000_0000 != 0 {
+        take(pos, 4)?; // EWKB SRID
+    }
+    let dims = 2 + z as usize + m as usize;
+    let mut point = |pos: &mut usize| -> Result<(), String> {
+        let mut c = [0.0; 2];
+        for (i, b) in take(pos, dims * 8)?.chunks_exact(8).take(2).enumerate() {
+            let b: [u8; 8] = b.try_into().unwrap();
+            c[i] = if little { f64::from_le_bytes(b) } else { f64::from_be_bytes(b) };
+        }
+        if !c[0].is_nan() && !c[1].is_nan() {
+            *bbox = [bbox[0].min(c[0]), bbox[1].min(c[1]), bbox[2].max(c[0]), bbox[3].max(c[1])];
+        }
+        Ok(())
+    };
+    // Counts are checked against the bytes left before looping over them
+    let count = |pos: &mut usize, min_size: usize| -> Result<u32, String> {
+        let n = u32_at(pos)?;
+        if (n as usize).saturating_mul(min_size) > wkb.len() - *pos {
+            return Err(format!("count {} beyond the end", n));
+        }
+        Ok(n)
+    };
+    match code {
+        1 => point(pos)?,
+        2 => (0..count(pos, dims * 8)?).try_for_each(|_| point(pos))?,
+        3 => {
+            for _ in 0..count(pos, 4)? {
+                (0..count(pos, dims * 8)?).try_for_each(|_| point(pos))?;
+            }
+        }
+        4..=7 => {
+            if depth >= MAX_GEOMETRY_DEPTH {
+                return Err("nested too deeply".to_string());
+            }
+            for _ in 0..count(pos, 9)? {
+                let (part, _) = wkb_geometry(wkb, pos, bbox, depth + 1)?;
+                if code != 7 && part != code - 3 {
+                    return Err(format!("{} holding a {}", GEOMETRY_TYPES[code as usize - 1], GEOMETRY_TYPES[part as usize - 1]));
+                }
+            }
+        }
+        _ => return Err(format!("geometry type {}", raw)),
+    }
+    Ok((code, z))
+}
+
+// ── Pandas metadata ─────────────────────────────────────────────────────────
+//
+// The `pandas` footer entry is what pyarrow writes for `DataFrame.to_parquet`
+// and reads back in `read_parquet`: which columns form the index, and each
+// column's pandas dtype. Nullable dtypes (`Int64`, `boolean`) keep optional
+// integer and boolean columns from turning into float64 and object.
+
+/// Parse `pandas: true | { index: column | column[] }` into the index
+/// columns, which must be top-level (none: the default RangeIndex)
+fn parse_pandas(config_js: &JsValue, fields: &[Field]) -> Result<Option<Vec<String>>, JsValue> {
+    let v = Reflect::get(config_js, &"pandas".into()).unwrap_or(JsValue::UNDEFINED);
+    if v.is_undefined() || v.is_null() || v == JsValue::FALSE {
+        return Ok(None);
+    }
+    if v == JsValue::TRUE {
+        return Ok(Some(Vec::new()));
+    }
+    let bad = || JsValue::from_str("pandas must be true or { index: column | column[] }");
+    if !v.is_object() || v.is_array() {
+        return Err(bad());
+    }
+    let index = Reflect::get(&v, &"index".into())?;
+    let index: Vec<String> = match (index.as_string(), index.dyn_ref::<Array>()) {
+        _ if index.is_undefined() || index.is_null() => Vec::new(),
+        (Some(column), _) => vec![column],
+        (None, Some(arr)) => arr.iter().map(|c| c.as_string()).collect::<Option<_>>().ok_or_else(bad)?,
+        _ => return Err(bad()),
+    };
+    for (i, column) in index.iter().enumerate() {
+        if !fields.iter().any(|f| f.name == *column) {
+            return Err(JsValue::from_str(&format!("pandas.index: unknown column '{}'", column)));
+        }
+        if index[..i].contains(column) {
+            return Err(JsValue::from_str(&format!("pandas.index: column '{}' listed twice", column)));
+        }
+    }
+    Ok(Some(index))
+}
+
+/// pandas_type, numpy_type and metadata pyarrow records for `field`
+fn pandas_dtype(field: &Field) -> Result<(String, String, JsValue), JsValue> {
+    let ct = match &field.kind {
+        FieldKind::Leaf(ct, _) => ct,
+        FieldKind::List(element) => {
+            let (element, ..) = pandas_dtype(element)?;
+            return Ok((format!("list[{}]", element), "object".to_string(), JsValue::NULL));
+        }
+        FieldKind::Struct(_) | FieldKind::Map(..) => return Ok(("object".to_string(), "object".to_string(), JsValue::NULL)),
+    };
+    // Optional integers and booleans get pandas' nullable dtypes
+    let (pandas, numpy) = match ct {
+        ColType::Str | ColType::Json => ("unicode", "object"),
+        ColType::Boolean => ("bool", if field.optional { "boolean" } else { "bool" }),
+        ColType::Int8 => ("int8", if field.optional { "Int8" } else { "int8" }),
+        ColType::Int16 => ("int16", if field.optional { "Int16" } else { "int16" }),
+        ColType::Int32 => ("int32", if field.optional { "Int32" } else { "int32" }),
+        ColType::Int64 => ("int64", if field.optional { "Int64" } else { "int64" }),
+        ColType::UInt8 => ("uint8", if field.optional { "UInt8" } else { "uint8" }),
+        ColType::UInt16 => ("uint16", if field.optional { "UInt16" } else { "uint16" }),
+        ColType::UInt32 => ("uint32", if field.optional { "UInt32" } else { "uint32" }),
+        ColType::UInt64 => ("uint64", if field.optional { "UInt64" } else { "uint64" }),
+        ColType::Float16 => ("float16", "float16"),
+        ColType::Float32 => ("float32", "float32"),
+        ColType::Float64 => ("float64", "float64"),
+        ColType::TimestampMillis | ColType::TimestampMicros | ColType::TimestampNanos => {
+            let unit = match ct {
+                ColType::TimestampMillis => "ms",
+                ColType::TimestampMicros => "us",
+                _ => "ns",
+            };
+            let FieldKind::Leaf(_, true) = field.kind else {
+                return Ok(("datetime".to_string(), format!("datetime64[{}]", unit), JsValue::NULL));
+            };
+            let meta = Object::new();
+            Reflect::set(&meta, &"timezone".into(), &"UTC".into())?;
+            return Ok(("datetimetz".to_string(), format!("datetime64[{}]", unit), meta.into()));
+        }
+        ColType::Date => ("date", "object"),
+        ColType::TimeMillis | ColType::TimeMicros => ("time", "object"),
+        ColType::Decimal(precision, scale) => {
+            let meta = Object::new();
+            Reflect::set(&meta, &"precision".into(), &JsValue::from(*precision))?;
+            Reflect::set(&meta, &"scale".into(), &JsValue::from(*scale))?;
+            return Ok(("decimal".to_string(), "object".to_string(), meta.into()));
+        }
+        ColType::Binary | ColType::Bson | ColType::Geometry | ColType::Uuid | ColType::Fixed(_) => ("bytes", "object"),
+    };
+    Ok((pandas.to_string(), numpy.to_string(), JsValue::NULL))
+}
+
+/// The `pandas` footer entry for `fields`, with `index` as the index columns
+fn pandas_metadata(fields: &[Field], index: &[String]) -> Result<String, JsValue> {
+    let entry = |name: &JsValue, pandas: &str, numpy: &str, metadata: &JsValue| -> Result<JsValue, JsValue> {
+        let obj = Object::new();
+        Reflect::set(&obj, &"name".into(), name)?;
+        Reflect::set(&obj, &"field_name".into(), name)?;
+        Reflect::set(&obj, &"pandas_type".into(), &pandas.into())?;
+        Reflect::set(&obj, &"numpy_type".into(), &numpy.into())?;
+        Reflect::set(&obj, &"metadata".into(), metadata)?;
+        Ok(obj.into())
+    };
+    let columns = Array::new();
+    for f in fields {
+        let (pandas, numpy, metadata) = pandas_dtype(f)?;
+        columns.push(&entry(&f.name.as_str().into(), &pandas, &numpy, &metadata)?);
+    }
+    // The column labels are plain strings
+    let encoding = Object::new();
+    Reflect::set(&encoding, &"encoding".into(), &"UTF-8".into())?;
+    let column_indexes = Array::of1(&entry(&JsValue::NULL, "unicode", "object", &encoding)?);
+    let creator = Object::new();
+    Reflect::set(&creator, &"library".into(), &"tiny-parquet".into())?;
+
+    let pandas = Object::new();
+    Reflect::set(&pandas, &"index_columns".into(), &index.iter().map(|c| JsValue::from(c.as_str())).collect::<Array>())?;
+    Reflect::set(&pandas, &"column_indexes".into(), &column_indexes)?;
+    Reflect::set(&pandas, &"columns".into(), &columns)?;
+    Reflect::set(&pandas, &"creator".into(), &creator)?;
+    Ok(String::from(js_sys::JSON::stringify(&pandas)?))
+}
+
+// ── Arrow schema ────────────────────────────────────────────────────────────
+//
+// `ARROW:schema` holds a base64 Arrow IPC message with the file's schema as
+// Arrow types. pyarrow and arrow-rs restore timezones and extension types
+// from it that the Parquet schema alone can't carry.
+
+/// A table field value: a scalar, or an offset to an object already written
+enum Slot {
+    U8(u8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    Offset(usize),
+}
+
+/// Minimal FlatBuffers builder. Like the reference builders it writes back to
+/// front: `buf` holds the bytes reversed and objects are addressed by their
+/// distance from the end, so children must be written before their parents.
+#[derive(Default)]
+struct FlatBuilder {
+    buf: Vec<u8>,
+}
+
+impl FlatBuilder {
+    /// Pad so that `extra` more bytes end `align`-aligned
+    fn pad(&mut self, align: usize, extra: usize) {
+        while !(self.buf.len() + extra).is_multiple_of(align) {
+            self.buf.push(0);
+        }
+    }
+
+    fn raw(&mut self, bytes: &[u8]) {
+        self.buf.extend(bytes.iter().rev());
+    }
+
+    fn uoffset(&mut self, target: usize) {
+        self.pad(4, 4);
+        let rel = (self.buf.len() + 4 - target) as u32;
+        self.raw(&rel.to_le_bytes());
+    }
+
+    fn string(&mut self, s: &str) -> usize {
+        self.pad(4, s.len() + 5);
+        self.buf.push(0);
+        self.raw(s.as_bytes());
+        self.raw(&(s.len() as u32).to_le_bytes());
+        self.buf.len()
+    }
+
+    /// Vector of offsets to tables or strings
+    fn vector(&mut self, items: &[usize]) -> usize {
+        self.pad(4, 4 * (items.len() + 1));
+        for &item in items.iter().rev() {
+            self.uoffset(item);
+        }
+        self.raw(&(items.len() as u32).to_le_bytes());
+        self.buf.len()
+    }
+
+    /// Table with the given `(slot, value)` fields, each written even when it
+    /// holds the schema default
+    fn table(&mut self, fields: &[(usize, Slot)]) -> usize {
+        let size = |s: &Slot| match s {
+            Slot::U8(_) => 1,
+            Slot::I16(_) => 2,
+            Slot::I32(_) | Slot::Offset(_) => 4,
+            Slot::I64(_) => 8,
+        };
+        let start = self.buf.len();
+        // Largest first: smaller fields then never need padding
+        let mut order: Vec<&(usize, Slot)> = fields.iter().collect();
+        order.sort_by_key(|(_, s)| std::cmp::Reverse(size(s)));
+        let mut positions = Vec::with_capacity(fields.len());
+        for (slot, value) in order {
+            match value {
+                Slot::U8(v) => self.raw(&[*v]),
+                Slot::I16(v) => { self.pad(2, 2); self.raw(&v.to_le_bytes()) }
+                Slot::I32(v) => { self.pad(4, 4); self.raw(&v.to_le_bytes()) }
+                Slot::I64(v) => { self.pad(8, 8); self.raw(&v.to_le_bytes()) }
+                Slot::Offset(target) => self.uoffset(*target),
+            }
+            positions.push((*slot, self.buf.len()));
+        }
+        // The vtable goes right before the table, so the table's signed offset
+        // to it is the vtable's size
+        let num_slots = fields.iter().map(|(slot, _)| slot + 1).max().unwrap_or(0);
+        let vtable_size = 4 + 2 * num_slots;
+        self.pad(4, 4);
+        self.raw(&(vtable_size as i32).to_le_bytes());
+        let table = self.buf.len();
+        let mut vtable = vec![0u16; num_slots];
+        for (slot, pos) in positions {
+            vtable[slot] = (table - pos) as u16;
+        }
+        for entry in vtable.iter().rev() {
+            self.raw(&entry.to_le_bytes());
+        }
+        self.raw(&((table - start) as u16).to_le_bytes());
+        self.raw(&(vtable_size as u16).to_le_bytes());
+        table
+    }
+
+    fn finish(mut self, root: usize) -> Vec<u8> {
+        self.pad(8, 4);
+        self.uoffset(root);
+        self.buf.reverse();
+        self.buf
+    }
+}
+
+// Arrow `Type` union members
+const ARROW_INT: u8 = 2;
+const ARROW_FLOAT: u8 = 3;
+const ARROW_BINARY: u8 = 4;
+const ARROW_UTF8: u8 = 5;
+const ARROW_BOOL: u8 = 6;
+const ARROW_DECIMAL: u8 = 7;
+const ARROW_DATE: u8 = 8;
+const ARROW_TIME: u8 = 9;
+const ARROW_TIMESTAMP: u8 = 10;
+const ARROW_LIST: u8 = 12;
+const ARROW_STRUCT: u8 = 13;
+const ARROW_FIXED_SIZE_BINARY: u8 = 15;
+const ARROW_MAP: u8 = 17;
+
+/// Arrow TimeUnit enum value
+fn arrow_time_unit(unit: TimeUnit) -> i16 {
+    match unit {
+        TimeUnit::Milliseconds => 1,
+        TimeUnit::Microseconds => 2,
+        TimeUnit::Nanoseconds => 3,
+    }
+}
+
+/// Arrow type of a primitive column: union member, type table, and the
+/// canonical extension type name if there is one
+fn arrow_leaf_type(b: &mut FlatBuilder, ct: &ColType, utc: bool) -> (u8, usize, Option<&'static str>) {
+    let int = |b: &mut FlatBuilder, bits: i32, signed: bool| b.table(&[(0, Slot::I32(bits)), (1, Slot::U8(signed as u8))]);
+    match ct {
+        ColType::Str => (ARROW_UTF8, b.table(&[]), None),
+        ColType::Json => (ARROW_UTF8, b.table(&[]), Some("arrow.json")),
+        ColType::Binary | ColType::Bson | ColType::Geometry => (ARROW_BINARY, b.table(&[]), None),
+        ColType::Boolean => (ARROW_BOOL, b.table(&[]), None),
+        ColType::Int8 => (ARROW_INT, int(b, 8, true), None),
+        ColType::Int16 => (ARROW_INT, int(b, 16, true), None),
+        ColType::Int32 => (ARROW_INT, int(b, 32, true), None),
+        ColType::Int64 => (ARROW_INT, int(b, 64, true), None),
+        ColType::UInt8 => (ARROW_INT, int(b, 8, false), None),
+        ColType::UInt16 => (ARROW_INT, int(b, 16, false), None),
+        ColType::UInt32 => (ARROW_INT, int(b, 32, false), None),
+        ColType::UInt64 => (ARROW_INT, int(b, 64, false), None),
+        ColType::Float16 => (ARROW_FLOAT, b.table(&[(0, Slot::I16(0))]), None),
+        ColType::Float32 => (ARROW_FLOAT, b.table(&[(0, Slot::I16(1))]), None),
+        ColType::Float64 => (ARROW_FLOAT, b.table(&[(0, Slot::I16(2))]), None),
+        ColType::Decimal(p, s) => {
+            let t = b.table(&[(0, Slot::I32(*p as i32)), (1, Slot::I32(*s as i32)), (2, Slot::I32(128))]);
+            (ARROW_DECIMAL, t, None)
+        }
+        // DateUnit DAY
+        ColType::Date => (ARROW_DATE, b.table(&[(0, Slot::I16(0))]), None),
+        ColType::TimeMillis => (ARROW_TIME, b.table(&[(0, Slot::I16(1)), (1, Slot::I32(32))]), None),
+        ColType::TimeMicros => (ARROW_TIME, b.table(&[(0, Slot::I16(2)), (1, Slot::I32(64))]), None),
+        ColType::TimestampMillis | ColType::TimestampMicros | ColType::TimestampNanos => {
+            let unit = Slot::I16(arrow_time_unit(ct.time_unit().unwrap()));
+            let t = if utc {
+                let tz = b.string("UTC");
+                b.table(&[(0, unit), (1, Slot::Offset(tz))])
+            } else {
+                b.table(&[(0, unit)])
+            };
+            (ARROW_TIMESTAMP, t, None)
+        }
+        ColType::Uuid => (ARROW_FIXED_SIZE_BINARY, b.table(&[(0, Slot::I32(16))]), Some("arrow.uuid")),
+        ColType::Fixed(n) => (ARROW_FIXED_SIZE_BINARY, b.table(&[(0, Slot::I32(*n as i32))]), None),
+    }
+}
+
+/// Arrow `Field` table
+fn arrow_field_table(
+    b: &mut FlatBuilder,
+    name: &str,
+    nullable: bool,
+    (type_id, type_table): (u8, usize),
+    children: &[usize],
+    extension: Option<&str>,
+) -> usize {
+    let name = b.string(name);
+    // Arrow C++ rejects fields without a children vector, even an empty one
+    let children = b.vector(children);
+    let mut fields = vec![
+        (0, Slot::Offset(name)),
+        (1, Slot::U8(nullable as u8)),
+        (2, Slot::U8(type_id)),
+        (3, Slot::Offset(type_table)),
+        (5, Slot::Offset(children)),
+    ];
+    if let Some(ext) = extension {
+        let pairs: Vec<usize> = [("ARROW:extension:name", ext), ("ARROW:extension:metadata", "")]
+            .iter()
+            .map(|(k, v)| {
+                let (k, v) = (b.string(k), b.string(v));
+                b.table(&[(0, Slot::Offset(k)), (1, Slot::Offset(v))])
+            })
+            .collect();
+        fields.push((6, Slot::Offset(b.vector(&pairs))));
+    }
+    b.table(&fields)
+}
+
+fn arrow_field(b: &mut FlatBuilder, f: &Field) -> usize {
+    match &f.kind {
+        FieldKind::Leaf(ct, utc) => {
+            let (id, t, ext) = arrow_leaf_type(b, ct, *utc);
+            arrow_field_table(b, &f.name, f.optional, (id, t), &[], ext)
+        }
+        FieldKind::List(element) => {
+            let child = arrow_field(b, element);
+            let t = b.table(&[]);
+            arrow_field_table(b, &f.name, f.optional, (ARROW_LIST, t), &[child], None)
+        }
+        FieldKind::Struct(fields) => {
+            let children: Vec<usize> = fields.iter().map(|c| arrow_field(b, c)).collect();
+            let t = b.table(&[]);
+            arrow_field_table(b, &f.name, f.optional, (ARROW_STRUCT, t), &children, None)
+        }
+        FieldKind::Map(key, value) => {
+            let entries = [arrow_field(b, key), arrow_field(b, value)];
+            let t = b.table(&[]);
+            let entries = arrow_field_table(b, "key_value", false, (ARROW_STRUCT, t), &entries, None);
+            // keysSorted: false
+            let t = b.table(&[(0, Slot::U8(0))]);
+            arrow_field_table(b, &f.name, f.optional, (ARROW_MAP, t), &[entries], None)
+        }
+    }
+}
+
+/// Encapsulated IPC schema message for `fields`: continuation marker,
+/// metadata length, then the Message flatbuffer padded to 8 bytes
+fn arrow_schema_message(fields: &[Field]) -> Vec<u8> {
+    let mut b = FlatBuilder::default();
+    let fields: Vec<usize> = fields.iter().map(|f| arrow_field(&mut b, f)).collect();
+    let fields = b.vector(&fields);
+    // endianness: Little
+    let schema = b.table(&[(0, Slot::I16(0)), (1, Slot::Offset(fields))]);
+    // version: V5, header: Schema, bodyLength: 0
+    let message = b.table(&[(0, Slot::I16(4)), (1, Slot::U8(1)), (2, Slot::Offset(schema)), (3, Slot::I64(0))]);
+    let flatbuffer = b.finish(message);
+    let mut out = Vec::with_capacity(flatbuffer.len() + 8);
+    out.extend_from_slice(&u32::MAX.to_le_bytes());
+    out.extend_from_slice(&(flatbuffer.len() as u32).to_le_bytes());
+    out.extend_from_slice(&flatbuffer);
+    out
+}
+
+/// Standard base64 with padding
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &byte)| n | (byte as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+// ── Bloom filters ───────────────────────────────────────────────────────────
+//
+// Split-block bloom filters as the parquet spec defines them, built with
+// parquet2's xxHash64 and block insertion: the hash of each value's PLAIN
+// bytes picks a 32-byte block and sets one bit in each of its eight words.
+
+fn bloom_hashes<T: PlainValue>(vals: &[T]) -> Vec<u64> {
+    let mut buf = Vec::new();
+    vals.iter()
+        .map(|v| {
+            buf.clear();
+            v.bloom_bytes(&mut buf);
+            hash_byte(&buf)
+        })
+        .collect()
+}
+
+const DEFAULT_BLOOM_FPP: f64 = 0.01;
+/// Largest bitset written, as in parquet-mr and arrow
+const MAX_BLOOM_BYTES: usize = 128 << 20;
+
+/// Bloom filter settings of one column
+#[derive(Clone, Copy)]
+struct BloomSpec {
+    /// Target false-positive rate
+    fpp: f64,
+    /// Distinct values to size for (default: the row group's own count)
+    ndv: Option<usize>,
+}
+
+/// Bitset bytes holding `ndv` values at false-positive rate `fpp` (the
+/// spec's formula), as a power of two of at least one block
+fn bloom_size(ndv: usize, fpp: f64) -> usize {
+    let bits = -8.0 * ndv as f64 / (1.0 - fpp.powf(1.0 / 8.0)).ln();
+    ((bits / 8.0).ceil() as usize).clamp(32, MAX_BLOOM_BYTES).next_power_of_two()
+}
+
+/// A column chunk's bloom filter bitset from its value hashes
+fn bloom_filter(mut hashes: Vec<u64>, spec: &BloomSpec) -> Vec<u8> {
+    hashes.sort_unstable();
+    hashes.dedup();
+    let mut bitset = vec![0; bloom_size(spec.ndv.unwrap_or(hashes.len()), spec.fpp)];
+    for h in hashes {
+        bloom_insert(&mut bitset, h);
+    }
+    bitset
+}
+
+/// Read `bloomFilters`: leaf column paths, or `{ path: true | { fpp, ndv } }`
+fn parse_bloom_filters(config_js: &JsValue, leaves: &[(String, &ColType)]) -> Result<Vec<(usize, BloomSpec)>, JsValue> {
+    let v = Reflect::get(config_js, &"bloomFilters".into()).unwrap_or(JsValue::UNDEFINED);
+    if v.is_undefined() || v.is_null() {
+        return Ok(Vec::new());
+    }
+    let bad = || JsValue::from_str("bloomFilters must be an array of column names, or an object like { column: { fpp, ndv } }");
+    let entries: Vec<(String, JsValue)> = match v.dyn_ref::<Array>() {
+        Some(arr) => arr.iter().map(|c| c.as_string().map(|s| (s, JsValue::TRUE))).collect::<Option<_>>().ok_or_else(bad)?,
+        None if v.is_object() => Object::entries(v.unchecked_ref::<Object>())
+            .iter()
+            .map(|e| {
+                let e: Array = e.into();
+                (e.get(0).as_string().unwrap_or_default(), e.get(1))
+            })
+            .collect(),
+        None => return Err(bad()),
+    };
+    let mut specs = Vec::new();
+    for (column, opts) in entries {
+        if opts.is_undefined() || opts.is_null() || opts == JsValue::FALSE {
+            continue;
+        }
+        let idx = leaves
+            .iter()
+            .position(|(path, _)| *path == column)
+            .ok_or_else(|| JsValue::from_str(&format!("bloomFilters: unknown column '{}'", column)))?;
+        if matches!(leaves[idx].1, ColType::Boolean) {
+            return Err(JsValue::from_str(&format!("bloomFilters: column '{}' is boolean, which bloom filters don't cover", column)));
+        }
+        let mut spec = BloomSpec { fpp: DEFAULT_BLOOM_FPP, ndv: None };
+        if opts.is_object() {
+            if let Some(fpp) = Reflect::get(&opts, &"fpp".into())?.as_f64() {
+                if !(fpp > 0.0 && fpp < 1.0) {
+                    return Err(JsValue::from_str(&format!("bloomFilters: fpp for '{}' must be between 0 and 1, got {}", column, fpp)));
+                }
+                spec.fpp = fpp;
+            }
+            spec.ndv = positive_option(&opts, "ndv").map_err(|e| JsValue::from_str(&format!("bloomFilters: '{}': {}", column, e.as_string().unwrap_or_default())))?;
+        } else if opts != JsValue::TRUE {
+            return Err(JsValue::from_str(&format!("bloomFilters: '{}' must map to true or {{ fpp, ndv }}", column)));
+        }
+        specs.push((idx, spec));
+    }
+    Ok(specs)
+}
+
+/// Append bloom filters (row group, leaf column, bitset) to a finished file
+/// whose footer is stripped, pointing the column metadata at them. `base` is
+/// the file offset `bytes` starts at (non-zero once rows went to `sink`).
+fn append_bloom_filters(bytes: &mut Vec<u8>, base: usize, meta: &mut FileMetaData, blooms: Vec<(usize, usize, Vec<u8>)>) -> Result<(), JsValue> {
+    for (rg, col, bitset) in blooms {
+        let offset = base + bytes.len();
+        let header = BloomFilterHeader {
+            num_bytes: bitset.len() as i32,
+            algorithm: BloomFilterAlgorithm::BLOCK(SplitBlockAlgorithm {}),
+            hash: BloomFilterHash::XXHASH(XxHash {}),
+            compression: BloomFilterCompression::UNCOMPRESSED(Uncompressed {}),
+        };
+        write_thrift(bytes, |p| header.write_to_out_protocol(p))?;
+        bytes.extend_from_slice(&bitset);
+        if let Some(m) = meta.row_groups[rg].columns[col].meta_data.as_mut() {
+            m.bloom_filter_offset = Some(offset as i64);
+        }
+    }
+    Ok(())
+}
+
+// ── Page indexes ────────────────────────────────────────────────────────────
+//
+// parquet2 writes a column index (per-page min/max/null counts) and an offset
+// index (page locations) for every column chunk, but marks every column index
+// UNORDERED and gives all-null pages a one-byte placeholder min/max. Both are
+// fixed in the finished file, so engines can binary-search sorted columns.
+
+/// Order two PLAIN-encoded min/max values of a `ct` column the way its
+/// statistics are ordered
+fn stat_order(ct: &ColType, a: &[u8], b: &[u8]) -> Ordering {
+    fn le<const N: usize>(b: &[u8]) -> [u8; N] {
+        b.try_into().unwrap_or([0; N])
+    }
+    match (ct.physical_type(), ct) {
+        (PhysicalType::Int32, ColType::UInt32) => u32::from_le_bytes(le(a)).cmp(&u32::from_le_bytes(le(b))),
+        (PhysicalType::Int32, _) => i32::from_le_bytes(le(a)).cmp(&i32::from_le_bytes(le(b))),
+        (PhysicalType::Int64, ColType::UInt64) => u64::from_le_bytes(le(a)).cmp(&u64::from_le_bytes(le(b))),
+        (PhysicalType::Int64, _) => i64::from_le_bytes(le(a)).cmp(&i64::from_le_bytes(le(b))),
+        (PhysicalType::Float, _) => f32::from_le_bytes(le(a)).total_cmp(&f32::from_le_bytes(le(b))),
+        (PhysicalType::Double, _) => f64::from_le_bytes(le(a)).total_cmp(&f64::from_le_bytes(le(b))),
+        (_, ColType::Float16) => Half(u16::from_le_bytes(le(a))).order_key().cmp(&Half(u16::from_le_bytes(le(b))).order_key()),
+        (_, ColType::Decimal(..)) => {
+            // Big-endian two's complement: with the sign bit flipped, byte order is numeric order
+            let key = |v: &[u8]| v.iter().enumerate().map(|(i, &x)| if i == 0 { x ^ 0x80 } else { x }).collect::<Vec<u8>>();
+            key(a).cmp(&key(b))
+        }
+        _ => a.cmp(b),
+    }
+}
+
+/// ASCENDING when neither the min nor the max of the non-null pages ever
+/// goes down from one page to the next, DESCENDING when neither goes up,
+/// else UNORDERED
+fn boundary_order(ct: &ColType, index: &ColumnIndex) -> BoundaryOrder {
+    let bounds: Vec<(&[u8], &[u8])> = index
+        .null_pages
+        .iter()
+        .zip(index.min_values.iter().zip(&index.max_values))
+        .filter(|(null, _)| !**null)
+        .map(|(_, (lo, hi))| (lo.as_slice(), hi.as_slice()))
+        .collect();
+    let steps = || bounds.windows(2).map(|w| (stat_order(ct, w[0].0, w[1].0), stat_order(ct, w[0].1, w[1].1)));
+    if steps().all(|(lo, hi)| lo != Ordering::Greater && hi != Ordering::Greater) {
+        BoundaryOrder::ASCENDING
+    } else if steps().all(|(lo, hi)| lo != Ordering::Less && hi != Ordering::Less) {
+        BoundaryOrder::DESCENDING
+    } else {
+        BoundaryOrder::UNORDERED
+    }
+}
+
+/// Rewrite the column indexes at the end of a finished file (footer
+/// stripped; the first `base` bytes already handed to the sink) with their
+/// boundary order and empty null-page bounds, moving the offset indexes
+/// that follow them to match. `leaves` are the schema's leaf columns.
+fn order_page_indexes(bytes: &mut Vec<u8>, base: usize, meta: &mut FileMetaData, leaves: &[(String, &ColType)]) -> Result<(), JsValue> {
+    let cols = || meta.row_groups.iter().flat_map(|rg| rg.columns.iter());
+    // parquet2 writes every column index, then every offset index, after the last row group
+    let Some(start) = cols().filter_map(|c| c.column_index_offset).min() else { return Ok(()) };
+    let start = start as usize - base;
+    let indexes = bytes.split_off(start);
+    let at = |offset: i64, len: i32| offset as usize - base - start..offset as usize - base - start + len as usize;
+
+    let cols = meta.row_groups.iter_mut().flat_map(|rg| rg.columns.iter_mut());
+    for (col, (_, ct)) in cols.zip(leaves.iter().cycle()) {
+        let (Some(offset), Some(len)) = (col.column_index_offset, col.column_index_length) else { continue };
+        let (mut index, _) = read_thrift::<ColumnIndex>(&indexes[at(offset, len)])?;
+        for (i, _) in index.null_pages.iter().enumerate().filter(|(_, null)| **null) {
+            index.min_values[i].clear();
+            index.max_values[i].clear();
+        }
+        index.boundary_order = boundary_order(ct, &index);
+        col.column_index_offset = Some((base + bytes.len()) as i64);
+        col.column_index_length = Some(write_thrift(bytes, |p| index.write_to_out_protocol(p))? as i32);
+    }
+    for col in meta.row_groups.iter_mut().flat_map(|rg| rg.columns.iter_mut()) {
+        if let (Some(offset), Some(len)) = (col.offset_index_offset, col.offset_index_length) {
+            col.offset_index_offset = Some((base + bytes.len()) as i64);
+            bytes.extend_from_slice(&indexes[at(offset, len)]);
+        }
+    }
+    Ok(())
+}
+
+// ── Compression ─────────────────────────────────────────────────────────────
+//
+// Pages are compressed here rather than by parquet2, whose codec features
+// link in every encoder. Snappy is always available; gzip, zstd, lz4 and
+// brotli come with the `codecs` feature, which builds the writer-full wasm.
+
+/// A column chunk's pages, compressed one at a time as the file writer asks
+/// for them
+struct PageCompressor {
+    pages: std::vec::IntoIter<Page>,
+    codec: CompressionOptions,
+    current: Option<CompressedPage>,
+}
+
+impl FallibleStreamingIterator for PageCompressor {
+    type Item = CompressedPage;
+    type Error = parquet2::error::Error;
+
+    fn advance(&mut self) -> parquet2::error::Result<()> {
+        self.current = self.pages.next().map(|page| compress_page(page, self.codec)).transpose()?;
+        Ok(())
+    }
+
+    fn get(&self) -> Option<&CompressedPage> {
+        self.current.as_ref()
+    }
+}
+
+/// Compress an encoded page with `codec`. The levels at the front of a V2
+/// data page stay uncompressed.
+fn compress_page(page: Page, codec: CompressionOptions) -> parquet2::error::Result<CompressedPage> {
+    Ok(match page {
+        Page::Data(page) => {
+            let levels = match page.header() {
+                DataPageHeader::V1(_) => 0,
+                DataPageHeader::V2(h) => (h.repetition_levels_byte_length + h.definition_levels_byte_length) as usize,
+            };
+            let buf = page.buffer();
+            let mut out = buf[..levels].to_vec();
+            compress(codec, &buf[levels..], &mut out)?;
+            let rows = page.selected_rows().and_then(|r| r.last()).map(|r| r.length);
+            CompressedPage::Data(CompressedDataPage::new(
+                page.header().clone(),
+                out,
+                codec.into(),
+                buf.len(),
+                page.descriptor.clone(),
+                rows,
+            ))
+        }
+        Page::Dict(page) => {
+            let mut out = Vec::new();
+            compress(codec, &page.buffer, &mut out)?;
+            CompressedPage::Dict(CompressedDictPage::new(out, codec.into(), page.buffer.len(), page.num_values, page.is_sorted))
+        }
+    })
+}
+
+/// Append `input` to `out`, compressed with `codec`
+fn compress(codec: CompressionOptions, input: &[u8], out: &mut Vec<u8>) -> parquet2::error::Result<()> {
+    match codec {
+        CompressionOptions::Uncompressed => out.extend_from_slice(input),
+        CompressionOptions::Snappy => {
+            let start = out.len();
+            out.resize(start + snap::raw::max_compress_len(input.len()), 0);
+            let n = snap::raw::Encoder::new()
+                .compress(input, &mut out[start..])
+                .map_err(|e| parquet2::error::Error::OutOfSpec(e.to_string()))?;
+            out.truncate(start + n);
+        }
+        #[cfg(feature = "codecs")]
+        CompressionOptions::Gzip(level) => gzip(input, level.unwrap_or_default().compression_level(), out),
+        #[cfg(feature = "codecs")]
+        CompressionOptions::Zstd(level) => {
+            out.extend_from_slice(&zstd_pure::compress(input, level.map_or(zstd_pure::DEFAULT_LEVEL, |l| l.compression_level())))
+        }
+        #[cfg(feature = "codecs")]
+        CompressionOptions::Lz4Raw => out.extend_from_slice(&lz4_flex::block::compress(input)),
+        #[cfg(feature = "codecs")]
+        CompressionOptions::Brotli(level) => {
+            let quality = level.unwrap_or_default().compression_level();
+            let mut encoder = brotli::CompressorWriter::new(&mut *out, 4096, quality, 22);
+            encoder.write_all(input)?;
+            encoder.into_inner();
+        }
+        other => {
+            return Err(parquet2::error::Error::FeatureNotSupported(format!(
+                "{:?} compression is not in this build",
+                other
+            )))
+        }
+    }
+    Ok(())
+}
+
+/// Append a single-member gzip stream of `input` to `out`
+#[cfg(feature = "codecs")]
+fn gzip(input: &[u8], level: u8, out: &mut Vec<u8>) {
+    // No mtime, no flags, unknown OS
+    out.extend_from_slice(&[0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff]);
+    out.extend_from_slice(&miniz_oxide::deflate::compress_to_vec(input, level));
+    out.extend_from_slice(&crc32fast::hash(input).to_le_bytes());
+    out.extend_from_slice(&(input.len() as u32).to_le_bytes());
+}
+
+// ── Footer rewriting ────────────────────────────────────────────────────────
+//
+// parquet2's FileWriter has no hook for row group fields like sorting_columns
+// or page header CRCs, so those are patched into the finished file.
+
+/// Drop the footer (metadata + length + magic) of a finished file
+fn strip_footer(bytes: &mut Vec<u8>) {
+    let len = bytes.len();
+    let meta_len = u32::from_le_bytes(bytes[len - 8..len - 4].try_into().unwrap()) as usize;
+    bytes.truncate(len - 8 - meta_len);
+}
+
+/// Serialize a thrift struct onto the end of `buf`, returning its size
+fn write_thrift(buf: &mut Vec<u8>, write: impl FnOnce(&mut TCompactOutputProtocol<&mut Vec<u8>>) -> parquet_format_safe::thrift::Result<usize>) -> Result<usize, JsValue> {
+    write(&mut TCompactOutputProtocol::new(buf))
+        .map_err(|e| JsValue::from_str(&format!("finalize error: {}", e)))
+}
+
+/// Read a thrift struct from the start of `buf`, returning it and its size
+fn read_thrift<T: ReadThrift>(buf: &[u8]) -> Result<(T, usize), JsValue> {
+    let mut rest = buf;
+    // The protocol's limit budgets decoded memory (8 bytes per list element), not input bytes
+    let limit = buf.len().saturating_mul(8);
+    let value = T::read_from_in_protocol(&mut TCompactInputProtocol::new(&mut rest, limit))
+        .map_err(|e| JsValue::from_str(&format!("finalize error: {}", e)))?;
+    Ok((value, buf.len() - rest.len()))
+}
+
+/// LogicalType union members for UNKNOWN and FLOAT16 (newer than parquet-format-safe)
+const LOGICAL_UNKNOWN: i16 = 11;
+const LOGICAL_FLOAT16: i16 = 15;
+
+/// Compact-protocol header of the first field of a LogicalType union: member
+/// 11 (UNKNOWN) and member 15 (FLOAT16), both empty structs
+const LOGICAL_UNKNOWN_HEADER: u8 = 0xBC;
+const LOGICAL_FLOAT16_HEADER: u8 = 0xFC;
+
+/// parquet-format-safe predates the FLOAT16 logical type, so float16 columns
+/// carry an UNKNOWN placeholder in `meta`; swap it for FLOAT16 in the encoded
+/// `footer`. Both encode to the same length, so nothing else moves.
+fn mark_float16(footer: &mut [u8], meta: &FileMetaData) -> Result<(), JsValue> {
+    let placeholder = |el: &SchemaElement| el.type_length == Some(2) && matches!(el.logical_type, Some(LogicalType::UNKNOWN(_)));
+    if !meta.schema.iter().any(placeholder) {
+        return Ok(());
+    }
+    let headers = logical_type_headers(footer).map_err(|e| JsValue::from_str(&format!("finalize error: {}", e)))?;
+    for (el, header) in meta.schema.iter().zip(headers) {
+        if !placeholder(el) {
+            continue;
+        }
+        match header {
+            Some((pos, LOGICAL_UNKNOWN)) => footer[pos] = LOGICAL_FLOAT16_HEADER,
+            _ => return Err(JsValue::from_str("finalize error: float16 schema element not found")),
+        }
+    }
+    Ok(())
+}
+
+/// Append `meta` as the file footer
+fn append_footer(bytes: &mut Vec<u8>, meta: &FileMetaData) -> Result<(), JsValue> {
+    let start = bytes.len();
+    let len = write_thrift(bytes, |p| meta.write_to_out_protocol(p))?;
+    mark_float16(&mut bytes[start..], meta)?;
+    bytes.extend_from_slice(&(len as u32).to_le_bytes());
+    bytes.extend_from_slice(b"PAR1");
+    Ok(())
+}
+
+/// New offset of the page that started at `old`
+fn relocate(pages: &[(i64, i64, i32)], old: i64) -> i64 {
+    pages.iter().find(|p| p.0 == old).map_or(old, |p| p.1)
+}
+
+/// Re-emit the pages and indexes of a finished file (footer stripped) with a
+/// CRC in every page header. Headers grow by a few bytes, so page, chunk and
+/// index offsets in `meta` are shifted to match.
+fn add_page_crcs(bytes: &[u8], meta: &mut FileMetaData) -> Result<Vec<u8>, JsValue> {
+    let mut out = Vec::with_capacity(bytes.len() + bytes.len() / 64);
+    out.extend_from_slice(b"PAR1");
+    // old page offset → (new offset, new size incl. header), per column chunk
+    let mut moved: Vec<Vec<(i64, i64, i32)>> = Vec::new();
+
+    for rg in meta.row_groups.iter_mut() {
+        let mut grown = 0i64;
+        for col in rg.columns.iter_mut() {
+            let cm = col
+                .meta_data
+                .as_mut()
+                .ok_or_else(|| JsValue::from_str("finalize error: missing column metadata"))?;
+            let start = cm.dictionary_page_offset.unwrap_or(cm.data_page_offset).min(cm.data_page_offset) as usize;
+            let end = start + cm.total_compressed_size as usize;
+            let new_start = out.len();
+            let mut pages = Vec::new();
+            let mut pos = start;
+            while pos < end {
+                let (mut header, header_len) = read_thrift::<PageHeader>(&bytes[pos..end])?;
+                let body = &bytes[pos + header_len..pos + header_len + header.compressed_page_size as usize];
+                header.crc = Some(crc32fast::hash(body) as i32);
+                let page_start = out.len();
+                write_thrift(&mut out, |p| header.write_to_out_protocol(p))?;
+                out.extend_from_slice(body);
+                pages.push((pos as i64, page_start as i64, (out.len() - page_start) as i32));
+                pos += header_len + body.len();
+            }
+            cm.data_page_offset = relocate(&pages, cm.data_page_offset);
+            cm.dictionary_page_offset = cm.dictionary_page_offset.map(|o| relocate(&pages, o));
+            let new_size = (out.len() - new_start) as i64;
+            grown += new_size - cm.total_compressed_size;
+            cm.total_uncompressed_size += new_size - cm.total_compressed_size;
+            cm.total_compressed_size = new_size;
+            // Like parquet2, follow the pages with a copy of the chunk's metadata
+            col.file_offset = out.len() as i64;
+            write_thrift(&mut out, |p| cm.write_to_out_protocol(p))?;
+            moved.push(pages);
+        }
+        rg.file_offset = rg.columns.first().and_then(|c| c.meta_data.as_ref()).map(|m| {
+            m.dictionary_page_offset.unwrap_or(m.data_page_offset).min(m.data_page_offset)
+        });
+        rg.total_compressed_size = rg.total_compressed_size.map(|s| s + grown);
+        rg.total_byte_size += grown;
+    }
+
+    // Column indexes hold no offsets: copy them over as-is
+    for col in meta.row_groups.iter_mut().flat_map(|rg| rg.columns.iter_mut()) {
+        if let (Some(off), Some(len)) = (col.column_index_offset, col.column_index_length) {
+            col.column_index_offset = Some(out.len() as i64);
+            out.extend_from_slice(&bytes[off as usize..off as usize + len as usize]);
+        }
+    }
+
+    // Offset indexes point at pages: rewrite their locations
+    let cols = meta.row_groups.iter_mut().flat_map(|rg| rg.columns.iter_mut());
+    for (col, pages) in cols.zip(moved.iter()) {
+        if let (Some(off), Some(len)) = (col.offset_index_offset, col.offset_index_length) {
+            let (mut index, _) = read_thrift::<OffsetIndex>(&bytes[off as usize..off as usize + len as usize])?;
+            for loc in index.page_locations.iter_mut() {
+                if let Some(&(_, offset, size)) = pages.iter().find(|p| p.0 == loc.offset) {
+                    loc.offset = offset;
+                    loc.compressed_page_size = size;
+                }
+            }
+            col.offset_index_offset = Some(out.len() as i64);
+            col.offset_index_length = Some(write_thrift(&mut out, |p| index.write_to_out_protocol(p))? as i32);
+        }
+    }
+
+    Ok(out)
+}
+
+// ── Encryption ──────────────────────────────────────────────────────────────
+//
+// Parquet modular encryption (AES_GCM_V1, encrypted footer). Every page
+// header, page, page index, bloom filter and the footer is stored as its own
+// AES-GCM module — length, nonce, ciphertext, tag — whose AAD names the
+// module's kind and place in the file, so modules can't be swapped or moved.
+// Columns in `columnKeys` are encrypted with their own key, the rest (and the
+// footer) with the footer key.
+
+type Aes192Gcm = Gcm<Aes192, U12>;
+
+/// AES-GCM with one key: AES-128, -192 or -256 by the key's length
+enum AesGcm {
+    Aes128(Aes128Gcm),
+    Aes192(Aes192Gcm),
+    Aes256(Aes256Gcm),
+}
+
+impl AesGcm {
+    fn new(key: &[u8]) -> Option<Self> {
+        match key.len() {
+            16 => Aes128Gcm::new_from_slice(key).ok().map(AesGcm::Aes128),
+            24 => Aes192Gcm::new_from_slice(key).ok().map(AesGcm::Aes192),
+            32 => Aes256Gcm::new_from_slice(key).ok().map(AesGcm::Aes256),
+            _ => None,
+        }
+    }
+
+    /// Encrypt `data` in place and return the 16-byte tag
+    fn seal(&self, nonce: &[u8; 12], aad: &[u8], data: &mut [u8]) -> Result<[u8; 16], JsValue> {
+        let nonce = Nonce::from_slice(nonce);
+        let tag = match self {
+            AesGcm::Aes128(c) => c.encrypt_in_place_detached(nonce, aad, data),
+            AesGcm::Aes192(c) => c.encrypt_in_place_detached(nonce, aad, data),
+            AesGcm::Aes256(c) => c.encrypt_in_place_detached(nonce, aad, data),
+        };
+        tag.map(Into::into).map_err(|_| JsValue::from_str("encryption: a module is too large for AES-GCM"))
+    }
+}
+
+/// Kinds of module, the first byte after the file's AAD
+const MODULE_FOOTER: u8 = 0;
+const MODULE_COLUMN_META: u8 = 1;
+const MODULE_DATA_PAGE: u8 = 2;
+const MODULE_DICT_PAGE: u8 = 3;
+const MODULE_DATA_PAGE_HEADER: u8 = 4;
+const MODULE_DICT_PAGE_HEADER: u8 = 5;
+const MODULE_COLUMN_INDEX: u8 = 6;
+const MODULE_OFFSET_INDEX: u8 = 7;
+const MODULE_BLOOM_HEADER: u8 = 8;
+const MODULE_BLOOM_BITSET: u8 = 9;
+
+/// Bytes a module adds around its plaintext: length, nonce and tag
+const MODULE_OVERHEAD: usize = 4 + 12 + 16;
+
+/// Parsed `encryption` config
+struct Encryption {
+    footer: AesGcm,
+    footer_key_metadata: Option<Vec<u8>>,
+    /// Columns with their own key
+    columns: Vec<ColumnKey>,
+    /// Stored in the file, ahead of its random part in every AAD
+    aad_prefix: Option<Vec<u8>>,
+}
+
+/// A column's own key, from `columnKeys`
+struct ColumnKey {
+    /// Dotted leaf column path
+    path: String,
+    cipher: AesGcm,
+    key_metadata: Option<Vec<u8>>,
+}
+
+impl Encryption {
+    /// Cipher of the column at dotted `path`, and its entry if it has its own key
+    fn column(&self, path: &str) -> (&AesGcm, Option<&ColumnKey>) {
+        match self.columns.iter().find(|c| c.path == path) {
+            Some(c) => (&c.cipher, Some(c)),
+            None => (&self.footer, None),
+        }
+    }
+}
+
+/// `n` bytes from the host's `crypto.getRandomValues`
+fn random_bytes(n: usize) -> Result<Vec<u8>, JsValue> {
+    let crypto = Reflect::get(&js_sys::global(), &"crypto".into())?;
+    let get = Reflect::get(&crypto, &"getRandomValues".into())
+        .ok()
+        .and_then(|f| f.dyn_into::<js_sys::Function>().ok())
+        .ok_or_else(|| JsValue::from_str("encryption needs crypto.getRandomValues"))?;
+    let buf = Uint8Array::new_with_length(n as u32);
+    get.call1(&crypto, &buf)?;
+    Ok(buf.to_vec())
+}
+
+/// Key bytes or key metadata: a Uint8Array, or (`text`) a string stored as UTF-8
+fn encryption_bytes(v: &JsValue, what: &str, text: bool) -> Result<Vec<u8>, JsValue> {
+    match (v.dyn_ref::<Uint8Array>(), v.as_string()) {
+        (Some(a), _) => Ok(a.to_vec()),
+        (None, Some(s)) if text => Ok(s.into_bytes()),
+        _ if text => Err(JsValue::from_str(&format!("encryption: {} must be a string or Uint8Array", what))),
+        _ => Err(JsValue::from_str(&format!("encryption: {} must be a Uint8Array", what))),
+    }
+}
+
+/// An AES-GCM cipher for `key`, which must be 16, 24 or 32 bytes
+fn encryption_key(v: &JsValue, what: &str) -> Result<AesGcm, JsValue> {
+    let key = encryption_bytes(v, what, false)?;
+    AesGcm::new(&key).ok_or_else(|| JsValue::from_str(&format!("encryption: {} must be 16, 24 or 32 bytes, got {}", what, key.len())))
+}
+
+/// Optional string-or-bytes member `key` of an `encryption` object
+fn encryption_member(obj: &JsValue, key: &str, what: &str) -> Result<Option<Vec<u8>>, JsValue> {
+    let v = Reflect::get(obj, &key.into()).unwrap_or(JsValue::UNDEFINED);
+    if v.is_undefined() || v.is_null() {
+        return Ok(None);
+    }
+    encryption_bytes(&v, what, true).map(Some)
+}
+
+/// Read `encryption`: `{ footerKey, footerKeyMetadata?, columnKeys?, aadPrefix? }`,
+/// where `columnKeys` maps leaf column paths to a key or `{ key, keyMetadata }`
+fn parse_encryption(config_js: &JsValue, leaf_paths: &[String]) -> Result<Option<Encryption>, JsValue> {
+    let v = Reflect::get(config_js, &"encryption".into()).unwrap_or(JsValue::UNDEFINED);
+    if v.is_undefined() || v.is_null() {
+        return Ok(None);
+    }
+    if !v.is_object() {
+        return Err(JsValue::from_str("encryption must be an object like { footerKey }"));
+    }
+    let footer_key = Reflect::get(&v, &"footerKey".into())?;
+    if footer_key.is_undefined() {
+        return Err(JsValue::from_str("encryption: footerKey is required"));
+    }
+    let mut columns = Vec::new();
+    let keys = Reflect::get(&v, &"columnKeys".into())?;
+    if keys.is_object() {
+        for e in Object::entries(keys.unchecked_ref::<Object>()).iter() {
+            let e: Array = e.into();
+            let path = e.get(0).as_string().unwrap_or_default();
+            if !leaf_paths.contains(&path) {
+                return Err(JsValue::from_str(&format!("encryption: unknown column '{}' in columnKeys", path)));
+            }
+            let what = format!("key for '{}'", path);
+            let entry = e.get(1);
+            let (key, key_metadata) = match entry.is_object() && !entry.is_instance_of::<Uint8Array>() {
+                true => (Reflect::get(&entry, &"key".into())?, encryption_member(&entry, "keyMetadata", &format!("keyMetadata for '{}'", path))?),
+                false => (entry, None),
+            };
+            columns.push(ColumnKey { cipher: encryption_key(&key, &what)?, path, key_metadata });
+        }
+    } else if !keys.is_undefined() && !keys.is_null() {
+        return Err(JsValue::from_str("encryption: columnKeys must be an object like { column: key }"));
+    }
+    Ok(Some(Encryption {
+        footer: encryption_key(&footer_key, "footerKey")?,
+        footer_key_metadata: encryption_member(&v, "footerKeyMetadata", "footerKeyMetadata")?,
+        columns,
+        aad_prefix: encryption_member(&v, "aadPrefix", "aadPrefix")?,
+    }))
+}
+
+/// AAD of a module: the file's AAD, the module kind, then the row group,
+/// column and (data pages only) page ordinals as 16-bit little-endian
+fn module_aad(file_aad: &[u8], kind: u8, ordinals: &[usize]) -> Result<Vec<u8>, JsValue> {
+    let mut aad = file_aad.to_vec();
+    aad.push(kind);
+    for &n in ordinals {
+        let n = i16::try_from(n).map_err(|_| JsValue::from_str("encryption: more than 32767 row groups, columns or pages in a column chunk"))?;
+        aad.extend_from_slice(&n.to_le_bytes());
+    }
+    Ok(aad)
+}
+
+/// Append `data` to `out` as a module encrypted with `cipher`, returning its size
+fn write_module(out: &mut Vec<u8>, cipher: &AesGcm, aad: &[u8], data: &[u8]) -> Result<usize, JsValue> {
+    let nonce: [u8; 12] = random_bytes(12)?.try_into().unwrap();
+    let start = out.len();
+    out.extend_from_slice(&((data.len() + MODULE_OVERHEAD - 4) as u32).to_le_bytes());
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(data);
+    let tag = cipher.seal(&nonce, aad, &mut out[start + 16..])?;
+    out.extend_from_slice(&tag);
+    Ok(out.len() - start)
+}
+
+/// Re-emit a finished file (footer stripped, nothing sent to a sink) with
+/// every page, page index and bloom filter encrypted, moving the offsets in
+/// `meta` to match and marking each column chunk with the key it uses.
+/// parquet2's plaintext copy of each chunk's metadata is left out.
+fn encrypt_file(bytes: &[u8], meta: &mut FileMetaData, enc: &Encryption, file_aad: &[u8]) -> Result<Vec<u8>, JsValue> {
+    let mut out = Vec::with_capacity(bytes.len() + bytes.len() / 32);
+    out.extend_from_slice(b"PARE");
+    let mut moved: Vec<Vec<(i64, i64, i32)>> = Vec::new();
+
+    for (r, rg) in meta.row_groups.iter_mut().enumerate() {
+        rg.ordinal = Some(i16::try_from(r).map_err(|_| JsValue::from_str("encryption: more than 32767 row groups"))?);
+        let mut grown = 0i64;
+        for (c, col) in rg.columns.iter_mut().enumerate() {
+            let cm = col
+                .meta_data
+                .as_mut()
+                .ok_or_else(|| JsValue::from_str("finalize error: missing column metadata"))?;
+            let (cipher, own_key) = enc.column(&cm.path_in_schema.join("."));
+            let start = cm.dictionary_page_offset.unwrap_or(cm.data_page_offset).min(cm.data_page_offset) as usize;
+            let end = start + cm.total_compressed_size as usize;
+            let new_start = out.len();
+            let mut pages = Vec::new();
+            let mut pos = start;
+            let mut ordinal = 0;
+            let (mut dictionary, mut first_data) = (None, None);
+            while pos < end {
+                let (mut header, header_len) = read_thrift::<PageHeader>(&bytes[pos..end])?;
+                let body = &bytes[pos + header_len..pos + header_len + header.compressed_page_size as usize];
+                let (body_kind, header_kind, ordinals) = match header.type_ {
+                    PageType::DICTIONARY_PAGE => (MODULE_DICT_PAGE, MODULE_DICT_PAGE_HEADER, vec![r, c]),
+                    _ => {
+                        ordinal += 1;
+                        (MODULE_DATA_PAGE, MODULE_DATA_PAGE_HEADER, vec![r, c, ordinal - 1])
+                    }
+                };
+                let mut sealed = Vec::with_capacity(body.len() + MODULE_OVERHEAD);
+                write_module(&mut sealed, cipher, &module_aad(file_aad, body_kind, &ordinals)?, body)?;
+                // The page size and CRC cover the page as stored
+                header.compressed_page_size = sealed.len() as i32;
+                if header.crc.is_some() {
+                    header.crc = Some(crc32fast::hash(&sealed) as i32);
+                }
+                let mut plain_header = Vec::new();
+                write_thrift(&mut plain_header, |p| header.write_to_out_protocol(p))?;
+                let page_start = out.len();
+                write_module(&mut out, cipher, &module_aad(file_aad, header_kind, &ordinals)?, &plain_header)?;
+                out.extend_from_slice(&sealed);
+                pages.push((pos as i64, page_start as i64, (out.len() - page_start) as i32));
+                match body_kind {
+                    MODULE_DICT_PAGE => dictionary = Some(page_start as i64),
+                    _ => first_data = first_data.or(Some(page_start as i64)),
+                }
+                pos += header_len + body.len();
+            }
+            // parquet2 points data_page_offset at the dictionary page and
+            // leaves dictionary_page_offset unset; readers can't look inside
+            // an encrypted header, so the offsets must say which page is which
+            cm.dictionary_page_offset = dictionary;
+            cm.data_page_offset = first_data.unwrap_or_else(|| relocate(&pages, cm.data_page_offset));
+            let new_size = (out.len() - new_start) as i64;
+            grown += new_size - cm.total_compressed_size;
+            cm.total_uncompressed_size += new_size - cm.total_compressed_size;
+            cm.total_compressed_size = new_size;
+            col.file_offset = new_start as i64;
+            col.crypto_metadata = Some(match own_key {
+                Some(key) => ColumnCryptoMetaData::ENCRYPTIONWITHCOLUMNKEY(EncryptionWithColumnKey {
+                    path_in_schema: cm.path_in_schema.clone(),
+                    key_metadata: key.key_metadata.clone(),
+                }),
+                None => ColumnCryptoMetaData::ENCRYPTIONWITHFOOTERKEY(EncryptionWithFooterKey {}),
+            });
+            moved.push(pages);
+        }
+        rg.file_offset = rg.columns.first().map(|c| c.file_offset);
+        rg.total_compressed_size = rg.total_compressed_size.map(|s| s + grown);
+        rg.total_byte_size += grown;
+    }
+
+    /// Every column chunk, with its row group and column ordinals
+    fn chunks(meta: &mut FileMetaData) -> impl Iterator<Item = (usize, usize, &mut ColumnChunk)> {
+        let row_groups = meta.row_groups.iter_mut().enumerate();
+        row_groups.flat_map(|(r, rg)| rg.columns.iter_mut().enumerate().map(move |(c, col)| (r, c, col)))
+    }
+    for (r, c, col) in chunks(meta) {
+        if let (Some(off), Some(len), Some(cm)) = (col.column_index_offset, col.column_index_length, &col.meta_data) {
+            let (cipher, _) = enc.column(&cm.path_in_schema.join("."));
+            let aad = module_aad(file_aad, MODULE_COLUMN_INDEX, &[r, c])?;
+            col.column_index_offset = Some(out.len() as i64);
+            col.column_index_length = Some(write_module(&mut out, cipher, &aad, &bytes[off as usize..off as usize + len as usize])? as i32);
+        }
+    }
+    for ((r, c, col), pages) in chunks(meta).zip(moved.iter()) {
+        if let (Some(off), Some(len), Some(cm)) = (col.offset_index_offset, col.offset_index_length, &col.meta_data) {
+            let (mut index, _) = read_thrift::<OffsetIndex>(&bytes[off as usize..off as usize + len as usize])?;
+            for loc in index.page_locations.iter_mut() {
+                if let Some(&(_, offset, size)) = pages.iter().find(|p| p.0 == loc.offset) {
+                    loc.offset = offset;
+                    loc.compressed_page_size = size;
+                }
+            }
+            let mut plain = Vec::new();
+            write_thrift(&mut plain, |p| index.write_to_out_protocol(p))?;
+            let (cipher, _) = enc.column(&cm.path_in_schema.join("."));
+            col.offset_index_offset = Some(out.len() as i64);
+            col.offset_index_length = Some(write_module(&mut out, cipher, &module_aad(file_aad, MODULE_OFFSET_INDEX, &[r, c])?, &plain)? as i32);
+        }
+    }
+    for (r, c, col) in chunks(meta) {
+        let Some(cm) = col.meta_data.as_mut() else { continue };
+        let Some(off) = cm.bloom_filter_offset else { continue };
+        let (header, header_len) = read_thrift::<BloomFilterHeader>(&bytes[off as usize..])?;
+        let bitset = &bytes[off as usize + header_len..off as usize + header_len + header.num_bytes as usize];
+        let (cipher, _) = enc.column(&cm.path_in_schema.join("."));
+        cm.bloom_filter_offset = Some(out.len() as i64);
+        write_module(&mut out, cipher, &module_aad(file_aad, MODULE_BLOOM_HEADER, &[r, c])?, &bytes[off as usize..off as usize + header_len])?;
+        write_module(&mut out, cipher, &module_aad(file_aad, MODULE_BLOOM_BITSET, &[r, c])?, bitset)?;
+    }
+
+    Ok(out)
+}
+
+/// Append `meta` as an encrypted footer: the metadata of columns with their
+/// own key is encrypted with it, then the whole footer with the footer key,
+/// behind the plaintext FileCryptoMetaData readers need to decrypt it
+fn append_encrypted_footer(bytes: &mut Vec<u8>, meta: &mut FileMetaData, enc: &Encryption, algorithm: EncryptionAlgorithm, file_aad: &[u8]) -> Result<(), JsValue> {
+    for (r, rg) in meta.row_groups.iter_mut().enumerate() {
+        for (c, col) in rg.columns.iter_mut().enumerate() {
+            let Some(cm) = &col.meta_data else { continue };
+            if let (cipher, Some(_)) = enc.column(&cm.path_in_schema.join(".")) {
+                let mut plain = Vec::new();
+                write_thrift(&mut plain, |p| cm.write_to_out_protocol(p))?;
+                let mut sealed = Vec::new();
+                write_module(&mut sealed, cipher, &module_aad(file_aad, MODULE_COLUMN_META, &[r, c])?, &plain)?;
+                col.encrypted_column_metadata = Some(sealed);
+                col.meta_data = None;
+            }
+        }
+    }
+    let start = bytes.len();
+    let crypto = FileCryptoMetaData { encryption_algorithm: algorithm, key_metadata: enc.footer_key_metadata.clone() };
+    write_thrift(bytes, |p| crypto.write_to_out_protocol(p))?;
+    let mut plain = Vec::new();
+    write_thrift(&mut plain, |p| meta.write_to_out_protocol(p))?;
+    mark_float16(&mut plain, meta)?;
+    write_module(bytes, &enc.footer, &module_aad(file_aad, MODULE_FOOTER, &[])?, &plain)?;
+    let len = bytes.len() - start;
+    bytes.extend_from_slice(&(len as u32).to_le_bytes());
+    bytes.extend_from_slice(b"PARE");
+    Ok(())
+}
+
+// ── Merging ─────────────────────────────────────────────────────────────────
+//
+// Row groups are copied over byte for byte; only the offsets in their
+// metadata change.
+
+/// Slice reader that counts the bytes consumed, so a thrift walk can tell
+/// where each field starts
+struct Tracked<'a> {
+    rest: &'a [u8],
+    pos: Rc<Cell<usize>>,
+}
+
+impl Read for Tracked<'_> {
+    fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.rest.read(out)?;
+        self.pos.set(self.pos.get() + n);
+        Ok(n)
+    }
+}
+
+/// For each element of the schema in the thrift-encoded `footer`, the
+/// position and union member of its logical_type header. Only single-byte
+/// headers are reported, so each can be swapped for another member's in place.
+fn logical_type_headers(footer: &[u8]) -> parquet_format_safe::thrift::Result<Vec<Option<(usize, i16)>>> {
+    let pos = Rc::new(Cell::new(0));
+    let mut p = TCompactInputProtocol::new(Tracked { rest: footer, pos: pos.clone() }, footer.len() * 2 + 1024);
+    let mut found = Vec::new();
+    p.read_struct_begin()?;
+    loop {
+        let field = p.read_field_begin()?;
+        if field.field_type == TType::Stop {
+            break;
+        }
+        // FileMetaData.schema (field 2): the flattened SchemaElement list
+        if field.id != Some(2) {
+            p.skip(field.field_type)?;
+            p.read_field_end()?;
+            continue;
+        }
+        let list = p.read_list_begin()?;
+        for _ in 0..list.size {
+            let mut header = None;
+            p.read_struct_begin()?;
+            loop {
+                let f = p.read_field_begin()?;
+                match (f.field_type, f.id) {
+                    (TType::Stop, _) => break,
+                    // logical_type: a union, so a single member
+                    (TType::Struct, Some(10)) => {
+                        p.read_struct_begin()?;
+                        loop {
+                            let start = pos.get();
+                            let m = p.read_field_begin()?;
+                            if m.field_type == TType::Stop {
+                                break;
+                            }
+                            // Short form: the id (a delta from 0) in the high nibble, struct type 12 in the low
+                            match m.id {
+                                Some(id @ 1..=15) if footer[start] == (id as u8) << 4 | 0x0C => header = Some((start, id)),
+                                _ => {}
+                            }
+                            p.skip(m.field_type)?;
+                            p.read_field_end()?;
+                        }
+                        p.read_struct_end()?;
+                    }
+                    _ => p.skip(f.field_type)?,
+                }
+                p.read_field_end()?;
+            }
+            p.read_struct_end()?;
+            found.push(header);
+        }
+        p.read_list_end()?;
+        break;
+    }
+    Ok(found)
+}
+
+/// Parse the footer of `bytes` (merge input `label`), returning it and the
+/// offset it starts at. FLOAT16 columns come back with the UNKNOWN
+/// placeholder, which `append_footer` turns into FLOAT16 again.
+fn read_footer(bytes: &[u8], label: &str) -> Result<(FileMetaData, usize), JsValue> {
+    let bad = |what: &str| JsValue::from_str(&format!("{}: {}", label, what));
+    let len = bytes.len();
+    if len >= 12 && &bytes[len - 4..] == b"PARE" {
+        return Err(bad("encrypted files aren't supported"));
+    }
+    if len < 12 || &bytes[..4] != b"PAR1" || &bytes[len - 4..] != b"PAR1" {
+        return Err(bad("not a parquet file"));
+    }
+    let meta_len = u32::from_le_bytes(bytes[len - 8..len - 4].try_into().unwrap()) as usize;
+    let start = len.checked_sub(8 + meta_len).filter(|&s| s >= 4).ok_or_else(|| bad("footer length out of range"))?;
+    let mut footer = bytes[start..len - 8].to_vec();
+    for (pos, member) in logical_type_headers(&footer).map_err(|e| bad(&format!("metadata: {}", e)))?.into_iter().flatten() {
+        if member == LOGICAL_FLOAT16 {
+            footer[pos] = LOGICAL_UNKNOWN_HEADER;
+        }
+    }
+    let (meta, _) = read_thrift::<FileMetaData>(&footer).map_err(|_| bad("metadata could not be parsed"))?;
+    if meta.encryption_algorithm.is_some() {
+        return Err(bad("encrypted files aren't supported"));
+    }
+    Ok((meta, start))
+}
+
+/// Bytes `start..start + len` of merge input `label`
+fn input_range(bytes: &[u8], label: &str, start: i64, len: i64, what: &str) -> Result<Range<usize>, JsValue> {
+    let range = usize::try_from(start).ok().zip(usize::try_from(len).ok()).map(|(s, l)| s..s.saturating_add(l));
+    range
+        .filter(|r| r.end <= bytes.len())
+        .ok_or_else(|| JsValue::from_str(&format!("{}: {} out of range", label, what)))
+}
+
+/// Column and offset index of a copied column chunk, if it has them
+type ChunkIndexes = (Option<Vec<u8>>, Option<OffsetIndex>);
+
+/// Copy the column chunks (and bloom filters) of every row group in `meta`
+/// from `bytes` to the end of `out`, moving the offsets in `meta` along. The
+/// row groups are numbered from `first_ordinal`; their page indexes are
+/// returned for `write_indexes`, one entry per column chunk.
+fn copy_row_groups(
+    out: &mut Vec<u8>,
+    bytes: &[u8],
+    label: &str,
+    meta: &mut FileMetaData,
+    first_ordinal: usize,
+) -> Result<Vec<ChunkIndexes>, JsValue> {
+    let bad = |what: &str| JsValue::from_str(&format!("{}: {}", label, what));
+    let mut indexes = Vec::new();
+    for (ordinal, rg) in (first_ordinal..).zip(meta.row_groups.iter_mut()) {
+        for col in rg.columns.iter_mut() {
+            if col.file_path.is_some() || col.crypto_metadata.is_some() {
+                return Err(bad("column chunks in other files or encrypted can't be merged"));
+            }
+            let cm = col.meta_data.as_mut().ok_or_else(|| bad("missing column metadata"))?;
+            // Some writers put 0 here for chunks without a dictionary page
+            cm.dictionary_page_offset = cm.dictionary_page_offset.filter(|&o| o > 0);
+            let start = cm.dictionary_page_offset.unwrap_or(cm.data_page_offset).min(cm.data_page_offset);
+            let chunk = input_range(bytes, label, start, cm.total_compressed_size, "column chunk")?;
+            let shift = out.len() as i64 - start;
+            out.extend_from_slice(&bytes[chunk]);
+            cm.data_page_offset += shift;
+            cm.dictionary_page_offset = cm.dictionary_page_offset.map(|o| o + shift);
+            cm.index_page_offset = cm.index_page_offset.map(|o| o + shift);
+            if let Some(off) = cm.bloom_filter_offset {
+                let rest = input_range(bytes, label, off, bytes.len() as i64 - off, "bloom filter")?;
+                let (header, header_len) = read_thrift::<BloomFilterHeader>(&bytes[rest])
+                    .map_err(|_| bad("bloom filter header could not be parsed"))?;
+                let filter = input_range(bytes, label, off, header_len as i64 + header.num_bytes as i64, "bloom filter")?;
+                cm.bloom_filter_offset = Some(out.len() as i64);
+                out.extend_from_slice(&bytes[filter]);
+            }
+            // Like parquet2, follow the pages with a copy of the chunk's metadata
+            col.file_offset = out.len() as i64;
+            write_thrift(out, |p| cm.write_to_out_protocol(p))?;
+
+            let column_index = match (col.column_index_offset, col.column_index_length) {
+                (Some(off), Some(len)) => Some(bytes[input_range(bytes, label, off, len as i64, "column index")?].to_vec()),
+                _ => None,
+            };
+            let offset_index = match (col.offset_index_offset, col.offset_index_length) {
+                (Some(off), Some(len)) => {
+                    let range = input_range(bytes, label, off, len as i64, "offset index")?;
+                    let (mut index, _) =
+                        read_thrift::<OffsetIndex>(&bytes[range]).map_err(|_| bad("offset index could not be parsed"))?;
+                    for loc in index.page_locations.iter_mut() {
+                        loc.offset += shift;
+                    }
+                    Some(index)
+                }
+                _ => None,
+            };
+            indexes.push((column_index, offset_index));
+        }
+        rg.file_offset = rg.columns.first().and_then(|c| c.meta_data.as_ref()).map(|m| {
+            m.dictionary_page_offset.unwrap_or(m.data_page_offset).min(m.data_page_offset)
+        });
+        rg.ordinal = rg.ordinal.map(|_| ordinal as i16);
+    }
+    Ok(indexes)
+}
+
+/// Write the page indexes `copy_row_groups` collected for `row_groups` to the
+/// end of `out`, pointing their column chunks at them
+fn write_indexes(out: &mut Vec<u8>, row_groups: &mut [RowGroup], indexes: &[ChunkIndexes]) -> Result<(), JsValue> {
+    // Column indexes hold no offsets: copy them over as-is
+    for (col, (column_index, _)) in row_groups.iter_mut().flat_map(|rg| rg.columns.iter_mut()).zip(indexes) {
+        if let Some(bytes) = column_index {
+            col.column_index_offset = Some(out.len() as i64);
+            out.extend_from_slice(bytes);
+        }
+    }
+    for (col, (_, offset_index)) in row_groups.iter_mut().flat_map(|rg| rg.columns.iter_mut()).zip(indexes) {
+        if let Some(index) = offset_index {
+            col.offset_index_offset = Some(out.len() as i64);
+            col.offset_index_length = Some(write_thrift(out, |p| index.write_to_out_protocol(p))? as i32);
+        }
+    }
+    Ok(())
+}
+
+/// File output shared by the `FileWriter` and `ParquetWriter`, so finished
+/// row groups can be taken out for `sink` while the file is still open
+#[derive(Clone, Default)]
+struct SharedBuf(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedBuf {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Incremental writer: every `appendRows` / `appendColumns` call is encoded
+/// and compressed into row groups right away, so the rows can be dropped on
+/// the JS side; `finish` writes the footer and returns the file (or hands
+/// each row group and the footer to `sink` as they're done).
+#[wasm_bindgen]
+pub struct ParquetWriter {
+    cfg: WriteConfig,
+    fields: Vec<Field>,
+    /// Dotted path of every leaf column, in schema order
+    leaf_paths: Vec<String>,
+    schema_desc: SchemaDescriptor,
+    sorted_by: Option<Vec<SortingColumn>>,
+    /// Key columns `append` sorts rows by: (leaf index, descending)
+    sort_by: Option<Vec<(usize, bool)>>,
+    /// None once finished
+    writer: Option<FileWriter<SharedBuf>>,
+    /// The writer's output, drained into `sink` after every row group
+    output: SharedBuf,
+    /// (row group, column, bounds) to patch into the footer statistics
+    bounds: Vec<(usize, usize, ParquetStatistics)>,
+    /// Value encoding of every leaf column
+    encodings: Vec<ValueEncoding>,
+    /// Leaf columns that get bloom filters
+    bloom: Vec<(usize, BloomSpec)>,
+    /// (row group, column, bitset) to write before the footer
+    blooms: Vec<(usize, usize, Vec<u8>)>,
+    /// Keys to encrypt the finished file with
+    encryption: Option<Encryption>,
+    /// Geometry columns to describe in the `geo` footer entry
+    geo: Vec<GeoColumn>,
+    /// Index columns of the `pandas` footer entry, when it's written
+    pandas: Option<Vec<String>>,
+    /// Bytes already handed to `sink`
+    flushed: usize,
+    row_groups: usize,
+    num_rows: usize,
+}
+
+#[wasm_bindgen]
+impl ParquetWriter {
+    #[wasm_bindgen(constructor)]
+    pub fn new(schema_js: &JsValue, config_js: &JsValue) -> Result<ParquetWriter, JsValue> {
+        let cfg = WriteConfig::from_js(config_js)?;
+
+        let schema_arr: &Array = schema_js
+            .dyn_ref::<Array>()
+            .ok_or_else(|| JsValue::from_str("schema must be an array"))?;
+
+        let num_cols = schema_arr.length() as usize;
+        let mut fields: Vec<Field> = Vec::with_capacity(num_cols);
+
+        for i in 0..num_cols {
+            let col = schema_arr.get(i as u32);
+            let name = Reflect::get(&col, &"name".into())
+                .ok()
+                .and_then(|v| v.as_string())
+                .ok_or_else(|| JsValue::from_str("schema element must have string 'name'"))?;
+            fields.push(Field::from_js(&col, name.clone(), &name)?);
+        }
+
+        // Primitive columns in schema order: dotted path and type
+        let mut leaves = Vec::new();
+        for f in &fields {
+            f.leaves(&f.name, &mut leaves);
+        }
+        let leaf_paths: Vec<String> = leaves.iter().map(|(path, _)| path.clone()).collect();
+        let parquet_fields: Vec<ParquetType> = fields.iter().map(Field::parquet_type).collect();
+
+        if let Some((column, _)) = cfg.compression.per_column.iter().find(|(p, _)| !leaf_paths.contains(p)) {
+            return Err(JsValue::from_str(&format!("compression.perColumn: unknown column '{}'", column)));
+        }
+        let sort_by = parse_sort_by(config_js, &fields, &leaf_paths)?;
+        let sorted_by = match (parse_sorted_by(config_js, &leaf_paths)?, &sort_by) {
+            (Some(_), Some(_)) => return Err(JsValue::from_str("sortBy records the sort order itself; drop sortedBy")),
+            // Rows sorted here are sorted in every row group
+            (None, Some(keys)) => Some(
+                keys.iter()
+                    .map(|&(i, descending)| SortingColumn { column_idx: i as i32, descending, nulls_first: false })
+                    .collect(),
+            ),
+            (sorted_by, None) => sorted_by,
+        };
+        let encodings = parse_value_encodings(config_js, &leaves)?;
+        let bloom = parse_bloom_filters(config_js, &leaves)?;
+        let encryption = parse_encryption(config_js, &leaf_paths)?;
+        let schema_desc = SchemaDescriptor::new(cfg.schema_name.clone(), parquet_fields);
+        if cfg.arrow_schema && cfg.metadata.iter().flatten().any(|kv| kv.key == "ARROW:schema") {
+            return Err(JsValue::from_str("arrowSchema: metadata already has an 'ARROW:schema' entry"));
+        }
+        let geo = parse_geo_columns(schema_arr, &fields, &leaves)?;
+        if !geo.is_empty() && cfg.metadata.iter().flatten().any(|kv| kv.key == "geo") {
+            return Err(JsValue::from_str("geometry columns write the 'geo' metadata entry; drop it from metadata"));
+        }
+        let pandas = parse_pandas(config_js, &fields)?;
+        if pandas.is_some() && cfg.metadata.iter().flatten().any(|kv| kv.key == "pandas") {
+            return Err(JsValue::from_str("pandas: metadata already has a 'pandas' entry"));
+        }
+        // Checksums are added by rewriting the finished file's pages
+        if cfg.page_checksums && cfg.sink.is_some() {
+            return Err(JsValue::from_str("pageChecksums can't be combined with sink"));
+        }
+        // So is encryption, which also changes the magic at the start
+        if encryption.is_some() && cfg.sink.is_some() {
+            return Err(JsValue::from_str("encryption can't be combined with sink"));
+        }
+
+        let options = WriteOptions {
+            write_statistics: cfg.statistics,
+            version: cfg.version,
+        };
+        let output = SharedBuf::default();
+        let writer = FileWriter::new(output.clone(), schema_desc.clone(), options, cfg.created_by.clone());
+
+        Ok(ParquetWriter {
+            cfg,
+            fields,
+            leaf_paths,
+            schema_desc,
+            sorted_by,
+            sort_by,
+            writer: Some(writer),
+            output,
+            bounds: Vec::new(),
+            encodings,
+            bloom,
+            blooms: Vec::new(),
+            encryption,
+            geo,
+            pandas,
+            flushed: 0,
+            row_groups: 0,
+            num_rows: 0,
+        })
+    }
+
+    /// Append an array of row objects (missing keys are null/undefined)
+    #[wasm_bindgen(js_name = "appendRows")]
+    pub fn append_rows(&mut self, rows: &JsValue) -> Result<(), JsValue> {
+        if !Array::is_array(rows) {
+            return Err(JsValue::from_str("appendRows expects an array of row objects"));
+        }
+        self.append(rows)
+    }
+
+    /// Append columnar data keyed by column name
+    #[wasm_bindgen(js_name = "appendColumns")]
+    pub fn append_columns(&mut self, data: &JsValue) -> Result<(), JsValue> {
+        if Array::is_array(data) || !data.is_object() {
+            return Err(JsValue::from_str("appendColumns expects an object of column arrays"));
+        }
+        self.append(data)
+    }
+
+    /// Rows written so far
+    #[wasm_bindgen(getter, js_name = "numRows")]
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Row groups written so far
+    #[wasm_bindgen(getter, js_name = "numRowGroups")]
+    pub fn num_row_groups(&self) -> usize {
+        self.row_groups
+    }
+
+    /// Write the footer and return the file. The writer can't be used afterwards.
+    pub fn finish(&mut self) -> Result<js_sys::Uint8Array, JsValue> {
+        let mut writer = self.writer.take().ok_or_else(|| JsValue::from_str("ParquetWriter is already finished"))?;
+
+        let mut metadata = self.cfg.metadata.clone();
+        if self.cfg.arrow_schema {
+            let arrow = base64(&arrow_schema_message(&self.fields));
+            metadata.get_or_insert_with(Vec::new).push(KeyValue { key: "ARROW:schema".to_string(), value: Some(arrow) });
+        }
+        if !self.geo.is_empty() {
+            let geo = geo_metadata(&self.geo)?;
+            metadata.get_or_insert_with(Vec::new).push(KeyValue { key: "geo".to_string(), value: Some(geo) });
+        }
+        if let Some(index) = &self.pandas {
+            let pandas = pandas_metadata(&self.fields, index)?;
+            metadata.get_or_insert_with(Vec::new).push(KeyValue { key: "pandas".to_string(), value: Some(pandas) });
+        }
+        writer
+            .end(metadata)
+            .map_err(|e| JsValue::from_str(&format!("finalize error: {}", e)))?;
+
+        let (buf, mut meta) = writer.into_inner_and_metadata();
+        // Everything not yet handed to the sink: the whole file, or the indexes and footer
+        let mut bytes = buf.0.take();
+        let mut leaves = Vec::new();
+        for f in &self.fields {
+            f.leaves(&f.name, &mut leaves);
+        }
+        let has_float16 = leaves.iter().any(|(_, ct)| matches!(ct, ColType::Float16));
+        let rewrite = self.sorted_by.is_some() || self.cfg.page_checksums || !self.bounds.is_empty() || !self.blooms.is_empty() || has_float16 || self.encryption.is_some();
+        if rewrite || self.cfg.statistics {
+            if let Some(sorting) = &self.sorted_by {
+                for rg in meta.row_groups.iter_mut() {
+                    rg.sorting_columns = Some(sorting.clone());
+                }
+            }
+            for (rg, col, b) in self.bounds.drain(..) {
+                let chunk_meta = meta.row_groups[rg].columns[col].meta_data.as_mut();
+                if let Some(stats) = chunk_meta.and_then(|m| m.statistics.as_mut()) {
+                    stats.min_value = b.min_value;
+                    stats.max_value = b.max_value;
+                }
+            }
+            // Leaf elements are the ones without children; append_footer turns
+            // these placeholders into FLOAT16
+            let leaf_elements = meta.schema.iter_mut().filter(|el| el.num_children.is_none());
+            for (el, (_, ct)) in leaf_elements.zip(&leaves) {
+                if matches!(ct, ColType::Float16) {
+                    el.logical_type = Some(LogicalType::UNKNOWN(NullType {}));
+                }
+            }
+            strip_footer(&mut bytes);
+            order_page_indexes(&mut bytes, self.flushed, &mut meta, &leaves)?;
+            if self.cfg.page_checksums {
+                bytes = add_page_crcs(&bytes, &mut meta)?;
+            }
+            append_bloom_filters(&mut bytes, self.flushed, &mut meta, std::mem::take(&mut self.blooms))?;
+            match &self.encryption {
+                Some(enc) => {
+                    let unique = random_bytes(8)?;
+                    let file_aad = [enc.aad_prefix.as_deref().unwrap_or_default(), &unique].concat();
+                    let algorithm = EncryptionAlgorithm::AESGCMV1(AesGcmV1 {
+                        aad_prefix: enc.aad_prefix.clone(),
+                        aad_file_unique: Some(unique),
+                        supply_aad_prefix: None,
+                    });
+                    bytes = encrypt_file(&bytes, &mut meta, enc, &file_aad)?;
+                    append_encrypted_footer(&mut bytes, &mut meta, enc, algorithm, &file_aad)?;
+                }
+                None => append_footer(&mut bytes, &meta)?,
+            }
+        }
+        if let Some(sink) = &self.cfg.sink {
+            sink.call1(&JsValue::NULL, &js_sys::Uint8Array::from(&bytes[..]))?;
+            return Ok(js_sys::Uint8Array::new_with_length(0));
+        }
+        let out = js_sys::Uint8Array::new_with_length(bytes.len() as u32);
+        out.copy_from(&bytes);
+        Ok(out)
+    }
+}
+
+impl ParquetWriter {
+    /// Encode `data` ({ col: [...] } or an array of row objects) and write it
+    /// as one or more row groups of at most `rowGroupSize` rows
+    fn append(&mut self, data_js: &JsValue) -> Result<(), JsValue> {
+        let cfg = &self.cfg;
+        let columns = self.schema_desc.columns();
+        let writer = self.writer.as_mut().ok_or_else(|| JsValue::from_str("ParquetWriter is already finished"))?;
+
+        // Pull every column out of JS once; row groups slice into these.
+        let rows = data_js.dyn_ref::<Array>();
+        let mut col_data: Vec<ColumnData> = Vec::with_capacity(self.leaf_paths.len());
+        for field in &self.fields {
+            let name = &field.name;
+            let arr_val = match rows {
+                Some(rows) => row_column(rows, name).into(),
+                None => Reflect::get(data_js, &JsValue::from_str(name))
+                    .map_err(|_| JsValue::from_str(&format!("missing column '{}'", name)))?,
+            };
+            let ct = match &field.kind {
+                FieldKind::Leaf(ct, _) => ct,
+                _ => {
+                    let arr = arr_val
+                        .dyn_into::<Array>()
+                        .map_err(|_| JsValue::from_str(&format!("column '{}' must be array", name)))?;
+                    let descs = &columns[col_data.len()..];
+                    col_data.extend(ColumnData::from_nested(field, &arr, descs, cfg)?);
+                    continue;
+                }
+            };
+            if let Some(c) = ColumnData::from_typed(&arr_val, ct, field.optional) {
+                col_data.push(c);
+                continue;
+            }
+            // Integer TypedArrays from_typed declined are read value by value, for the row error
+            let arr: Array = if (ct.int_range().is_some() || matches!(ct, ColType::UInt64)) && js_sys::ArrayBuffer::is_view(&arr_val) {
+                Array::from(&arr_val)
+            } else {
+                arr_val
+                    .dyn_into::<Array>()
+                    .map_err(|_| JsValue::from_str(&format!("column '{}' must be array", name)))?
+            };
+            col_data.push(ColumnData::from_js(&arr, &CellName { name, rows: None }, ct, field.optional, field.default.as_ref(), cfg)?);
+        }
+
+        let num_rows = col_data.first().map(|c| c.num_rows).unwrap_or(0);
+        for (name, c) in self.leaf_paths.iter().zip(col_data.iter()) {
+            if c.num_rows != num_rows {
+                return Err(JsValue::from_str(&format!(
+                    "column '{}' has {} values, expected {}",
+                    name, c.num_rows, num_rows
+                )));
+            }
+        }
+        if let Some(keys) = &self.sort_by {
+            sort_rows(&mut col_data, keys, num_rows);
+        }
+        for g in &mut self.geo {
+            g.add(&col_data[g.leaf].values);
+        }
+
+        let mut start = 0;
+        while start < num_rows {
+            let end = (start + cfg.row_group_size).min(num_rows);
+
+            // Build compressed column iterators (FileWriter expects CompressedPage)
+            let mut col_iters: Vec<
+                Result<
+                    DynStreamingIterator<'static, CompressedPage, parquet2::error::Error>,
+                    parquet2::error::Error,
+                >,
+            > = Vec::with_capacity(col_data.len());
+
+            for (i, c) in col_data.iter().enumerate() {
+                if let Some(b) = c.chunk_bounds(start..end).filter(|_| cfg.statistics) {
+                    self.bounds.push((self.row_groups, i, b));
+                }
+                if let Some((_, spec)) = self.bloom.iter().find(|(leaf, _)| *leaf == i) {
+                    if let Some(hashes) = c.bloom_hashes(start..end) {
+                        self.blooms.push((self.row_groups, i, bloom_filter(hashes, spec)));
+                    }
+                }
+                let pages = c.pages(start..end, cfg, &columns[i].descriptor, self.encodings[i]);
+                let compressed_pages = DynStreamingIterator::new(PageCompressor {
+                    pages: pages.into_iter(),
+                    codec: cfg.compression.column(&self.leaf_paths[i]),
+                    current: None,
+                });
+                col_iters.push(Ok(compressed_pages));
+            }
+
+            writer
+                .write(DynIter::new(col_iters.into_iter()))
+                .map_err(|e| JsValue::from_str(&format!("write error: {}", e)))?;
+            self.row_groups += 1;
+            start = end;
+
+            if let Some(sink) = &cfg.sink {
+                let buf = self.output.0.take();
+                self.flushed += buf.len();
+                if let Err(e) = sink.call1(&JsValue::NULL, &js_sys::Uint8Array::from(&buf[..])) {
+                    // The file now has a hole: refuse to go on
+                    self.writer = None;
+                    return Err(e);
+                }
+            }
+        }
+        self.num_rows += num_rows;
+        Ok(())
+    }
+}
+
+#[wasm_bindgen(js_name = "writeParquet")]
+pub fn write_parquet(
+    schema_js: &JsValue,
+    data_js: &JsValue,
+    config_js: &JsValue,
+) -> Result<js_sys::Uint8Array, JsValue> {
+    // No schema: infer one from the data
+    let schema = match schema_js.is_null() || schema_js.is_undefined() {
+        true => infer_schema(data_js)?.into(),
+        false => schema_js.clone(),
+    };
+    let mut writer = ParquetWriter::new(&schema, config_js)?;
+    writer.append(data_js)?;
+    writer.finish()
+}
+
+/// Concatenate parquet files with identical schemas into one, copying their
+/// row groups (with any page indexes and bloom filters) without re-encoding.
+/// Footer key-value metadata and `created_by` come from the first file.
+#[wasm_bindgen(js_name = "mergeParquet")]
+pub fn merge_parquet(files: &Array) -> Result<js_sys::Uint8Array, JsValue> {
+    if files.length() == 0 {
+        return Err(JsValue::from_str("mergeParquet needs at least one file"));
+    }
+    let mut out = b"PAR1".to_vec();
+    let mut merged: Option<FileMetaData> = None;
+    let mut indexes = Vec::new();
+
+    for (i, file) in files.iter().enumerate() {
+        let bytes = file
+            .dyn_into::<js_sys::Uint8Array>()
+            .map_err(|_| JsValue::from_str("mergeParquet takes an array of Uint8Arrays"))?
+            .to_vec();
+        let label = format!("file {}", i);
+        let (mut meta, _) = read_footer(&bytes, &label)?;
+        if merged.as_ref().is_some_and(|m| m.schema != meta.schema) {
+            return Err(JsValue::from_str(&format!("{}: schema differs from file 0", label)));
+        }
+        let first_ordinal = merged.as_ref().map_or(0, |m| m.row_groups.len());
+        indexes.extend(copy_row_groups(&mut out, &bytes, &label, &mut meta, first_ordinal)?);
+        match &mut merged {
+            None => merged = Some(meta),
+            Some(m) => {
+                m.num_rows += meta.num_rows;
+                m.row_groups.append(&mut meta.row_groups);
+            }
+        }
+    }
+
+    let mut meta = merged.unwrap();
+    write_indexes(&mut out, &mut meta.row_groups, &indexes)?;
+    append_footer(&mut out, &meta)?;
+    Ok(js_sys::Uint8Array::from(&out[..]))
+}
+
+/// Add `data` to an existing file as new row groups. Everything before the
+/// old footer is kept byte for byte; the new row groups follow it, then a
+/// footer listing both. `schema` and `config` are as for `writeParquet` and
+/// must produce the file's schema; the old footer's metadata is kept.
+#[wasm_bindgen(js_name = "appendToParquet")]
+pub fn append_to_parquet(
+    file: &js_sys::Uint8Array,
+    schema_js: &JsValue,
+    data_js: &JsValue,
+    config_js: &JsValue,
+) -> Result<js_sys::Uint8Array, JsValue> {
+    let mut writer = ParquetWriter::new(schema_js, config_js)?;
+    if writer.cfg.sink.is_some() {
+        return Err(JsValue::from_str("appendToParquet doesn't support sink"));
+    }
+    if writer.encryption.is_some() {
+        return Err(JsValue::from_str("appendToParquet doesn't support encryption"));
+    }
+    writer.append(data_js)?;
+    let added_bytes = writer.finish()?.to_vec();
+    let bytes = file.to_vec();
+    let (mut meta, footer_start) = read_footer(&bytes, "file")?;
+    let (mut added, _) = read_footer(&added_bytes, "new rows")?;
+    if added.schema != meta.schema {
+        return Err(JsValue::from_str("schema doesn't match the file's schema"));
+    }
+
+    let mut out = bytes;
+    out.truncate(footer_start);
+    let indexes = copy_row_groups(&mut out, &added_bytes, "new rows", &mut added, meta.row_groups.len())?;
+    write_indexes(&mut out, &mut added.row_groups, &indexes)?;
+    meta.num_rows += added.num_rows;
+    meta.row_groups.append(&mut added.row_groups);
+    append_footer(&mut out, &meta)?;
+    Ok(js_sys::Uint8Array::from(&out[..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet_format_safe::{FieldRepetitionType, Type};
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+    }
+
+    /// Test cases 2, 4, 8, 10, 14 and 16 of the GCM specification submitted
+    /// to NIST (McGrew & Viega): every key size, with and without AAD
+    #[test]
+    fn aes_gcm_known_answers() {
+        let k = "feffe9928665731c6d6a8f9467308308";
+        let p = "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39";
+        let a = "feedfacedeadbeeffeedfacedeadbeefabaddad2";
+        let iv = "cafebabefacedbaddecaf888";
+        let zero = "00000000000000000000000000000000";
+        let cases = [
+            (zero.to_string(), zero, "", "000000000000000000000000", "0388dace60b6a392f328c2b971b2fe78", "ab6e47d42cec13bdf53a67b21257bddf"),
+            (k.to_string(), p, a, iv, "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091", "5bc94fbc3221a5db94fae95ae7121a47"),
+            (format!("{zero}{}", &zero[..16]), zero, "", "000000000000000000000000", "98e7247c07f0fe411c267e4384b0f600", "2ff58d80033927ab8ef4d4587514f0fb"),
+            (format!("{k}{}", &k[..16]), p, a, iv, "3980ca0b3c00e841eb06fac4872a2757859e1ceaa6efd984628593b40ca1e19c7d773d00c144c525ac619d18c84a3f4718e2448b2fe324d9ccda2710", "2519498e80f1478f37ba55bd6d27618c"),
+            (format!("{zero}{zero}"), zero, "", "000000000000000000000000", "cea7403d4d606b6e074ec5d3baf39d18", "d0d1c8a799996bf0265b98b5d48ab919"),
+            (format!("{k}{k}"), p, a, iv, "522dc1f099567d07f47f37a32a84427d643a8cdcbfe5c0c97598a2bd2555d1aa8cb08e48590dbb3da7b08b1056828838c5f61e6393ba7a0abcc9f662", "76fc6ece0f4e1768cddf8853bb2d551b"),
+        ];
+        for (key, plain, aad, nonce, cipher, tag) in cases {
+            let gcm = AesGcm::new(&hex(&key)).unwrap();
+            let mut data = hex(plain);
+            let sealed = gcm.seal(&hex(nonce).try_into().unwrap(), &hex(aad), &mut data).unwrap();
+            assert_eq!(data, hex(cipher), "ciphertext, {}-bit key", key.len() * 4);
+            assert_eq!(sealed.to_vec(), hex(tag), "tag, {}-bit key", key.len() * 4);
+        }
+    }
+
+    #[test]
+    fn aes_gcm_rejects_other_key_sizes() {
+        for len in [0, 15, 17, 31, 33] {
+            assert!(AesGcm::new(&vec![0; len]).is_none());
+        }
+    }
+
+    /// Only the logical_type of the float16 element changes: not a null
+    /// column's UNKNOWN, nor a binary field holding the placeholder's bytes
+    #[test]
+    fn mark_float16_patches_only_schema_elements() {
+        let unknown = || Some(LogicalType::UNKNOWN(NullType {}));
+        let half = SchemaElement::new(Type::FIXED_LEN_BYTE_ARRAY, 2, FieldRepetitionType::REQUIRED, "h".into(), None, None, None, None, None, unknown());
+        let null = SchemaElement::new(Type::INT32, None, FieldRepetitionType::OPTIONAL, "n".into(), None, None, None, None, None, unknown());
+        let root = SchemaElement::new(None, None, None, "schema".into(), 2, None, None, None, None, None);
+        let mut payload = Vec::new();
+        write_thrift(&mut payload, |p| half.write_to_out_protocol(p)).unwrap();
+        let meta = FileMetaData::new(2, vec![root, half, null], 0, vec![], None, None, None, None, payload.clone());
+
+        let mut footer = Vec::new();
+        write_thrift(&mut footer, |p| meta.write_to_out_protocol(p)).unwrap();
+        mark_float16(&mut footer, &meta).unwrap();
+
+        let headers = logical_type_headers(&footer).unwrap();
+        assert_eq!(headers.iter().map(|h| h.map(|(_, m)| m)).collect::<Vec<_>>(), [None, Some(LOGICAL_FLOAT16), Some(LOGICAL_UNKNOWN)]);
+        assert!(footer.ends_with(&[&payload[..], &[0]].concat()));
+    }
+
+    /// Each codec's output inflates back with that codec's own decoder
+    #[cfg(feature = "codecs")]
+    #[test]
+    fn codecs_round_trip() {
+        let input: Vec<u8> = (0..20_000u32).flat_map(|i| (i % 251).to_le_bytes()).collect();
+        let codecs = [
+            CompressionOptions::Snappy,
+            CompressionOptions::Gzip(Some(GzipLevel::try_new(9).unwrap())),
+            CompressionOptions::Zstd(Some(ZstdLevel::try_new(3).unwrap())),
+            CompressionOptions::Lz4Raw,
+            CompressionOptions::Brotli(None),
+        ];
+        for codec in codecs {
+            let mut out = b"levels".to_vec();
+            compress(codec, &input, &mut out).unwrap();
+            assert_eq!(&out[..6], b"levels");
+            let out = &out[6..];
+            let back = match codec {
+                CompressionOptions::Snappy => snap::raw::Decoder::new().decompress_vec(out).unwrap(),
+                CompressionOptions::Gzip(_) => {
+                    let len = out.len();
+                    assert_eq!(out[len - 8..len - 4], crc32fast::hash(&input).to_le_bytes());
+                    miniz_oxide::inflate::decompress_to_vec(&out[10..len - 8]).unwrap()
+                }
+                CompressionOptions::Zstd(_) => {
+                    let mut back = Vec::new();
+                    ruzstd::decoding::StreamingDecoder::new(out).unwrap().read_to_end(&mut back).unwrap();
+                    back
+                }
+                CompressionOptions::Lz4Raw => lz4_flex::block::decompress(out, input.len()).unwrap(),
+                _ => {
+                    let mut back = Vec::new();
+                    brotli::Decompressor::new(out, 4096).read_to_end(&mut back).unwrap();
+                    back
+                }
+            };
+            assert!(back == input, "{:?} did not round-trip", codec);
+        }
+    }
+
+    fn config() -> WriteConfig {
+        WriteConfig {
+            compression: Compression { default: CompressionOptions::Uncompressed, per_column: Vec::new() },
+            dictionary: true,
+            dictionary_page_size: DEFAULT_DICTIONARY_PAGE_SIZE,
+            dictionary_ratio: 1.0,
+            statistics: true,
+            row_group_size: DEFAULT_ROW_GROUP_SIZE,
+            data_page_size: DEFAULT_DATA_PAGE_SIZE,
+            data_page_rows: usize::MAX,
+            version: Version::V1,
+            metadata: None,
+            created_by: None,
+            schema_name: "schema".to_string(),
+            page_checksums: false,
+            strict: false,
+            coercion: Coercion::Coerce,
+            arrow_schema: false,
+            sink: None,
+        }
+    }
+
+    /// PLAIN dictionary, num_values, indices, bit width, dictionary-encoded pages
+    type Dict = (Vec<u8>, usize, Vec<u32>, u32, usize);
+
+    fn dict<T: PlainValue>(vals: &[T], page_lens: &[usize], cfg: &WriteConfig) -> Option<Dict> {
+        let (page, indices, bits, pages) = try_build_dict(vals, page_lens, cfg)?;
+        let Page::Dict(page) = page else { panic!("not a dictionary page") };
+        Some((page.buffer, page.num_values, indices, bits, pages))
+    }
+
+    #[test]
+    fn dictionary_encodes_numbers() {
+        let (buf, n, indices, bits, pages) = dict(&[200i64, 404, 200, 500, 404], &[5], &config()).unwrap();
+        assert_eq!(buf, encode_plain([200i64, 404, 500].iter()));
+        assert_eq!((n, indices, bits, pages), (3, vec![0, 1, 0, 2, 1], 2, 1));
+
+        // Floats match bit for bit: -0.0 isn't 0.0, and NaN is itself
+        let (_, n, indices, ..) = dict(&[0.0f64, -0.0, f64::NAN, f64::NAN, 0.0], &[5], &config()).unwrap();
+        assert_eq!((n, indices), (3, vec![0, 1, 2, 2, 0]));
+    }
+
+    #[test]
+    fn dictionary_stops_at_256_entries() {
+        let vals: Vec<i32> = (0..300).collect();
+        let (_, n, indices, bits, pages) = dict(&vals, &[256, 44], &config()).unwrap();
+        assert_eq!((n, indices.len(), bits, pages), (256, 256, 8, 1));
+        assert!(dict(&vals[..257], &[257], &config()).is_none());
+    }
+
+    #[test]
+    fn dictionary_stops_at_its_page_size() {
+        let cfg = WriteConfig { dictionary_page_size: 16, ..config() };
+        let (_, n, indices, _, pages) = dict(&[1i64, 2, 1, 3], &[2, 2], &cfg).unwrap();
+        assert_eq!((n, indices, pages), (2, vec![0, 1], 1));
+        assert!(dict(&[1i64, 2, 3], &[3], &cfg).is_none());
+    }
 }
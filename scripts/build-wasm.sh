@@ -0,0 +1,40 @@
+#!/usr/bin/env sh
+# Rebuild wasm/{reader,writer,writer-full}.wasm and the wasm-bindgen glue that
+# loads them (wasm/{reader,writer,writer-full}_bindings.js). The two must always
+# be committed together: the glue's import names are hashed per build.
+# writer-full is the writer crate built with `--features codecs`.
+# wasm/<side>.sources records which Rust sources each build came from; the
+# tests fail when it no longer matches the tree, so commit it with the build.
+#
+# Needs: Rust with the wasm32-unknown-unknown target, wasm-bindgen-cli at the
+# version pinned in the Cargo.toml files, wasm-opt (binaryen) and Node.js.
+#
+# Usage: scripts/build-wasm.sh [reader|writer|writer-full]...   (all by default)
+set -eu
+
+root=$(cd "$(dirname "$0")/.." && pwd)
+version=$(sed -n 's/^wasm-bindgen = "=\(.*\)"$/\1/p' "$root/parquet-writer/Cargo.toml")
+case "$(wasm-bindgen --version)" in
+    *" $version") ;;
+    *) echo "wasm-bindgen-cli $version is required (cargo install wasm-bindgen-cli --version $version)" >&2; exit 1 ;;
+esac
+
+for side in ${*:-reader writer writer-full}; do
+    case "$side" in
+        reader|writer) crate="parquet-$side"; features="" ;;
+        writer-full) crate="parquet-writer"; features="codecs" ;;
+        *) echo "unknown side: $side" >&2; exit 1 ;;
+    esac
+    name=$(echo "$crate" | tr - _)
+    out="$root/$crate/pkg"
+    (cd "$root/$crate" && cargo build --target wasm32-unknown-unknown --release --features "$features")
+    wasm-bindgen "$root/$crate/target/wasm32-unknown-unknown/release/$name.wasm" \
+        --out-dir "$out" --target web --no-typescript
+    # rustc's wasm32 target enables these by default; wasm-opt has to be told
+    wasm-opt "$out/${name}_bg.wasm" -o "$root/wasm/$side.wasm" -Oz \
+        --enable-bulk-memory --enable-nontrapping-float-to-int --enable-sign-ext \
+        --enable-mutable-globals --enable-reference-types --enable-multivalue
+    cp "$out/$name.js" "$root/wasm/${side}_bindings.js"
+    node "$root/scripts/wasm-sources.mjs" "$side" > "$root/wasm/$side.sources"
+    echo "wasm/$side.wasm: $(wc -c < "$root/wasm/$side.wasm") bytes"
+done
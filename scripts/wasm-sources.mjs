@@ -0,0 +1,43 @@
+#!/usr/bin/env node
+// Fingerprint of the Rust sources a wasm build is compiled from.
+// scripts/build-wasm.sh stores it next to each build as wasm/<side>.sources,
+// and the tests compare that with the tree's: a Rust change committed without
+// rebuilding the wasm fails them.
+//
+// Usage: node scripts/wasm-sources.mjs reader|writer|writer-full
+
+import { createHash } from 'node:crypto';
+import { readdirSync, readFileSync } from 'node:fs';
+import { fileURLToPath } from 'node:url';
+
+const root = new URL('../', import.meta.url);
+
+/** The crates each wasm build compiles */
+const CRATES = {
+  reader: ['parquet-reader'],
+  writer: ['parquet-writer', 'zstd-pure'],
+  'writer-full': ['parquet-writer', 'zstd-pure'],
+};
+
+/** Paths of the files under `dir`, relative to the repo root */
+function files(dir) {
+  return readdirSync(new URL(`${dir}/`, root), { withFileTypes: true })
+    .flatMap((entry) => (entry.isDirectory() ? files(`${dir}/${entry.name}`) : [`${dir}/${entry.name}`]));
+}
+
+/** SHA-256 over the paths and contents of the sources `side` is built from */
+export function sourcesHash(side) {
+  const crates = CRATES[side];
+  if (!crates) throw new Error(`unknown side: ${side}`);
+  const hash = createHash('sha256');
+  for (const path of crates.flatMap((crate) => [`${crate}/Cargo.toml`, ...files(`${crate}/src`).sort()])) {
+    // Checked-out line endings don't change the build
+    const text = readFileSync(new URL(path, root), 'utf8').replace(/\r\n/g, '\n');
+    hash.update(`${path}\0${text}\0`);
+  }
+  return hash.digest('hex');
+}
+
+if (process.argv[1] === fileURLToPath(import.meta.url)) {
+  console.log(sourcesHash(process.argv[2]));
+}
@@ -1,2 +1,2 @@
-export { readParquet } from './reader.js';
-export { writeParquet } from './writer.js';
+export { readParquet, readParquetMetadata, readParquetSchema, inspectPages, dumpFooter, readParquetBatches, ParquetFile } from './reader.js';
+export { writeParquet, ParquetWriter, mergeParquet, appendToParquet, inferSchema } from './writer.js';
@@ -4,5 +4,5 @@
  * @module tiny-parquet
  */
 
-export { readParquet } from './reader.js';
-export { writeParquet } from './writer.js';
+export { readParquet, readParquetMetadata, readParquetSchema, inspectPages, dumpFooter, readParquetBatches, ParquetFile } from './reader.js';
+export { writeParquet, ParquetWriter, mergeParquet, appendToParquet, inferSchema } from './writer.js';
@@ -1,18 +1,357 @@
+export interface ColumnStatistics {
+    /** Smallest non-null value across all row groups. */
+    min?: number | string | boolean;
+    /** Largest non-null value across all row groups. */
+    max?: number | string | boolean;
+    nullCount?: number;
+    /** Only reported for single-row-group files. */
+    distinctCount?: number;
+}
+
 export interface ColumnSchema {
     name: string;
-    type: 'string' | 'int32' | 'int64' | 'float32' | 'float64' | 'boolean' | 'timestamp';
+    type:
+        | 'string' | 'int32' | 'int64' | 'float32' | 'float64' | 'boolean'
+        | 'timestamp' | 'date' | 'time_millis' | 'time_micros' | 'time_nanos'
+        | 'decimal' | 'uuid' | 'fixed' | 'binary'
+        | 'int8' | 'int16' | 'uint8' | 'uint16' | 'uint32' | 'uint64'
+        | 'float16' | 'json' | 'bson' | 'geometry' | 'list' | 'struct' | 'map';
+    /** `list` columns: the element's schema entry (without `name`). */
+    items?: Omit<ColumnSchema, 'name'>;
+    /** `struct` columns: the child fields' schema entries. */
+    fields?: ColumnSchema[];
+    /** `map` columns: the key and value schema entries (without `name`). */
+    key?: Omit<ColumnSchema, 'name'>;
+    value?: Omit<ColumnSchema, 'name'>;
+    /** Present (true) when the column is nullable; null slots come back as `null`. */
+    optional?: boolean;
+    /** Timestamp/time columns: whether values are UTC instants (true) or local wall-clock values (false). */
+    isAdjustedToUTC?: boolean;
+    /** Unit of the returned values of `timestamp` columns. */
+    unit?: 'millis' | 'micros' | 'nanos';
+    /** Byte width of `fixed` columns. */
+    length?: number;
+    /** Precision and scale of `decimal` columns. */
+    precision?: number;
+    scale?: number;
+    /** Parquet field ID, when the writer stored one. */
+    id?: number;
+    /** Column-chunk statistics from the footer, when the writer stored them. */
+    statistics?: ColumnStatistics;
 }
 
 export interface ReadResult {
     schema: ColumnSchema[];
+    /** Column arrays (TypedArrays for required numeric/boolean columns with `typedArrays: true`). */
     data: Record<string, any[]>;
+    /** Footer key-value metadata (pandas, ARROW:schema, geo, ...). Keys stored without a value map to `null`. */
+    metadata: Record<string, string | null>;
+    /** Layout of every row group in the file (including ones the read skipped). */
+    rowGroups: RowGroupInfo[];
     numRows: number;
+    /** Checksum mismatches found with `verifyChecksums: 'warn'`. */
+    warnings?: string[];
+}
+
+/** Result of `readParquet` with `format: 'rows'`. */
+export interface RowsReadResult extends Omit<ReadResult, 'data'> {
+    data: Record<string, any>[];
+}
+
+export interface ReadOptions {
+    /** Maximum rows to decode. Default: 500. */
+    maxRows?: number;
+    /** Rows to skip before collecting `maxRows` (pagination). Default: 0. */
+    offset?: number;
+    /** Only decode these columns (returned in this order). Default: all. */
+    columns?: string[];
+    /**
+     * Instead of `columns`: `{ newName: 'oldName' }` decodes the mapped columns (in key order) and returns
+     * them — in `schema`, `data` and `where` rows — under the new names. `filter` still uses file names.
+     */
+    select?: Record<string, string>;
+    /** Only decode these row groups (0-based, returned in this order). Default: all. */
+    rowGroups?: number[];
+    /** 'columns' returns `{ col: values[] }`, 'rows' returns `[{ col: value }, ...]`. Default: 'columns'. */
+    format?: 'columns' | 'rows';
+    /** Return required numeric/boolean columns as Int32Array / Float32Array / Float64Array / Uint8Array (0/1). Default: false. */
+    typedArrays?: boolean;
+    /** 'bigint' returns int64 values as BigInt (BigInt64Array with `typedArrays`) instead of lossy Numbers. Default: 'number'. */
+    int64?: 'number' | 'bigint';
+    /** DATE columns as 'YYYY-MM-DD' strings ('string'), JS Dates at UTC midnight ('date'), or days since the epoch ('number'). Default: 'string'. */
+    dates?: 'string' | 'date' | 'number';
+    /** TIME columns as 'HH:MM:SS.fff' strings (3/6/9 fraction digits for millis/micros/nanos) or raw counts since midnight. Default: 'string'. */
+    times?: 'string' | 'number';
+    /** DECIMAL columns as Numbers (scale applied, may round) or exact strings. Default: 'number'. */
+    decimals?: 'number' | 'string';
+    /** `binary` and `fixed` column values as Uint8Arrays ('bytes') or lowercase hex strings ('hex'). Default: 'bytes'. */
+    binary?: 'bytes' | 'hex';
+    /** Columns listed in GeoParquet `geo` metadata as GeoJSON geometry objects or WKB Uint8Arrays. Default: 'geojson'. */
+    geometry?: 'geojson' | 'wkb';
+    /** Unit timestamp columns are converted to (coarser units floor), or 'raw' to keep each column's own unit. Default: 'millis'. */
+    timestamps?: 'millis' | 'micros' | 'nanos' | 'raw';
+    /** uint64 columns as BigInt, or as Numbers with an error for values beyond 2^53 ('error'). Default: 'bigint'. */
+    uint64?: 'bigint' | 'error';
+    /** JSON columns as their text, or JSON.parse'd values ('parse'). Default: 'string'. */
+    json?: 'string' | 'parse';
+    /** MAP columns as plain objects (keys become strings), or JS Maps that keep key types ('map'). Default: 'object'. */
+    maps?: 'object' | 'map';
+    /** Check page CRCs where the writer stored them: `true` throws on mismatch, `'warn'` reports in `warnings`. Default: false. */
+    verifyChecksums?: boolean | 'warn';
+    /** Keys for a file written with Parquet modular encryption. */
+    decryption?: DecryptionOptions;
+    /** Only return rows passing every condition; `offset` and `maxRows` count matching rows. */
+    filter?: FilterCondition[];
+    /**
+     * Called with each decoded row (that passed `filter`, selected columns only) and its row index in the
+     * file; only rows it returns truthy for end up in `data`. `offset` and `maxRows` count kept rows.
+     */
+    where?: (row: Record<string, any>, index: number) => unknown;
+}
+
+/**
+ * A condition on a top-level primitive column. Values compare like JS `<` / `>` against the decoded
+ * cells (so after `dates`, `timestamps`, `int64`, ... apply). Nulls only pass `'is null'`.
+ */
+export type FilterCondition =
+    | { column: string; op: '=' | '==' | '!=' | '<' | '<=' | '>' | '>='; value: any }
+    | { column: string; op: 'in' | 'not in'; value: any[] }
+    | { column: string; op: 'is null' | 'is not null' };
+
+/** A whole file, or its bytes in order as stream chunks (`response.body`, `file.stream()`, ...). */
+export interface DecryptionOptions {
+    /** Key of the footer, and of every column not encrypted with its own key: 16, 24 or 32 bytes. */
+    footerKey?: Uint8Array;
+    /** Keys of columns encrypted with their own, by leaf path (`'a.b'` for nested columns). Only needed to read those columns. */
+    columnKeys?: Record<string, Uint8Array>;
+    /** AAD prefix, for files written without storing theirs. */
+    aadPrefix?: string | Uint8Array;
 }
 
+export type ParquetSource = Uint8Array | ReadableStream<Uint8Array> | AsyncIterable<Uint8Array> | Iterable<Uint8Array>;
+
 /**
  * Read a Parquet file and return columnar data.
  *
- * @param fileBytes - Raw Parquet file bytes.
- * @param maxRows - Maximum rows to decode. Default: 500.
+ * @param fileBytes - Raw Parquet file bytes, or a stream of them (copied into wasm memory chunk by chunk).
+ * @param options - Options, or just the maximum rows to decode (default 500).
+ */
+export function readParquet(fileBytes: ParquetSource, options: ReadOptions & { format: 'rows' }): Promise<RowsReadResult>;
+export function readParquet(fileBytes: ParquetSource, options?: number | ReadOptions): Promise<ReadResult>;
+
+export interface ColumnChunkInfo {
+    /** Dotted path of the leaf column, e.g. `'tags.list.element'`. */
+    path: string;
+    /** 'UNCOMPRESSED', 'SNAPPY', 'GZIP', 'BROTLI', 'LZ4', 'ZSTD', 'LZ4_RAW', ... */
+    codec: string;
+    /** Values in the chunk, nulls and repeated values included. */
+    numValues: number;
+    /** Encodings used by the chunk's pages, e.g. `['PLAIN', 'RLE', 'RLE_DICTIONARY']`. */
+    encodings: string[];
+    compressedSize: number;
+    uncompressedSize: number;
+    /** File offset of the chunk's first page. */
+    offset: number;
+    /** File offset of the chunk's bloom filter, or null when it has none. */
+    bloomFilterOffset: number | null;
+}
+
+export interface RowGroupInfo {
+    numRows: number;
+    /** Uncompressed size of the row group's column data. */
+    totalByteSize: number;
+    /** Compressed size of the row group's column chunks. */
+    compressedSize: number;
+    columns: ColumnChunkInfo[];
+}
+
+export interface ParquetMetadata {
+    numRows: number;
+    /** Format version from the footer (1 or 2). */
+    version: number;
+    /** The writing library, e.g. `'parquet-cpp-arrow version 14.0.1'`, when stored. */
+    createdBy: string | null;
+    schema: ColumnSchema[];
+    rowGroups: RowGroupInfo[];
+    /** Footer key-value metadata. */
+    metadata: Record<string, string | null>;
+}
+
+/**
+ * Parse only the footer: schema, row group layout and key-value metadata, without decoding pages.
+ *
+ * @param fileBytes - File bytes, a Blob/File (only its tail is read), a stream (buffered whole), or an open `ParquetFile` (left open).
+ * @param options - `readParquet` options that shape schema entries (`columns`, value types of statistics).
+ */
+export function readParquetMetadata(fileBytes: ParquetSource | Blob | ParquetFile | RemoteParquetFile, options?: ReadOptions): Promise<ParquetMetadata>;
+
+/**
+ * Column names and types (nested columns included) from the footer alone; schema entries without statistics.
+ *
+ * @param fileBytes - The whole file or just its end (the last 64 KB is usually enough), or a Blob/File (only its end is read).
+ */
+export function readParquetSchema(fileBytes: Uint8Array | Blob): Promise<Omit<ColumnSchema, 'statistics'>[]>;
+
+/** Thrift ColumnMetaData, as in `dumpFooter`. */
+export interface FooterColumnMetaData {
+    type: string;
+    encodings: string[];
+    pathInSchema: string[];
+    codec: string;
+    numValues: number;
+    totalUncompressedSize: number;
+    totalCompressedSize: number;
+    keyValueMetadata?: { key: string; value: string | null }[];
+    dataPageOffset: number;
+    indexPageOffset?: number;
+    dictionaryPageOffset?: number;
+    /** Plain-encoded values as hex. */
+    statistics?: { max?: string; min?: string; nullCount?: number; distinctCount?: number; maxValue?: string; minValue?: string };
+    encodingStats?: { pageType: string; encoding: string; count: number }[];
+    bloomFilterOffset?: number;
+}
+
+/** Thrift FileMetaData as returned by `dumpFooter`: parquet.thrift field names in camelCase, enums by name. */
+export interface FooterDump {
+    version: number;
+    schema: {
+        type?: string;
+        typeLength?: number;
+        repetitionType?: 'REQUIRED' | 'OPTIONAL' | 'REPEATED';
+        name: string;
+        numChildren?: number;
+        convertedType?: string;
+        scale?: number;
+        precision?: number;
+        fieldId?: number;
+        /** One member, e.g. `{ TIMESTAMP: { isAdjustedToUTC: true, unit: 'MILLIS' } }` or `{ STRING: {} }`. */
+        logicalType?: Record<string, Record<string, any>>;
+    }[];
+    numRows: number;
+    rowGroups: {
+        columns: {
+            filePath?: string;
+            fileOffset: number;
+            metaData?: FooterColumnMetaData;
+            offsetIndexOffset?: number;
+            offsetIndexLength?: number;
+            columnIndexOffset?: number;
+            columnIndexLength?: number;
+            encrypted?: true;
+        }[];
+        totalByteSize: number;
+        numRows: number;
+        sortingColumns?: { columnIdx: number; descending: boolean; nullsFirst: boolean }[];
+        fileOffset?: number;
+        totalCompressedSize?: number;
+        ordinal?: number;
+    }[];
+    keyValueMetadata?: { key: string; value: string | null }[];
+    createdBy?: string;
+    columnOrders?: 'TYPE_ORDER'[];
+    encryptionAlgorithm?: 'AES_GCM_V1' | 'AES_GCM_CTR_V1';
+}
+
+/**
+ * The footer's thrift FileMetaData, every field of it, as a JSON-able object.
+ *
+ * @param fileBytes - The whole file or just its end, or a Blob/File (only its end is read).
  */
-export function readParquet(fileBytes: Uint8Array, maxRows?: number): Promise<ReadResult>;
+export function dumpFooter(fileBytes: Uint8Array | Blob): Promise<FooterDump>;
+
+export interface PageInfo {
+    rowGroup: number;
+    /** File offset of the page header. */
+    offset: number;
+    type: 'dictionary' | 'data' | 'index' | 'unknown';
+    /** Data page header version. */
+    version?: 1 | 2;
+    /** e.g. 'PLAIN', 'RLE_DICTIONARY', 'DELTA_BINARY_PACKED'. */
+    encoding?: string;
+    /** Values in the page, nulls included (dictionary pages: dictionary entries). */
+    numValues?: number;
+    /** V2 data pages only. */
+    numNulls?: number;
+    numRows?: number;
+    headerSize: number;
+    compressedSize: number;
+    uncompressedSize: number;
+    /** Whether the header carries a page CRC. */
+    crc: boolean;
+}
+
+/**
+ * Page headers of one column across all row groups; nothing is decompressed.
+ *
+ * @param fileBytes - File bytes or a stream of them, or an open `ParquetFile` (left open).
+ * @param column - A leaf column's dotted path, or a top-level column with a single leaf.
+ */
+export function inspectPages(fileBytes: ParquetSource | ParquetFile, column: string): Promise<PageInfo[]>;
+
+export interface BatchOptions extends ReadOptions {
+    /** Rows per batch. Default: 10000. */
+    batchSize?: number;
+    /** Total rows to decode. Default: all. */
+    maxRows?: number;
+}
+
+/**
+ * Decode a file in batches of rows, yielding to the event loop between batches.
+ *
+ * @param fileBytes - File bytes or a stream of them, or an open `ParquetFile` (left open).
+ * @param options - `readParquet` options plus `batchSize`.
+ */
+export function readParquetBatches(fileBytes: ParquetSource | ParquetFile | RemoteParquetFile, options: BatchOptions & { format: 'rows' }): AsyncGenerator<Record<string, any>[]>;
+export function readParquetBatches(fileBytes: ParquetSource | ParquetFile | RemoteParquetFile, options?: BatchOptions): AsyncGenerator<Record<string, any[]>>;
+
+/** A Parquet file whose footer is parsed once; reads decode only what they ask for. */
+export class ParquetFile {
+    private constructor();
+    /** Parse the footer. The bytes are copied into wasm memory (streams chunk by chunk). */
+    static open(fileBytes: ParquetSource, options?: { decryption?: DecryptionOptions }): Promise<ParquetFile>;
+    /** Fetch the footer of a remote file with a Range request; column chunks are fetched as reads need them. */
+    static fromUrl(url: string | URL, init?: RequestInit): Promise<RemoteParquetFile>;
+    /** Total rows in the file. */
+    readonly numRows: number;
+    /** Row groups in the file. */
+    readonly numRowGroups: number;
+    /** Footer key-value metadata. */
+    metadata(): Record<string, string | null>;
+    /** Schema entries with statistics; `columns` selects entries. */
+    schema(options?: ReadOptions): ColumnSchema[];
+    /** Footer summary, as returned by `readParquetMetadata`. */
+    info(options?: ReadOptions): ParquetMetadata;
+    /** Same result as `readParquet(fileBytes, options)`. */
+    read(options: ReadOptions & { format: 'rows' }): RowsReadResult;
+    read(options?: number | ReadOptions): ReadResult;
+    /** Decode one whole row group; `maxRows` and `offset` count within it. */
+    readRowGroup(index: number, options: ReadOptions & { format: 'rows' }): Record<string, any>[];
+    readRowGroup(index: number, options?: ReadOptions): Record<string, any[]>;
+    /** Decode every value of one column across `rowGroups` (default all). */
+    readColumn(name: string, options?: ReadOptions): any[];
+    /** Page headers of one column, as returned by `inspectPages`. */
+    inspectPages(column: string): PageInfo[];
+    /**
+     * Whether a leaf column may hold `value`, by its bloom filters: false only when every row group's filter
+     * rules it out. Values are given as reads return them with default options.
+     */
+    mightContain(column: string, value: any): boolean;
+    /**
+     * File byte ranges (`[start, end)`) that `read(options)` needs and that aren't loaded yet. Missing page indexes
+     * come back alone, then the pages they pick: call until it returns none.
+     */
+    missingRanges(options?: number | ReadOptions): [number, number][];
+    /** Add file bytes read at `offset`. */
+    addRange(offset: number, bytes: Uint8Array): void;
+    /** Release the wasm-side copy of the file. */
+    free(): void;
+}
+
+/** A `ParquetFile` opened with `fromUrl`: reads first fetch the column chunks they need. */
+export interface RemoteParquetFile extends Omit<ParquetFile, 'read' | 'readRowGroup' | 'readColumn'> {
+    read(options: ReadOptions & { format: 'rows' }): Promise<RowsReadResult>;
+    read(options?: number | ReadOptions): Promise<ReadResult>;
+    readRowGroup(index: number, options: ReadOptions & { format: 'rows' }): Promise<Record<string, any>[]>;
+    readRowGroup(index: number, options?: ReadOptions): Promise<Record<string, any[]>>;
+    readColumn(name: string, options?: ReadOptions): Promise<any[]>;
+}
@@ -1,102 +1,17 @@
 /**
  * tiny-parquet/reader — WASM Parquet Reader
  * Supports: Node.js, Browser, Cloudflare Workers, Vercel Edge, Deno, Bun
- * WASM size: ~140KB
+ * WASM size: 1.5 MB
  */
 
-let wasm;
-let initPromise = null;
-
-// ── Heap / object table ──────────────────────────────────────────────────────
-const heap = new Array(128).fill(undefined);
-heap.push(undefined, null, true, false);
-let heap_next = heap.length;
-let stack_pointer = 128;
-
-function addHeapObject(obj) {
-    if (heap_next === heap.length) heap.push(heap.length + 1);
-    const idx = heap_next;
-    heap_next = heap[idx];
-    heap[idx] = obj;
-    return idx;
-}
-function addBorrowedObject(obj) {
-    if (stack_pointer === 1) throw new Error('out of js stack');
-    heap[--stack_pointer] = obj;
-    return stack_pointer;
-}
-function getObject(idx) { return heap[idx]; }
-function dropObject(idx) {
-    if (idx < 132) return;
-    heap[idx] = heap_next;
-    heap_next = idx;
-}
-function takeObject(idx) {
-    const ret = getObject(idx);
-    dropObject(idx);
-    return ret;
-}
-
-// ── Memory helpers ───────────────────────────────────────────────────────────
-let cachedUint8 = null;
-function getUint8() {
-    if (cachedUint8 === null || cachedUint8.byteLength === 0)
-        cachedUint8 = new Uint8Array(wasm.memory.buffer);
-    return cachedUint8;
-}
-let cachedDV = null;
-function getDV() {
-    if (cachedDV === null || cachedDV.buffer.detached === true ||
-        (cachedDV.buffer.detached === undefined && cachedDV.buffer !== wasm.memory.buffer))
-        cachedDV = new DataView(wasm.memory.buffer);
-    return cachedDV;
-}
+import initBindings, * as bindings from '../wasm/reader_bindings.js';
 
-const decoder = new TextDecoder('utf-8', { ignoreBOM: true, fatal: true });
-decoder.decode(); // warm up
-
-function getStringFromWasm(ptr, len) {
-    ptr = ptr >>> 0;
-    return decoder.decode(getUint8().subarray(ptr, ptr + len));
-}
-function getArrayU8(ptr, len) {
-    ptr = ptr >>> 0;
-    return getUint8().subarray(ptr, ptr + len);
-}
-function isLikeNone(x) { return x === undefined || x === null; }
-function handleError(f, args) {
-    try { return f.apply(this, args); }
-    catch (e) { wasm.__wbindgen_export(addHeapObject(e)); }
-}
-
-// ── WASM imports ─────────────────────────────────────────────────────────────
-function getImports() {
-    const wbg = { __proto__: null };
-
-    wbg.__wbg___wbindgen_throw_be289d5034ed271b = (a, b) => {
-        throw new Error(getStringFromWasm(a, b));
-    };
-    wbg.__wbg_length_32ed9a279acd054c = (a) => getObject(a).length;
-    wbg.__wbg_new_361308b2356cecd0 = () => addHeapObject(new Object());
-    wbg.__wbg_new_3eb36ae241fe6f44 = () => addHeapObject(new Array());
-    wbg.__wbg_prototypesetcall_bdcdcc5842e4d77d = (arg0, arg1, arg2) => {
-        Uint8Array.prototype.set.call(getArrayU8(arg0, arg1), getObject(arg2));
-    };
-    wbg.__wbg_push_8ffdcb2063340ba5 = (a, b) => getObject(a).push(getObject(b));
-    wbg.__wbg_set_6cb8631f80447a67 = (...args) => handleError((a, b, c) => {
-        return Reflect.set(getObject(a), getObject(b), getObject(c));
-    }, args);
-    wbg.__wbindgen_cast_0000000000000001 = (a) => addHeapObject(a);
-    wbg.__wbindgen_cast_0000000000000002 = (a, b) => addHeapObject(getStringFromWasm(a, b));
-    wbg.__wbindgen_object_drop_ref = (a) => takeObject(a);
-
-    return { './parquet_reader_bg.js': wbg };
-}
+let initPromise = null;
 
 // ── WASM loader (universal) ──────────────────────────────────────────────────
+// `wasm/reader_bindings.js` is wasm-bindgen's glue for `wasm/reader.wasm`; both
+// are generated together by `scripts/build-wasm.sh`.
 async function loadWasm() {
-    const imports = getImports();
-
     // Node.js
     if (typeof process !== 'undefined' && process.versions?.node) {
         const { readFileSync } = await import('node:fs');
@@ -104,27 +19,16 @@ async function loadWasm() {
         const { fileURLToPath } = await import('node:url');
         const __dirname = dirname(fileURLToPath(import.meta.url));
         const bytes = readFileSync(join(__dirname, '..', 'wasm', 'reader.wasm'));
-        const { instance } = await WebAssembly.instantiate(bytes, imports);
-        return instance.exports;
+        return initBindings({ module_or_path: bytes });
     }
 
     // Edge / Browser — resolve relative to this module
-    const wasmUrl = new URL('../wasm/reader.wasm', import.meta.url);
-    const response = await fetch(wasmUrl);
-    const bytes = await response.arrayBuffer();
-    const { instance } = await WebAssembly.instantiate(bytes, imports);
-    return instance.exports;
+    return initBindings({ module_or_path: new URL('../wasm/reader.wasm', import.meta.url) });
 }
 
 // ── Init (lazy singleton) ────────────────────────────────────────────────────
-async function init() {
-    if (initPromise) return initPromise;
-    initPromise = loadWasm().then(exports => {
-        wasm = exports;
-        cachedUint8 = null;
-        cachedDV = null;
-        return wasm;
-    });
+function init() {
+    initPromise ??= loadWasm();
     return initPromise;
 }
 
@@ -132,9 +36,36 @@ async function init() {
 /**
  * Read a Parquet file and return columnar data.
  *
- * @param {Uint8Array} fileBytes - Raw Parquet file bytes.
- * @param {number} [maxRows=500] - Maximum rows to decode.
- * @returns {Promise<{schema: Array<{name: string, type: string}>, data: Record<string, any[]>, numRows: number}>}
+ * @param {Uint8Array|ReadableStream<Uint8Array>|AsyncIterable<Uint8Array>} fileBytes - Raw Parquet file bytes, or a
+ *   stream of them (e.g. `response.body`, `file.stream()`), copied into wasm memory chunk by chunk.
+ * @param {number|Object} [options] - Max rows to decode (default 500), or an options object:
+ * @param {number} [options.maxRows=500] - Maximum rows to decode.
+ * @param {number} [options.offset=0] - Rows to skip before collecting `maxRows`.
+ * @param {string[]} [options.columns] - Only decode these columns (in this order).
+ * @param {Record<string, string>} [options.select] - Instead of `columns`: `{ newName: 'oldName' }`, decoding the
+ *   mapped columns (in key order) and returning them under the new names.
+ * @param {number[]} [options.rowGroups] - Only decode these row groups (0-based, in this order).
+ * @param {'columns'|'rows'} [options.format='columns'] - Column arrays, or an array of row objects.
+ * @param {boolean} [options.typedArrays=false] - Return required numeric/boolean columns as TypedArrays.
+ * @param {'number'|'bigint'} [options.int64='number'] - Return int64 values as BigInt (BigInt64Array with `typedArrays`) to keep full precision.
+ * @param {'string'|'date'|'number'} [options.dates='string'] - DATE columns as 'YYYY-MM-DD', JS Dates, or day counts.
+ * @param {'string'|'number'} [options.times='string'] - TIME columns as 'HH:MM:SS.fff' strings, or raw counts since midnight.
+ * @param {'number'|'string'} [options.decimals='number'] - DECIMAL columns as Numbers, or exact strings for high precision.
+ * @param {'bytes'|'hex'} [options.binary='bytes'] - Binary and fixed-length byte values as Uint8Arrays or hex strings.
+ * @param {'geojson'|'wkb'} [options.geometry='geojson'] - GeoParquet geometry columns as GeoJSON objects or WKB bytes.
+ * @param {'millis'|'micros'|'nanos'|'raw'} [options.timestamps='millis'] - Unit to normalize timestamp columns to ('raw' keeps each column's unit).
+ * @param {'bigint'|'error'} [options.uint64='bigint'] - uint64 columns as BigInt, or as Numbers that throw past 2^53.
+ * @param {'string'|'parse'} [options.json='string'] - JSON columns as text, or parsed into JS values.
+ * @param {'object'|'map'} [options.maps='object'] - MAP columns as plain objects or JS Maps.
+ * @param {boolean|'warn'} [options.verifyChecksums=false] - Check page CRCs; `true` throws on mismatch, `'warn'` reports in `warnings`.
+ * @param {{footerKey?: Uint8Array, columnKeys?: Record<string, Uint8Array>, aadPrefix?: string|Uint8Array}} [options.decryption] -
+ *   Keys for a file written with Parquet modular encryption: the footer key, and the keys of columns encrypted with their own.
+ * @param {Array<{column: string, op: string, value?: any}>} [options.filter] - Only return rows passing all of these
+ *   conditions (`op`: '=', '!=', '<', '<=', '>', '>=', 'in', 'not in', 'is null', 'is not null'). Row groups and pages
+ *   whose statistics rule out a match are skipped; `offset` and `maxRows` count matching rows.
+ * @param {(row: Record<string, any>, index: number) => boolean} [options.where] - Called with each decoded row (that
+ *   passed `filter`) and its index in the file; only rows it returns truthy for are kept.
+ * @returns {Promise<{schema: Array<{name: string, type: string}>, data: Record<string, any[]>, metadata: Record<string, string|null>, rowGroups: Array<{numRows: number, totalByteSize: number, compressedSize: number, columns: Array<{path: string, codec: string, numValues: number, encodings: string[]}>}>, numRows: number, warnings?: string[]}>}
  *
  * @example
  * const { schema, data, numRows } = await readParquet(bytes);
@@ -142,22 +73,459 @@ async function init() {
  * // data: { url: ['https://example.com'], ts: [1708000000] }
  * // numRows: 1
  */
-export async function readParquet(fileBytes, maxRows = 500) {
+export async function readParquet(fileBytes, options = {}) {
+    if (!(fileBytes instanceof Uint8Array)) {
+        const file = await ParquetFile.open(fileBytes, options);
+        try {
+            return file.read(options);
+        } finally {
+            file.free();
+        }
+    }
+    await init();
+    return bindings.readParquet(fileBytes, options);
+}
+
+/**
+ * Parse only the footer of a Parquet file: schema, row group layout (sizes,
+ * codecs, encodings) and key-value metadata. No pages are decoded, and for
+ * Uint8Arrays and Blobs only the footer is copied into wasm memory, so this is
+ * cheap enough to run on every file a user picks.
+ *
+ * @param {Uint8Array|Blob|ReadableStream<Uint8Array>|AsyncIterable<Uint8Array>|ParquetFile} fileBytes - File bytes,
+ *   a Blob/File (only its tail is read), a stream (buffered whole), or an open `ParquetFile`, which is left open.
+ * @param {Object} [options] - `readParquet` options that shape schema entries (`columns`, and value types of statistics),
+ *   and `decryption` for encrypted files.
+ * @returns {Promise<{numRows: number, version: number, createdBy: string|null, schema: Array<{name: string, type: string}>, rowGroups: Array<{numRows: number, totalByteSize: number, compressedSize: number, columns: Array<{path: string, codec: string, numValues: number, encodings: string[], compressedSize: number, uncompressedSize: number, offset: number}>}>, metadata: Record<string, string|null>}>}
+ *
+ * @example
+ * const info = await readParquetMetadata(input.files[0]);
+ * // info.numRows: 12345, info.rowGroups[0].columns[0].codec: 'SNAPPY'
+ */
+export async function readParquetMetadata(fileBytes, options = {}) {
+    if (fileBytes instanceof ParquetFile) return fileBytes.info(options);
+    await init();
+    if (typeof Blob !== 'undefined' && fileBytes instanceof Blob) {
+        const tail = await blobTail(fileBytes);
+        const file = new ParquetFile(parseTail(tail, fileBytes.size, options));
+        try {
+            return file.info(options);
+        } finally {
+            file.free();
+        }
+    }
+    if (!(fileBytes instanceof Uint8Array)) {
+        const file = await ParquetFile.open(fileBytes, options);
+        try {
+            return file.info(options);
+        } finally {
+            file.free();
+        }
+    }
+    return bindings.readParquetMetadata(fileBytes, options);
+}
+
+/**
+ * Column names and types, nested columns included, from the footer alone — the
+ * `schema` of `readParquet`'s result without statistics. For upload flows that
+ * validate a file's schema before reading (or sending) it.
+ *
+ * @param {Uint8Array|Blob} fileBytes - The whole file or just its end (the last 64 KB is enough unless the footer
+ *   is bigger), or a Blob/File, of which only the end is read.
+ * @returns {Promise<Array<{name: string, type: string}>>}
+ *
+ * @example
+ * const schema = await readParquetSchema(input.files[0]);
+ * // [{ name: 'url', type: 'string', optional: true }, { name: 'tags', type: 'list', items: { type: 'string' } }]
+ */
+export async function readParquetSchema(fileBytes) {
     await init();
-    const retptr = wasm.__wbindgen_add_to_stack_pointer(-16);
+    if (typeof Blob !== 'undefined' && fileBytes instanceof Blob) {
+        fileBytes = await blobTail(fileBytes);
+    } else if (!(fileBytes instanceof Uint8Array)) {
+        throw new TypeError('expected a Uint8Array or Blob');
+    }
+    return bindings.readParquetSchema(fileBytes);
+}
+
+/**
+ * The footer's thrift FileMetaData, field for field, as a JSON-able object —
+ * for parquet debugging UIs that want to show everything the writer stored.
+ * Field names follow parquet.thrift in camelCase (`rowGroups[0].columns[0].metaData.dataPageOffset`),
+ * enums come back by name (`'INT64'`, `'SNAPPY'`), logical types as `{ TIMESTAMP: { isAdjustedToUTC, unit } }`
+ * and binary statistics as hex. Only the footer is copied into wasm memory.
+ *
+ * @param {Uint8Array|Blob} fileBytes - The whole file or just its end, or a Blob/File (only its end is read).
+ * @returns {Promise<Object>}
+ *
+ * @example
+ * console.log(JSON.stringify(await dumpFooter(bytes), null, 2));
+ */
+export async function dumpFooter(fileBytes) {
+    await init();
+    if (typeof Blob !== 'undefined' && fileBytes instanceof Blob) {
+        fileBytes = await blobTail(fileBytes);
+    } else if (!(fileBytes instanceof Uint8Array)) {
+        throw new TypeError('expected a Uint8Array or Blob');
+    }
+    return bindings.dumpFooter(fileBytes);
+}
+
+/**
+ * List the pages of one column straight from their headers, across all row
+ * groups, without decompressing anything. For debugging files from other
+ * writers: which encodings they picked, how big their pages are, whether
+ * they use V2 pages or write CRCs.
+ *
+ * @param {Uint8Array|ReadableStream<Uint8Array>|AsyncIterable<Uint8Array>|ParquetFile} fileBytes - File bytes or a
+ *   stream of them (as for `readParquet`), or an open `ParquetFile`, which is left open.
+ * @param {string} column - A leaf column's dotted path (as in `rowGroups[i].columns[j].path`), or a top-level column
+ *   with a single leaf.
+ * @returns {Promise<Array<{rowGroup: number, offset: number, type: 'dictionary'|'data'|'index'|'unknown', version?: 1|2, encoding?: string, numValues?: number, numNulls?: number, numRows?: number, headerSize: number, compressedSize: number, uncompressedSize: number, crc: boolean}>>}
+ *
+ * @example
+ * const pages = await inspectPages(bytes, 'tags.list.element');
+ * // [{ rowGroup: 0, offset: 4, type: 'dictionary', encoding: 'PLAIN', numValues: 12, ... }, { type: 'data', version: 1, ... }]
+ */
+export async function inspectPages(fileBytes, column) {
+    if (fileBytes instanceof ParquetFile) return fileBytes.inspectPages(column);
+    if (!(fileBytes instanceof Uint8Array)) {
+        const file = await ParquetFile.open(fileBytes);
+        try {
+            return file.inspectPages(column);
+        } finally {
+            file.free();
+        }
+    }
+    await init();
+    return bindings.inspectPages(fileBytes, column);
+}
+
+/**
+ * Decode a Parquet file in batches of rows, yielding to the event loop between
+ * batches so a UI can render progressively instead of blocking on one decode.
+ *
+ * @param {Uint8Array|ReadableStream<Uint8Array>|AsyncIterable<Uint8Array>|ParquetFile} fileBytes - File bytes or a
+ *   stream of them (as for `readParquet`), or an open `ParquetFile` (including one from `fromUrl`), which is left open.
+ * @param {Object} [options] - `readParquet` options, plus:
+ * @param {number} [options.batchSize=10000] - Rows per batch.
+ * @param {number} [options.maxRows] - Total rows to decode (default: all).
+ * @returns {AsyncGenerator<Record<string, any[]>|Array<Record<string, any>>>} Each batch's `data`, shaped by `format`.
+ *
+ * @example
+ * for await (const batch of readParquetBatches(bytes, { batchSize: 1000, format: 'rows' })) {
+ *     table.append(batch);
+ * }
+ */
+export async function* readParquetBatches(fileBytes, options = {}) {
+    const { batchSize = 10_000, maxRows = Infinity, offset = 0, ...rest } = options;
+    if (!(batchSize >= 1)) throw new Error('batchSize must be a positive number');
+    const owned = !(fileBytes instanceof ParquetFile);
+    const file = owned ? await ParquetFile.open(fileBytes, rest) : fileBytes;
     try {
-        wasm.readParquet(
-            retptr,
-            addBorrowedObject(fileBytes),
-            isLikeNone(maxRows) ? 0x100000001 : (maxRows) >>> 0,
-        );
-        const r0 = getDV().getInt32(retptr + 0, true);
-        const r1 = getDV().getInt32(retptr + 4, true);
-        const r2 = getDV().getInt32(retptr + 8, true);
-        if (r2) throw takeObject(r1);
-        return takeObject(r0);
+        const end = Math.min(offset + maxRows, file.numRows);
+        for (let start = offset; start < end; start += batchSize) {
+            const size = Math.min(batchSize, end - start);
+            const { data } = await file.read({ ...rest, offset: start, maxRows: size });
+            // With a `filter`, fewer rows than the file holds may match
+            const rows = Array.isArray(data) ? data.length : (Object.values(data)[0]?.length ?? size);
+            if (rows > 0) yield data;
+            if (rows < size) break;
+            // Let the event loop (rendering, input) run before the next batch
+            await new Promise((resolve) => setTimeout(resolve, 0));
+        }
     } finally {
-        wasm.__wbindgen_add_to_stack_pointer(16);
-        heap[stack_pointer++] = undefined;
+        if (owned) file.free();
+    }
+}
+
+/**
+ * Copy a ReadableStream or async iterable of byte chunks into a wasm-side
+ * FileBuffer (owned by the caller).
+ */
+async function bufferChunks(source) {
+    let chunks;
+    if (typeof source?.getReader === 'function') {
+        const reader = source.getReader();
+        chunks = (async function* () {
+            try {
+                for (;;) {
+                    const { done, value } = await reader.read();
+                    if (done) return;
+                    yield value;
+                }
+            } finally {
+                reader.releaseLock();
+            }
+        })();
+    } else if (typeof source === 'object' && (typeof source?.[Symbol.asyncIterator] === 'function' || typeof source?.[Symbol.iterator] === 'function')) {
+        chunks = source;
+    } else {
+        throw new TypeError('expected a Uint8Array, ReadableStream or async iterable of Uint8Array chunks');
+    }
+
+    const buffer = new bindings.FileBuffer();
+    try {
+        for await (const chunk of chunks) {
+            buffer.push(ArrayBuffer.isView(chunk)
+                ? new Uint8Array(chunk.buffer, chunk.byteOffset, chunk.byteLength)
+                : new Uint8Array(chunk));
+        }
+    } catch (e) {
+        buffer.free();
+        throw e;
+    }
+    return buffer;
+}
+
+/**
+ * A Parquet file whose footer is parsed once. Reads reuse the parsed metadata
+ * and decode only the requested row groups and columns.
+ *
+ * @example
+ * const file = await ParquetFile.open(bytes);
+ * for (let i = 0; i < file.numRowGroups; i++) {
+ *     const data = file.readRowGroup(i, { columns: ['url'] });
+ * }
+ * file.free();
+ */
+export class ParquetFile {
+    /** The wasm-side file; null once freed */
+    #file;
+
+    constructor(file) {
+        this.#file = file;
+    }
+
+    /**
+     * Parse the footer of a Parquet file. The bytes are copied into wasm memory;
+     * streams are copied chunk by chunk as they arrive.
+     * @param {Uint8Array|ReadableStream<Uint8Array>|AsyncIterable<Uint8Array>} fileBytes - Raw Parquet file bytes, or a stream of them.
+     * @param {Object} [options] - `decryption` keys, for an encrypted file.
+     * @returns {Promise<ParquetFile>}
+     */
+    static async open(fileBytes, options = {}) {
+        await init();
+        if (!(fileBytes instanceof Uint8Array)) {
+            // fromBuffer takes the buffer over, freeing it on failure too
+            const buffer = await bufferChunks(fileBytes);
+            return new ParquetFile(bindings.ParquetFile.fromBuffer(buffer, options));
+        }
+        return new ParquetFile(new bindings.ParquetFile(fileBytes, options));
+    }
+
+    /**
+     * Open a remote Parquet file with HTTP Range requests: the footer is fetched
+     * now, column chunks only when a read needs them. The returned file's
+     * `read`, `readRowGroup` and `readColumn` are async.
+     * @param {string|URL} url - Server must support Range requests (otherwise the whole file is downloaded).
+     * @param {RequestInit} [init] - Extra `fetch` options, e.g. auth headers.
+     * @returns {Promise<RemoteParquetFile>}
+     */
+    static async fromUrl(url, init = {}) {
+        const fetchRange = async (range) => {
+            const headers = new Headers(init.headers);
+            headers.set('Range', `bytes=${range}`);
+            const res = await fetch(url, { ...init, headers });
+            if (!res.ok) throw new Error(`fetch ${url}: HTTP ${res.status}`);
+            return res;
+        };
+
+        const res = await fetchRange(`-${FOOTER_READ_SIZE}`);
+        let tail = new Uint8Array(await res.arrayBuffer());
+        // No Range support: the whole file arrived
+        if (res.status !== 206) return ParquetFile.open(tail);
+        const fileLength = Number(res.headers.get('Content-Range')?.split('/')[1]);
+        if (!Number.isFinite(fileLength)) throw new Error(`fetch ${url}: response has no file size in Content-Range`);
+
+        // Footers longer than the first read need a second request
+        if (tail.length >= 8) {
+            const footerLength = new DataView(tail.buffer, tail.byteOffset, tail.length).getUint32(tail.length - 8, true) + 8;
+            if (footerLength > tail.length && footerLength <= fileLength) {
+                const rest = await fetchRange(`${fileLength - footerLength}-${fileLength - tail.length - 1}`);
+                const head = new Uint8Array(await rest.arrayBuffer());
+                const full = new Uint8Array(head.length + tail.length);
+                full.set(head);
+                full.set(tail, head.length);
+                tail = full;
+            }
+        }
+
+        return new RemoteParquetFile(parseTail(tail, fileLength, {}), fetchRange);
+    }
+
+    #handle() {
+        if (!this.#file) throw new Error('ParquetFile has been freed');
+        return this.#file;
+    }
+
+    /** Total rows in the file. */
+    get numRows() {
+        return this.#handle().numRows;
+    }
+
+    /** Row groups in the file. */
+    get numRowGroups() {
+        return this.#handle().numRowGroups;
+    }
+
+    /**
+     * Footer key-value metadata.
+     * @returns {Record<string, string|null>}
+     */
+    metadata() {
+        return this.#handle().metadata();
+    }
+
+    /**
+     * Schema entries with statistics, as in `readParquet`'s result.
+     * @param {Object} [options] - `readParquet` options; `columns` selects entries.
+     * @returns {Array<{name: string, type: string}>}
+     */
+    schema(options = {}) {
+        return this.#handle().schema(options);
+    }
+
+    /**
+     * Footer summary, as returned by `readParquetMetadata`.
+     * @param {Object} [options] - As for `schema`.
+     */
+    info(options = {}) {
+        return this.#handle().info(options);
+    }
+
+    /**
+     * Same result as `readParquet(bytes, options)`, without re-parsing the footer.
+     * @param {number|Object} [options] - As for `readParquet`.
+     */
+    read(options = {}) {
+        return this.#handle().read(options);
+    }
+
+    /**
+     * Decode one whole row group. `maxRows` and `offset` count within it.
+     * @param {number} index - 0-based row group index.
+     * @param {Object} [options] - `readParquet` options (`rowGroups` is ignored).
+     * @returns {Record<string, any[]>|Array<Record<string, any>>}
+     */
+    readRowGroup(index, options = {}) {
+        return this.#handle().readRowGroup(index, options);
+    }
+
+    /**
+     * Decode every value of one column (across `options.rowGroups`, default all).
+     * @param {string} name - Top-level column name.
+     * @param {Object} [options] - `readParquet` options (`columns` and `format` are ignored).
+     * @returns {any[]|ArrayLike<any>}
+     */
+    readColumn(name, options = {}) {
+        return this.#handle().readColumn(name, options);
+    }
+
+    /**
+     * Page headers of one column, as returned by `inspectPages`. Remote files
+     * need the column's chunks loaded first (see `missingRanges`).
+     * @param {string} column - Leaf column path, or a top-level column with one leaf.
+     */
+    inspectPages(column) {
+        return this.#handle().inspectPages(column);
+    }
+
+    /**
+     * Whether a column may hold `value`, by its bloom filters: `false` only
+     * when the filter of every row group rules it out. Row groups without a
+     * filter (or, for remote files, whose filter isn't loaded) count as maybe.
+     * @param {string} column - Leaf column path, or a top-level column with one leaf.
+     * @param {any} value - As reads return it with default options: a string, Number, BigInt or Uint8Array.
+     * @returns {boolean}
+     */
+    mightContain(column, value) {
+        return this.#handle().mightContain(column, value);
+    }
+
+    /**
+     * File byte ranges `read(options)` needs that aren't loaded yet. Always
+     * empty for files opened from bytes. When page indexes are missing, only
+     * they are returned, and the next call picks the pages to fetch: call
+     * until it returns none.
+     * @param {number|Object} [options] - As for `read`.
+     * @returns {Array<[number, number]>} `[start, end)` offsets.
+     */
+    missingRanges(options = {}) {
+        return this.#handle().missingRanges(options);
+    }
+
+    /**
+     * Add file bytes read at `offset`, e.g. one of `missingRanges()`.
+     * @param {number} offset
+     * @param {Uint8Array} bytes
+     */
+    addRange(offset, bytes) {
+        this.#handle().addRange(offset, bytes);
+    }
+
+    /** Release the wasm-side copy of the file. */
+    free() {
+        this.#file?.free();
+        this.#file = null;
+    }
+}
+
+/** Bytes fetched from the end of a remote file, hoping to get the whole footer. */
+const FOOTER_READ_SIZE = 64 * 1024;
+
+/** Parse the footer from the last bytes of a `fileLength`-byte file; returns the wasm-side file. */
+function parseTail(tail, fileLength, options) {
+    return bindings.ParquetFile.fromTail(tail, fileLength, options);
+}
+
+/** The end of a Blob holding its footer: one read, or two when the footer is larger than `FOOTER_READ_SIZE`. */
+async function blobTail(blob) {
+    const tail = new Uint8Array(await blob.slice(-FOOTER_READ_SIZE).arrayBuffer());
+    if (tail.length < 8) return tail;
+    const footerLength = new DataView(tail.buffer, tail.byteOffset, tail.length).getUint32(tail.length - 8, true) + 8;
+    if (footerLength <= tail.length || footerLength > blob.size) return tail;
+    return new Uint8Array(await blob.slice(blob.size - footerLength).arrayBuffer());
+}
+
+/**
+ * A `ParquetFile` from `ParquetFile.fromUrl`. Reads fetch the column chunks
+ * they need first — just the pages, for columns with page indexes — in
+ * parallel (adjacent ranges in one request), and keep them for later reads.
+ */
+class RemoteParquetFile extends ParquetFile {
+    #fetchRange;
+
+    constructor(file, fetchRange) {
+        super(file);
+        this.#fetchRange = fetchRange;
+    }
+
+    async #load(options) {
+        // Page indexes come back first when they aren't loaded; the next round fetches the pages they pick
+        for (let ranges; (ranges = this.missingRanges(options)).length > 0;) {
+            await Promise.all(ranges.map(async ([start, end]) => {
+                const res = await this.#fetchRange(`${start}-${end - 1}`);
+                const bytes = new Uint8Array(await res.arrayBuffer());
+                if (res.status !== 206 || bytes.length !== end - start)
+                    throw new Error(`range ${start}-${end - 1}: server returned ${bytes.length} bytes (HTTP ${res.status})`);
+                this.addRange(start, bytes);
+            }));
+        }
+    }
+
+    async read(options = {}) {
+        await this.#load(options);
+        return super.read(options);
+    }
+
+    async readRowGroup(index, options = {}) {
+        await this.#load({ maxRows: Infinity, ...options, rowGroups: [index] });
+        return super.readRowGroup(index, options);
+    }
+
+    async readColumn(name, options = {}) {
+        await this.#load({ maxRows: Infinity, ...options, columns: [name] });
+        return super.readColumn(name, options);
     }
 }
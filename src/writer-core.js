@@ -0,0 +1,312 @@
+/**
+ * tiny-parquet writer API, shared by the 'tiny-parquet/writer' and
+ * 'tiny-parquet/writer-full' entry points. Each binds it to its own wasm
+ * build: they differ only in which page codecs are compiled in.
+ */
+
+// ── WASM loader (universal) ──────────────────────────────────────────────────
+/**
+ * Lazy singleton that instantiates a wasm-bindgen module: `initBindings` is
+ * its glue's default export, `wasmUrl()` the URL of its .wasm file.
+ */
+function wasmLoader(initBindings, wasmUrl) {
+  let initPromise = null;
+
+  async function loadWasm() {
+    // Node.js
+    if (typeof process !== 'undefined' && process.versions?.node) {
+      const { readFileSync } = await import('node:fs');
+      const { fileURLToPath } = await import('node:url');
+      return initBindings({ module_or_path: readFileSync(fileURLToPath(wasmUrl())) });
+    }
+
+    // Edge / Browser — resolve relative to the entry module
+    return initBindings({ module_or_path: wasmUrl() });
+  }
+
+  return () => {
+    initPromise ??= loadWasm();
+    return initPromise;
+  };
+}
+
+/**
+ * Adapt `config.sink` to the synchronous `(chunk) => void` the wasm writer
+ * calls. Chunks for a WritableStream are queued on its writer; `ready()`
+ * waits for backpressure and surfaces failed writes.
+ */
+function toSink(sink) {
+  if (typeof sink === 'function') {
+    return { write: sink, ready: async () => {}, close: async () => {}, abort: () => {} };
+  }
+  if (typeof sink?.getWriter !== 'function') throw new TypeError('sink must be a function or a WritableStream');
+  const writer = sink.getWriter();
+  let failed = null;
+  return {
+    write: (chunk) => { writer.write(chunk).catch((e) => { failed ??= e; }); },
+    ready: async () => {
+      await writer.ready;
+      if (failed) throw failed;
+    },
+    close: () => writer.close(),
+    abort: (reason) => { writer.abort(reason).catch(() => {}); },
+  };
+}
+
+// ── Public API ───────────────────────────────────────────────────────────────
+/**
+ * The writer API over one wasm build.
+ *
+ * @param {Object} bindings - The build's wasm-bindgen exports.
+ * @param {function(Object): Promise} initBindings - Its glue's default export.
+ * @param {function(): URL} wasmUrl - Where its .wasm file is.
+ */
+export function createWriter(bindings, initBindings, wasmUrl) {
+  const init = wasmLoader(initBindings, wasmUrl);
+
+  /**
+   * Write a Parquet file from columnar data.
+   *
+   * @param {Array<{name: string, type: string, optional?: boolean, id?: number}>|null} schema - Column definitions,
+   *   or null to infer them from `data` (see `inferSchema`).
+   *   Supported types: 'string', 'int32', 'int64', 'float32', 'float64', 'boolean',
+   *   'timestamp' (millis), 'timestamp_micros', 'timestamp_nanos', 'date', 'time_millis', 'time_micros',
+   *   'decimal(p,s)', 'uuid', 'fixed(n)', 'binary', 'int8', 'int16', 'uint8', 'uint16', 'uint32', 'uint64',
+   *   'float16', 'json', 'bson', 'geometry', 'list' (with `items`, the element type or schema)
+   *   'struct' (with `fields`, an array of child column definitions) and 'map' (with `key` and `value`)
+   *   Set `optional: true` to allow null/undefined values in a column.
+   *   Set `id` to store a Parquet field ID (Iceberg/Delta schema evolution).
+   *   Set `isAdjustedToUTC: false` on timestamp/time columns holding local (naive) values.
+   *   Set `default` on a primitive column for the value `coercion: 'default'` writes in place of invalid cells.
+   *   Set `crs` (PROJJSON) and `edges` ('planar' | 'spherical') on geometry columns (GeoJSON or WKB cells) for the `geo` metadata.
+   * @param {Record<string, any[]>|Array<Record<string, any>>} data - Columnar data keyed by column name,
+   *   or an array of row objects (missing keys are treated as null/undefined).
+   *   Numeric and boolean columns may be TypedArrays (copied in one call, no nulls).
+   * @param {Object} [config] - Optional configuration.
+   * @param {string|Object} [config.compression='snappy'] - 'snappy' | 'gzip' | 'zstd' | 'lz4' | 'brotli' | 'none',
+   *   or { default, perColumn: { column: codec } }. gzip, zstd, lz4 and brotli need 'tiny-parquet/writer-full'
+   * @param {number} [config.compressionLevel] - Codec level (gzip: 0-10, zstd: 1-22, brotli: 0-11)
+   * @param {boolean} [config.dictionary=true] - Dictionary encoding for string and numeric columns
+   * @param {number} [config.dictionaryPageSize=1048576] - Max dictionary bytes per column chunk; later pages fall back to PLAIN
+   * @param {number} [config.dictionaryRatio=1] - Max distinct values per value in a chunk's first page for a dictionary
+   * @param {string[]|Object<string, true|'length'>} [config.deltaEncoding] - Leaf columns to delta-encode (no dictionary):
+   *   int32/int64 as DELTA_BINARY_PACKED, byte arrays as DELTA_BYTE_ARRAY, or DELTA_LENGTH_BYTE_ARRAY with 'length'
+   * @param {string[]} [config.byteStreamSplit] - float32/float64 leaf columns to store as BYTE_STREAM_SPLIT (no dictionary)
+   * @param {Object<string, string>} [config.encodings] - Encoding per leaf column, overriding `dictionary`:
+   *   'dictionary' | 'plain' | 'delta' | 'byte_stream_split'
+   * @param {boolean} [config.statistics=true] - Min/max/null_count statistics for pages and column chunks
+   * @param {number} [config.rowGroupSize=1048576] - Max rows per row group
+   * @param {number} [config.dataPageSize=1048576] - Target uncompressed bytes per data page
+   * @param {number} [config.dataPageRows] - Max rows per data page (default: unlimited)
+   * @param {'v1'|'v2'} [config.version='v1'] - Data page header format
+   * @param {Object<string, string>} [config.metadata] - Key-value metadata for the file footer
+   * @param {string} [config.createdBy] - Footer created_by string
+   * @param {string} [config.schemaName='schema'] - Name of the root schema node
+   * @param {Array<{column: string, descending?: boolean, nullsFirst?: boolean}>} [config.sortedBy] - Declared sort order (recorded as sorting_columns)
+   * @param {string[]} [config.sortBy] - Sort rows by these top-level columns first ('-id' for descending), recording the order
+   * @param {boolean} [config.pageChecksums=false] - Store a CRC-32 in every page header
+   * @param {string[]|Object<string, true|{fpp?: number, ndv?: number}>} [config.bloomFilters] - Leaf columns to write a
+   *   split-block bloom filter for in every row group, optionally with a false-positive rate (default 0.01) and the
+   *   distinct values to size for (default: each row group's own count)
+   * @param {boolean} [config.strict=false] - Reject cells of the wrong JS type, missing cells in required columns and
+   *   values that can't be stored exactly (e.g. unsafe int64 Numbers), naming the column and row
+   * @param {'coerce'|'null'|'default'|'error'} [config.coercion='coerce'] - What mistyped cells, NaN in non-float columns
+   *   and missing cells in required columns become: JS conversions, null (optional columns), the column's `default`, or
+   *   an error ('error' is the default with `strict`)
+   * @param {boolean} [config.arrowSchema=false] - Embed the schema as Arrow types under the `ARROW:schema` metadata key
+   * @param {true|{index?: string|string[]}} [config.pandas] - Write the `pandas` metadata entry: column dtypes and the
+   *   top-level columns pandas uses as the DataFrame index
+   * @param {WritableStream<Uint8Array>|function(Uint8Array): void} [config.sink] - Receive the file a row group
+   *   at a time (then the footer) instead of as one Uint8Array. A stream is closed once the file is complete.
+   * @param {Object} [config.encryption] - Encrypt the file with AES-GCM (Parquet modular encryption, encrypted footer)
+   * @param {Uint8Array} config.encryption.footerKey - 16, 24 or 32-byte key for the footer and columns without their own key
+   * @param {string|Uint8Array} [config.encryption.footerKeyMetadata] - Stored with the footer, e.g. a key ID
+   * @param {Object<string, Uint8Array|{key: Uint8Array, keyMetadata?: string|Uint8Array}>} [config.encryption.columnKeys] -
+   *   Leaf columns (dotted paths) encrypted with their own key
+   * @param {string|Uint8Array} [config.encryption.aadPrefix] - Stored in the file and authenticated with every module
+   * @returns {Promise<Uint8Array|undefined>} The Parquet file bytes (undefined with `sink`).
+   *
+   * @example
+   * const bytes = await writeParquet(
+   *   [{ name: 'url', type: 'string' }, { name: 'ts', type: 'int64' }],
+   *   { url: ['https://example.com'], ts: [1708000000] },
+   *   { compression: 'snappy' }
+   * );
+   */
+  async function writeParquet(schema, data, config = {}) {
+    await init();
+    const sink = config?.sink == null ? null : toSink(config.sink);
+    if (sink) config = { ...config, sink: sink.write };
+    let bytes;
+    try {
+      bytes = bindings.writeParquet(schema, data, config);
+    } catch (err) {
+      sink?.abort(err);
+      throw err;
+    }
+    if (!sink) return bytes;
+    await sink.close();
+  }
+
+  /**
+   * Infer the schema `writeParquet(null, data)` would use: whole Numbers within
+   * 32 bits → int32, other Numbers → float64, BigInts → int64, booleans, strings,
+   * Dates → timestamp, Uint8Arrays → binary, arrays → list, objects → struct.
+   * Columns with nulls (or missing keys) are optional.
+   *
+   * @param {Record<string, any[]>|Array<Record<string, any>>} data - Columnar data or row objects.
+   * @returns {Promise<Array<Object>>} Column definitions.
+   */
+  async function inferSchema(data) {
+    await init();
+    return bindings.inferSchema(data);
+  }
+
+  /**
+   * Concatenate Parquet files with identical schemas into one. Row groups are
+   * copied byte for byte (with their page indexes and bloom filters), so
+   * nothing is decoded or re-compressed. Footer metadata comes from the first file.
+   *
+   * @param {Uint8Array[]} files - Complete Parquet files, in output order.
+   * @returns {Promise<Uint8Array>} The merged file.
+   *
+   * @example
+   * const bytes = await mergeParquet([monday, tuesday, wednesday]);
+   */
+  async function mergeParquet(files) {
+    if (!Array.isArray(files)) throw new TypeError('mergeParquet takes an array of Uint8Arrays');
+    await init();
+    return bindings.mergeParquet(files);
+  }
+
+  /**
+   * Add rows to an existing Parquet file as new row groups. The file's bytes up
+   * to its footer are kept as they are; only the footer is rewritten.
+   *
+   * @param {Uint8Array} fileBytes - The existing file.
+   * @param {Array<Object>} schema - Column definitions, as for `writeParquet`; must produce the file's schema.
+   * @param {Record<string, any[]>|Array<Record<string, any>>} data - The rows to add, as for `writeParquet`.
+   * @param {Object} [config] - As for `writeParquet`, without `sink`. The file's footer metadata is kept.
+   * @returns {Promise<Uint8Array>} The extended file.
+   *
+   * @example
+   * const bytes = await appendToParquet(existing, schema, { url: ['https://example.org'], ts: [1708000060] });
+   */
+  async function appendToParquet(fileBytes, schema, data, config = {}) {
+    await init();
+    return bindings.appendToParquet(fileBytes, schema, data, config);
+  }
+
+  /**
+   * Incremental Parquet writer. Each `appendRows` / `appendColumns` call is
+   * encoded and compressed into one or more row groups (split at `rowGroupSize`)
+   * right away, so only the compressed file is held in memory — not the rows.
+   * With `config.sink`, not even that: each row group is handed over as it's done.
+   *
+   * @example
+   * const writer = new ParquetWriter([{ name: 'url', type: 'string' }], { rowGroupSize: 100_000 });
+   * for await (const batch of batches) await writer.appendRows(batch);
+   * const bytes = await writer.finish();
+   */
+  class ParquetWriter {
+    #writer = null;
+    #finished = false;
+    #schema;
+    #config;
+    #sink = null;
+
+    /**
+     * @param {Array<Object>} schema - Column definitions, as for `writeParquet`.
+     * @param {Object} [config] - Options, as for `writeParquet` (including `sink`).
+     *   Schema and config errors surface on the first call.
+     */
+    constructor(schema, config = {}) {
+      this.#schema = schema;
+      if (config?.sink != null) {
+        this.#sink = toSink(config.sink);
+        config = { ...config, sink: this.#sink.write };
+      }
+      this.#config = config;
+    }
+
+    async #handle() {
+      await init();
+      if (this.#finished) throw new Error('ParquetWriter is already finished');
+      this.#writer ??= new bindings.ParquetWriter(this.#schema, this.#config);
+      return this.#writer;
+    }
+
+    async #append(method, data) {
+      const writer = await this.#handle();
+      writer[method](data);
+      try {
+        await this.#sink?.ready();
+      } catch (e) {
+        this.free();
+        throw e;
+      }
+    }
+
+    /**
+     * Append rows as an array of row objects (missing keys are null/undefined).
+     * @param {Array<Record<string, any>>} rows
+     * @returns {Promise<void>}
+     */
+    appendRows(rows) {
+      return this.#append('appendRows', rows);
+    }
+
+    /**
+     * Append columnar data keyed by column name; every column needs the same length.
+     * @param {Record<string, any[]>} columns
+     * @returns {Promise<void>}
+     */
+    appendColumns(columns) {
+      return this.#append('appendColumns', columns);
+    }
+
+    /** Rows written so far. */
+    get numRows() {
+      return this.#writer?.numRows ?? 0;
+    }
+
+    /** Row groups written so far. */
+    get numRowGroups() {
+      return this.#writer?.numRowGroups ?? 0;
+    }
+
+    /**
+     * Write the footer and return the file bytes. The writer is freed afterwards.
+     * With `sink`, the footer goes to the sink (a stream is closed) and nothing is returned.
+     * @returns {Promise<Uint8Array|undefined>}
+     */
+    async finish() {
+      const writer = await this.#handle();
+      const sink = this.#sink;
+      let bytes;
+      try {
+        bytes = writer.finish();
+      } catch (err) {
+        sink?.abort(err);
+        throw err;
+      } finally {
+        this.#sink = null;
+        this.free();
+      }
+      if (!sink) return bytes;
+      await sink.close();
+    }
+
+    /** Release the wasm-side writer without finishing the file (a sink stream is aborted). */
+    free() {
+      const writer = this.#writer;
+      this.#writer = null;
+      this.#finished = true;
+      this.#sink?.abort(new Error('ParquetWriter freed before finish'));
+      this.#sink = null;
+      writer?.free();
+    }
+  }
+
+  return { writeParquet, inferSchema, mergeParquet, appendToParquet, ParquetWriter };
+}
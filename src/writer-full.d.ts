@@ -0,0 +1,2 @@
+/** Same API as tiny-parquet/writer, built with gzip, zstd, lz4 and brotli too. */
+export * from './writer.js';
@@ -0,0 +1,16 @@
+/**
+ * tiny-parquet/writer-full — WASM Parquet Writer with every codec
+ * Same API as tiny-parquet/writer, plus gzip, zstd, lz4 and brotli compression
+ */
+
+import initBindings, * as bindings from '../wasm/writer-full_bindings.js';
+import { createWriter } from './writer-core.js';
+
+// `wasm/writer-full_bindings.js` is wasm-bindgen's glue for `wasm/writer-full.wasm`
+// (the writer crate built with `--features codecs`); both are generated
+// together by `scripts/build-wasm.sh`.
+export const { writeParquet, inferSchema, mergeParquet, appendToParquet, ParquetWriter } = createWriter(
+  bindings,
+  initBindings,
+  () => new URL('../wasm/writer-full.wasm', import.meta.url),
+);
@@ -1,23 +1,211 @@
-export interface ColumnSchema {
+export type PrimitiveType =
+    | 'string' | 'int32' | 'int64' | 'float32' | 'float64' | 'boolean'
+    | 'timestamp' | 'timestamp_millis' | 'timestamp_micros' | 'timestamp_nanos'
+    | 'date' | 'time_millis' | 'time_micros'
+    | `decimal(${number},${number})` | 'uuid' | `fixed(${number})` | 'binary'
+    | 'int8' | 'int16' | 'uint8' | 'uint16' | 'uint32' | 'uint64'
+    | 'float16' | 'json' | 'bson' | 'geometry';
+
+/** A column's type and options; `ColumnSchema` adds the name. */
+export interface FieldSchema {
+    type: PrimitiveType | 'list' | 'struct' | 'map';
+    /** `list` columns: the element type, or a schema for it (e.g. `{ type: 'int32', optional: true }`). */
+    items?: PrimitiveType | FieldSchema;
+    /** `struct` columns: the child fields, read from the same-named keys of each value. */
+    fields?: ColumnSchema[];
+    /** `map` columns: the key type (a required primitive). */
+    key?: PrimitiveType | FieldSchema;
+    /** `map` columns: the value type, or a schema for it. */
+    value?: PrimitiveType | FieldSchema;
+    /** Allow null/undefined values (written with definition levels). Default: false. */
+    optional?: boolean;
+    /** Timestamp/time columns: false stores local (naive) values instead of UTC instants. Default: true. */
+    isAdjustedToUTC?: boolean;
+    /** Parquet field ID (used by Iceberg/Delta for schema evolution). */
+    id?: number;
+    /** Primitive columns: written in place of invalid cells with `coercion: 'default'`. */
+    default?: any;
+    /** `geometry` columns: PROJJSON coordinate reference system for the `geo` metadata. Default: OGC:CRS84. */
+    crs?: object | null;
+    /** `geometry` columns: how edges between points are interpolated. Default: 'planar'. */
+    edges?: 'planar' | 'spherical';
+}
+
+export interface ColumnSchema extends FieldSchema {
     name: string;
-    type: 'string' | 'int32' | 'int64' | 'float32' | 'float64' | 'boolean' | 'timestamp';
 }
 
+export type Codec = 'snappy' | 'gzip' | 'zstd' | 'lz4' | 'brotli' | 'none';
+
 export interface WriteConfig {
-    compression?: 'snappy' | 'none';
+    /**
+     * Page codec, or `{ default, perColumn: { column: codec } }` to pick it per leaf column. Default: 'snappy'.
+     * gzip, zstd, lz4 and brotli need 'tiny-parquet/writer-full'.
+     */
+    compression?: Codec | { default?: Codec; perColumn?: Record<string, Codec> };
+    /** Codec level: gzip 0-10 (default 6), zstd 1-22 (default 3), brotli 0-11 (default 1). */
+    compressionLevel?: number;
+    /** Dictionary-encode string and numeric columns with ≤256 distinct values. Default: true. */
     dictionary?: boolean;
+    /** Max PLAIN bytes of a chunk's dictionary; the page that would exceed it and all after are PLAIN. Default: 1048576. */
+    dictionaryPageSize?: number;
+    /** Write a chunk without dictionary if its first page has more distinct values per value than this, in (0, 1]. Default: 1. */
+    dictionaryRatio?: number;
+    /**
+     * Delta-encode these leaf columns instead (no dictionary): int32/int64 as DELTA_BINARY_PACKED, byte arrays
+     * as DELTA_BYTE_ARRAY, or DELTA_LENGTH_BYTE_ARRAY when mapped to 'length'.
+     */
+    deltaEncoding?: string[] | Record<string, boolean | 'length'>;
+    /** Store these float32/float64 leaf columns as BYTE_STREAM_SPLIT instead (no dictionary); pairs well with zstd. */
+    byteStreamSplit?: string[];
+    /**
+     * Encoding per leaf column, overriding `dictionary`: 'dictionary' (PLAIN when a chunk has too many distinct
+     * values), 'plain', or 'delta' / 'byte_stream_split' as in `deltaEncoding` / `byteStreamSplit`.
+     */
+    encodings?: Record<string, 'dictionary' | 'plain' | 'delta' | 'byte_stream_split'>;
+    /** Write min/max/null_count statistics for pages and column chunks. Default: true. */
+    statistics?: boolean;
+    /** Max rows per row group. Default: 1048576. */
+    rowGroupSize?: number;
+    /** Target uncompressed bytes per data page. Default: 1048576. */
+    dataPageSize?: number;
+    /** Max rows per data page. Default: unlimited. */
+    dataPageRows?: number;
+    /** Data page format. 'v2' keeps levels uncompressed, ahead of the values. Default: 'v1'. */
+    version?: 'v1' | 'v2';
+    /** File-level key-value metadata stored in the footer. */
+    metadata?: Record<string, string | null>;
+    /** Footer `created_by` string, e.g. 'my-app version 1.2.0'. Default: unset. */
+    createdBy?: string;
+    /** Name of the root schema node. Default: 'schema'. */
+    schemaName?: string;
+    /** Declared sort order, recorded as sorting_columns in every row group. Rows are not reordered. */
+    sortedBy?: SortingColumn[];
+    /**
+     * Sort rows by these top-level columns before writing ('-id' for descending; nulls last), and record the
+     * order as sorting_columns. Can't be combined with `sortedBy`.
+     */
+    sortBy?: string[];
+    /** Store a CRC-32 of every page in its header. Default: false. */
+    pageChecksums?: boolean;
+    /**
+     * Leaf columns (dotted paths) to write a split-block bloom filter for, in every row group. `fpp` is the target
+     * false-positive rate (default 0.01); `ndv` the distinct values per row group to size for (default: the actual count).
+     */
+    bloomFilters?: string[] | Record<string, boolean | { fpp?: number; ndv?: number }>;
+    /**
+     * Reject cells of the wrong JS type (instead of writing 0, '' or false), missing cells in required columns,
+     * and values that can't be stored exactly, e.g. int64 Numbers beyond 2^53 or decimals with too many fraction
+     * digits. Errors name the column, row and the cell's JS type. Default: false.
+     */
+    strict?: boolean;
+    /**
+     * What cells of the wrong JS type, NaN in non-float columns and null/undefined in required columns become:
+     * JS conversions (0, '', false — 'coerce'), null in optional columns ('null'), the column's `default`
+     * ('default'), or an error ('error'). Default: 'coerce', or 'error' with `strict`.
+     */
+    coercion?: 'coerce' | 'null' | 'default' | 'error';
+    /** Embed the schema as Arrow types under the `ARROW:schema` metadata key (timezones, extension types). Default: false. */
+    arrowSchema?: boolean;
+    /** Write the `pandas` metadata entry (dtypes, and `index` columns as the DataFrame index) for `pd.read_parquet`. Default: unset. */
+    pandas?: true | { index?: string | string[] };
+    /**
+     * Receive the file a row group at a time, then the footer, instead of one Uint8Array at the end.
+     * A WritableStream is written with backpressure and closed when the file is complete; a callback
+     * is called synchronously. Can't be combined with `pageChecksums` or `encryption`.
+     */
+    sink?: WritableStream<Uint8Array> | ((chunk: Uint8Array) => void);
+    /** Encrypt the file with AES-GCM (Parquet modular encryption, encrypted footer). */
+    encryption?: EncryptionConfig;
+}
+
+export interface EncryptionConfig {
+    /** 16, 24 or 32-byte AES key for the footer and every column without its own key. */
+    footerKey: Uint8Array;
+    /** Stored with the footer for the reader's key management, e.g. a key ID. */
+    footerKeyMetadata?: string | Uint8Array;
+    /** Leaf columns (dotted paths) encrypted with their own key, optionally with key metadata. */
+    columnKeys?: Record<string, Uint8Array | { key: Uint8Array; keyMetadata?: string | Uint8Array }>;
+    /** Stored in the file and bound into the authentication of every module. */
+    aadPrefix?: string | Uint8Array;
+}
+
+export interface SortingColumn {
+    column: string;
+    /** Default: false. */
+    descending?: boolean;
+    /** Default: false (nulls last). */
+    nullsFirst?: boolean;
 }
 
 /**
  * Write a Parquet file from columnar data.
  *
- * @param schema - Column definitions with name and type.
- * @param data - Columnar data keyed by column name.
+ * @param schema - Column definitions with name and type, or null to infer them from `data`.
+ * @param data - Columnar data keyed by column name, or an array of row objects (missing keys count as null).
  * @param config - Optional configuration (compression, etc).
- * @returns The Parquet file as a Uint8Array.
+ * @returns The Parquet file as a Uint8Array (nothing with `sink`).
  */
 export function writeParquet(
-    schema: ColumnSchema[],
-    data: Record<string, any[]>,
+    schema: ColumnSchema[] | null,
+    data: Record<string, any[] | ArrayLike<number>> | Record<string, any>[],
+    config: WriteConfig & { sink: NonNullable<WriteConfig['sink']> },
+): Promise<void>;
+export function writeParquet(
+    schema: ColumnSchema[] | null,
+    data: Record<string, any[] | ArrayLike<number>> | Record<string, any>[],
     config?: WriteConfig,
 ): Promise<Uint8Array>;
+
+/**
+ * The schema `writeParquet(null, data)` infers: column types from the values
+ * (Numbers → int32/float64, BigInt → int64, Date → timestamp, arrays → list,
+ * objects → struct, ...), optional where there are nulls.
+ */
+export function inferSchema(data: Record<string, any[] | ArrayLike<number>> | Record<string, any>[]): Promise<ColumnSchema[]>;
+
+/**
+ * Concatenate Parquet files with identical schemas, copying row groups byte
+ * for byte. Footer metadata comes from the first file.
+ *
+ * @param files - Complete Parquet files, in output order.
+ * @returns The merged file.
+ */
+export function mergeParquet(files: Uint8Array[]): Promise<Uint8Array>;
+
+/**
+ * Add rows to an existing file as new row groups, rewriting only the footer.
+ *
+ * @param fileBytes - The existing Parquet file.
+ * @param schema - Column definitions; must produce the file's schema.
+ * @param data - The rows to add, columnar or as row objects.
+ * @param config - As for `writeParquet`, without `sink`. The file's footer metadata is kept.
+ * @returns The extended file.
+ */
+export function appendToParquet(
+    fileBytes: Uint8Array,
+    schema: ColumnSchema[],
+    data: Record<string, any[] | ArrayLike<number>> | Record<string, any>[],
+    config?: Omit<WriteConfig, 'sink'>,
+): Promise<Uint8Array>;
+
+/**
+ * Incremental Parquet writer: each append is encoded into row groups right
+ * away, so only the compressed file is kept in memory.
+ */
+export class ParquetWriter {
+    /** Schema and config errors surface on the first call. */
+    constructor(schema: ColumnSchema[], config?: WriteConfig);
+    /** Append an array of row objects (missing keys count as null). */
+    appendRows(rows: Record<string, any>[]): Promise<void>;
+    /** Append columnar data keyed by column name. */
+    appendColumns(columns: Record<string, any[] | ArrayLike<number>>): Promise<void>;
+    /** Rows written so far. */
+    readonly numRows: number;
+    /** Row groups written so far. */
+    readonly numRowGroups: number;
+    /** Write the footer and return the file (or send it to `sink`). The writer can't be used afterwards. */
+    finish(): Promise<Uint8Array | undefined>;
+    /** Release the writer without finishing the file (aborting a `sink` stream). */
+    free(): void;
+}
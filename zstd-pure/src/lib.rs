@@ -0,0 +1,299 @@
+//! Pure-Rust zstd compression for the writer's ZSTD pages, so the wasm
+//! builds need no C toolchain (the `zstd` crate binds libzstd). Decoding is
+//! left to ruzstd, which the reader calls directly.
+//!
+//! `compress` runs ruzstd's block encoder (literals, sequences, entropy
+//! tables) over matches found by `ChainMatcher` below, whose window and
+//! search effort grow with the level the way libzstd's do, so higher levels
+//! trade time for smaller output.
+
+use ruzstd::encoding::{CompressionLevel, FrameCompressor, Matcher, Sequence};
+
+/// zstd's own default level, used for level 0 as libzstd does
+pub const DEFAULT_LEVEL: i32 = 3;
+
+/// Highest level libzstd accepts; higher ones are clamped to it
+const MAX_LEVEL: i32 = 22;
+
+/// Compress `data` as one zstd frame. `level` is libzstd's: 1-22, with 0 and
+/// below meaning `DEFAULT_LEVEL`.
+pub fn compress(data: &[u8], level: i32) -> Vec<u8> {
+    let level = if level <= 0 { DEFAULT_LEVEL } else { level.min(MAX_LEVEL) };
+    let matcher = ChainMatcher::new(level, data.len());
+    // ruzstd only reads the level to pick its block encoder; the matcher decides the ratio
+    let mut compressor = FrameCompressor::new_with_matcher(matcher, CompressionLevel::Fastest);
+    let mut frame = Vec::new();
+    compressor.set_source(data);
+    compressor.set_drain(&mut frame);
+    compressor.compress();
+    frame
+}
+
+/// zstd's largest block; ruzstd fills one space per block
+const BLOCK_SIZE: usize = 128 * 1024;
+/// Shortest match worth a sequence (zstd allows 3, but 4-byte matches rarely pay off)
+const MIN_MATCH: usize = 4;
+/// Longest match emitted. zstd allows 131074, but ruzstd 0.8 writes the extra
+/// bits of lengths past 65538 wrongly, so longer runs become several matches
+const MAX_MATCH: usize = 65538;
+const HASH_LOG: u32 = 16;
+
+/// Hash-chain match finder. Level 1 looks at one candidate per position,
+/// like ruzstd's own `Fastest`; each level up widens the window and walks
+/// further down the chain, and from level 6 on a match is only taken when
+/// the next position doesn't start a longer one (lazy matching).
+///
+/// The whole input is already in memory (`compress` takes a slice), so every
+/// committed block is kept and positions index `history` directly.
+struct ChainMatcher {
+    history: Vec<u8>,
+    /// Start of the block being matched
+    block_start: usize,
+    /// Positions below this are in the hash chains
+    hashed: usize,
+    /// Most recent position (+1) of each 4-byte hash
+    head: Vec<u32>,
+    /// Previous position (+1) with the same hash, by position within the window
+    chain: Vec<u32>,
+    window_log: u32,
+    depth: usize,
+    lazy: bool,
+    /// Stop searching once a match this long is found
+    nice_len: usize,
+}
+
+impl ChainMatcher {
+    fn new(level: i32, input_len: usize) -> Self {
+        let level = level.clamp(1, MAX_LEVEL) as u32;
+        // No point in a window longer than the input; zstd's smallest is 1 KB
+        let input_log = input_len.max(1).next_power_of_two().ilog2().max(10);
+        let window_log = (17 + level.min(18) / 3).min(input_log);
+        ChainMatcher {
+            history: Vec::with_capacity(input_len),
+            block_start: 0,
+            hashed: 0,
+            head: vec![0; 1 << HASH_LOG],
+            chain: vec![0; 1 << window_log],
+            window_log,
+            depth: if level == 1 { 1 } else { (1 << (level / 2)).min(512) },
+            lazy: level >= 6,
+            nice_len: (16 * level as usize).min(BLOCK_SIZE),
+        }
+    }
+
+    fn hash(bytes: &[u8]) -> usize {
+        let v = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        (v.wrapping_mul(0x9E37_79B1) >> (32 - HASH_LOG)) as usize
+    }
+
+    /// Add every position below `end` that has `MIN_MATCH` bytes after it to the chains
+    fn insert_until(&mut self, end: usize) {
+        let last = self.history.len().saturating_sub(MIN_MATCH - 1);
+        let mask = (1 << self.window_log) - 1;
+        while self.hashed < end.min(last) {
+            let pos = self.hashed;
+            let h = Self::hash(&self.history[pos..]);
+            self.chain[pos & mask] = self.head[h];
+            self.head[h] = pos as u32 + 1;
+            self.hashed += 1;
+        }
+    }
+
+    /// Longest earlier match for `pos` within the window, as `(len, offset)`
+    fn best_match(&mut self, pos: usize, end: usize) -> Option<(usize, usize)> {
+        if pos + MIN_MATCH > end {
+            return None;
+        }
+        self.insert_until(pos);
+        let window = 1usize << self.window_log;
+        let mask = window - 1;
+        let limit = (end - pos).min(MAX_MATCH);
+        let mut best: Option<(usize, usize)> = None;
+        let mut cand = self.head[Self::hash(&self.history[pos..])] as usize;
+        for _ in 0..self.depth {
+            if cand == 0 {
+                break;
+            }
+            let at = cand - 1;
+            if pos - at > window {
+                break;
+            }
+            let len = common_prefix(&self.history[at..end], &self.history[pos..end]).min(limit);
+            if len >= MIN_MATCH && best.is_none_or(|(l, _)| len > l) {
+                best = Some((len, pos - at));
+                if len >= self.nice_len || len == limit {
+                    break;
+                }
+            }
+            let prev = self.chain[at & mask] as usize;
+            // A slot overwritten by a newer position ends the chain
+            if prev == 0 || prev > at {
+                break;
+            }
+            cand = prev;
+        }
+        best
+    }
+}
+
+fn common_prefix(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+impl Matcher for ChainMatcher {
+    fn get_next_space(&mut self) -> Vec<u8> {
+        vec![0; BLOCK_SIZE]
+    }
+
+    fn get_last_space(&mut self) -> &[u8] {
+        &self.history[self.block_start..]
+    }
+
+    fn commit_space(&mut self, space: Vec<u8>) {
+        self.block_start = self.history.len();
+        self.history.extend_from_slice(&space);
+    }
+
+    fn skip_matching(&mut self) {
+        self.insert_until(self.history.len());
+    }
+
+    fn start_matching(&mut self, mut handle_sequence: impl for<'a> FnMut(Sequence<'a>)) {
+        let end = self.history.len();
+        // (literals start, match start, offset, match length)
+        let mut found = Vec::new();
+        let mut literals = self.block_start;
+        let mut pos = self.block_start;
+        while pos < end {
+            let Some((mut len, mut offset)) = self.best_match(pos, end) else {
+                pos += 1;
+                continue;
+            };
+            if self.lazy {
+                while let Some((next_len, next_offset)) = self.best_match(pos + 1, end) {
+                    if next_len <= len {
+                        break;
+                    }
+                    pos += 1;
+                    (len, offset) = (next_len, next_offset);
+                }
+            }
+            found.push((literals, pos, offset, len));
+            pos += len;
+            literals = pos;
+        }
+        self.insert_until(end);
+
+        // ruzstd can't build a literal-length table whose only code is 0, so
+        // when every match directly follows the previous one, the first gives
+        // up a byte to literals (or all of it, if that would leave it too short)
+        if !found.is_empty() && found.iter().all(|&(start, at, ..)| start == at) {
+            let (start, at, offset, len) = found[0];
+            if len > MIN_MATCH {
+                found[0] = (start, at + 1, offset, len - 1);
+            } else {
+                found.remove(0);
+                match found.first_mut() {
+                    Some(next) => next.0 = start,
+                    None => literals = start,
+                }
+            }
+        }
+
+        for (start, at, offset, match_len) in found {
+            handle_sequence(Sequence::Triple { literals: &self.history[start..at], offset, match_len });
+        }
+        if literals < end {
+            handle_sequence(Sequence::Literals { literals: &self.history[literals..end] });
+        }
+    }
+
+    fn reset(&mut self, _level: CompressionLevel) {
+        self.history.clear();
+        self.block_start = 0;
+        self.hashed = 0;
+        self.head.fill(0);
+        self.chain.fill(0);
+    }
+
+    fn window_size(&self) -> u64 {
+        1 << self.window_log
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn decompress(frame: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        ruzstd::decoding::StreamingDecoder::new(frame).unwrap().read_to_end(&mut out).unwrap();
+        out
+    }
+
+    /// What `_test/fixtures/zstd_rows_*.zst` were compressed from (see its README)
+    fn rows() -> Vec<u8> {
+        (0..10_000).flat_map(|i| format!("{},user{},{}\n", i, i % 97, i % 13 * 250).into_bytes()).collect()
+    }
+
+    #[test]
+    fn round_trips_at_every_level() {
+        let mut noise = Vec::with_capacity(100_000);
+        let mut x = 0x2545_f491_u32;
+        for _ in 0..100_000 {
+            x ^= x << 13;
+            x ^= x >> 17;
+            x ^= x << 5;
+            noise.push(x as u8);
+        }
+        let inputs = [
+            Vec::new(),
+            b"x".to_vec(),
+            rows(),
+            noise,
+            // One run longer than MAX_MATCH
+            vec![7; 140_000],
+            // Nothing but matches, each right after the last
+            b"abcd".repeat(20_000),
+        ];
+        for level in [0, 1, 3, 9, 19, 22, 99] {
+            for input in &inputs {
+                assert!(decompress(&compress(input, level)) == *input, "level {}, {} bytes", level, input.len());
+            }
+        }
+    }
+
+    /// Frames from libzstd 1.5.7 decode to the same rows, and ours for them
+    /// stay under twice libzstd's size at the same level
+    #[test]
+    fn matches_libzstd_frames() {
+        let rows = rows();
+        for level in [1, 19] {
+            let path = format!("{}/../_test/fixtures/zstd_rows_{}.zst", env!("CARGO_MANIFEST_DIR"), level);
+            let theirs = std::fs::read(path).unwrap();
+            assert!(decompress(&theirs) == rows, "level {}", level);
+            let ours = compress(&rows, level);
+            assert!(ours.len() < theirs.len() * 2, "level {}: {} bytes, libzstd {}", level, ours.len(), theirs.len());
+        }
+    }
+
+    /// libzstd decodes our frames, when its CLI is on the PATH
+    #[test]
+    fn libzstd_decodes_output() {
+        let rows = rows();
+        for level in [1, 3, 19] {
+            let path = std::env::temp_dir().join(format!("zstd-pure-{}-{}.zst", std::process::id(), level));
+            std::fs::write(&path, compress(&rows, level)).unwrap();
+            let out = std::process::Command::new("zstd").arg("-dc").arg(&path).output();
+            std::fs::remove_file(&path).unwrap();
+            let out = match out {
+                Ok(out) => out,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+                Err(e) => panic!("zstd: {}", e),
+            };
+            assert!(out.status.success(), "level {}: {}", level, String::from_utf8_lossy(&out.stderr));
+            assert!(out.stdout == rows, "level {}", level);
+        }
+    }
+}